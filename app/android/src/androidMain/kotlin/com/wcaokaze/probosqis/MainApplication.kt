@@ -37,8 +37,13 @@ import com.wcaokaze.probosqis.foundation.page.PPageSwitcherState
 import com.wcaokaze.probosqis.mastodon.repository.AccountRepository
 import com.wcaokaze.probosqis.mastodon.repository.AndroidAccountRepository
 import com.wcaokaze.probosqis.mastodon.repository.AndroidAppRepository
+import com.wcaokaze.probosqis.mastodon.repository.AndroidHttpClientRegistry
+import com.wcaokaze.probosqis.mastodon.repository.AndroidPersistentCache
+import com.wcaokaze.probosqis.mastodon.repository.AndroidStatusRepository
 import com.wcaokaze.probosqis.mastodon.repository.AndroidTimelineRepository
 import com.wcaokaze.probosqis.mastodon.repository.AppRepository
+import com.wcaokaze.probosqis.mastodon.repository.HttpClientRegistry
+import com.wcaokaze.probosqis.mastodon.repository.StatusRepository
 import com.wcaokaze.probosqis.mastodon.repository.TimelineRepository
 import com.wcaokaze.probosqis.nodeinfo.repository.AndroidNodeInfoRepository
 import com.wcaokaze.probosqis.nodeinfo.repository.NodeInfoRepository
@@ -155,6 +160,10 @@ class MainApplication : Application() {
       single<AccountRepository> { AndroidAccountRepository() }
       single<NodeInfoRepository> { AndroidNodeInfoRepository() }
       single<TimelineRepository> { AndroidTimelineRepository() }
+      single<StatusRepository> { AndroidStatusRepository() }
+      single<HttpClientRegistry>(createdAtStart = true) {
+         AndroidHttpClientRegistry(appVersion = BuildConfig.VERSION_NAME)
+      }
    }
 
    private val appKoinModule = module {
@@ -164,9 +173,19 @@ class MainApplication : Application() {
    override fun onCreate() {
       super.onCreate()
 
+      AndroidPersistentCache.initialize(this)
+
       startKoin {
          androidContext(this@MainApplication)
          modules(koinModule, repositoriesKoinModule, appKoinModule)
       }
    }
+
+   override fun onTrimMemory(level: Int) {
+      super.onTrimMemory(level)
+
+      if (level >= TRIM_MEMORY_UI_HIDDEN) {
+         AndroidPersistentCache.flush()
+      }
+   }
 }
@@ -46,7 +46,12 @@ import com.wcaokaze.probosqis.mastodon.repository.AccountRepository
 import com.wcaokaze.probosqis.mastodon.repository.AppRepository
 import com.wcaokaze.probosqis.mastodon.repository.DesktopAccountRepository
 import com.wcaokaze.probosqis.mastodon.repository.DesktopAppRepository
+import com.wcaokaze.probosqis.mastodon.repository.DesktopHttpClientRegistry
+import com.wcaokaze.probosqis.mastodon.repository.DesktopPersistentCache
+import com.wcaokaze.probosqis.mastodon.repository.DesktopStatusRepository
 import com.wcaokaze.probosqis.mastodon.repository.DesktopTimelineRepository
+import com.wcaokaze.probosqis.mastodon.repository.HttpClientRegistry
+import com.wcaokaze.probosqis.mastodon.repository.StatusRepository
 import com.wcaokaze.probosqis.mastodon.repository.TimelineRepository
 import com.wcaokaze.probosqis.nodeinfo.repository.DesktopNodeInfoRepository
 import com.wcaokaze.probosqis.nodeinfo.repository.NodeInfoRepository
@@ -164,10 +169,18 @@ object Main {
       single<AccountRepository> { DesktopAccountRepository() }
       single<NodeInfoRepository> { DesktopNodeInfoRepository() }
       single<TimelineRepository> { DesktopTimelineRepository() }
+      single<StatusRepository> { DesktopStatusRepository() }
+      single<HttpClientRegistry>(createdAtStart = true) {
+         DesktopHttpClientRegistry(
+            appVersion = System.getProperty("probosqis.versionName")
+         )
+      }
    }
 
    @JvmStatic
    fun main(vararg args: String) {
+      DesktopPersistentCache.initialize(probosqisDataDir)
+
       application {
          val appCoroutineScope = rememberCoroutineScope()
 
@@ -194,6 +207,8 @@ object Main {
             }
          }
       }
+
+      DesktopPersistentCache.flush()
    }
 
    private fun loadNativeLib() {
@@ -0,0 +1,49 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.ext.kotlin.webapi
+
+import java.io.IOException
+import kotlin.coroutines.resume
+import kotlin.coroutines.resumeWithException
+import kotlin.coroutines.suspendCoroutine
+
+/**
+ * Rust側の非同期のWebAPIの結果を受け取る。
+ * `ext_reqwest::callback::WebApiCallback`から呼ばれる
+ */
+interface WebApiCallback<in T> {
+   fun onSuccess(result: T)
+   fun onFailure(exception: IOException)
+}
+
+/**
+ * [request]に渡した[WebApiCallback]が呼ばれるまでサスペンドする。
+ * 待っている間Rust側でもスレッドを占有しない
+ */
+suspend inline fun <T> awaitWebApi(
+   crossinline request: (WebApiCallback<T>) -> Unit
+): T = suspendCoroutine { continuation ->
+   request(object : WebApiCallback<T> {
+      override fun onSuccess(result: T) {
+         continuation.resume(result)
+      }
+
+      override fun onFailure(exception: IOException) {
+         continuation.resumeWithException(exception)
+      }
+   })
+}
@@ -0,0 +1,68 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.ext.kotlin.webapi
+
+import kotlinx.datetime.Instant
+import java.io.IOException
+
+/**
+ * WebAPIの呼び出しで発生したエラー。
+ * Rust側の`ext_reqwest::error::WebApiError`に対応する
+ */
+sealed class WebApiException(message: String?) : IOException(message)
+
+/** サーバーに接続できなかった、通信が切断されたなど */
+class NetworkException(message: String?) : WebApiException(message)
+
+class TimeoutException(message: String?) : WebApiException(message)
+
+/**
+ * エラーを示すステータスコードが返された。
+ * [error]はレスポンスボディ`{"error": "..."}`の内容
+ */
+class HttpStatusException(
+   val statusCode: Int,
+   val error: String?
+) : WebApiException(
+   if (error != null) { "HTTP $statusCode: $error" } else { "HTTP $statusCode" }
+)
+
+/**
+ * 401 Unauthorized。アクセストークンが無効であるか、取り消されている。
+ * ユーザーに再度ログインしてもらう必要がある
+ */
+class UnauthorizedException(val error: String?) : WebApiException(error)
+
+/**
+ * 429 Too Many Requests。[resetTime]以降に再度リクエストできる
+ */
+class RateLimitException(val resetTime: Instant?) : WebApiException(
+   if (resetTime != null) { "rate limited until $resetTime" } else { "rate limited" }
+) {
+   @Suppress("UNUSED")
+   private constructor(resetTimeEpochMillis: Long?) : this(
+      resetTimeEpochMillis?.let(Instant::fromEpochMilliseconds)
+   )
+}
+
+/** レスポンスボディが期待した形式のJSONでない */
+class JsonDecodeException(message: String?) : WebApiException(message)
+
+/** サーバーがProbosqisの対応していないソフトウェアまたはバージョンである */
+class UnsupportedServerException(message: String?) : WebApiException(message)
+
+class InvalidUrlException(message: String?) : WebApiException(message)
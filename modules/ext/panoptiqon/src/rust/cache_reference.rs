@@ -0,0 +1,152 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! `#[serde(with = "ext_panoptiqon::cache_reference")]`で`Cache`のフィールドを
+//! 中身の値ではなく[CacheContent::key]としてシリアライズするためのモジュール。
+//!
+//! デシリアライズ時には[register]で登録された[RepositoryHolder]から
+//! キーに対応する`Cache`を探す。そのため参照先の値がすでに
+//! Repositoryに保存されている必要がある。
+//! `Option<Cache<T>>`には[option]、`Vec<Cache<T>>`には[vec]を使う。
+
+use std::any::{Any, TypeId};
+use std::sync::{PoisonError, RwLock};
+use panoptiqon::cache::{Cache, CacheContent};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::repository_holder::RepositoryHolder;
+
+static HOLDERS: RwLock<Vec<(TypeId, &'static (dyn Any + Send + Sync))>>
+   = RwLock::new(Vec::new());
+
+/// `T`のCacheのデシリアライズに`holder`を使うようにする
+pub fn register<T>(holder: &'static RepositoryHolder<T>)
+   where T: CacheContent + 'static,
+         RepositoryHolder<T>: Send + Sync
+{
+   let mut holders = HOLDERS.write().unwrap_or_else(PoisonError::into_inner);
+   holders.retain(|(type_id, _)| *type_id != TypeId::of::<T>());
+   holders.push((TypeId::of::<T>(), holder));
+}
+
+fn resolve<T>(key: &T::Key) -> anyhow::Result<Cache<T>>
+   where T: CacheContent + 'static
+{
+   let holders = HOLDERS.read().unwrap_or_else(PoisonError::into_inner);
+
+   let holder = holders.iter()
+      .find(|(type_id, _)| *type_id == TypeId::of::<T>())
+      .and_then(|(_, holder)| holder.downcast_ref::<RepositoryHolder<T>>())
+      .ok_or_else(|| anyhow::anyhow!(
+         "no repository registered for {}", std::any::type_name::<T>()
+      ))?;
+
+   holder.resolve(key)
+      .ok_or_else(|| anyhow::anyhow!(
+         "{} not found in the repository", std::any::type_name::<T>()
+      ))
+}
+
+pub fn serialize<T, S>(cache: &Cache<T>, serializer: S) -> Result<S::Ok, S::Error>
+   where T: CacheContent,
+         T::Key: Serialize,
+         S: Serializer
+{
+   cache.get().key().serialize(serializer)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Cache<T>, D::Error>
+   where T: CacheContent + 'static,
+         T::Key: Deserialize<'de>,
+         D: Deserializer<'de>
+{
+   use serde::de::Error;
+
+   let key = T::Key::deserialize(deserializer)?;
+   resolve(&key).map_err(D::Error::custom)
+}
+
+pub mod option {
+   use panoptiqon::cache::{Cache, CacheContent};
+   use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+   pub fn serialize<T, S>(
+      cache: &Option<Cache<T>>,
+      serializer: S
+   ) -> Result<S::Ok, S::Error>
+      where T: CacheContent,
+            T::Key: Serialize,
+            S: Serializer
+   {
+      match cache {
+         Some(cache) => serializer.serialize_some(&cache.get().key()),
+         None => serializer.serialize_none(),
+      }
+   }
+
+   pub fn deserialize<'de, T, D>(
+      deserializer: D
+   ) -> Result<Option<Cache<T>>, D::Error>
+      where T: CacheContent + 'static,
+            T::Key: Deserialize<'de>,
+            D: Deserializer<'de>
+   {
+      use serde::de::Error;
+
+      let Some(key) = Option::<T::Key>::deserialize(deserializer)? else {
+         return Ok(None);
+      };
+
+      let cache = super::resolve(&key).map_err(D::Error::custom)?;
+      Ok(Some(cache))
+   }
+}
+
+pub mod vec {
+   use panoptiqon::cache::{Cache, CacheContent};
+   use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+   pub fn serialize<T, S>(
+      caches: &[Cache<T>],
+      serializer: S
+   ) -> Result<S::Ok, S::Error>
+      where T: CacheContent,
+            T::Key: Serialize,
+            S: Serializer
+   {
+      use serde::ser::SerializeSeq;
+
+      let mut seq = serializer.serialize_seq(Some(caches.len()))?;
+      for cache in caches {
+         seq.serialize_element(&cache.get().key())?;
+      }
+      seq.end()
+   }
+
+   pub fn deserialize<'de, T, D>(
+      deserializer: D
+   ) -> Result<Vec<Cache<T>>, D::Error>
+      where T: CacheContent + 'static,
+            T::Key: Deserialize<'de>,
+            D: Deserializer<'de>
+   {
+      use serde::de::Error;
+
+      Vec::<T::Key>::deserialize(deserializer)?
+         .iter()
+         .map(|key| super::resolve(key).map_err(D::Error::custom))
+         .collect()
+   }
+}
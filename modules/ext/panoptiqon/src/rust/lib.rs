@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+pub mod cache_reference;
+pub mod persistence;
 pub mod repository_holder;
 
 #[cfg(feature="jvm")]
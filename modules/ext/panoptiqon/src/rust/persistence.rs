@@ -0,0 +1,736 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File, OpenOptions};
+use std::hash::Hasher;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::{PoisonError, RwLock};
+use panoptiqon::cache::{Cache, CacheContent};
+use panoptiqon::repository::Repository;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+static DIRECTORY: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// 永続化されるRepositoryのファイルを置くディレクトリを設定する。
+///
+/// Repositoryの内容はそのRepositoryが最初にread/writeされたときに復元されるため、
+/// それより前に呼ぶ必要がある。設定されていない場合Repositoryはメモリ上にのみ
+/// 保持される。
+pub fn set_directory(directory: impl Into<PathBuf>) {
+   let mut lock = DIRECTORY.write().unwrap_or_else(PoisonError::into_inner);
+   *lock = Some(directory.into());
+}
+
+fn directory() -> Option<PathBuf> {
+   DIRECTORY.read().ok()?.clone()
+}
+
+/// [RepositoryHolder][crate::repository_holder::RepositoryHolder]の内容を
+/// ディスクに永続化する方法
+pub struct Persistence<T: CacheContent> {
+   name: &'static str,
+   size_budget: u64,
+   encode_key: fn(&T::Key) -> anyhow::Result<String>,
+   encode: fn(&T) -> anyhow::Result<Vec<u8>>,
+   decode: fn(&[u8]) -> anyhow::Result<T>,
+}
+
+impl<T: CacheContent> Persistence<T> {
+   /// `name`はファイル名として使われる。
+   /// ファイル内の有効なエントリの合計が`size_budget`バイトを超えると
+   /// 最後に保存された時刻の古いものから破棄される。
+   pub const fn new(
+      name: &'static str,
+      size_budget: u64,
+      encode_key: fn(&T::Key) -> anyhow::Result<String>,
+      encode: fn(&T) -> anyhow::Result<Vec<u8>>,
+      decode: fn(&[u8]) -> anyhow::Result<T>,
+   ) -> Persistence<T> {
+      Persistence { name, size_budget, encode_key, encode, decode }
+   }
+
+   /// ファイルを開き、保存されているエントリを`repository`に復元する。
+   /// ディレクトリが設定されていないかファイルを開けなかった場合はNone。
+   ///
+   /// 同じRepositoryの他のエントリを参照しているエントリは参照先より先に
+   /// 保存されていることがあるため、復元できるエントリがなくなるまで
+   /// 繰り返し復元を試みる。最後まで復元できなかったエントリは削除される。
+   pub(crate) fn restore(
+      &self,
+      repository: &mut Repository<T>,
+      mut on_restored: impl FnMut(&Cache<T>)
+   ) -> Option<PersistentStore> {
+      let path = directory()?.join(self.name);
+      let mut store = PersistentStore::open(path, self.size_budget).ok()?;
+
+      let mut entries = store.load_all().ok()?;
+      loop {
+         let entry_count = entries.len();
+
+         entries.retain(|(_, bytes)| {
+            match (self.decode)(bytes) {
+               Ok(value) => {
+                  let cache = repository.save(value);
+                  on_restored(&cache);
+                  false
+               }
+               Err(_) => true
+            }
+         });
+
+         if entries.is_empty() || entries.len() == entry_count { break; }
+      }
+
+      for (key, _) in entries {
+         let _ = store.remove(&key);
+      }
+
+      Some(store)
+   }
+
+   pub(crate) fn persist(
+      &self,
+      store: &mut PersistentStore,
+      value: &T
+   ) -> anyhow::Result<()> {
+      let key = (self.encode_key)(&value.key())?;
+      let bytes = (self.encode)(value)?;
+      store.put(&key, &bytes)?;
+      Ok(())
+   }
+
+   /// 永続化されている値から変更されている場合のみ書き込む。
+   /// 破棄されたなどで永続化されていない値は書き込まない
+   pub(crate) fn persist_if_changed(
+      &self,
+      store: &mut PersistentStore,
+      value: &T
+   ) -> anyhow::Result<()> {
+      let key = (self.encode_key)(&value.key())?;
+      if !store.contains_key(&key) { return Ok(()); }
+
+      let bytes = (self.encode)(value)?;
+      if !store.contains_value(&key, &bytes) {
+         store.put(&key, &bytes)?;
+      }
+      Ok(())
+   }
+}
+
+impl<T> Persistence<T>
+   where T: CacheContent + Serialize + DeserializeOwned,
+         T::Key: Serialize
+{
+   /// キーと値をJSONにエンコードして永続化する
+   pub const fn json(name: &'static str, size_budget: u64) -> Persistence<T> {
+      Persistence::new(
+         name,
+         size_budget,
+         encode_json_key::<T>,
+         encode_json::<T>,
+         decode_json::<T>,
+      )
+   }
+}
+
+fn encode_json_key<T>(key: &T::Key) -> anyhow::Result<String>
+   where T: CacheContent,
+         T::Key: Serialize
+{
+   Ok(serde_json::to_string(key)?)
+}
+
+fn encode_json<T: Serialize>(value: &T) -> anyhow::Result<Vec<u8>> {
+   Ok(serde_json::to_vec(value)?)
+}
+
+fn decode_json<T: DeserializeOwned>(bytes: &[u8]) -> anyhow::Result<T> {
+   Ok(serde_json::from_slice(bytes)?)
+}
+
+const RECORD_PUT: u8 = 1;
+const RECORD_REMOVE: u8 = 2;
+
+/// ファイルがこれより小さい間はコンパクションを行わない
+const MIN_COMPACTION_SIZE: u64 = 64 * 1024;
+
+/// 追記のみを行うログ形式のKey-Valueストア。
+///
+/// 各レコードは`[種別 u8][キー長 u32][キー][値長 u32][値]`の形式で
+/// ファイル末尾に追記され、同じキーのレコードは後のものが優先される。
+/// 削除レコードは値長と値を持たない。
+/// 書き込み中にプロセスが終了した場合など末尾のレコードが壊れている場合は
+/// 開く際にそのレコード以降が切り捨てられる。
+/// 無効になったレコードがファイルの半分を超えるとファイルを書き直す。
+pub struct PersistentStore {
+   path: PathBuf,
+   file: File,
+   file_size: u64,
+   entries: HashMap<String, Entry>,
+   live_size: u64,
+   size_budget: u64,
+   next_sequence: u64,
+}
+
+struct Entry {
+   value_offset: u64,
+   value_len: u32,
+   /// 値が変更されたかどうかをファイルを読まずに判定するためのハッシュ
+   value_hash: u64,
+   sequence: u64,
+}
+
+impl Entry {
+   fn record_size(&self, key: &str) -> u64 {
+      record_size(key, Some(self.value_len))
+   }
+}
+
+fn hash_value(value: &[u8]) -> u64 {
+   let mut hasher = DefaultHasher::new();
+   hasher.write(value);
+   hasher.finish()
+}
+
+fn record_size(key: &str, value_len: Option<u32>) -> u64 {
+   match value_len {
+      Some(value_len) => 1 + 4 + key.len() as u64 + 4 + value_len as u64,
+      None            => 1 + 4 + key.len() as u64,
+   }
+}
+
+enum Record {
+   Put { key: String, value_offset: u64, value_len: u32 },
+   Remove { key: String },
+}
+
+impl PersistentStore {
+   pub fn open(
+      path: impl Into<PathBuf>,
+      size_budget: u64
+   ) -> io::Result<PersistentStore> {
+      let path = path.into();
+      if let Some(parent) = path.parent() {
+         fs::create_dir_all(parent)?;
+      }
+
+      let mut file = OpenOptions::new()
+         .read(true)
+         .write(true)
+         .create(true)
+         .truncate(false)
+         .open(&path)?;
+
+      let mut content = Vec::new();
+      file.read_to_end(&mut content)?;
+
+      let mut store = PersistentStore {
+         path,
+         file,
+         file_size: 0,
+         entries: HashMap::new(),
+         live_size: 0,
+         size_budget,
+         next_sequence: 0,
+      };
+
+      let mut offset = 0;
+      while let Some((record, next_offset)) = parse_record(&content, offset) {
+         match record {
+            Record::Put { key, value_offset, value_len } => {
+               let value_start = value_offset as usize;
+               let value = &content[value_start..value_start + value_len as usize];
+               store.insert_entry(key, value_offset, value_len, hash_value(value));
+            }
+            Record::Remove { key } => {
+               store.remove_entry(&key);
+            }
+         }
+         offset = next_offset;
+      }
+
+      store.file_size = offset as u64;
+      if offset < content.len() {
+         store.file.set_len(store.file_size)?;
+      }
+
+      store.evict()?;
+      store.compact_if_needed()?;
+      Ok(store)
+   }
+
+   /// 保存されているすべてのエントリを保存された順に返す
+   pub fn load_all(&mut self) -> io::Result<Vec<(String, Vec<u8>)>> {
+      let mut entries: Vec<_> = self.entries.iter().collect();
+      entries.sort_by_key(|(_, entry)| entry.sequence);
+
+      let mut values = Vec::with_capacity(entries.len());
+      for (key, entry) in entries {
+         let mut value = vec![0; entry.value_len as usize];
+         self.file.seek(SeekFrom::Start(entry.value_offset))?;
+         self.file.read_exact(&mut value)?;
+         values.push((key.clone(), value));
+      }
+
+      Ok(values)
+   }
+
+   pub fn put(&mut self, key: &str, value: &[u8]) -> io::Result<()> {
+      let value_len = u32::try_from(value.len())
+         .map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+      let value_offset = self.append(RECORD_PUT, key, Some(value))?;
+      self.insert_entry(key.to_string(), value_offset, value_len, hash_value(value));
+      self.evict()?;
+      self.compact_if_needed()
+   }
+
+   pub fn remove(&mut self, key: &str) -> io::Result<()> {
+      if !self.entries.contains_key(key) { return Ok(()); }
+
+      self.append(RECORD_REMOVE, key, None)?;
+      self.remove_entry(key);
+      self.compact_if_needed()
+   }
+
+   pub fn contains_key(&self, key: &str) -> bool {
+      self.entries.contains_key(key)
+   }
+
+   /// `key`に`value`と同じ値が保存されている場合true
+   pub fn contains_value(&self, key: &str, value: &[u8]) -> bool {
+      self.entries.get(key).is_some_and(|entry|
+         entry.value_len as usize == value.len() && entry.value_hash == hash_value(value)
+      )
+   }
+
+   /// ファイル末尾にレコードを書き込み、値の位置を返す
+   fn append(
+      &mut self,
+      kind: u8,
+      key: &str,
+      value: Option<&[u8]>
+   ) -> io::Result<u64> {
+      let key_len = u32::try_from(key.len())
+         .map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+      let mut record = Vec::with_capacity(
+         record_size(key, value.map(|v| v.len() as u32)) as usize
+      );
+      record.push(kind);
+      record.extend_from_slice(&key_len.to_le_bytes());
+      record.extend_from_slice(key.as_bytes());
+      if let Some(value) = value {
+         record.extend_from_slice(&(value.len() as u32).to_le_bytes());
+         record.extend_from_slice(value);
+      }
+
+      self.file.seek(SeekFrom::Start(self.file_size))?;
+      self.file.write_all(&record)?;
+
+      let value_offset = self.file_size + record_size(key, Some(0));
+      self.file_size += record.len() as u64;
+      Ok(value_offset)
+   }
+
+   fn insert_entry(
+      &mut self,
+      key: String,
+      value_offset: u64,
+      value_len: u32,
+      value_hash: u64
+   ) {
+      self.remove_entry(&key);
+
+      let entry = Entry {
+         value_offset,
+         value_len,
+         value_hash,
+         sequence: self.next_sequence,
+      };
+      self.next_sequence += 1;
+      self.live_size += entry.record_size(&key);
+      self.entries.insert(key, entry);
+   }
+
+   fn remove_entry(&mut self, key: &str) {
+      if let Some(entry) = self.entries.remove(key) {
+         self.live_size -= entry.record_size(key);
+      }
+   }
+
+   fn evict(&mut self) -> io::Result<()> {
+      if self.live_size <= self.size_budget { return Ok(()); }
+
+      let mut keys: Vec<_> = self.entries.iter()
+         .map(|(key, entry)| (entry.sequence, key.clone()))
+         .collect();
+      keys.sort();
+
+      for (_, key) in keys {
+         if self.live_size <= self.size_budget { break; }
+         self.append(RECORD_REMOVE, &key, None)?;
+         self.remove_entry(&key);
+      }
+
+      Ok(())
+   }
+
+   fn compact_if_needed(&mut self) -> io::Result<()> {
+      if self.file_size < MIN_COMPACTION_SIZE { return Ok(()); }
+      if self.file_size <= self.live_size * 2 { return Ok(()); }
+      self.compact()
+   }
+
+   /// 有効なエントリのみを新しいファイルに書き出して置き換える
+   fn compact(&mut self) -> io::Result<()> {
+      let temp_path = self.path.with_extension("compacting");
+      let entries = self.load_all()?;
+
+      let mut temp_file = File::create(&temp_path)?;
+      let mut offset = 0;
+      let mut new_offsets = Vec::with_capacity(entries.len());
+
+      for (key, value) in &entries {
+         let mut record = Vec::with_capacity(
+            record_size(key, Some(value.len() as u32)) as usize
+         );
+         record.push(RECORD_PUT);
+         record.extend_from_slice(&(key.len() as u32).to_le_bytes());
+         record.extend_from_slice(key.as_bytes());
+         record.extend_from_slice(&(value.len() as u32).to_le_bytes());
+         record.extend_from_slice(value);
+         temp_file.write_all(&record)?;
+
+         new_offsets.push(offset + record_size(key, Some(0)));
+         offset += record.len() as u64;
+      }
+
+      temp_file.sync_all()?;
+      drop(temp_file);
+      fs::rename(&temp_path, &self.path)?;
+
+      self.file = OpenOptions::new().read(true).write(true).open(&self.path)?;
+      self.file_size = offset;
+      for ((key, _), value_offset) in entries.iter().zip(new_offsets) {
+         if let Some(entry) = self.entries.get_mut(key) {
+            entry.value_offset = value_offset;
+         }
+      }
+
+      Ok(())
+   }
+}
+
+/// `offset`から1レコード読み込み、次のレコードの位置とともに返す。
+/// レコードが途中で途切れているか不正な場合はNone
+fn parse_record(content: &[u8], offset: usize) -> Option<(Record, usize)> {
+   fn read_u32(content: &[u8], offset: usize) -> Option<u32> {
+      let bytes = content.get(offset..offset + 4)?;
+      Some(u32::from_le_bytes(bytes.try_into().ok()?))
+   }
+
+   let kind = *content.get(offset)?;
+   let key_len = read_u32(content, offset + 1)? as usize;
+   let key_start = offset + 5;
+   let key = content.get(key_start..key_start + key_len)?;
+   let key = String::from_utf8(key.to_vec()).ok()?;
+   let key_end = key_start + key_len;
+
+   match kind {
+      RECORD_PUT => {
+         let value_len = read_u32(content, key_end)?;
+         let value_start = key_end + 4;
+         let value_end = value_start + value_len as usize;
+         if value_end > content.len() { return None; }
+
+         let record = Record::Put {
+            key,
+            value_offset: value_start as u64,
+            value_len,
+         };
+         Some((record, value_end))
+      }
+      RECORD_REMOVE => {
+         Some((Record::Remove { key }, key_end))
+      }
+      _ => None
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use std::fs;
+   use std::path::PathBuf;
+   use panoptiqon::cache::{Cache, CacheContent};
+   use serde::{Deserialize, Serialize};
+   use crate::repository_holder::RepositoryHolder;
+   use super::{Persistence, PersistentStore};
+
+   /// dropされたときにファイルを削除する
+   struct TempFile(PathBuf);
+
+   impl Drop for TempFile {
+      fn drop(&mut self) {
+         let _ = fs::remove_file(&self.0);
+         let _ = fs::remove_file(self.0.with_extension("compacting"));
+
+         // 他のテストのファイルが残っている間は空でないため削除されない
+         if let Some(directory) = self.0.parent() {
+            let _ = fs::remove_dir(directory);
+         }
+      }
+   }
+
+   fn temp_path(name: &str) -> (PathBuf, TempFile) {
+      let directory = std::env::temp_dir()
+         .join(format!("ext_panoptiqon_persistence_{}", std::process::id()));
+      let path = directory.join(name);
+      let _ = fs::remove_file(&path);
+      (path.clone(), TempFile(path))
+   }
+
+   #[test]
+   fn round_trip() {
+      let (path, _temp_file) = temp_path("round_trip");
+
+      {
+         let mut store = PersistentStore::open(&path, u64::MAX).unwrap();
+         store.put("a", b"value a").unwrap();
+         store.put("b", b"value b").unwrap();
+         store.put("a", b"new value a").unwrap();
+         store.put("c", b"value c").unwrap();
+         store.remove("c").unwrap();
+      }
+
+      let mut store = PersistentStore::open(&path, u64::MAX).unwrap();
+      assert_eq!(
+         vec![
+            ("b".to_string(), b"value b".to_vec()),
+            ("a".to_string(), b"new value a".to_vec()),
+         ],
+         store.load_all().unwrap()
+      );
+   }
+
+   #[test]
+   fn truncated_record_is_discarded() {
+      let (path, _temp_file) = temp_path("truncated_record_is_discarded");
+
+      {
+         let mut store = PersistentStore::open(&path, u64::MAX).unwrap();
+         store.put("a", b"value a").unwrap();
+         store.put("b", b"value b").unwrap();
+      }
+
+      let file_size = fs::metadata(&path).unwrap().len();
+      let file = fs::OpenOptions::new().write(true).open(&path).unwrap();
+      file.set_len(file_size - 3).unwrap();
+      drop(file);
+
+      {
+         let mut store = PersistentStore::open(&path, u64::MAX).unwrap();
+         assert_eq!(
+            vec![("a".to_string(), b"value a".to_vec())],
+            store.load_all().unwrap()
+         );
+         store.put("c", b"value c").unwrap();
+      }
+
+      let mut store = PersistentStore::open(&path, u64::MAX).unwrap();
+      assert_eq!(
+         vec![
+            ("a".to_string(), b"value a".to_vec()),
+            ("c".to_string(), b"value c".to_vec()),
+         ],
+         store.load_all().unwrap()
+      );
+   }
+
+   #[test]
+   fn evicts_oldest_entries() {
+      let (path, _temp_file) = temp_path("evicts_oldest_entries");
+
+      // 1レコードあたり 1 + 4 + 1 + 4 + 10 = 20バイト
+      let mut store = PersistentStore::open(&path, 50).unwrap();
+      store.put("a", &[0; 10]).unwrap();
+      store.put("b", &[1; 10]).unwrap();
+      store.put("a", &[2; 10]).unwrap();
+      store.put("c", &[3; 10]).unwrap();
+
+      assert!(!store.contains_key("b"));
+      assert!(store.contains_key("a"));
+      assert!(store.contains_key("c"));
+      drop(store);
+
+      let mut store = PersistentStore::open(&path, 50).unwrap();
+      assert_eq!(
+         vec![
+            ("a".to_string(), vec![2; 10]),
+            ("c".to_string(), vec![3; 10]),
+         ],
+         store.load_all().unwrap()
+      );
+   }
+
+   #[test]
+   fn compaction() {
+      let (path, _temp_file) = temp_path("compaction");
+
+      let mut store = PersistentStore::open(&path, u64::MAX).unwrap();
+      for i in 0..100 {
+         store.put("a", &[i; 1024]).unwrap();
+      }
+      store.put("b", b"value b").unwrap();
+
+      assert!(fs::metadata(&path).unwrap().len() < 64 * 1024);
+      drop(store);
+
+      let mut store = PersistentStore::open(&path, u64::MAX).unwrap();
+      assert_eq!(
+         vec![
+            ("a".to_string(), vec![99; 1024]),
+            ("b".to_string(), b"value b".to_vec()),
+         ],
+         store.load_all().unwrap()
+      );
+   }
+
+   #[derive(Debug, PartialEq, Deserialize, Serialize)]
+   struct Content {
+      id: i64,
+      text: String,
+   }
+
+   impl CacheContent for Content {
+      type Key = i64;
+
+      fn key(&self) -> i64 {
+         self.id
+      }
+   }
+
+   #[test]
+   fn restore_repository_holder() {
+      let (path, _temp_file) = temp_path("restore_repository_holder");
+      super::set_directory(path.parent().unwrap());
+
+      let holder = RepositoryHolder::<Content>::with_persistence(
+         Persistence::json("restore_repository_holder", u64::MAX)
+      );
+
+      {
+         let mut repository = holder.write().unwrap();
+         repository.save(Content { id: 0, text: "a".to_string() });
+         repository.save(Content { id: 1, text: "b".to_string() });
+         repository.save(Content { id: 0, text: "c".to_string() });
+      }
+
+      let holder = RepositoryHolder::<Content>::with_persistence(
+         Persistence::json("restore_repository_holder", u64::MAX)
+      );
+
+      let repository = holder.read().unwrap();
+      assert_eq!(
+         Content { id: 0, text: "c".to_string() },
+         *repository.load(0).unwrap().get()
+      );
+      assert_eq!(
+         Content { id: 1, text: "b".to_string() },
+         *repository.load(1).unwrap().get()
+      );
+      assert!(repository.load(2).is_err());
+   }
+
+   #[test]
+   fn flush_values_set_to_cache() {
+      let (path, _temp_file) = temp_path("flush_values_set_to_cache");
+      super::set_directory(path.parent().unwrap());
+
+      let holder = RepositoryHolder::<Content>::with_persistence(
+         Persistence::json("flush_values_set_to_cache", u64::MAX)
+      );
+
+      let cache = holder.write().unwrap()
+         .save(Content { id: 0, text: "a".to_string() });
+      cache.set(Content { id: 0, text: "b".to_string() });
+      holder.flush();
+
+      // 変更されていない値は書き込まれない
+      let file_size = fs::metadata(&path).unwrap().len();
+      holder.flush();
+      assert_eq!(file_size, fs::metadata(&path).unwrap().len());
+
+      let holder = RepositoryHolder::<Content>::with_persistence(
+         Persistence::json("flush_values_set_to_cache", u64::MAX)
+      );
+
+      let repository = holder.read().unwrap();
+      assert_eq!(
+         Content { id: 0, text: "b".to_string() },
+         *repository.load(0).unwrap().get()
+      );
+   }
+
+   #[derive(Debug, PartialEq, Deserialize, Serialize)]
+   struct Node {
+      id: i64,
+      #[serde(with = "crate::cache_reference::option")]
+      parent: Option<Cache<Node>>,
+   }
+
+   impl CacheContent for Node {
+      type Key = i64;
+
+      fn key(&self) -> i64 {
+         self.id
+      }
+   }
+
+   static NODE_REPO: RepositoryHolder<Node> = RepositoryHolder::with_persistence(
+      Persistence::json("restore_references", u64::MAX)
+   );
+
+   static RESTORED_NODE_REPO: RepositoryHolder<Node>
+      = RepositoryHolder::with_persistence(
+         Persistence::json("restore_references", u64::MAX)
+      );
+
+   #[test]
+   fn restore_references() {
+      let (path, _temp_file) = temp_path("restore_references");
+      super::set_directory(path.parent().unwrap());
+
+      crate::cache_reference::register(&NODE_REPO);
+      {
+         let mut repository = NODE_REPO.write().unwrap();
+         let parent = repository.save(Node { id: 0, parent: None });
+         repository.save(Node { id: 1, parent: Some(parent) });
+         // 参照先が参照元より後に保存されている
+         repository.save(Node { id: 0, parent: None });
+      }
+
+      crate::cache_reference::register(&RESTORED_NODE_REPO);
+      let repository = RESTORED_NODE_REPO.read().unwrap();
+      let parent = repository.load(0).unwrap();
+      let child = repository.load(1).unwrap();
+      assert_eq!(Some(parent), child.get().parent);
+   }
+}
@@ -13,19 +13,27 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
-use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::sync::{Mutex, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 #[cfg(feature="jvm")]
 use {
    jni::JNIEnv,
    panoptiqon::convert_jvm::{CloneIntoJvm, CloneIntoJvmHelper},
 };
-use panoptiqon::cache::CacheContent;
+use panoptiqon::cache::{Cache, CacheContent};
 use panoptiqon::repository::Repository;
+use crate::persistence::{Persistence, PersistentStore};
 
 pub struct RepositoryHolder<T: CacheContent> {
-   lock: RwLock<LazyInitRepository<T>>
+   lock: RwLock<LazyInitRepository<T>>,
+   persistence: Option<Persistence<T>>,
+   store: Mutex<Option<PersistentStore>>,
+   /// [resolve][RepositoryHolder::resolve]用。
+   /// 同じスレッドがRepositoryのロックを取得している間もキーから
+   /// Cacheを引けるようにRepositoryとは別のロックで管理する
+   index: RwLock<Option<HashMap<T::Key, Cache<T>>>>,
 }
 
 enum LazyInitRepository<T: CacheContent> {
@@ -65,7 +73,8 @@ pub struct RepositoryReadGuard<'a, T: CacheContent> {
 }
 
 pub struct RepositoryWriteGuard<'a, T: CacheContent> {
-   lock_guard: RwLockWriteGuard<'a, LazyInitRepository<T>>
+   lock_guard: RwLockWriteGuard<'a, LazyInitRepository<T>>,
+   holder: &'a RepositoryHolder<T>,
 }
 
 impl<'a, T: CacheContent> Deref for RepositoryReadGuard<'a, T> {
@@ -102,10 +111,94 @@ impl<'a, T: CacheContent> DerefMut for RepositoryWriteGuard<'a, T> {
    }
 }
 
+impl<'a, T: CacheContent> RepositoryWriteGuard<'a, T> {
+   /// [Repository::save]と同様にRepositoryに保存する。
+   /// 永続化が設定されている場合はディスクにも書き込む。
+   pub fn save(&mut self, value: T) -> Cache<T> {
+      self.holder.persist(&value);
+      let cache = self.deref_mut().save(value);
+      self.holder.index(&cache);
+      cache
+   }
+}
+
 impl<T: CacheContent> RepositoryHolder<T> {
    pub const fn new() -> Self {
       RepositoryHolder {
-         lock: RwLock::new(LazyInitRepository::None)
+         lock: RwLock::new(LazyInitRepository::None),
+         persistence: None,
+         store: Mutex::new(None),
+         index: RwLock::new(None),
+      }
+   }
+
+   /// 内容がディスクに永続化されるRepositoryHolder。
+   ///
+   /// 永続化された内容は最初にread/writeされたときに復元される。
+   /// 永続化のディレクトリは
+   /// [set_directory][crate::persistence::set_directory]で設定する。
+   pub const fn with_persistence(persistence: Persistence<T>) -> Self {
+      RepositoryHolder {
+         lock: RwLock::new(LazyInitRepository::None),
+         persistence: Some(persistence),
+         store: Mutex::new(None),
+         index: RwLock::new(None),
+      }
+   }
+
+   /// このRepositoryHolderに保存されたCacheをキーから探す。
+   ///
+   /// Repositoryのロックを取得しないため、Repositoryが初期化されていない場合は
+   /// 初期化されずにNoneとなる。
+   pub fn resolve(&self, key: &T::Key) -> Option<Cache<T>> {
+      let index = self.index.read().unwrap_or_else(PoisonError::into_inner);
+      index.as_ref()?.get(key).cloned()
+   }
+
+   fn index(&self, cache: &Cache<T>) {
+      let key = cache.get().key();
+      let mut index = self.index.write().unwrap_or_else(PoisonError::into_inner);
+      index.get_or_insert_with(HashMap::new).insert(key, cache.clone());
+   }
+
+   fn restore(&self, lock: &mut LazyInitRepository<T>) {
+      let Some(ref persistence) = self.persistence else { return; };
+      let LazyInitRepository::Repository(ref mut repository) = *lock else {
+         return;
+      };
+
+      let mut store = self.store.lock().unwrap_or_else(PoisonError::into_inner);
+      *store = persistence.restore(repository, |cache| self.index(cache));
+   }
+
+   fn persist(&self, value: &T) {
+      let Some(ref persistence) = self.persistence else { return; };
+
+      let mut store = self.store.lock().unwrap_or_else(PoisonError::into_inner);
+      if let Some(ref mut store) = *store {
+         // ディスクへの書き込みに失敗してもメモリ上のRepositoryには保存する
+         let _ = persistence.persist(store, value);
+      }
+   }
+
+   /// [Cache::set]などでRepositoryを経由せずに変更された値をディスクに書き込む。
+   ///
+   /// [RepositoryWriteGuard::save]で保存された値はその時点で書き込まれているため、
+   /// 変更されていない値は書き込まれない
+   pub fn flush(&self) {
+      let Some(ref persistence) = self.persistence else { return; };
+
+      let caches: Vec<Cache<T>> = {
+         let index = self.index.read().unwrap_or_else(PoisonError::into_inner);
+         index.iter().flat_map(|index| index.values().cloned()).collect()
+      };
+
+      let mut store = self.store.lock().unwrap_or_else(PoisonError::into_inner);
+      let Some(ref mut store) = *store else { return; };
+
+      for cache in caches {
+         // 書き込みに失敗した値は次のflushで再度書き込みを試みる
+         let _ = persistence.persist_if_changed(store, &cache.get());
       }
    }
 
@@ -123,7 +216,10 @@ impl<T: CacheContent> RepositoryHolder<T> {
       drop(lock);
 
       let mut lock = self.lock.write().map_err(|_| PoisonError::new(()))?;
-      lock.initialize();
+      if !lock.is_initialized() {
+         lock.initialize();
+         self.restore(&mut lock);
+      }
       drop(lock);
 
       self.read()
@@ -148,7 +244,10 @@ impl<T: CacheContent> RepositoryHolder<T> {
       drop(lock);
 
       let mut lock = self.lock.write().map_err(|_| PoisonError::new(()))?;
-      lock.initialize(env);
+      if !lock.is_initialized() {
+         lock.initialize(env);
+         self.restore(&mut lock);
+      }
       drop(lock);
 
       self.read(env)
@@ -160,10 +259,12 @@ impl<T: CacheContent> RepositoryHolder<T> {
 
       if !lock.is_initialized() {
          lock.initialize();
+         self.restore(&mut lock);
       }
 
       let repository_guard = RepositoryWriteGuard {
-         lock_guard: lock
+         lock_guard: lock,
+         holder: self,
       };
 
       Ok(repository_guard)
@@ -180,10 +281,12 @@ impl<T: CacheContent> RepositoryHolder<T> {
 
       if !lock.is_initialized() {
          lock.initialize(env);
+         self.restore(&mut lock);
       }
 
       let repository_guard = RepositoryWriteGuard {
-         lock_guard: lock
+         lock_guard: lock,
+         holder: self,
       };
 
       Ok(repository_guard)
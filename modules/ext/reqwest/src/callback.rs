@@ -0,0 +1,90 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![cfg(feature="jvm")]
+
+use std::future::Future;
+
+use jni::{JavaVM, JNIEnv};
+use jni::objects::{GlobalRef, JObject, JValue};
+
+use crate::runtime::RUNTIME;
+use crate::unwrap_or_throw::new_exception;
+
+/// `com.wcaokaze.probosqis.ext.kotlin.webapi.WebApiCallback`。
+/// 非同期のWebAPIの結果をJVMに返す
+pub struct WebApiCallback {
+   vm: JavaVM,
+   callback: GlobalRef,
+}
+
+impl WebApiCallback {
+   pub fn new(env: &mut JNIEnv, callback: &JObject) -> jni::errors::Result<WebApiCallback> {
+      Ok(WebApiCallback {
+         vm: env.get_java_vm()?,
+         callback: env.new_global_ref(callback)?,
+      })
+   }
+
+   /// `future`を共有のランタイムで実行し、結果を`into_jvm`でJVMのオブジェクトに
+   /// 変換して`onSuccess`に渡す。失敗した場合は
+   /// [UnwrapOrThrowWebApiException][crate::unwrap_or_throw::UnwrapOrThrowWebApiException]
+   /// と同じ例外を`onFailure`に渡す
+   pub fn spawn<T, F, C>(self, future: F, into_jvm: C)
+      where T: Send + 'static,
+            F: Future<Output = anyhow::Result<T>> + Send + 'static,
+            C: for<'local> FnOnce(&mut JNIEnv<'local>, T) -> JObject<'local> + Send + 'static
+   {
+      RUNTIME.spawn(async move {
+         let result = future.await;
+         self.complete(result, into_jvm);
+      });
+   }
+
+   fn complete<T, C>(self, result: anyhow::Result<T>, into_jvm: C)
+      where C: for<'local> FnOnce(&mut JNIEnv<'local>, T) -> JObject<'local>
+   {
+      // ランタイムのスレッドはJVMにアタッチしたままにし、
+      // ローカル参照はフレームごとに解放する
+      let Ok(mut env) = self.vm.attach_current_thread_as_daemon() else { return; };
+
+      let _ = env.with_local_frame(16, |env| -> jni::errors::Result<()> {
+         match result {
+            Ok(value) => {
+               let value = into_jvm(env, value);
+               env.call_method(
+                  &self.callback, "onSuccess", "(Ljava/lang/Object;)V",
+                  &[JValue::Object(&value)]
+               )?;
+            }
+            Err(e) => {
+               let exception = new_exception(env, &e);
+               env.call_method(
+                  &self.callback, "onFailure", "(Ljava/io/IOException;)V",
+                  &[JValue::Object(&exception)]
+               )?;
+            }
+         }
+
+         Ok(())
+      });
+
+      if env.exception_check().unwrap_or(false) {
+         let _ = env.exception_describe();
+         let _ = env.exception_clear();
+      }
+   }
+}
@@ -0,0 +1,298 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+use reqwest::Proxy;
+use url::Url;
+
+use crate::error::{WebApiError, WebApiResult};
+
+static REGISTRY: LazyLock<RwLock<ClientRegistry>>
+   = LazyLock::new(|| RwLock::new(ClientRegistry::new()));
+
+/// HTTPクライアントの設定
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ClientSettings {
+   pub connect_timeout: Duration,
+   /// 非同期のクライアントでは読み込みのタイムアウト。
+   /// blockingのreqwestには読み込みのみのタイムアウトがないため、
+   /// blockingのクライアントではリクエスト全体のタイムアウトとして扱う。
+   /// SSEやメディアのアップロードなど長時間掛かるリクエストは
+   /// リクエストごとに上書きすること
+   pub read_timeout: Duration,
+   /// `http`, `https`, `socks5`, `socks5h`のプロキシ
+   pub proxy: Option<Url>,
+   /// User-Agentに含めるアプリのバージョン
+   pub app_version: Option<String>,
+}
+
+impl Default for ClientSettings {
+   fn default() -> ClientSettings {
+      ClientSettings {
+         connect_timeout: Duration::from_secs(10),
+         read_timeout: Duration::from_secs(30),
+         proxy: None,
+         app_version: None,
+      }
+   }
+}
+
+impl ClientSettings {
+   pub fn user_agent(&self) -> String {
+      match &self.app_version {
+         Some(app_version) => format!("Probosqis/{app_version}"),
+         None => "Probosqis".to_string(),
+      }
+   }
+
+   fn build_client(&self) -> WebApiResult<Client> {
+      let mut builder = Client::builder()
+         .user_agent(self.user_agent())
+         .connect_timeout(self.connect_timeout)
+         .timeout(self.read_timeout);
+
+      if let Some(proxy) = self.build_proxy()? {
+         builder = builder.proxy(proxy);
+      }
+
+      Ok(builder.build()?)
+   }
+
+   fn build_async_client(&self) -> WebApiResult<reqwest::Client> {
+      // 非同期のクライアントではボディの読み込みにもタイムアウトを設定できる
+      let mut builder = reqwest::Client::builder()
+         .user_agent(self.user_agent())
+         .connect_timeout(self.connect_timeout)
+         .read_timeout(self.read_timeout);
+
+      if let Some(proxy) = self.build_proxy()? {
+         builder = builder.proxy(proxy);
+      }
+
+      Ok(builder.build()?)
+   }
+
+   fn build_proxy(&self) -> WebApiResult<Option<Proxy>> {
+      let Some(proxy) = &self.proxy else { return Ok(None); };
+
+      // 受け付けるスキームはreqwestのバージョンによって変わるため、ここで確認する
+      if !matches!(proxy.scheme(), "http" | "https" | "socks5" | "socks5h") {
+         return Err(WebApiError::InvalidUrl(proxy.to_string()));
+      }
+
+      let proxy = Proxy::all(proxy.clone())
+         .map_err(|_| WebApiError::InvalidUrl(proxy.to_string()))?;
+
+      Ok(Some(proxy))
+   }
+}
+
+struct Entry {
+   settings: ClientSettings,
+   client: Client,
+   async_client: reqwest::Client,
+}
+
+impl Entry {
+   fn new(settings: ClientSettings) -> WebApiResult<Entry> {
+      let client = settings.build_client()?;
+      let async_client = settings.build_async_client()?;
+      Ok(Entry { settings, client, async_client })
+   }
+}
+
+struct ClientRegistry {
+   default: Entry,
+   /// インスタンスごとの設定。キーはインスタンスのorigin
+   instances: HashMap<String, Entry>,
+}
+
+impl ClientRegistry {
+   fn new() -> ClientRegistry {
+      ClientRegistry {
+         default: Entry::new(ClientSettings::default())
+            .expect("failed to build the default HTTP client"),
+         instances: HashMap::new(),
+      }
+   }
+}
+
+fn instance_key(instance_url: &Url) -> String {
+   instance_url.origin().ascii_serialization()
+}
+
+/// `url`へのリクエストに使うクライアント。
+/// インスタンスごとの設定がなければデフォルトの設定のクライアントを返す
+pub fn client_for(url: &Url) -> Client {
+   let registry = REGISTRY.read().unwrap();
+
+   registry.instances.get(&instance_key(url))
+      .unwrap_or(&registry.default)
+      .client.clone()
+}
+
+/// [client_for]の非同期版
+pub fn async_client_for(url: &Url) -> reqwest::Client {
+   let registry = REGISTRY.read().unwrap();
+
+   registry.instances.get(&instance_key(url))
+      .unwrap_or(&registry.default)
+      .async_client.clone()
+}
+
+/// `url`へのリクエストに適用される設定。
+/// reqwestを経由しない接続で設定を反映するために使う
+pub fn settings_for(url: &Url) -> ClientSettings {
+   let registry = REGISTRY.read().unwrap();
+
+   registry.instances.get(&instance_key(url))
+      .unwrap_or(&registry.default)
+      .settings.clone()
+}
+
+pub fn default_client() -> Client {
+   REGISTRY.read().unwrap().default.client.clone()
+}
+
+pub fn default_settings() -> ClientSettings {
+   REGISTRY.read().unwrap().default.settings.clone()
+}
+
+pub fn set_default_settings(settings: ClientSettings) -> WebApiResult<()> {
+   let entry = Entry::new(settings)?;
+   REGISTRY.write().unwrap().default = entry;
+   Ok(())
+}
+
+pub fn instance_settings(instance_url: &Url) -> Option<ClientSettings> {
+   REGISTRY.read().unwrap()
+      .instances.get(&instance_key(instance_url))
+      .map(|entry| entry.settings.clone())
+}
+
+/// インスタンスごとの設定を変更する。`None`の場合はデフォルトの設定に戻す
+pub fn set_instance_settings(
+   instance_url: &Url,
+   settings: Option<ClientSettings>
+) -> WebApiResult<()> {
+   let key = instance_key(instance_url);
+
+   match settings {
+      Some(settings) => {
+         let entry = Entry::new(settings)?;
+         REGISTRY.write().unwrap().instances.insert(key, entry);
+      }
+      None => {
+         REGISTRY.write().unwrap().instances.remove(&key);
+      }
+   }
+
+   Ok(())
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use std::time::Duration;
+
+   use url::Url;
+
+   use super::ClientSettings;
+   use crate::error::WebApiError;
+
+   #[test]
+   fn instance_settings_override_default() {
+      let instance_url = Url::parse("https://override.example.com/").unwrap();
+      let settings = ClientSettings {
+         connect_timeout: Duration::from_secs(1),
+         ..ClientSettings::default()
+      };
+
+      super::set_instance_settings(&instance_url, Some(settings.clone())).unwrap();
+
+      assert_eq!(
+         Some(settings),
+         super::instance_settings(&Url::parse("https://override.example.com/api/v1/statuses").unwrap())
+      );
+      assert_eq!(
+         None,
+         super::instance_settings(&Url::parse("https://other.example.com/").unwrap())
+      );
+
+      super::set_instance_settings(&instance_url, None).unwrap();
+      assert_eq!(None, super::instance_settings(&instance_url));
+   }
+
+   #[test]
+   fn user_agent() {
+      let settings = ClientSettings {
+         app_version: Some("1.2.3".to_string()),
+         ..ClientSettings::default()
+      };
+      assert_eq!("Probosqis/1.2.3", settings.user_agent());
+      assert_eq!("Probosqis", ClientSettings::default().user_agent());
+   }
+
+   #[test]
+   fn socks_proxy() {
+      use std::io::{Read, Write};
+      use std::net::{TcpListener, TcpStream};
+      use std::thread;
+
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let port = listener.local_addr().unwrap().port();
+
+      let proxy = thread::spawn(move || {
+         let (mut stream, _) = listener.accept().unwrap();
+         let mut version = [0];
+         stream.read_exact(&mut version).unwrap();
+         version[0]
+      });
+
+      let instance_url = Url::parse("https://socks-proxy.example.com/").unwrap();
+      let settings = ClientSettings {
+         proxy: Some(Url::parse(&format!("socks5h://127.0.0.1:{port}")).unwrap()),
+         ..ClientSettings::default()
+      };
+      super::set_instance_settings(&instance_url, Some(settings)).unwrap();
+
+      // プロキシは応答しないのでリクエストは失敗する
+      let _ = super::client_for(&instance_url).get(instance_url.clone()).send();
+
+      // プロキシに接続されなかった場合にテストが止まらないよう、0を送っておく
+      let _ = TcpStream::connect(("127.0.0.1", port)).and_then(|mut s| s.write_all(&[0]));
+
+      // SOCKS5のハンドシェイクはバージョン5から始まる
+      assert_eq!(5, proxy.join().unwrap());
+
+      super::set_instance_settings(&instance_url, None).unwrap();
+   }
+
+   #[test]
+   fn reject_invalid_proxy() {
+      let instance_url = Url::parse("https://invalid-proxy.example.com/").unwrap();
+      let settings = ClientSettings {
+         proxy: Some(Url::parse("ftp://proxy.example.com/").unwrap()),
+         ..ClientSettings::default()
+      };
+
+      let result = super::set_instance_settings(&instance_url, Some(settings));
+      assert!(matches!(result, Err(WebApiError::InvalidUrl(_))));
+      assert_eq!(None, super::instance_settings(&instance_url));
+   }
+}
@@ -0,0 +1,246 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use reqwest::blocking::Response;
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+
+pub type WebApiResult<T> = Result<T, WebApiError>;
+
+/// WebAPIの呼び出しで発生したエラー
+#[derive(Debug)]
+pub enum WebApiError {
+   /// サーバーに接続できなかった、通信が切断されたなど
+   Network(Box<dyn Error + Send + Sync>),
+   Timeout,
+   /// エラーを示すステータスコードが返された。
+   /// `error`はレスポンスボディ`{"error": "..."}`の内容
+   HttpStatus {
+      status: u16,
+      error: Option<String>,
+   },
+   /// 401 Unauthorized。アクセストークンが無効であるか、取り消されている
+   Unauthorized {
+      error: Option<String>,
+   },
+   /// 429 Too Many Requests。`reset_time`はX-RateLimit-Resetヘッダの値
+   RateLimited {
+      reset_time: Option<DateTime<Utc>>,
+   },
+   /// レスポンスボディが期待した形式のJSONでない
+   Decode(Box<dyn Error + Send + Sync>),
+   /// サーバーがProbosqisの対応していないソフトウェアまたはバージョンである
+   UnsupportedServer(String),
+   InvalidUrl(String),
+}
+
+impl WebApiError {
+   /// エラーを示すステータスコードのレスポンスから[WebApiError]を生成する
+   pub fn from_status(
+      status: u16,
+      error: Option<String>,
+      reset_time: Option<DateTime<Utc>>
+   ) -> WebApiError {
+      match status {
+         401 => WebApiError::Unauthorized { error },
+         429 => WebApiError::RateLimited { reset_time },
+         _   => WebApiError::HttpStatus { status, error },
+      }
+   }
+}
+
+impl Display for WebApiError {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      match self {
+         WebApiError::Network(e) => write!(f, "network error: {e}"),
+         WebApiError::Timeout => write!(f, "timed out"),
+         WebApiError::HttpStatus { status, error: Some(error) }
+            => write!(f, "HTTP {status}: {error}"),
+         WebApiError::HttpStatus { status, error: None }
+            => write!(f, "HTTP {status}"),
+         WebApiError::Unauthorized { error: Some(error) }
+            => write!(f, "unauthorized: {error}"),
+         WebApiError::Unauthorized { error: None }
+            => write!(f, "unauthorized"),
+         WebApiError::RateLimited { reset_time: Some(reset_time) }
+            => write!(f, "rate limited until {reset_time}"),
+         WebApiError::RateLimited { reset_time: None }
+            => write!(f, "rate limited"),
+         WebApiError::Decode(e) => write!(f, "unexpected response: {e}"),
+         WebApiError::UnsupportedServer(message)
+            => write!(f, "unsupported server: {message}"),
+         WebApiError::InvalidUrl(url) => write!(f, "invalid url: {url}"),
+      }
+   }
+}
+
+impl Error for WebApiError {
+   fn source(&self) -> Option<&(dyn Error + 'static)> {
+      match self {
+         WebApiError::Network(e) | WebApiError::Decode(e) => Some(e.as_ref()),
+         _ => None,
+      }
+   }
+}
+
+impl From<reqwest::Error> for WebApiError {
+   fn from(e: reqwest::Error) -> WebApiError {
+      if e.is_timeout() {
+         WebApiError::Timeout
+      } else if e.is_decode() {
+         WebApiError::Decode(Box::new(e))
+      } else if let Some(status) = e.status() {
+         WebApiError::from_status(status.as_u16(), None, None)
+      } else {
+         WebApiError::Network(Box::new(e))
+      }
+   }
+}
+
+impl From<url::ParseError> for WebApiError {
+   fn from(e: url::ParseError) -> WebApiError {
+      WebApiError::InvalidUrl(e.to_string())
+   }
+}
+
+impl From<serde_json::Error> for WebApiError {
+   fn from(e: serde_json::Error) -> WebApiError {
+      WebApiError::Decode(Box::new(e))
+   }
+}
+
+impl From<std::io::Error> for WebApiError {
+   fn from(e: std::io::Error) -> WebApiError {
+      if e.kind() == std::io::ErrorKind::TimedOut {
+         WebApiError::Timeout
+      } else {
+         WebApiError::Network(Box::new(e))
+      }
+   }
+}
+
+pub trait ResponseExt: Sized {
+   /// ステータスコードがエラーを示している場合、レスポンスボディの
+   /// `{"error": "..."}`を読み取って[WebApiError]を返す
+   fn check_status(self) -> WebApiResult<Self>;
+}
+
+impl ResponseExt for Response {
+   fn check_status(self) -> WebApiResult<Response> {
+      let status = self.status();
+      if !status.is_client_error() && !status.is_server_error() {
+         return Ok(self);
+      }
+
+      let reset_time = reset_time_header(self.headers());
+
+      let error = self.text().ok()
+         .and_then(|body| parse_error_body(&body));
+
+      Err(WebApiError::from_status(status.as_u16(), error, reset_time))
+   }
+}
+
+pub trait AsyncResponseExt: Sized {
+   /// [ResponseExt::check_status]の非同期版
+   fn check_status_async(self) -> impl Future<Output = WebApiResult<Self>> + Send;
+}
+
+impl AsyncResponseExt for reqwest::Response {
+   async fn check_status_async(self) -> WebApiResult<reqwest::Response> {
+      let status = self.status();
+      if !status.is_client_error() && !status.is_server_error() {
+         return Ok(self);
+      }
+
+      let reset_time = reset_time_header(self.headers());
+
+      let error = self.text().await.ok()
+         .and_then(|body| parse_error_body(&body));
+
+      Err(WebApiError::from_status(status.as_u16(), error, reset_time))
+   }
+}
+
+fn reset_time_header(headers: &HeaderMap) -> Option<DateTime<Utc>> {
+   headers.get("X-RateLimit-Reset")
+      .and_then(|value| value.to_str().ok())
+      .and_then(parse_reset_time)
+}
+
+fn parse_error_body(body: &str) -> Option<String> {
+   #[derive(Deserialize)]
+   struct ErrorBody {
+      error: String,
+   }
+
+   serde_json::from_str::<ErrorBody>(body).ok()
+      .map(|body| body.error)
+}
+
+fn parse_reset_time(value: &str) -> Option<DateTime<Utc>> {
+   DateTime::parse_from_rfc3339(value).ok()
+      .map(|time| time.with_timezone(&Utc))
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use chrono::{TimeZone, Utc};
+   use super::{parse_error_body, parse_reset_time, WebApiError};
+
+   #[test]
+   fn error_body() {
+      assert_eq!(
+         Some("The access token was revoked".to_string()),
+         parse_error_body(r#"{"error":"The access token was revoked"}"#)
+      );
+
+      assert_eq!(None, parse_error_body("<html></html>"));
+      assert_eq!(None, parse_error_body(""));
+   }
+
+   #[test]
+   fn reset_time() {
+      assert_eq!(
+         Some(Utc.with_ymd_and_hms(2025, 1, 2, 3, 5, 0).unwrap()),
+         parse_reset_time("2025-01-02T03:05:00.000Z")
+      );
+
+      assert_eq!(None, parse_reset_time("300"));
+   }
+
+   #[test]
+   fn from_status() {
+      assert!(matches!(
+         WebApiError::from_status(401, Some("The access token is invalid".to_string()), None),
+         WebApiError::Unauthorized { error: Some(e) } if e == "The access token is invalid"
+      ));
+
+      assert!(matches!(
+         WebApiError::from_status(429, None, None),
+         WebApiError::RateLimited { reset_time: None }
+      ));
+
+      assert!(matches!(
+         WebApiError::from_status(404, Some("Record not found".to_string()), None),
+         WebApiError::HttpStatus { status: 404, error: Some(e) } if e == "Record not found"
+      ));
+   }
+}
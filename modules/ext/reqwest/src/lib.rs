@@ -13,10 +13,12 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::cell::LazyCell;
+pub mod callback;
+pub mod client_registry;
+pub mod error;
+pub mod rate_limit;
+pub mod replay;
+pub mod runtime;
+pub mod transport;
+pub mod unwrap_or_throw;
 
-use reqwest::blocking::Client;
-
-pub const CLIENT: LazyCell<Client> = LazyCell::new(
-   || Client::builder().build().unwrap()
-);
@@ -0,0 +1,516 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::blocking::Response;
+use reqwest::header::{HeaderMap, AUTHORIZATION, RETRY_AFTER};
+use reqwest::StatusCode;
+use url::Url;
+
+use crate::error::{WebApiError, WebApiResult};
+use crate::transport::{AsyncRequestBuilder, RequestBuilder};
+
+/// 残りがlimitのこの割合を下回ったら緊急でないリクエストを抑制し、
+/// ユーザーの操作によるリクエストのために残しておく
+const NON_URGENT_RESERVE_DIVISOR: u32 = 10;
+
+/// 緊急でないリクエストをリセット時刻まで待たせる最大の時間。
+/// これより長く待つ必要がある場合はリクエストを中止する
+const MAX_NON_URGENT_DELAY: TimeDelta = TimeDelta::seconds(10);
+
+const MAX_RETRIES: u32 = 3;
+const INITIAL_BACKOFF: TimeDelta = TimeDelta::seconds(1);
+
+/// リトライまでにこれより長く待つ必要がある場合はリトライせずエラーとする
+const MAX_RETRY_DELAY: TimeDelta = TimeDelta::seconds(60);
+
+static BUDGETS: LazyLock<Mutex<HashMap<BudgetKey, RateLimitBudget>>>
+   = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+thread_local! {
+   static PRIORITY: Cell<Priority> = const { Cell::new(Priority::Urgent) };
+
+   /// Noneでない場合、リクエスト先ではなくこのキーのレート制限の残りで抑制する
+   static THROTTLE_KEY: RefCell<Option<BudgetKey>> = const { RefCell::new(None) };
+}
+
+/// X-RateLimit-*ヘッダで通知されたレート制限の状態
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct RateLimitBudget {
+   pub limit: u32,
+   pub remaining: u32,
+   pub reset_time: DateTime<Utc>,
+}
+
+impl RateLimitBudget {
+   fn from_headers(headers: &HeaderMap) -> Option<RateLimitBudget> {
+      let header = |name: &str| headers.get(name)?.to_str().ok();
+
+      let budget = RateLimitBudget {
+         limit: header("X-RateLimit-Limit")?.parse().ok()?,
+         remaining: header("X-RateLimit-Remaining")?.parse().ok()?,
+         reset_time: DateTime::parse_from_rfc3339(header("X-RateLimit-Reset")?).ok()?
+            .with_timezone(&Utc),
+      };
+
+      Some(budget)
+   }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Priority {
+   /// ユーザーの操作によるリクエスト。レート制限の残りにかかわらず送信する
+   Urgent,
+   /// 先読みやアイコンのダウンロードなど。レート制限の残りが少ない場合は
+   /// リセット時刻まで待つか、中止する
+   NonUrgent,
+}
+
+/// レート制限はインスタンスごと、かつアクセストークン(アカウント)ごとに管理される。
+/// アクセストークンなしのリクエストはIPアドレスごとに制限される
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+struct BudgetKey {
+   origin: String,
+   /// メモリ上にアクセストークンを保持しないようハッシュ値で持つ
+   token_hash: Option<u64>,
+}
+
+impl BudgetKey {
+   fn new(url: &Url, access_token: Option<&str>) -> BudgetKey {
+      BudgetKey {
+         origin: url.origin().ascii_serialization(),
+         token_hash: access_token.map(|token| {
+            let mut hasher = DefaultHasher::new();
+            token.hash(&mut hasher);
+            hasher.finish()
+         }),
+      }
+   }
+
+   fn from_request(url: &Url, headers: &HeaderMap) -> BudgetKey {
+      let access_token = headers
+         .get(AUTHORIZATION)
+         .and_then(|value| value.to_str().ok())
+         .and_then(|value| value.strip_prefix("Bearer "));
+
+      BudgetKey::new(url, access_token)
+   }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Throttle {
+   Send,
+   Delay(TimeDelta),
+   Reject(DateTime<Utc>),
+}
+
+/// 現在のレート制限の状態を返す。
+/// まだレスポンスを受け取っていない場合やリセット時刻を過ぎている場合はNone
+pub fn budget(
+   instance_base_url: &Url,
+   access_token: Option<&str>
+) -> Option<RateLimitBudget> {
+   let key = BudgetKey::new(instance_base_url, access_token);
+   let budgets = BUDGETS.lock().unwrap();
+   budgets.get(&key)
+      .filter(|budget| budget.reset_time > Utc::now())
+      .cloned()
+}
+
+/// `f`の中で送信されるリクエストを[Priority::NonUrgent]として扱う
+pub fn non_urgent<R>(f: impl FnOnce() -> R) -> R {
+   struct Restore(Priority);
+
+   impl Drop for Restore {
+      fn drop(&mut self) {
+         PRIORITY.set(self.0);
+      }
+   }
+
+   let _restore = Restore(PRIORITY.replace(Priority::NonUrgent));
+   f()
+}
+
+/// [non_urgent]と同様だが、リクエスト先にかかわらず`instance_base_url`と
+/// `access_token`のレート制限の残りに応じて抑制する。
+/// アイコンなどインスタンスとは別のホストにあるメディアのダウンロードに使う
+pub fn non_urgent_for<R>(
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   f: impl FnOnce() -> R
+) -> R {
+   struct Restore(Option<BudgetKey>);
+
+   impl Drop for Restore {
+      fn drop(&mut self) {
+         THROTTLE_KEY.set(self.0.take());
+      }
+   }
+
+   let key = BudgetKey::new(instance_base_url, access_token);
+   let _restore = Restore(THROTTLE_KEY.replace(Some(key)));
+   non_urgent(f)
+}
+
+pub trait RateLimitedSend {
+   /// レート制限を考慮してリクエストを送信する。
+   ///
+   /// [non_urgent]の中で呼ばれた場合はレート制限の残りが少なければ待機または中止する。
+   /// 429 Too Many Requestsと503 Service Unavailableは指数バックオフでリトライし、
+   /// Retry-Afterヘッダがあればそれに従う。
+   /// ステータスコードのチェックは行わないため、必要に応じて
+   /// [ResponseExt::check_status][crate::error::ResponseExt::check_status]を呼ぶこと
+   fn send_rate_limited(self) -> WebApiResult<Response>;
+}
+
+impl RateLimitedSend for RequestBuilder<'_> {
+   fn send_rate_limited(self) -> WebApiResult<Response> {
+      let (transport, request) = self.build();
+      let mut request = request?;
+      let key = BudgetKey::from_request(request.url(), request.headers());
+      let throttle_key = THROTTLE_KEY.with_borrow(Clone::clone)
+         .unwrap_or_else(|| key.clone());
+
+      if let Some(delay) = throttle_delay(&throttle_key, PRIORITY.get())? {
+         thread::sleep(delay);
+      }
+
+      let mut attempt = 0;
+      loop {
+         let retry_request = request.try_clone();
+         let response = transport.execute(request)?;
+
+         let Some(delay) = retry_delay_after(&key, response.status(), response.headers(), attempt)
+            else { return Ok(response); };
+
+         // multipartなどボディを複製できないリクエストはリトライできない
+         let Some(retry_request) = retry_request else { return Ok(response); };
+
+         thread::sleep(delay);
+         attempt += 1;
+         request = retry_request;
+      }
+   }
+}
+
+pub trait AsyncRateLimitedSend {
+   /// [RateLimitedSend::send_rate_limited]の非同期版。
+   ///
+   /// 優先度はこのメソッドを呼んだ時点のものが使われるため、
+   /// [non_urgent]や[non_urgent_for]の中でFutureを生成すれば
+   /// awaitはその外で行ってもよい
+   fn send_rate_limited_async(self) -> impl Future<Output = WebApiResult<reqwest::Response>> + Send;
+}
+
+impl AsyncRateLimitedSend for AsyncRequestBuilder<'_> {
+   fn send_rate_limited_async(self) -> impl Future<Output = WebApiResult<reqwest::Response>> + Send {
+      let priority = PRIORITY.get();
+      let throttle_key = THROTTLE_KEY.with_borrow(Clone::clone);
+
+      async move {
+         let (transport, request) = self.build();
+         let mut request = request?;
+         let key = BudgetKey::from_request(request.url(), request.headers());
+         let throttle_key = throttle_key.unwrap_or_else(|| key.clone());
+
+         if let Some(delay) = throttle_delay(&throttle_key, priority)? {
+            tokio::time::sleep(delay).await;
+         }
+
+         let mut attempt = 0;
+         loop {
+            let retry_request = request.try_clone();
+            let response = transport.execute_async(request).await?;
+
+            let Some(delay) = retry_delay_after(&key, response.status(), response.headers(), attempt)
+               else { return Ok(response); };
+
+            let Some(retry_request) = retry_request else { return Ok(response); };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+            request = retry_request;
+         }
+      }
+   }
+}
+
+/// レート制限の残りに応じて送信前に待つ時間を返す。待つ必要がなければNone
+fn throttle_delay(key: &BudgetKey, priority: Priority) -> WebApiResult<Option<Duration>> {
+   let budget = BUDGETS.lock().unwrap().get(key).cloned();
+
+   match throttle(budget.as_ref(), priority, Utc::now()) {
+      Throttle::Send => Ok(None),
+      Throttle::Delay(delay) => Ok(Some(to_std(delay))),
+      Throttle::Reject(reset_time) => {
+         Err(WebApiError::RateLimited { reset_time: Some(reset_time) })
+      }
+   }
+}
+
+/// レスポンスのレート制限の状態を記録し、リトライするべきであれば
+/// リトライまでに待つ時間を返す
+fn retry_delay_after(
+   key: &BudgetKey,
+   status: StatusCode,
+   headers: &HeaderMap,
+   attempt: u32
+) -> Option<Duration> {
+   if let Some(budget) = RateLimitBudget::from_headers(headers) {
+      BUDGETS.lock().unwrap().insert(key.clone(), budget);
+   }
+
+   if status != StatusCode::TOO_MANY_REQUESTS
+      && status != StatusCode::SERVICE_UNAVAILABLE
+   {
+      return None;
+   }
+
+   if attempt >= MAX_RETRIES {
+      return None;
+   }
+
+   let delay = retry_delay(headers, attempt, Utc::now());
+   if delay > MAX_RETRY_DELAY {
+      return None;
+   }
+
+   Some(to_std(delay))
+}
+
+fn throttle(
+   budget: Option<&RateLimitBudget>,
+   priority: Priority,
+   now: DateTime<Utc>
+) -> Throttle {
+   let Some(budget) = budget else { return Throttle::Send; };
+
+   if priority == Priority::Urgent
+      || budget.reset_time <= now
+      || budget.remaining > budget.limit / NON_URGENT_RESERVE_DIVISOR
+   {
+      return Throttle::Send;
+   }
+
+   let delay = budget.reset_time - now;
+   if delay <= MAX_NON_URGENT_DELAY {
+      Throttle::Delay(delay)
+   } else {
+      Throttle::Reject(budget.reset_time)
+   }
+}
+
+/// Retry-After、X-RateLimit-Resetの順にヘッダを参照し、
+/// どちらもなければ指数バックオフで待ち時間を決める
+fn retry_delay(headers: &HeaderMap, attempt: u32, now: DateTime<Utc>) -> TimeDelta {
+   let retry_after = headers.get(RETRY_AFTER)
+      .and_then(|value| value.to_str().ok())
+      .and_then(|value| {
+         if let Ok(seconds) = value.trim().parse() {
+            Some(TimeDelta::seconds(seconds))
+         } else {
+            let time = DateTime::parse_from_rfc2822(value).ok()?;
+            Some(time.with_timezone(&Utc) - now)
+         }
+      });
+
+   let reset = || RateLimitBudget::from_headers(headers)
+      .map(|budget| budget.reset_time - now);
+
+   retry_after.or_else(reset)
+      .map(|delay| delay.max(TimeDelta::zero()))
+      .unwrap_or(INITIAL_BACKOFF * 2_i32.pow(attempt))
+}
+
+fn to_std(delay: TimeDelta) -> Duration {
+   delay.to_std().unwrap_or(Duration::ZERO)
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use std::thread::JoinHandle;
+   use chrono::{TimeDelta, TimeZone, Utc};
+   use reqwest::header::HeaderMap;
+   use url::Url;
+   use super::{retry_delay, throttle, Priority, RateLimitBudget, Throttle};
+
+   #[test]
+   fn throttle_non_urgent_requests() {
+      let now = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+      let budget = |remaining, reset_after| RateLimitBudget {
+         limit: 300,
+         remaining,
+         reset_time: now + TimeDelta::seconds(reset_after),
+      };
+
+      assert_eq!(Throttle::Send, throttle(None, Priority::NonUrgent, now));
+      assert_eq!(Throttle::Send, throttle(Some(&budget(100, 300)), Priority::NonUrgent, now));
+      assert_eq!(Throttle::Send, throttle(Some(&budget(0, 300)), Priority::Urgent, now));
+      assert_eq!(Throttle::Send, throttle(Some(&budget(0, -1)), Priority::NonUrgent, now));
+
+      assert_eq!(
+         Throttle::Delay(TimeDelta::seconds(5)),
+         throttle(Some(&budget(29, 5)), Priority::NonUrgent, now)
+      );
+
+      assert_eq!(
+         Throttle::Reject(now + TimeDelta::seconds(300)),
+         throttle(Some(&budget(0, 300)), Priority::NonUrgent, now)
+      );
+   }
+
+   #[test]
+   fn delay_before_retry() {
+      let now = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+      let headers = |pairs: &[(&'static str, &str)]| {
+         let mut headers = HeaderMap::new();
+         for &(name, value) in pairs {
+            headers.insert(name, value.parse().unwrap());
+         }
+         headers
+      };
+
+      assert_eq!(
+         TimeDelta::seconds(1),
+         retry_delay(&headers(&[]), 0, now)
+      );
+      assert_eq!(
+         TimeDelta::seconds(4),
+         retry_delay(&headers(&[]), 2, now)
+      );
+      assert_eq!(
+         TimeDelta::seconds(30),
+         retry_delay(&headers(&[("retry-after", "30")]), 2, now)
+      );
+      assert_eq!(
+         TimeDelta::seconds(90),
+         retry_delay(&headers(&[("retry-after", "Wed, 01 Jan 2025 00:01:30 GMT")]), 0, now)
+      );
+      assert_eq!(
+         TimeDelta::seconds(20),
+         retry_delay(
+            &headers(&[
+               ("x-ratelimit-limit", "300"),
+               ("x-ratelimit-remaining", "0"),
+               ("x-ratelimit-reset", "2025-01-01T00:00:20.000Z"),
+            ]),
+            0, now
+         )
+      );
+   }
+
+   #[test]
+   fn retry_service_unavailable() {
+      use crate::transport::{ReqwestTransport, Transport};
+      use super::RateLimitedSend;
+
+      let (url, server) = serve_service_unavailable_once();
+
+      let transport: &dyn Transport = &ReqwestTransport;
+      let response = transport
+         .get(url.join("api/v1/timelines/home").unwrap())
+         .bearer_auth("access token")
+         .send_rate_limited()
+         .unwrap();
+
+      assert_eq!(200, response.status().as_u16());
+      server.join().unwrap();
+
+      let budget = super::budget(&url, Some("access token")).unwrap();
+      assert_eq!(300, budget.limit);
+      assert_eq!(299, budget.remaining);
+      assert_eq!(None, super::budget(&url, Some("another token")));
+      assert_eq!(None, super::budget(&url, None));
+   }
+
+   #[test]
+   fn retry_service_unavailable_async() {
+      use crate::runtime::RUNTIME;
+      use crate::transport::{ReqwestTransport, Transport};
+      use super::AsyncRateLimitedSend;
+
+      let (url, server) = serve_service_unavailable_once();
+
+      let transport: &dyn Transport = &ReqwestTransport;
+      let response = RUNTIME.block_on(
+         transport
+            .get_async(url.join("api/v1/timelines/home").unwrap())
+            .bearer_auth("access token")
+            .send_rate_limited_async()
+      ).unwrap();
+
+      assert_eq!(200, response.status().as_u16());
+      server.join().unwrap();
+
+      let budget = super::budget(&url, Some("access token")).unwrap();
+      assert_eq!(299, budget.remaining);
+   }
+
+   /// 1回目は503、2回目は200を返すサーバーを起動する
+   fn serve_service_unavailable_once() -> (Url, JoinHandle<()>) {
+      use std::io::{BufRead, BufReader, Write};
+      use std::net::TcpListener;
+      use std::thread;
+
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let port = listener.local_addr().unwrap().port();
+
+      let server = thread::spawn(move || {
+         let responses = [
+            "HTTP/1.1 503 Service Unavailable\r\n\
+             Retry-After: 0\r\n\
+             Content-Length: 0\r\n\
+             Connection: close\r\n\
+             \r\n".to_string(),
+            format!(
+               "HTTP/1.1 200 OK\r\n\
+                X-RateLimit-Limit: 300\r\n\
+                X-RateLimit-Remaining: 299\r\n\
+                X-RateLimit-Reset: {}\r\n\
+                Content-Length: 2\r\n\
+                Connection: close\r\n\
+                \r\n\
+                []",
+               (Utc::now() + TimeDelta::minutes(5)).to_rfc3339()
+            ),
+         ];
+
+         for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+               line.clear();
+            }
+
+            stream.write_all(response.as_bytes()).unwrap();
+         }
+      });
+
+      let url = format!("http://127.0.0.1:{port}/").parse().unwrap();
+      (url, server)
+   }
+}
@@ -0,0 +1,597 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+
+use reqwest::blocking::{Request, Response};
+use reqwest::header::{
+   HeaderMap, HeaderName, HeaderValue, CONNECTION, CONTENT_ENCODING, CONTENT_LENGTH,
+   CONTENT_TYPE, TRANSFER_ENCODING,
+};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::ser::PrettyFormatter;
+use serde_json::Value;
+use url::{Position, Url};
+
+use crate::error::WebApiResult;
+use crate::transport::{ReqwestTransport, ResponseFuture, Transport};
+
+/// fixtureファイルに記録したレスポンスを順に返す[Transport]。
+/// 送信されたリクエストは記録され、fixtureに書かれたリクエストと一致しなければpanicする。
+///
+/// fixtureは1回のリクエストとレスポンスを1要素とするJSONの配列。
+///
+/// ```json
+/// [
+///    {
+///       "request": {
+///          "method": "POST",
+///          "url": "https://example.com/api/v1/accounts/1/follow",
+///          "headers": { "authorization": "Bearer access token" },
+///          "form": { "reblogs": "true", "languages[]": ["ja", "en"] }
+///       },
+///       "response": {
+///          "status": 200,
+///          "headers": { "x-ratelimit-remaining": "299" },
+///          "body": { "id": "1", "following": true }
+///       }
+///    }
+/// ]
+/// ```
+///
+/// * `request.url` - クエリはデコードしたうえで順序も含めて照合する
+/// * `request.headers` - 書かれたヘッダのみを照合する
+/// * `request.form`, `request.json`, `request.body` - それぞれフォーム、JSON、
+///   文字列としてボディを照合する。いずれもなければボディは照合しない
+/// * `response.status` - 省略時は200
+/// * `response.body` - 文字列はそのまま、それ以外はJSONとしてボディにする
+///
+/// fixtureは[RecordingTransport]で実際の通信から記録することもできる
+pub struct ReplayTransport {
+   exchanges: Mutex<VecDeque<Exchange>>,
+   requests: Mutex<Vec<RecordedRequest>>,
+}
+
+/// [ReplayTransport]に送信されたリクエスト
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+   pub method: Method,
+   pub url: Url,
+   pub headers: HeaderMap,
+   /// ボディがない場合、およびストリームのボディを非同期で送信した場合はNone
+   pub body: Option<Vec<u8>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Exchange {
+   request: ExpectedRequest,
+   response: CannedResponse,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExpectedRequest {
+   method: String,
+   url: Url,
+   #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+   headers: BTreeMap<String, String>,
+   #[serde(skip_serializing_if = "Option::is_none")]
+   form: Option<BTreeMap<String, FormValue>>,
+   #[serde(skip_serializing_if = "Option::is_none")]
+   json: Option<Value>,
+   #[serde(skip_serializing_if = "Option::is_none")]
+   body: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum FormValue {
+   One(String),
+   Many(Vec<String>),
+}
+
+#[derive(Serialize, Deserialize)]
+struct CannedResponse {
+   #[serde(default = "default_status")]
+   status: u16,
+   #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+   headers: BTreeMap<String, String>,
+   #[serde(default, skip_serializing_if = "Value::is_null")]
+   body: Value,
+}
+
+fn default_status() -> u16 {
+   200
+}
+
+impl ReplayTransport {
+   /// fixtureファイルを読み込む。読み込めなければpanicする
+   pub fn load(path: impl AsRef<Path>) -> ReplayTransport {
+      let path = path.as_ref();
+      let json = fs::read_to_string(path)
+         .unwrap_or_else(|e| panic!("cannot read fixture {}: {e}", path.display()));
+
+      ReplayTransport::from_json(&json)
+   }
+
+   pub fn from_json(json: &str) -> ReplayTransport {
+      let exchanges: Vec<Exchange> = serde_json::from_str(json)
+         .unwrap_or_else(|e| panic!("invalid fixture: {e}"));
+
+      ReplayTransport {
+         exchanges: Mutex::new(exchanges.into()),
+         requests: Mutex::new(Vec::new()),
+      }
+   }
+
+   /// これまでに送信されたリクエスト
+   pub fn requests(&self) -> Vec<RecordedRequest> {
+      self.requests.lock().unwrap().clone()
+   }
+
+   fn replay(&self, request: RecordedRequest) -> http::Response<Vec<u8>> {
+      let exchange = self.exchanges.lock().unwrap().pop_front()
+         .unwrap_or_else(|| panic!("unexpected request: {} {}", request.method, request.url));
+
+      exchange.request.verify(&request);
+      self.requests.lock().unwrap().push(request);
+
+      exchange.response.into_http_response()
+   }
+}
+
+impl Transport for ReplayTransport {
+   fn execute(&self, mut request: Request) -> WebApiResult<Response> {
+      // 実際の送信と同様にボディを読み切る。読み取りに失敗した場合はリクエストも失敗する
+      let body = match request.body_mut() {
+         Some(body) => Some(body.buffer()?.to_vec()),
+         None => None,
+      };
+
+      let recorded = RecordedRequest {
+         method: request.method().clone(),
+         url: request.url().clone(),
+         headers: request.headers().clone(),
+         body,
+      };
+
+      Ok(self.replay(recorded).into())
+   }
+
+   fn execute_async(&self, request: reqwest::Request) -> ResponseFuture<'_> {
+      let recorded = RecordedRequest {
+         method: request.method().clone(),
+         url: request.url().clone(),
+         headers: request.headers().clone(),
+         body: request.body().and_then(|body| body.as_bytes()).map(|bytes| bytes.to_vec()),
+      };
+
+      let response = self.replay(recorded).into();
+      Box::pin(async move { Ok(response) })
+   }
+}
+
+impl Drop for ReplayTransport {
+   fn drop(&mut self) {
+      if thread::panicking() { return; }
+
+      let exchanges = self.exchanges.get_mut().unwrap();
+      if let Some(exchange) = exchanges.front() {
+         panic!(
+            "{} request(s) not sent: {} {}",
+            exchanges.len(), exchange.request.method, exchange.request.url
+         );
+      }
+   }
+}
+
+/// 実際に通信し、そのリクエストとレスポンスを[ReplayTransport]のfixtureとして
+/// ファイルに書き出す[Transport]。
+///
+/// ファイルはリクエストのたびにそれまでのすべての通信で上書きされる。
+/// `authorization`ヘッダもそのまま書き出されるため、fixtureとしてコミットする前に
+/// アクセストークンを書き換えること
+pub struct RecordingTransport {
+   transport: Box<dyn Transport>,
+   path: PathBuf,
+   exchanges: Mutex<Vec<Exchange>>,
+}
+
+impl RecordingTransport {
+   /// [ReqwestTransport]で通信し、`path`に書き出す
+   pub fn new(path: impl Into<PathBuf>) -> RecordingTransport {
+      RecordingTransport::with_transport(ReqwestTransport, path)
+   }
+
+   /// `transport`で通信し、`path`に書き出す
+   pub fn with_transport(
+      transport: impl Transport + 'static,
+      path: impl Into<PathBuf>
+   ) -> RecordingTransport {
+      RecordingTransport {
+         transport: Box::new(transport),
+         path: path.into(),
+         exchanges: Mutex::new(Vec::new()),
+      }
+   }
+
+   fn record(
+      &self,
+      request: ExpectedRequest,
+      status: StatusCode,
+      headers: &HeaderMap,
+      body: &[u8]
+   ) -> WebApiResult<()> {
+      let exchange = Exchange {
+         request,
+         response: CannedResponse::from_parts(status, headers, body),
+      };
+
+      let mut exchanges = self.exchanges.lock().unwrap();
+      exchanges.push(exchange);
+
+      let mut json = Vec::new();
+      let formatter = PrettyFormatter::with_indent(b"   ");
+      let mut serializer = serde_json::Serializer::with_formatter(&mut json, formatter);
+      exchanges.serialize(&mut serializer)?;
+      json.push(b'\n');
+
+      fs::write(&self.path, json)?;
+      Ok(())
+   }
+}
+
+impl Transport for RecordingTransport {
+   fn execute(&self, mut request: Request) -> WebApiResult<Response> {
+      // ストリームのボディも送信前にバッファしておけば記録したうえで送信できる
+      let body = match request.body_mut() {
+         Some(body) => Some(body.buffer()?.to_vec()),
+         None => None,
+      };
+
+      let expected = ExpectedRequest::from_parts(
+         request.method(), request.url(), request.headers(), body.as_deref()
+      );
+
+      let response = self.transport.execute(request)?;
+      let status = response.status();
+      let headers = response.headers().clone();
+      let body = response.bytes()?;
+
+      self.record(expected, status, &headers, &body)?;
+
+      Ok(rebuild_response(status, headers, body.to_vec()).into())
+   }
+
+   fn execute_async(&self, request: reqwest::Request) -> ResponseFuture<'_> {
+      let expected = ExpectedRequest::from_parts(
+         request.method(),
+         request.url(),
+         request.headers(),
+         request.body().and_then(|body| body.as_bytes())
+      );
+
+      let response = self.transport.execute_async(request);
+
+      Box::pin(async move {
+         let response = response.await?;
+         let status = response.status();
+         let headers = response.headers().clone();
+         let body = response.bytes().await?;
+
+         self.record(expected, status, &headers, &body)?;
+
+         Ok(rebuild_response(status, headers, body.to_vec()).into())
+      })
+   }
+}
+
+fn rebuild_response(
+   status: StatusCode,
+   headers: HeaderMap,
+   body: Vec<u8>
+) -> http::Response<Vec<u8>> {
+   let mut response = http::Response::new(body);
+   *response.status_mut() = status;
+   *response.headers_mut() = headers;
+   response
+}
+
+impl ExpectedRequest {
+   fn from_parts(
+      method: &Method,
+      url: &Url,
+      headers: &HeaderMap,
+      body: Option<&[u8]>
+   ) -> ExpectedRequest {
+      let content_type = headers.get(CONTENT_TYPE)
+         .and_then(|v| v.to_str().ok())
+         .unwrap_or_default();
+
+      let mut form = None;
+      let mut json = None;
+      let mut text = None;
+
+      if let Some(body) = body.filter(|body| !body.is_empty()) {
+         if content_type.starts_with("application/x-www-form-urlencoded") {
+            let mut fields: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for (name, value) in url::form_urlencoded::parse(body) {
+               fields.entry(name.into_owned()).or_default().push(value.into_owned());
+            }
+
+            form = Some(
+               fields.into_iter()
+                  .map(|(name, mut values)| {
+                     let value = if values.len() == 1 && !name.ends_with("[]") {
+                        FormValue::One(values.remove(0))
+                     } else {
+                        FormValue::Many(values)
+                     };
+                     (name, value)
+                  })
+                  .collect()
+            );
+         } else if content_type.starts_with("application/json") {
+            json = serde_json::from_slice(body).ok();
+         } else if !content_type.starts_with("multipart/") {
+            // multipartはboundaryが毎回変わるため照合できない
+            text = String::from_utf8(body.to_vec()).ok();
+         }
+      }
+
+      ExpectedRequest {
+         method: method.to_string(),
+         url: url.clone(),
+         headers: headers.iter()
+            .filter_map(|(name, value)| {
+               Some((name.to_string(), value.to_str().ok()?.to_string()))
+            })
+            .collect(),
+         form,
+         json,
+         body: text,
+      }
+   }
+
+   fn verify(&self, actual: &RecordedRequest) {
+      assert_eq!(self.method, actual.method.as_str(), "method of {}", actual.url);
+
+      // クエリはエンコードの表記揺れを無視するためデコードしてから比較する
+      assert_eq!(&self.url[..Position::AfterPath], &actual.url[..Position::AfterPath]);
+      assert_eq!(
+         self.url.query_pairs().collect::<Vec<_>>(),
+         actual.url.query_pairs().collect::<Vec<_>>(),
+         "query of {} {}", actual.method, actual.url
+      );
+
+      for (name, value) in &self.headers {
+         assert_eq!(
+            Some(value.as_str()),
+            actual.headers.get(name).and_then(|v| v.to_str().ok()),
+            "header {name} of {} {}", actual.method, actual.url
+         );
+      }
+
+      let body = actual.body.as_deref().unwrap_or_default();
+
+      if let Some(form) = &self.form {
+         let expected: BTreeMap<&str, Vec<&str>> = form.iter()
+            .map(|(name, value)| match value {
+               FormValue::One(value) => (name.as_str(), vec![value.as_str()]),
+               FormValue::Many(values) => (name.as_str(), values.iter().map(String::as_str).collect()),
+            })
+            .collect();
+
+         let mut actual_form: BTreeMap<String, Vec<String>> = BTreeMap::new();
+         for (name, value) in url::form_urlencoded::parse(body) {
+            actual_form.entry(name.into_owned()).or_default().push(value.into_owned());
+         }
+         let actual_form: BTreeMap<&str, Vec<&str>> = actual_form.iter()
+            .map(|(name, values)| (name.as_str(), values.iter().map(String::as_str).collect()))
+            .collect();
+
+         assert_eq!(expected, actual_form, "form of {} {}", actual.method, actual.url);
+      }
+
+      if let Some(json) = &self.json {
+         let actual_json: Value = serde_json::from_slice(body)
+            .unwrap_or_else(|e| panic!("body of {} {} is not JSON: {e}", actual.method, actual.url));
+
+         assert_eq!(json, &actual_json, "JSON of {} {}", actual.method, actual.url);
+      }
+
+      if let Some(expected_body) = &self.body {
+         assert_eq!(
+            expected_body.as_bytes(), body,
+            "body of {} {}", actual.method, actual.url
+         );
+      }
+   }
+}
+
+impl CannedResponse {
+   fn from_parts(status: StatusCode, headers: &HeaderMap, body: &[u8]) -> CannedResponse {
+      // ボディの長さや転送方法は再生時のボディと一致しなくなるため記録しない
+      let headers = headers.iter()
+         .filter(|(name, _)| {
+            ![CONTENT_LENGTH, TRANSFER_ENCODING, CONTENT_ENCODING, CONNECTION].contains(name)
+         })
+         .filter_map(|(name, value)| {
+            Some((name.to_string(), value.to_str().ok()?.to_string()))
+         })
+         .collect();
+
+      // 文字列のJSONは再生時にそのままボディにされてしまうためJSONとしては記録しない
+      let body = match serde_json::from_slice(body) {
+         Ok(Value::String(_)) | Err(_) => {
+            Value::String(String::from_utf8_lossy(body).into_owned())
+         }
+         Ok(json) => json,
+      };
+
+      CannedResponse {
+         status: status.as_u16(),
+         headers,
+         body: match body {
+            Value::String(body) if body.is_empty() => Value::Null,
+            body => body,
+         },
+      }
+   }
+
+   fn into_http_response(self) -> http::Response<Vec<u8>> {
+      let mut builder = http::Response::builder().status(self.status);
+
+      let headers = builder.headers_mut().unwrap();
+      for (name, value) in &self.headers {
+         headers.append(
+            HeaderName::from_bytes(name.as_bytes()).unwrap(),
+            HeaderValue::from_str(value).unwrap()
+         );
+      }
+
+      let body = match self.body {
+         Value::Null => Vec::new(),
+         Value::String(body) => body.into_bytes(),
+         body => {
+            headers.entry(CONTENT_TYPE)
+               .or_insert(HeaderValue::from_static("application/json"));
+            serde_json::to_vec(&body).unwrap()
+         }
+      };
+
+      builder.body(body).unwrap()
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use std::{env, fs, process};
+
+   use crate::runtime::RUNTIME;
+   use crate::transport::Transport;
+   use super::{RecordingTransport, ReplayTransport};
+
+   const FIXTURE: &str = r#"[
+      {
+         "request": {
+            "method": "POST",
+            "url": "https://example.com/api/v1/statuses?id[]=a%20b",
+            "headers": { "authorization": "Bearer access token" },
+            "form": { "status": "content", "media_ids[]": ["1", "2"] }
+         },
+         "response": {
+            "status": 201,
+            "headers": { "x-ratelimit-remaining": "299" },
+            "body": { "id": "status id" }
+         }
+      }
+   ]"#;
+
+   #[test]
+   fn replay_response() {
+      let replay = ReplayTransport::from_json(FIXTURE);
+      let transport: &dyn Transport = &replay;
+
+      let response = transport
+         .post("https://example.com/api/v1/statuses?id%5B%5D=a+b".parse().unwrap())
+         .bearer_auth("access token")
+         .form(&[("media_ids[]", "1"), ("status", "content"), ("media_ids[]", "2")])
+         .send()
+         .unwrap();
+
+      assert_eq!(201, response.status().as_u16());
+      assert_eq!("299", response.headers()["x-ratelimit-remaining"]);
+      assert_eq!("application/json", response.headers()["content-type"]);
+      assert_eq!(r#"{"id":"status id"}"#, response.text().unwrap());
+
+      assert_eq!(1, replay.requests().len());
+   }
+
+   #[test]
+   #[should_panic(expected = "form of POST")]
+   fn panic_on_unexpected_request() {
+      let replay = ReplayTransport::from_json(FIXTURE);
+      let transport: &dyn Transport = &replay;
+
+      let _ = transport
+         .post("https://example.com/api/v1/statuses?id[]=a%20b".parse().unwrap())
+         .bearer_auth("access token")
+         .form(&[("status", "content"), ("media_ids[]", "1")])
+         .send();
+   }
+
+   #[test]
+   fn record_and_replay() {
+      let path = env::temp_dir().join(format!("record_and_replay_{}.json", process::id()));
+
+      let fixture = FIXTURE.trim_end().trim_end_matches(']').to_string() + r#",
+         {
+            "request": {
+               "method": "GET",
+               "url": "https://example.com/api/v1/timelines/home?limit=40"
+            },
+            "response": {
+               "headers": { "link": "<https://example.com/api/v1/timelines/home?max_id=1>; rel=\"next\"" },
+               "body": []
+            }
+         }
+      ]"#;
+
+      let send = |transport: &dyn Transport| {
+         let response = transport
+            .post("https://example.com/api/v1/statuses?id[]=a%20b".parse().unwrap())
+            .bearer_auth("access token")
+            .form(&[("status", "content"), ("media_ids[]", "1"), ("media_ids[]", "2")])
+            .send()
+            .unwrap();
+
+         assert_eq!(201, response.status().as_u16());
+         assert_eq!(r#"{"id":"status id"}"#, response.text().unwrap());
+
+         let response = RUNTIME
+            .block_on(
+               transport
+                  .get_async("https://example.com/api/v1/timelines/home?limit=40".parse().unwrap())
+                  .send()
+            )
+            .unwrap();
+
+         assert!(response.headers()["link"].to_str().unwrap().contains("max_id=1"));
+         assert_eq!("[]", RUNTIME.block_on(response.text()).unwrap());
+      };
+
+      let recording = RecordingTransport::with_transport(
+         ReplayTransport::from_json(&fixture),
+         &path
+      );
+      send(&recording);
+
+      // 記録したfixtureで同じリクエストを再生できる
+      let replay = ReplayTransport::load(&path);
+      send(&replay);
+      assert_eq!(2, replay.requests().len());
+
+      let recorded = fs::read_to_string(&path).unwrap();
+      assert!(recorded.contains(r#""media_ids[]": ["#));
+      assert!(recorded.contains(r#""authorization": "Bearer access token""#));
+
+      fs::remove_file(&path).unwrap();
+   }
+}
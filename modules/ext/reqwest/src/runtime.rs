@@ -0,0 +1,29 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::LazyLock;
+
+use tokio::runtime::{Builder, Runtime};
+
+/// 非同期のWebAPIを実行する共有のランタイム。
+/// レスポンスを待っている間はスレッドを占有しないため、
+/// 多数のリクエストを同時に送ってもワーカースレッドはCPUのコア数で足りる
+pub static RUNTIME: LazyLock<Runtime> = LazyLock::new(|| {
+   Builder::new_multi_thread()
+      .thread_name("probosqis-webapi")
+      .enable_all()
+      .build()
+      .expect("failed to build the tokio runtime")
+});
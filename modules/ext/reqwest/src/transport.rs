@@ -0,0 +1,198 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use reqwest::blocking::multipart::Form;
+use reqwest::blocking::{Request, Response};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::Method;
+use serde::Serialize;
+use url::Url;
+
+use crate::client_registry::{async_client_for, client_for};
+use crate::error::WebApiResult;
+
+pub type ResponseFuture<'a>
+   = Pin<Box<dyn Future<Output = WebApiResult<reqwest::Response>> + Send + 'a>>;
+
+/// Web APIのリクエストを送信する。
+///
+/// 通常は[ReqwestTransport]を使う。テストでは
+/// [ReplayTransport][crate::replay::ReplayTransport]を使うことで
+/// fixtureファイルに記録したレスポンスを返すことができる
+pub trait Transport: Send + Sync {
+   fn execute(&self, request: Request) -> WebApiResult<Response>;
+
+   /// [Transport::execute]の非同期版
+   fn execute_async(&self, request: reqwest::Request) -> ResponseFuture<'_>;
+}
+
+impl dyn Transport + '_ {
+   pub fn request(&self, method: Method, url: Url) -> RequestBuilder<'_> {
+      RequestBuilder {
+         transport: self,
+         inner: client_for(&url).request(method, url),
+      }
+   }
+
+   pub fn get(&self, url: Url) -> RequestBuilder<'_> {
+      self.request(Method::GET, url)
+   }
+
+   pub fn post(&self, url: Url) -> RequestBuilder<'_> {
+      self.request(Method::POST, url)
+   }
+
+   pub fn put(&self, url: Url) -> RequestBuilder<'_> {
+      self.request(Method::PUT, url)
+   }
+
+   pub fn delete(&self, url: Url) -> RequestBuilder<'_> {
+      self.request(Method::DELETE, url)
+   }
+
+   /// [request][Self::request]の非同期版
+   pub fn request_async(&self, method: Method, url: Url) -> AsyncRequestBuilder<'_> {
+      AsyncRequestBuilder {
+         transport: self,
+         inner: async_client_for(&url).request(method, url),
+      }
+   }
+
+   pub fn get_async(&self, url: Url) -> AsyncRequestBuilder<'_> {
+      self.request_async(Method::GET, url)
+   }
+
+   pub fn post_async(&self, url: Url) -> AsyncRequestBuilder<'_> {
+      self.request_async(Method::POST, url)
+   }
+
+   pub fn put_async(&self, url: Url) -> AsyncRequestBuilder<'_> {
+      self.request_async(Method::PUT, url)
+   }
+
+   pub fn delete_async(&self, url: Url) -> AsyncRequestBuilder<'_> {
+      self.request_async(Method::DELETE, url)
+   }
+}
+
+/// [client_registry][crate::client_registry]のクライアントで実際に通信する[Transport]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReqwestTransport;
+
+impl Transport for ReqwestTransport {
+   fn execute(&self, request: Request) -> WebApiResult<Response> {
+      Ok(client_for(request.url()).execute(request)?)
+   }
+
+   fn execute_async(&self, request: reqwest::Request) -> ResponseFuture<'_> {
+      let response = async_client_for(request.url()).execute(request);
+      Box::pin(async move { Ok(response.await?) })
+   }
+}
+
+/// [Transport]を通して送信するリクエストのビルダー。
+/// [reqwest::blocking::RequestBuilder]のうちWeb APIの呼び出しに使うものを持つ
+pub struct RequestBuilder<'t> {
+   transport: &'t dyn Transport,
+   inner: reqwest::blocking::RequestBuilder,
+}
+
+impl<'t> RequestBuilder<'t> {
+   pub fn header<K, V>(self, key: K, value: V) -> RequestBuilder<'t>
+      where HeaderName: TryFrom<K>,
+            <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+            HeaderValue: TryFrom<V>,
+            <HeaderValue as TryFrom<V>>::Error: Into<http::Error>
+   {
+      RequestBuilder { inner: self.inner.header(key, value), ..self }
+   }
+
+   pub fn bearer_auth(self, token: impl Display) -> RequestBuilder<'t> {
+      RequestBuilder { inner: self.inner.bearer_auth(token), ..self }
+   }
+
+   /// クライアントに設定されたタイムアウトをこのリクエストのみ上書きする
+   pub fn timeout(self, timeout: Duration) -> RequestBuilder<'t> {
+      RequestBuilder { inner: self.inner.timeout(timeout), ..self }
+   }
+
+   pub fn form<T: Serialize + ?Sized>(self, form: &T) -> RequestBuilder<'t> {
+      RequestBuilder { inner: self.inner.form(form), ..self }
+   }
+
+   pub fn json<T: Serialize + ?Sized>(self, json: &T) -> RequestBuilder<'t> {
+      RequestBuilder { inner: self.inner.json(json), ..self }
+   }
+
+   pub fn multipart(self, form: Form) -> RequestBuilder<'t> {
+      RequestBuilder { inner: self.inner.multipart(form), ..self }
+   }
+
+   /// レート制限を考慮せずに送信する。通常は
+   /// [RateLimitedSend::send_rate_limited][crate::rate_limit::RateLimitedSend::send_rate_limited]
+   /// を使うこと
+   pub fn send(self) -> WebApiResult<Response> {
+      let (transport, request) = self.build();
+      transport.execute(request?)
+   }
+
+   pub(crate) fn build(self) -> (&'t dyn Transport, WebApiResult<Request>) {
+      (self.transport, self.inner.build().map_err(Into::into))
+   }
+}
+
+/// [RequestBuilder]の非同期版
+pub struct AsyncRequestBuilder<'t> {
+   transport: &'t dyn Transport,
+   inner: reqwest::RequestBuilder,
+}
+
+impl<'t> AsyncRequestBuilder<'t> {
+   pub fn header<K, V>(self, key: K, value: V) -> AsyncRequestBuilder<'t>
+      where HeaderName: TryFrom<K>,
+            <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+            HeaderValue: TryFrom<V>,
+            <HeaderValue as TryFrom<V>>::Error: Into<http::Error>
+   {
+      AsyncRequestBuilder { inner: self.inner.header(key, value), ..self }
+   }
+
+   pub fn bearer_auth(self, token: impl Display) -> AsyncRequestBuilder<'t> {
+      AsyncRequestBuilder { inner: self.inner.bearer_auth(token), ..self }
+   }
+
+   pub fn form<T: Serialize + ?Sized>(self, form: &T) -> AsyncRequestBuilder<'t> {
+      AsyncRequestBuilder { inner: self.inner.form(form), ..self }
+   }
+
+   pub fn json<T: Serialize + ?Sized>(self, json: &T) -> AsyncRequestBuilder<'t> {
+      AsyncRequestBuilder { inner: self.inner.json(json), ..self }
+   }
+
+   /// [RequestBuilder::send]の非同期版
+   pub async fn send(self) -> WebApiResult<reqwest::Response> {
+      let (transport, request) = self.build();
+      transport.execute_async(request?).await
+   }
+
+   pub(crate) fn build(self) -> (&'t dyn Transport, WebApiResult<reqwest::Request>) {
+      (self.transport, self.inner.build().map_err(Into::into))
+   }
+}
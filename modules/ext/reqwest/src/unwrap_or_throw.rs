@@ -0,0 +1,122 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![cfg(feature="jvm")]
+
+use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+use jni::JNIEnv;
+use jni::objects::{JObject, JThrowable, JValue};
+use panoptiqon::convert_jvm::CloneIntoJvm;
+use panoptiqon::jvm_type::JvmType;
+use panoptiqon::jvm_types::{JvmException, JvmLong, JvmNullable, JvmString};
+
+use crate::error::WebApiError;
+
+/// [WebApiError]をその種類に応じた
+/// `com.wcaokaze.probosqis.ext.kotlin.webapi.WebApiException`のサブクラスとして
+/// throwする。WebApiError以外のエラーは`java.io.IOException`としてthrowする
+pub trait UnwrapOrThrowWebApiException: UnwrapOrThrow<Err = anyhow::Error> + Sized {
+   fn unwrap_or_throw_web_api_exception(self, env: &mut JNIEnv) -> Self::Output {
+      self.unwrap_or_throw_exception(env, |env, err| {
+         JvmException::from_j_throwable(new_exception(env, err))
+      })
+   }
+}
+
+impl<T> UnwrapOrThrowWebApiException for T
+   where T: UnwrapOrThrow<Err = anyhow::Error>
+{}
+
+/// 戻り値のないJNI関数のために、[UnwrapOrThrowWebApiException]と同じ例外をthrowする
+pub fn throw_web_api_exception(env: &mut JNIEnv, err: &anyhow::Error) {
+   let exception = new_exception(env, err);
+   env.throw(exception).unwrap();
+}
+
+pub(crate) fn new_exception<'local>(
+   env: &mut JNIEnv<'local>,
+   err: &anyhow::Error
+) -> JThrowable<'local> {
+   let message: JvmString = err.to_string().clone_into_jvm(env);
+
+   let Some(web_api_error) = err.downcast_ref::<WebApiError>() else {
+      if let Some(e) = err.downcast_ref::<reqwest::Error>() {
+         let class = if e.is_timeout() { "TimeoutException" } else { "NetworkException" };
+         return new_message_exception(env, class, message);
+      }
+
+      return JThrowable::from(
+         env.new_object(
+            "java/io/IOException", "(Ljava/lang/String;)V",
+            &[message.j_string().into()]
+         ).unwrap()
+      );
+   };
+
+   match web_api_error {
+      WebApiError::Network(_) => new_message_exception(env, "NetworkException", message),
+      WebApiError::Timeout => new_message_exception(env, "TimeoutException", message),
+      WebApiError::HttpStatus { status, error } => {
+         let error: JvmNullable<JvmString> = error.clone_into_jvm(env);
+         new_web_api_exception(
+            env, "HttpStatusException", "(ILjava/lang/String;)V",
+            &[JValue::Int(*status as i32), error.j_object().into()]
+         )
+      }
+      WebApiError::Unauthorized { error } => {
+         let error: JvmNullable<JvmString> = error.clone_into_jvm(env);
+         new_web_api_exception(
+            env, "UnauthorizedException", "(Ljava/lang/String;)V",
+            &[error.j_object().into()]
+         )
+      }
+      WebApiError::RateLimited { reset_time } => {
+         let reset_time: JvmNullable<JvmLong> = reset_time
+            .map(|t| t.timestamp_millis())
+            .clone_into_jvm(env);
+
+         new_web_api_exception(
+            env, "RateLimitException", "(Ljava/lang/Long;)V",
+            &[reset_time.j_object().into()]
+         )
+      }
+      WebApiError::Decode(_) => new_message_exception(env, "JsonDecodeException", message),
+      WebApiError::UnsupportedServer(_) => new_message_exception(env, "UnsupportedServerException", message),
+      WebApiError::InvalidUrl(_) => new_message_exception(env, "InvalidUrlException", message),
+   }
+}
+
+fn new_message_exception<'local>(
+   env: &mut JNIEnv<'local>,
+   class_name: &str,
+   message: JvmString<'local>
+) -> JThrowable<'local> {
+   new_web_api_exception(
+      env, class_name, "(Ljava/lang/String;)V",
+      &[message.j_string().into()]
+   )
+}
+
+fn new_web_api_exception<'local>(
+   env: &mut JNIEnv<'local>,
+   class_name: &str,
+   signature: &str,
+   args: &[JValue]
+) -> JThrowable<'local> {
+   let class = format!("com/wcaokaze/probosqis/ext/kotlin/webapi/{class_name}");
+   let exception: JObject = env.new_object(class, signature, args).unwrap();
+   JThrowable::from(exception)
+}
@@ -108,7 +108,26 @@ data class Account(
    )
 
    @Serializable
-   data class Id(val instanceUrl: Url, val local: LocalId)
+   data class Id(val instanceUrl: Url, val local: LocalId) {
+      constructor(
+         rawInstanceUrl: String,
+         rawLocalId: String,
+         @Suppress("UNUSED_PARAMETER")
+         dummy: Unit?
+      ) : this(
+         Url(rawInstanceUrl),
+         LocalId(rawLocalId)
+      )
+
+      val rawInstanceUrl: String
+         get() = instanceUrl.raw
+
+      val rawLocalId: String
+         get() = local.value
+
+      val dummy: Unit?
+         get() = null
+   }
 
    @Serializable
    @JvmInline
@@ -51,6 +51,10 @@ data class CustomEmoji(
    val rawStaticImageUrl: String?
       get() = staticImageUrl?.raw
 
+   fun displayUrl(reducesMotion: Boolean): Url {
+      return if (reducesMotion) { staticImageUrl ?: imageUrl } else { imageUrl }
+   }
+
    val dummy: Unit?
       get() = null
 }
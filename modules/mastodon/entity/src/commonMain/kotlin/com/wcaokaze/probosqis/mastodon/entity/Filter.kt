@@ -77,6 +77,7 @@ data class Filter(
       companion object {
          val WARN = Action("warn")
          val HIDE = Action("hide")
+         val BLUR = Action("blur")
       }
    }
 
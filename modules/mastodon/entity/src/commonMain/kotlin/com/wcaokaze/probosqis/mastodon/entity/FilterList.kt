@@ -0,0 +1,42 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.entity
+
+import com.wcaokaze.probosqis.ext.kotlin.Url
+
+/**
+ * [accountId]のアカウントが設定しているフィルタの一覧
+ */
+data class FilterList(
+   val accountId: Account.Id,
+   val filters: List<Filter>,
+) {
+   constructor(
+      rawInstanceUrl: String,
+      rawAccountLocalId: String,
+      filters: List<Filter>,
+   ) : this(
+      Account.Id(Url(rawInstanceUrl), Account.LocalId(rawAccountLocalId)),
+      filters,
+   )
+
+   val rawInstanceUrl: String
+      get() = accountId.instanceUrl.raw
+
+   val rawAccountLocalId: String
+      get() = accountId.local.value
+}
@@ -0,0 +1,92 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.entity
+
+import com.wcaokaze.probosqis.ext.kotlin.Url
+import com.wcaokaze.probosqis.panoptiqon.Cache
+import kotlinx.datetime.Instant
+
+/**
+ * 通知。
+ *
+ * グループ化された通知の場合、[accounts]に通知を発生させたアカウントのうち
+ * いくつかが含まれる。グループ化されていない通知の場合[accounts]は1件になる
+ */
+data class Notification(
+   val id: Id,
+   val type: Type,
+   val createdTime: Instant?,
+   val accounts: List<Cache<Account>>,
+   val status: Cache<Status>?,
+   val groupKey: String?,
+   val notificationCount: Long?,
+) {
+   constructor(
+      rawInstanceUrl: String,
+      rawLocalId: String,
+      rawType: String,
+      createdTimeEpochMillis: Long?,
+      accounts: List<Cache<Account>>,
+      status: Cache<Status>?,
+      groupKey: String?,
+      notificationCount: Long?,
+   ) : this(
+      Id(Url(rawInstanceUrl), LocalId(rawLocalId)),
+      Type(rawType),
+      createdTimeEpochMillis?.let(Instant::fromEpochMilliseconds),
+      accounts,
+      status,
+      groupKey,
+      notificationCount,
+   )
+
+   val rawInstanceUrl: String
+      get() = id.instanceUrl.raw
+
+   val rawLocalId: String
+      get() = id.local.raw
+
+   val rawType: String
+      get() = type.raw
+
+   val createdTimeEpochMillis: Long?
+      get() = createdTime?.toEpochMilliseconds()
+
+   data class Id(
+      val instanceUrl: Url,
+      val local: LocalId
+   )
+
+   @JvmInline
+   value class LocalId(val raw: String)
+
+   @JvmInline
+   value class Type(val raw: String) {
+      companion object {
+         val MENTION        = Type("mention")
+         val BOOST          = Type("reblog")
+         val FAVORITE       = Type("favourite")
+         val FOLLOW         = Type("follow")
+         val FOLLOW_REQUEST = Type("follow_request")
+         val POLL           = Type("poll")
+         val UPDATE         = Type("update")
+         val STATUS         = Type("status")
+         val ADMIN_SIGN_UP  = Type("admin.sign_up")
+         val ADMIN_REPORT   = Type("admin.report")
+      }
+   }
+}
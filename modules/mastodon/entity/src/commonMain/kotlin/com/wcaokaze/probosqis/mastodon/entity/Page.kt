@@ -0,0 +1,37 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.entity
+
+/**
+ * タイムラインなどのページングされたリストの1ページ分
+ */
+data class Page<out T>(
+   val items: List<T>,
+   /** このページより古い要素を取得するためのカーソル */
+   val next: PageCursor?,
+   /** このページより新しい要素を取得するためのカーソル */
+   val prev: PageCursor?,
+)
+
+/**
+ * 最初のページを取得する場合はすべてnullのPageCursorを使う
+ */
+data class PageCursor(
+   val maxId: String? = null,
+   val sinceId: String? = null,
+   val minId: String? = null,
+)
@@ -0,0 +1,85 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.entity
+
+import com.wcaokaze.probosqis.ext.kotlin.Url
+
+/**
+ * ログイン中のアカウントから見た[id]のアカウントとの関係
+ *
+ * @param languages
+ *   フォロー中のアカウントの投稿のうちホームタイムラインに表示する言語。
+ *   空の場合すべての言語
+ */
+data class Relationship(
+   val id: Account.Id,
+   val isFollowing: Boolean?,
+   val isShowingBoosts: Boolean?,
+   val isNotifying: Boolean?,
+   val languages: List<String>,
+   val isFollowedBy: Boolean?,
+   val isBlocking: Boolean?,
+   val isBlockedBy: Boolean?,
+   val isMuting: Boolean?,
+   val isMutingNotifications: Boolean?,
+   val isRequested: Boolean?,
+   val isRequestedBy: Boolean?,
+   val isDomainBlocking: Boolean?,
+   val isEndorsed: Boolean?,
+   val note: String?,
+) {
+   constructor(
+      rawInstanceUrl: String,
+      rawLocalId: String,
+      isFollowing: Boolean?,
+      isShowingBoosts: Boolean?,
+      isNotifying: Boolean?,
+      languages: List<String>,
+      isFollowedBy: Boolean?,
+      isBlocking: Boolean?,
+      isBlockedBy: Boolean?,
+      isMuting: Boolean?,
+      isMutingNotifications: Boolean?,
+      isRequested: Boolean?,
+      isRequestedBy: Boolean?,
+      isDomainBlocking: Boolean?,
+      isEndorsed: Boolean?,
+      note: String?,
+   ) : this(
+      Account.Id(Url(rawInstanceUrl), Account.LocalId(rawLocalId)),
+      isFollowing,
+      isShowingBoosts,
+      isNotifying,
+      languages,
+      isFollowedBy,
+      isBlocking,
+      isBlockedBy,
+      isMuting,
+      isMutingNotifications,
+      isRequested,
+      isRequestedBy,
+      isDomainBlocking,
+      isEndorsed,
+      note,
+   )
+
+   val rawInstanceUrl: String
+      get() = id.instanceUrl.raw
+
+   val rawLocalId: String
+      get() = id.local.value
+}
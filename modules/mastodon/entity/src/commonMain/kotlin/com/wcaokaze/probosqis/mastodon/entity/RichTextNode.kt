@@ -0,0 +1,120 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.entity
+
+import com.wcaokaze.probosqis.ext.kotlin.Url
+
+sealed class RichTextNode {
+   data class Text(val text: String) : RichTextNode()
+
+   data class Paragraph(val children: List<RichTextNode>) : RichTextNode()
+
+   data class Link(
+      val url: Url,
+      val children: List<RichTextNode>,
+   ) : RichTextNode() {
+      constructor(
+         rawUrl: String,
+         children: List<RichTextNode>,
+         @Suppress("UNUSED_PARAMETER")
+         dummy: Unit?,
+      ) : this(
+         Url(rawUrl),
+         children,
+      )
+
+      val rawUrl: String
+         get() = url.raw
+
+      val dummy: Unit?
+         get() = null
+   }
+
+   data class Mention(
+      val accountId: Account.Id?,
+      val acct: String?,
+      val url: Url,
+      val children: List<RichTextNode>,
+   ) : RichTextNode() {
+      constructor(
+         accountId: Account.Id?,
+         acct: String?,
+         rawUrl: String,
+         children: List<RichTextNode>,
+         @Suppress("UNUSED_PARAMETER")
+         dummy: Unit?,
+      ) : this(
+         accountId,
+         acct,
+         Url(rawUrl),
+         children,
+      )
+
+      val rawUrl: String
+         get() = url.raw
+
+      val dummy: Unit?
+         get() = null
+   }
+
+   data class Hashtag(
+      val name: String,
+      val url: Url,
+      val children: List<RichTextNode>,
+   ) : RichTextNode() {
+      constructor(
+         name: String,
+         rawUrl: String,
+         children: List<RichTextNode>,
+         @Suppress("UNUSED_PARAMETER")
+         dummy: Unit?,
+      ) : this(
+         name,
+         Url(rawUrl),
+         children,
+      )
+
+      val rawUrl: String
+         get() = url.raw
+
+      val dummy: Unit?
+         get() = null
+   }
+
+   data class Emoji(val emoji: CustomEmoji) : RichTextNode()
+
+   data class Strong(val children: List<RichTextNode>) : RichTextNode()
+
+   data class Emphasis(val children: List<RichTextNode>) : RichTextNode()
+
+   data class Deleted(val children: List<RichTextNode>) : RichTextNode()
+
+   data class Code(val children: List<RichTextNode>) : RichTextNode()
+
+   data class Preformatted(val children: List<RichTextNode>) : RichTextNode()
+
+   data class BlockQuote(val children: List<RichTextNode>) : RichTextNode()
+
+   data class UnorderedList(val children: List<RichTextNode>) : RichTextNode()
+
+   data class OrderedList(
+      val start: Long?,
+      val children: List<RichTextNode>,
+   ) : RichTextNode()
+
+   data class ListItem(val children: List<RichTextNode>) : RichTextNode()
+}
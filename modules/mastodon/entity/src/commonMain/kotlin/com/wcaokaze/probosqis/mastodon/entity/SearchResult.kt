@@ -0,0 +1,52 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.entity
+
+import com.wcaokaze.probosqis.panoptiqon.Cache
+import kotlinx.datetime.Instant
+
+data class SearchResult(
+   val accounts: List<Cache<Account>>,
+   val statuses: List<Status>,
+   val hashtags: List<Hashtag>,
+) {
+   data class Hashtag(
+      val hashtag: Status.Hashtag,
+      /** 新しい順の日ごとの使用状況 */
+      val history: List<HashtagHistory>,
+      val isFollowing: Boolean?,
+   )
+
+   data class HashtagHistory(
+      val day: Instant?,
+      val useCount: Long?,
+      val accountCount: Long?,
+   ) {
+      constructor(
+         dayEpochMillis: Long?,
+         useCount: Long?,
+         accountCount: Long?,
+      ) : this(
+         dayEpochMillis?.let(Instant::fromEpochMilliseconds),
+         useCount,
+         accountCount,
+      )
+
+      val dayEpochMillis: Long?
+         get() = day?.toEpochMilliseconds()
+   }
+}
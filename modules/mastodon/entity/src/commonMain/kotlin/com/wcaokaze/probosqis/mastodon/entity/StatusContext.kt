@@ -0,0 +1,58 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.entity
+
+import com.wcaokaze.probosqis.panoptiqon.Cache
+
+/**
+ * 投稿の前後のスレッド
+ */
+data class StatusContext(
+   /** 返信先を辿った投稿。古い順 */
+   val ancestors: List<Cache<Status>>,
+   /** 返信を辿った投稿。深さ優先順 */
+   val descendants: List<Cache<Status>>,
+   /** [ancestors]の先頭から始まる返信ツリー */
+   val replyTree: ReplyTreeNode,
+   /**
+    * 対象の投稿に同じアカウントが続けて返信した投稿。古い順。
+    * 対象の投稿自身は含まない
+    */
+   val selfReplyChain: List<Cache<Status>>,
+) {
+   sealed class ReplyTreeNode {
+      abstract val replies: List<ReplyTreeNode>
+
+      data class Status(
+         val status: Cache<com.wcaokaze.probosqis.mastodon.entity.Status>,
+         override val replies: List<ReplyTreeNode>,
+         /** 返信数のうち[replies]に含まれなかった返信の数 */
+         val missingReplyCount: Long,
+      ) : ReplyTreeNode()
+
+      /**
+       * 公開範囲やブロック、削除などにより取得できなかった投稿。
+       *
+       * 返信先が取得できなかった返信は、取得できなかった投稿ごとにまとめて
+       * 対象の投稿の[replies]の末尾に置かれる
+       */
+      data class Missing(
+         val statusId: com.wcaokaze.probosqis.mastodon.entity.Status.Id,
+         override val replies: List<ReplyTreeNode>,
+      ) : ReplyTreeNode()
+   }
+}
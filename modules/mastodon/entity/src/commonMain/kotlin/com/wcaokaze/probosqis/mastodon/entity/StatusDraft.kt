@@ -0,0 +1,94 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.entity
+
+import kotlin.time.Duration
+import kotlin.time.Duration.Companion.seconds
+
+/**
+ * 投稿、編集するStatusの内容。
+ * 新規投稿時にnullの項目はCredentialAccountのデフォルト値が使われる
+ */
+data class StatusDraft(
+   val text: String? = null,
+   val spoilerText: String? = null,
+   val visibility: Status.Visibility? = null,
+   val isSensitive: Boolean? = null,
+   /** ISO 639-1の言語コード */
+   val language: String? = null,
+   val repliedStatusId: Status.Id? = null,
+   val mediaAttachmentIds: List<MediaAttachment.Id> = emptyList(),
+   val poll: Poll? = null,
+) {
+   constructor(
+      text: String?,
+      spoilerText: String?,
+      rawVisibility: String?,
+      isSensitive: Boolean?,
+      language: String?,
+      repliedStatusId: Status.Id?,
+      rawMediaAttachmentIds: List<String>,
+      poll: Poll?,
+      @Suppress("UNUSED_PARAMETER")
+      dummy: Unit?
+   ) : this(
+      text,
+      spoilerText,
+      rawVisibility?.let(Status::Visibility),
+      isSensitive,
+      language,
+      repliedStatusId,
+      rawMediaAttachmentIds.map(MediaAttachment::Id),
+      poll,
+   )
+
+   val rawVisibility: String?
+      get() = visibility?.raw
+
+   val rawMediaAttachmentIds: List<String>
+      get() = mediaAttachmentIds.map(MediaAttachment.Id::raw)
+
+   val dummy: Unit?
+      get() = null
+
+   data class Poll(
+      val options: List<String>,
+      val duration: Duration,
+      val allowsMultipleChoices: Boolean,
+      val hidesTotals: Boolean,
+   ) {
+      constructor(
+         options: List<String>,
+         durationSeconds: Long,
+         allowsMultipleChoices: Boolean,
+         hidesTotals: Boolean,
+         @Suppress("UNUSED_PARAMETER")
+         dummy: Unit?
+      ) : this(
+         options,
+         durationSeconds.seconds,
+         allowsMultipleChoices,
+         hidesTotals,
+      )
+
+      val durationSeconds: Long
+         get() = duration.inWholeSeconds
+
+      val dummy: Unit?
+         get() = null
+   }
+}
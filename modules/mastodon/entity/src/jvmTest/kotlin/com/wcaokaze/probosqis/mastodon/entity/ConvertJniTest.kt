@@ -167,6 +167,58 @@ class ConvertJniTest {
    private external fun `account_fromRust$createInstance`(): Cache<Instance>
    private external fun `account_fromRust$createMovedTo`(): Cache<Account>
 
+   // ==== CredentialAccount ===================================================
+
+   @Test
+   fun credentialAccount_visibility_toRust() {
+      val account = `credentialAccount_visibility_toRust$createAccount`()
+
+      val credentialAccounts = listOf(
+         Status.Visibility.PUBLIC,
+         Status.Visibility.UNLISTED,
+         Status.Visibility.PRIVATE,
+         Status.Visibility.DIRECT,
+         Status.Visibility("unknown visibility"),
+         null,
+      ).map { visibility ->
+         CredentialAccount(
+            account.value.id,
+            account,
+            rawProfileNote = null,
+            rawProfileFields = emptyList(),
+            defaultPostVisibility = visibility,
+            defaultPostSensitivity = null,
+            defaultPostLanguage = null,
+            followRequestCount = null,
+            role = null,
+         )
+      }
+
+      `credentialAccount_visibility_toRust$assert`(credentialAccounts)
+   }
+
+   private external fun `credentialAccount_visibility_toRust$createAccount`(): Cache<Account>
+   private external fun `credentialAccount_visibility_toRust$assert`(credentialAccounts: List<CredentialAccount>)
+
+   @Test
+   fun credentialAccount_visibility_fromRust() {
+      val credentialAccounts = `credentialAccount_visibility_fromRust$createCredentialAccounts`()
+
+      assertEquals(
+         listOf(
+            Status.Visibility.PUBLIC,
+            Status.Visibility.UNLISTED,
+            Status.Visibility.PRIVATE,
+            Status.Visibility.DIRECT,
+            Status.Visibility("unknown visibility"),
+            null,
+         ),
+         credentialAccounts.map { it.defaultPostVisibility }
+      )
+   }
+
+   private external fun `credentialAccount_visibility_fromRust$createCredentialAccounts`(): List<CredentialAccount>
+
    // ==== CustomEmoji =========================================================
 
    @Test
@@ -429,6 +481,59 @@ class ConvertJniTest {
 
    private external fun `filterResult_nulls_fromRust$createFilterResult`(): FilterResult
 
+   // ==== FilterList ==========================================================
+
+   @Test
+   fun filterList_enums_toRust() {
+      `filterList_enums_toRust$assert`(enumFilterList())
+   }
+
+   private external fun `filterList_enums_toRust$assert`(filterList: FilterList)
+
+   @Test
+   fun filterList_enums_fromRust() {
+      val filterList = `filterList_enums_fromRust$createFilterList`()
+
+      assertEquals(enumFilterList(), filterList)
+   }
+
+   private external fun `filterList_enums_fromRust$createFilterList`(): FilterList
+
+   private fun enumFilterList(): FilterList {
+      val filterActions = listOf(
+         Filter.Action.WARN,
+         Filter.Action.HIDE,
+         Filter.Action.BLUR,
+         Filter.Action("unknown action"),
+         null,
+      )
+
+      return FilterList(
+         Account.Id(
+            Url("https://example.com/instance/url"),
+            Account.LocalId("account id"),
+         ),
+         filterActions.mapIndexed { i, filterAction ->
+            Filter(
+               Filter.Id("filter id$i"),
+               title = null,
+               context = listOf(
+                  Filter.Context.HOME,
+                  Filter.Context.NOTIFICATIONS,
+                  Filter.Context.PUBLIC,
+                  Filter.Context.THREAD,
+                  Filter.Context.ACCOUNT,
+                  Filter.Context("unknown context"),
+               ),
+               expireTime = null,
+               filterAction,
+               keywords = emptyList(),
+               statuses = emptyList(),
+            )
+         },
+      )
+   }
+
    // ==== MediaAttachment =====================================================
 
    @Test
@@ -1226,6 +1331,66 @@ class ConvertJniTest {
 
    private external fun `previewCard_nulls_fromRust$createPreviewCard`(): PreviewCard
 
+   // ==== RichTextNode ========================================================
+
+   private val richTextNodes = listOf(
+      RichTextNode.Paragraph(listOf(
+         RichTextNode.Mention(
+            Account.Id(Url("https://example.com/instance/url"), Account.LocalId("account id")),
+            "acct",
+            Url("https://example.com/@username"),
+            listOf(RichTextNode.Text("@username")),
+         ),
+         RichTextNode.Text(" "),
+         RichTextNode.Mention(
+            accountId = null,
+            acct = null,
+            Url("https://example.com/@unknown"),
+            listOf(RichTextNode.Text("@unknown")),
+         ),
+         RichTextNode.Hashtag(
+            "hashtag",
+            Url("https://example.com/tags/hashtag"),
+            listOf(RichTextNode.Text("#hashtag")),
+         ),
+         RichTextNode.Link(
+            Url("https://example.com/link"),
+            listOf(RichTextNode.Text("example.com/link")),
+         ),
+         RichTextNode.Strong(listOf(RichTextNode.Text("strong"))),
+         RichTextNode.Emphasis(listOf(RichTextNode.Text("emphasis"))),
+         RichTextNode.Deleted(listOf(RichTextNode.Text("deleted"))),
+         RichTextNode.Code(listOf(RichTextNode.Text("code"))),
+      )),
+      RichTextNode.Preformatted(listOf(RichTextNode.Text("preformatted"))),
+      RichTextNode.BlockQuote(listOf(RichTextNode.Text("block quote"))),
+      RichTextNode.UnorderedList(listOf(
+         RichTextNode.ListItem(listOf(RichTextNode.Text("item"))),
+      )),
+      RichTextNode.OrderedList(
+         start = 3L,
+         listOf(
+            RichTextNode.ListItem(listOf(RichTextNode.Text("item"))),
+         ),
+      ),
+      RichTextNode.OrderedList(start = null, emptyList()),
+   )
+
+   @Test
+   fun richText_toRust() {
+      `richText_toRust$assert`(richTextNodes)
+   }
+
+   private external fun `richText_toRust$assert`(nodes: List<RichTextNode>)
+
+   @Test
+   fun richText_fromRust() {
+      val nodes = `richText_fromRust$createNodes`()
+      assertEquals(richTextNodes, nodes)
+   }
+
+   private external fun `richText_fromRust$createNodes`(): List<RichTextNode>
+
    // ==== Role ================================================================
 
    @Test
@@ -16,10 +16,11 @@
 use chrono::{DateTime, Utc};
 use isolang::Language;
 use panoptiqon::cache::Cache;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
-use crate::custom_emoji::CustomEmoji;
+use crate::custom_emoji::{self, CustomEmoji, EmojiTextRun};
 use crate::instance::Instance;
+use crate::rich_text::{self, RichTextNode};
 use crate::role::Role;
 use crate::status::StatusVisibility;
 
@@ -32,13 +33,14 @@ use {
       JvmBoolean, JvmCache, JvmList, JvmLong, JvmNullable, JvmString, JvmUnit,
    },
    crate::jvm_types::{
-      JvmAccount, JvmAccountProfileField, JvmCredentialAccount, JvmCustomEmoji,
-      JvmInstance, JvmRelationalAccount, JvmRole,
+      JvmAccount, JvmAccountId, JvmAccountProfileField, JvmCredentialAccount,
+      JvmCustomEmoji, JvmInstance, JvmRelationalAccount, JvmRole,
    },
 };
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct Account {
+   #[serde(with = "ext_panoptiqon::cache_reference")]
    pub instance: Cache<Instance>,
    pub id: AccountId,
    pub username: Option<String>,
@@ -57,6 +59,7 @@ pub struct Account {
    pub is_group: Option<bool>,
    pub is_discoverable: Option<bool>,
    pub is_noindex: Option<bool>,
+   #[serde(with = "ext_panoptiqon::cache_reference::option")]
    pub moved_to: Option<Cache<Account>>,
    pub is_suspended: Option<bool>,
    pub is_limited: Option<bool>,
@@ -67,9 +70,10 @@ pub struct Account {
    pub followee_count: Option<u64>,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct CredentialAccount {
    pub id: AccountId,
+   #[serde(with = "ext_panoptiqon::cache_reference")]
    pub account: Cache<Account>,
    pub raw_profile_note: Option<String>,
    pub raw_profile_fields: Vec<AccountProfileField>,
@@ -80,28 +84,59 @@ pub struct CredentialAccount {
    pub role: Option<Role>,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct RelationalAccount {
+   #[serde(with = "ext_panoptiqon::cache_reference")]
    pub account: Cache<Account>,
    pub mute_expire_time: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize, Serialize)]
 pub struct AccountId {
    pub instance_url: Url,
    pub local: AccountLocalId,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize, Serialize)]
 pub struct AccountLocalId(pub String);
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct AccountProfileField {
    pub name: Option<String>,
    pub value: Option<String>,
    pub verified_time: Option<DateTime<Utc>>,
 }
 
+impl Account {
+   pub fn parse_display_name(&self) -> Option<Vec<EmojiTextRun>> {
+      let display_name = self.display_name.as_ref()?;
+      Some(custom_emoji::tokenize_emojis(display_name, &self.emojis_in_profile))
+   }
+
+   /// 自己紹介のHTMLをパースする。
+   /// 自己紹介のメンションやハッシュタグはサーバーから一覧が返されないため
+   /// リンクのclassから判定される
+   pub fn parse_profile_note(&self) -> Option<Vec<RichTextNode>> {
+      let profile_note = self.profile_note.as_ref()?;
+      Some(rich_text::parse_html(profile_note, &[], &[], &self.emojis_in_profile))
+   }
+}
+
+impl AccountProfileField {
+   /// `emojis`にはアカウントの`Account::emojis_in_profile`を渡す
+   pub fn parse_name(&self, emojis: &[CustomEmoji]) -> Option<Vec<EmojiTextRun>> {
+      let name = self.name.as_ref()?;
+      Some(custom_emoji::tokenize_emojis(name, emojis))
+   }
+
+   /// 値はHTMLのためリンクなども含めてパースする。
+   /// `emojis`にはアカウントの`Account::emojis_in_profile`を渡す
+   pub fn parse_value(&self, emojis: &[CustomEmoji]) -> Option<Vec<RichTextNode>> {
+      let value = self.value.as_ref()?;
+      Some(rich_text::parse_html(value, &[], &[], emojis))
+   }
+}
+
 #[cfg(feature = "jvm")]
 convert_jvm_helper! {
    static ACCOUNT_HELPER = impl struct AccountConvertHelper
@@ -466,7 +501,7 @@ impl<'local> CloneIntoJvm<'local, JvmCredentialAccount<'local>> for CredentialAc
          &self.account,
          &self.raw_profile_note,
          &self.raw_profile_fields,
-         &self.default_post_visibility.as_ref().map(|v| &v.0),
+         &self.default_post_visibility.as_ref().map(|v| v.raw()),
          &self.default_post_sensitivity,
          &self.default_post_language.map(|l| l.to_639_1().unwrap().to_string()),
          &self.follow_request_count.map(|u| u as i64),
@@ -501,7 +536,7 @@ impl<'local> CloneFromJvm<'local, JvmCredentialAccount<'local>> for CredentialAc
          account,
          raw_profile_note,
          raw_profile_fields,
-         default_post_visibility: raw_default_post_visibility.map(StatusVisibility),
+         default_post_visibility: raw_default_post_visibility.map(StatusVisibility::from_raw),
          default_post_sensitivity,
          default_post_language: default_post_language
             .map(|code| Language::from_639_1(&code).unwrap()),
@@ -559,6 +594,63 @@ impl<'local> CloneFromJvm<'local, JvmRelationalAccount<'local>> for RelationalAc
    }
 }
 
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static ACCOUNT_ID_HELPER = impl struct AccountIdConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/Account$Id"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmAccountId<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+            Lkotlin/Unit;\
+         )V";
+
+      fn raw_instance_url<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawInstanceUrl",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn raw_local_id<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawLocalId",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn dummy<'local>(..) -> Option<()>
+         where jvm_type: JvmNullable<'local, JvmUnit<'local>>,
+               jvm_getter_method: "getDummy",
+               jvm_return_type: "Lkotlin/Unit;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmAccountId<'local>> for AccountId {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmAccountId<'local> {
+      ACCOUNT_ID_HELPER.clone_into_jvm(
+         env,
+         &self.instance_url.as_str(),
+         &self.local.0,
+         &None::<()>
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmAccountId<'local>> for AccountId {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmAccountId<'local>
+   ) -> AccountId {
+      let raw_instance_url = ACCOUNT_ID_HELPER.raw_instance_url(env, jvm_instance);
+      let raw_local_id     = ACCOUNT_ID_HELPER.raw_local_id    (env, jvm_instance);
+
+      AccountId {
+         instance_url: raw_instance_url.parse().unwrap(),
+         local: AccountLocalId(raw_local_id),
+      }
+   }
+}
+
 #[cfg(feature = "jvm")]
 convert_jvm_helper! {
    static ACCOUNT_PROFILE_FIELD_HELPER = impl struct AccountProfileFieldConvertHelper
@@ -16,7 +16,7 @@
 
 use chrono::{DateTime, Utc};
 use panoptiqon::cache::Cache;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
 use crate::instance::Instance;
 
@@ -29,8 +29,9 @@ use {
    crate::jvm_types::{JvmApplication, JvmInstance},
 };
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct Application {
+   #[serde(with = "ext_panoptiqon::cache_reference")]
    pub instance: Cache<Instance>,
    pub name: String,
    pub website: Option<Url>,
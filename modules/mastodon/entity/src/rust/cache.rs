@@ -17,13 +17,17 @@
 use url::Url;
 use panoptiqon::cache::CacheContent;
 use crate::account::{Account, AccountId, CredentialAccount};
+use crate::custom_emoji::CustomEmojiCatalogue;
+use crate::filter::FilterList;
 use crate::instance::Instance;
 use crate::poll::{NoCredentialPoll, PollId};
+use crate::relationship::Relationship;
 use crate::status::{NoCredentialStatus, Status, StatusId};
 
 #[cfg(feature = "jvm")]
 use crate::jvm_types::{
-   JvmAccount, JvmCredentialAccount, JvmInstance, JvmPollNoCredential, JvmStatus,
+   JvmAccount, JvmCredentialAccount, JvmCustomEmojiCatalogue, JvmFilterList,
+   JvmInstance, JvmPollNoCredential, JvmRelationship, JvmStatus,
    JvmStatusNoCredential,
 };
 
@@ -60,6 +64,39 @@ impl CacheContent for CredentialAccount {
    }
 }
 
+impl CacheContent for CustomEmojiCatalogue {
+   type Key = Url;
+
+   #[cfg(feature = "jvm")]
+   type JvmType<'local> = JvmCustomEmojiCatalogue<'local>;
+
+   fn key(&self) -> Url {
+      self.instance.get().url.clone()
+   }
+}
+
+impl CacheContent for FilterList {
+   type Key = AccountId;
+
+   #[cfg(feature = "jvm")]
+   type JvmType<'local> = JvmFilterList<'local>;
+
+   fn key(&self) -> AccountId {
+      self.account_id.clone()
+   }
+}
+
+impl CacheContent for Relationship {
+   type Key = AccountId;
+
+   #[cfg(feature = "jvm")]
+   type JvmType<'local> = JvmRelationship<'local>;
+
+   fn key(&self) -> AccountId {
+      self.id.clone()
+   }
+}
+
 impl CacheContent for Status {
    type Key = StatusId;
 
@@ -14,8 +14,9 @@
  * limitations under the License.
  */
 
+use chrono::{DateTime, TimeDelta, Utc};
 use panoptiqon::cache::Cache;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
 use crate::instance::Instance;
 
@@ -24,12 +25,18 @@ use {
    jni::JNIEnv,
    ext_panoptiqon::convert_jvm_helper,
    panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm},
-   panoptiqon::jvm_types::{JvmBoolean, JvmCache, JvmNullable, JvmString, JvmUnit},
-   crate::jvm_types::{JvmCustomEmoji, JvmInstance},
+   panoptiqon::jvm_type::JvmType,
+   panoptiqon::jvm_types::{
+      JvmBoolean, JvmCache, JvmList, JvmNullable, JvmString, JvmUnit,
+   },
+   crate::jvm_types::{
+      JvmCustomEmoji, JvmCustomEmojiCatalogue, JvmEmojiTextRun, JvmInstance,
+   },
 };
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct CustomEmoji {
+   #[serde(with = "ext_panoptiqon::cache_reference")]
    pub instance: Cache<Instance>,
    pub shortcode: String,
    pub image_url: Url,
@@ -38,6 +45,246 @@ pub struct CustomEmoji {
    pub category: Option<String>,
 }
 
+impl CustomEmoji {
+   /// 表示に使うURL。
+   /// 視差効果を減らす設定のときは静止画があればそちらを使う
+   pub fn display_url(&self, reduces_motion: bool) -> &Url {
+      match (reduces_motion, &self.static_image_url) {
+         (true, Some(static_image_url)) => static_image_url,
+         _ => &self.image_url,
+      }
+   }
+}
+
+/// テキストを`:shortcode:`の位置で分割したもの
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+pub enum EmojiTextRun {
+   Text(String),
+   Emoji(Box<CustomEmoji>),
+}
+
+/// `text`中の`:shortcode:`のうち`emojis`に含まれるものをカスタム絵文字に置き換える。
+///
+/// `:a::b:`のように隣接するshortcodeはそれぞれ置き換えるが、
+/// `12:30:45`のように英数字に隣接するものはshortcodeとみなさない。
+/// カスタム絵文字は画像であり絵文字シーケンスを構成できないため、
+/// カスタム絵文字に隣接するゼロ幅接合子は取り除く
+pub fn tokenize_emojis(text: &str, emojis: &[CustomEmoji]) -> Vec<EmojiTextRun> {
+   let mut runs = vec![];
+   let mut text_start = 0;
+   let mut search_start = 0;
+
+   while let Some(open) = text[search_start..].find(':').map(|i| search_start + i) {
+      let Some(close) = text[open + 1..].find(':').map(|i| open + 1 + i) else {
+         break;
+      };
+
+      let shortcode = &text[open + 1..close];
+
+      let is_bounded = !text[..open].ends_with(|c: char| c.is_alphanumeric())
+         && !text[close + 1..].starts_with(|c: char| c.is_alphanumeric());
+
+      let emoji = if is_bounded && is_shortcode(shortcode) {
+         emojis.iter().find(|e| e.shortcode == shortcode)
+      } else {
+         None
+      };
+
+      match emoji {
+         Some(emoji) => {
+            let preceding_text = text[text_start..open].trim_end_matches(ZERO_WIDTH_JOINER);
+            push_text_run(&mut runs, preceding_text);
+            runs.push(EmojiTextRun::Emoji(Box::new(emoji.clone())));
+            text_start = close + 1;
+            search_start = close + 1;
+         }
+         None => {
+            // 閉じ側のコロンが次のshortcodeの開始である可能性がある
+            search_start = close;
+         }
+      }
+   }
+
+   push_text_run(&mut runs, &text[text_start..]);
+   runs
+}
+
+fn is_shortcode(shortcode: &str) -> bool {
+   shortcode.len() >= 2
+      && shortcode.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// カスタム絵文字の直前直後のゼロ幅接合子を取り除いて追加する。
+/// 空になったTextは追加しない
+fn push_text_run(runs: &mut Vec<EmojiTextRun>, text: &str) {
+   let text = if matches!(runs.last(), Some(EmojiTextRun::Emoji(_))) {
+      text.trim_start_matches(ZERO_WIDTH_JOINER)
+   } else {
+      text
+   };
+
+   if text.is_empty() {
+      return;
+   }
+
+   runs.push(EmojiTextRun::Text(text.to_string()));
+}
+
+/// `instance`で使えるカスタム絵文字の一覧
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub struct CustomEmojiCatalogue {
+   #[serde(with = "ext_panoptiqon::cache_reference")]
+   pub instance: Cache<Instance>,
+   pub emojis: Vec<CustomEmoji>,
+   pub fetched_time: DateTime<Utc>,
+}
+
+/// 絵文字ピッカーの1カテゴリ。`name`がNoneのものはカテゴリ未設定の絵文字
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub struct CustomEmojiCategory {
+   pub name: Option<String>,
+   pub emojis: Vec<CustomEmoji>,
+}
+
+impl CustomEmojiCatalogue {
+   /// `fetched_time`から`ttl`以上経過している場合true
+   pub fn is_expired(&self, ttl: TimeDelta, now: DateTime<Utc>) -> bool {
+      self.fetched_time + ttl <= now
+   }
+
+   /// ピッカーに表示する絵文字をカテゴリごとに分ける。
+   ///
+   /// `is_visible_in_picker`がfalseの絵文字は含まれない。
+   /// カテゴリ未設定のものが先頭で、以降はカテゴリ名順。
+   /// カテゴリ内の順序はサーバーが返した順
+   pub fn picker_categories(&self) -> Vec<CustomEmojiCategory> {
+      let mut categories: Vec<CustomEmojiCategory> = vec![];
+
+      let visible_emojis = self.emojis.iter()
+         .filter(|emoji| emoji.is_visible_in_picker != Some(false));
+
+      for emoji in visible_emojis {
+         let category = categories.iter_mut()
+            .find(|category| category.name == emoji.category);
+
+         match category {
+            Some(category) => category.emojis.push(emoji.clone()),
+            None => {
+               categories.push(CustomEmojiCategory {
+                  name: emoji.category.clone(),
+                  emojis: vec![emoji.clone()],
+               });
+            }
+         }
+      }
+
+      categories.sort_by(|a, b| a.name.cmp(&b.name));
+      categories
+   }
+
+   /// 入力補完のためにshortcodeを検索する。大文字小文字は区別しない。
+   ///
+   /// 完全一致、前方一致、`_`などで区切られた単語の前方一致、
+   /// 先頭の文字が一致し`query`の文字を順に含むもの、の順に並ぶ。
+   /// ピッカーに表示されない絵文字は完全一致の場合のみ含まれる
+   pub fn search(&self, query: &str, limit: usize) -> Vec<CustomEmoji> {
+      let query = query.trim().trim_matches(':').to_ascii_lowercase();
+      if query.is_empty() {
+         return vec![];
+      }
+
+      let mut matches: Vec<(ShortcodeMatch, &CustomEmoji)> = self.emojis.iter()
+         .filter_map(|emoji| {
+            let shortcode_match = match_shortcode(&emoji.shortcode, &query)?;
+
+            if emoji.is_visible_in_picker == Some(false)
+               && shortcode_match != ShortcodeMatch::Exact
+            {
+               return None;
+            }
+
+            Some((shortcode_match, emoji))
+         })
+         .collect();
+
+      matches.sort_by(|(a_match, a), (b_match, b)| {
+         a_match.cmp(b_match)
+            .then(a.shortcode.len().cmp(&b.shortcode.len()))
+            .then_with(|| a.shortcode.cmp(&b.shortcode))
+      });
+
+      matches.into_iter()
+         .take(limit)
+         .map(|(_, emoji)| emoji.clone())
+         .collect()
+   }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum ShortcodeMatch {
+   Exact,
+   Prefix,
+   WordPrefix,
+   Fuzzy,
+}
+
+/// `query`は小文字に揃えてあること
+fn match_shortcode(shortcode: &str, query: &str) -> Option<ShortcodeMatch> {
+   let lowercase_shortcode = shortcode.to_ascii_lowercase();
+
+   if lowercase_shortcode == query {
+      return Some(ShortcodeMatch::Exact);
+   }
+
+   if lowercase_shortcode.starts_with(query) {
+      return Some(ShortcodeMatch::Prefix);
+   }
+
+   let is_word_prefix = word_start_indices(shortcode)
+      .any(|i| lowercase_shortcode[i..].starts_with(query));
+
+   if is_word_prefix {
+      return Some(ShortcodeMatch::WordPrefix);
+   }
+
+   let mut shortcode_chars = lowercase_shortcode.chars();
+   let mut query_chars = query.chars();
+
+   if shortcode_chars.next() != query_chars.next() {
+      return None;
+   }
+
+   let is_subsequence = query_chars
+      .all(|q| shortcode_chars.by_ref().any(|c| c == q));
+
+   if is_subsequence {
+      Some(ShortcodeMatch::Fuzzy)
+   } else {
+      None
+   }
+}
+
+/// `blob_cat`の`cat`や`BlobCat`の`Cat`、`cat2`の`2`のような
+/// 単語の開始位置。先頭は含まない
+fn word_start_indices(shortcode: &str) -> impl Iterator<Item = usize> + '_ {
+   shortcode.char_indices()
+      .zip(shortcode.chars().skip(1))
+      .filter_map(|((i, prev), c)| {
+         let is_word_start = (prev == '_' && c != '_')
+            || (prev.is_lowercase() && c.is_uppercase())
+            || (prev.is_alphabetic() && c.is_numeric())
+            || (prev.is_numeric() && c.is_alphabetic());
+
+         if is_word_start {
+            Some(i + prev.len_utf8())
+         } else {
+            None
+         }
+      })
+}
+
 #[cfg(feature = "jvm")]
 convert_jvm_helper! {
    static HELPER = impl struct CustomEmojiConvertHelper
@@ -130,3 +377,345 @@ impl<'local> CloneFromJvm<'local, JvmCustomEmoji<'local>> for CustomEmoji {
       }
    }
 }
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static EMOJI_TEXT_RUN_TEXT_HELPER = impl struct EmojiTextRunTextConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/EmojiTextRun$Text"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmEmojiTextRun<'local>
+         where jvm_constructor: "(Ljava/lang/String;)V";
+
+      fn text<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getText",
+               jvm_return_type: "Ljava/lang/String;";
+   }
+
+   static EMOJI_TEXT_RUN_EMOJI_HELPER = impl struct EmojiTextRunEmojiConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/EmojiTextRun$Emoji"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmEmojiTextRun<'local>
+         where jvm_constructor: "(Lcom/wcaokaze/probosqis/mastodon/entity/CustomEmoji;)V";
+
+      fn emoji<'local>(..) -> CustomEmoji
+         where jvm_type: JvmCustomEmoji<'local>,
+               jvm_getter_method: "getEmoji",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/CustomEmoji;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmEmojiTextRun<'local>> for EmojiTextRun {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmEmojiTextRun<'local> {
+      match self {
+         EmojiTextRun::Text(text) => {
+            EMOJI_TEXT_RUN_TEXT_HELPER.clone_into_jvm(env, text)
+         }
+
+         EmojiTextRun::Emoji(emoji) => {
+            EMOJI_TEXT_RUN_EMOJI_HELPER.clone_into_jvm(env, emoji.as_ref())
+         }
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmEmojiTextRun<'local>> for EmojiTextRun {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmEmojiTextRun<'local>
+   ) -> EmojiTextRun {
+      if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/EmojiTextRun$Text"
+         )
+         .unwrap()
+      {
+         let text = EMOJI_TEXT_RUN_TEXT_HELPER.text(env, jvm_instance);
+         EmojiTextRun::Text(text)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/EmojiTextRun$Emoji"
+         )
+         .unwrap()
+      {
+         let emoji = EMOJI_TEXT_RUN_EMOJI_HELPER.emoji(env, jvm_instance);
+         EmojiTextRun::Emoji(Box::new(emoji))
+      } else {
+         panic!("Unexpected class");
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static CATALOGUE_HELPER = impl struct CustomEmojiCatalogueConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/CustomEmojiCatalogue"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmCustomEmojiCatalogue<'local>
+         where jvm_constructor: "(\
+            Lcom/wcaokaze/probosqis/panoptiqon/Cache;\
+            Ljava/util/List;\
+            J\
+         )V";
+
+      fn instance<'local>(..) -> Cache<Instance>
+         where jvm_type: JvmCache<'local, JvmInstance<'local>>,
+               jvm_getter_method: "getInstance",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/panoptiqon/Cache;";
+
+      fn emojis<'local>(..) -> Vec<CustomEmoji>
+         where jvm_type: JvmList<'local, JvmCustomEmoji<'local>>,
+               jvm_getter_method: "getEmojis",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn fetched_time_epoch_millis<'local>(..) -> i64
+         where jvm_getter_method: "getFetchedTimeEpochMillis",
+               jvm_return_type: "J";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmCustomEmojiCatalogue<'local>> for CustomEmojiCatalogue {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmCustomEmojiCatalogue<'local> {
+      CATALOGUE_HELPER.clone_into_jvm(
+         env,
+         &self.instance,
+         &self.emojis,
+         self.fetched_time.timestamp_millis(),
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmCustomEmojiCatalogue<'local>> for CustomEmojiCatalogue {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmCustomEmojiCatalogue<'local>
+   ) -> CustomEmojiCatalogue {
+      let instance                  = CATALOGUE_HELPER.instance                 (env, jvm_instance);
+      let emojis                    = CATALOGUE_HELPER.emojis                   (env, jvm_instance);
+      let fetched_time_epoch_millis = CATALOGUE_HELPER.fetched_time_epoch_millis(env, jvm_instance);
+
+      CustomEmojiCatalogue {
+         instance,
+         emojis,
+         fetched_time: DateTime::from_timestamp_millis(fetched_time_epoch_millis).unwrap(),
+      }
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use chrono::{TimeZone, Utc};
+   use panoptiqon::cache::Cache;
+   use crate::instance::Instance;
+   use super::{
+      tokenize_emojis, CustomEmoji, CustomEmojiCatalogue, CustomEmojiCategory,
+      EmojiTextRun,
+   };
+
+   fn emoji(shortcode: &str) -> CustomEmoji {
+      let instance = Instance {
+         url: "https://custom-emoji.example.com/".parse().unwrap(),
+         version: "4.3.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      CustomEmoji {
+         instance: Cache::new(instance),
+         shortcode: shortcode.to_string(),
+         image_url: format!("https://custom-emoji.example.com/{shortcode}.gif").parse().unwrap(),
+         static_image_url: Some(format!("https://custom-emoji.example.com/{shortcode}.png").parse().unwrap()),
+         is_visible_in_picker: None,
+         category: None,
+      }
+   }
+
+   fn text(text: &str) -> EmojiTextRun {
+      EmojiTextRun::Text(text.to_string())
+   }
+
+   fn emoji_run(shortcode: &str) -> EmojiTextRun {
+      EmojiTextRun::Emoji(Box::new(emoji(shortcode)))
+   }
+
+   #[test]
+   fn adjacent_shortcodes() {
+      let emojis = [emoji("blobcat"), emoji("wave")];
+
+      assert_eq!(
+         vec![emoji_run("blobcat"), emoji_run("wave"), text(" "), emoji_run("wave")],
+         tokenize_emojis(":blobcat::wave: :wave:", &emojis)
+      );
+
+      assert_eq!(
+         vec![text("::"), emoji_run("wave"), text("::")],
+         tokenize_emojis(":::wave:::", &emojis)
+      );
+   }
+
+   #[test]
+   fn invalid_shortcodes() {
+      let emojis = [emoji("blobcat"), emoji("30")];
+
+      assert_eq!(
+         vec![text("12:30:45 :unknown:"), emoji_run("blobcat"), text(" :blob cat: :")],
+         tokenize_emojis("12:30:45 :unknown::blobcat: :blob cat: :", &emojis)
+      );
+
+      assert_eq!(
+         vec![text("a:blobcat: :blobcat:b")],
+         tokenize_emojis("a:blobcat: :blobcat:b", &emojis)
+      );
+
+      assert_eq!(
+         vec![text(":::blobcat\u{200D}:")],
+         tokenize_emojis(":::blobcat\u{200D}:", &emojis)
+      );
+   }
+
+   #[test]
+   fn zero_width_joiner() {
+      let emojis = [emoji("blobcat"), emoji("wave")];
+
+      assert_eq!(
+         vec![text("👨‍👩‍👧 "), emoji_run("blobcat"), emoji_run("wave"), text("👍")],
+         tokenize_emojis("👨‍👩‍👧 \u{200D}:blobcat:\u{200D}:wave:\u{200D}👍", &emojis)
+      );
+
+      assert_eq!(
+         vec![text("\u{200D}a\u{200D}")],
+         tokenize_emojis("\u{200D}a\u{200D}", &emojis)
+      );
+   }
+
+   #[test]
+   fn display_url() {
+      let emoji = emoji("blobcat");
+      assert_eq!("https://custom-emoji.example.com/blobcat.gif", emoji.display_url(false).as_str());
+      assert_eq!("https://custom-emoji.example.com/blobcat.png", emoji.display_url(true).as_str());
+
+      let emoji = CustomEmoji {
+         static_image_url: None,
+         ..emoji
+      };
+      assert_eq!("https://custom-emoji.example.com/blobcat.gif", emoji.display_url(true).as_str());
+   }
+
+   fn catalogue(emojis: Vec<CustomEmoji>) -> CustomEmojiCatalogue {
+      CustomEmojiCatalogue {
+         instance: emojis[0].instance.clone(),
+         emojis,
+         fetched_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      }
+   }
+
+   #[test]
+   fn picker_categories() {
+      let emoji = |shortcode: &str, category: Option<&str>, is_visible_in_picker: Option<bool>| {
+         CustomEmoji {
+            category: category.map(str::to_string),
+            is_visible_in_picker,
+            ..emoji(shortcode)
+         }
+      };
+
+      let catalogue = catalogue(vec![
+         emoji("blobfox", Some("blob"), Some(true)),
+         emoji("thinking", None, Some(true)),
+         emoji("amongus", Some("among"), None),
+         emoji("secret", None, Some(false)),
+         emoji("blobcat", Some("blob"), Some(true)),
+      ]);
+
+      assert_eq!(
+         vec![
+            CustomEmojiCategory {
+               name: None,
+               emojis: vec![emoji("thinking", None, Some(true))],
+            },
+            CustomEmojiCategory {
+               name: Some("among".to_string()),
+               emojis: vec![emoji("amongus", Some("among"), None)],
+            },
+            CustomEmojiCategory {
+               name: Some("blob".to_string()),
+               emojis: vec![
+                  emoji("blobfox", Some("blob"), Some(true)),
+                  emoji("blobcat", Some("blob"), Some(true)),
+               ],
+            },
+         ],
+         catalogue.picker_categories()
+      );
+   }
+
+   #[test]
+   fn search() {
+      let catalogue = catalogue(vec![
+         emoji("BlobCatHeart"),
+         emoji("cat"),
+         emoji("blob_cat"),
+         emoji("catgirl"),
+         emoji("cat2"),
+         emoji("concat"),
+         emoji("c_a_t"),
+         CustomEmoji {
+            is_visible_in_picker: Some(false),
+            ..emoji("cats")
+         },
+      ]);
+
+      let search = |query: &str, limit: usize| {
+         catalogue.search(query, limit)
+            .into_iter()
+            .map(|e| e.shortcode)
+            .collect::<Vec<_>>()
+      };
+
+      assert_eq!(
+         vec!["cat", "cat2", "catgirl", "blob_cat", "BlobCatHeart", "c_a_t", "concat"],
+         search(":Cat", 10)
+      );
+
+      assert_eq!(vec!["cat", "cat2"], search("cat", 2));
+      assert_eq!(vec!["cats"], search("cats", 10));
+      assert_eq!(vec!["BlobCatHeart"], search("bch", 10));
+      assert_eq!(Vec::<String>::new(), search("::", 10));
+   }
+
+   #[test]
+   fn serde_round_trip() {
+      use ext_panoptiqon::cache_reference;
+      use ext_panoptiqon::repository_holder::RepositoryHolder;
+
+      static INSTANCE_REPO: RepositoryHolder<Instance> = RepositoryHolder::new();
+
+      // CustomEmojiのinstanceはキーとして書き込まれるため、Repositoryから復元する
+      cache_reference::register(&INSTANCE_REPO);
+      let instance = INSTANCE_REPO.write().unwrap()
+         .save(emoji("wave").instance.get().clone());
+      let emoji = |shortcode: &str| CustomEmoji {
+         instance: instance.clone(),
+         ..emoji(shortcode)
+      };
+
+      let runs = vec![
+         text("hello "),
+         EmojiTextRun::Emoji(Box::new(emoji("wave"))),
+      ];
+      let json = serde_json::to_string(&runs).unwrap();
+      assert_eq!(runs, serde_json::from_str::<Vec<EmojiTextRun>>(&json).unwrap());
+
+      let category = CustomEmojiCategory {
+         name: Some("blobs".to_string()),
+         emojis: vec![emoji("blobcat"), emoji("blobfox")],
+      };
+      let json = serde_json::to_string(&category).unwrap();
+      assert_eq!(category, serde_json::from_str::<CustomEmojiCategory>(&json).unwrap());
+   }
+}
@@ -15,8 +15,10 @@
  */
 
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
-use crate::status::StatusId;
+use serde::{Deserialize, Serialize};
+use crate::account::AccountId;
+use crate::rich_text::{parse_html, RichTextNode};
+use crate::status::{NoCredentialStatus, StatusId};
 
 #[cfg(feature = "jvm")]
 use {
@@ -26,12 +28,14 @@ use {
    panoptiqon::jvm_types::{
       JvmBoolean, JvmList, JvmLong, JvmNullable, JvmString, JvmUnit,
    },
+   crate::account::AccountLocalId,
    crate::jvm_types::{
-      JvmFilter, JvmFilterKeyword, JvmFilterResult, JvmFilterStatus, JvmStatusId,
+      JvmFilter, JvmFilterKeyword, JvmFilterList, JvmFilterResult, JvmFilterStatus,
+      JvmStatusId,
    },
 };
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct Filter {
    pub id: FilterId,
    pub title: Option<String>,
@@ -42,41 +46,336 @@ pub struct Filter {
    pub statuses: Vec<FilterStatus>,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct FilterId(pub String);
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
-pub struct FilterContext(pub String);
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum FilterContext {
+   Home,
+   Notifications,
+   Public,
+   Thread,
+   Account,
+   Unknown(String),
+}
+
+impl FilterContext {
+   pub fn from_raw(raw: String) -> FilterContext {
+      match raw.as_str() {
+         "home"          => FilterContext::Home,
+         "notifications" => FilterContext::Notifications,
+         "public"        => FilterContext::Public,
+         "thread"        => FilterContext::Thread,
+         "account"       => FilterContext::Account,
+         _ => FilterContext::Unknown(raw),
+      }
+   }
+
+   pub fn raw(&self) -> &str {
+      match self {
+         FilterContext::Home          => "home",
+         FilterContext::Notifications => "notifications",
+         FilterContext::Public        => "public",
+         FilterContext::Thread        => "thread",
+         FilterContext::Account       => "account",
+         FilterContext::Unknown(raw)  => raw,
+      }
+   }
+}
+
+impl From<String> for FilterContext {
+   fn from(raw: String) -> FilterContext {
+      FilterContext::from_raw(raw)
+   }
+}
+
+impl From<FilterContext> for String {
+   fn from(value: FilterContext) -> String {
+      value.raw().to_string()
+   }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum FilterAction {
+   Warn,
+   Hide,
+   /// since mastodon 4.4.0
+   Blur,
+   Unknown(String),
+}
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
-pub struct FilterAction(pub String);
+impl FilterAction {
+   pub fn from_raw(raw: String) -> FilterAction {
+      match raw.as_str() {
+         "warn" => FilterAction::Warn,
+         "hide" => FilterAction::Hide,
+         "blur" => FilterAction::Blur,
+         _ => FilterAction::Unknown(raw),
+      }
+   }
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+   pub fn raw(&self) -> &str {
+      match self {
+         FilterAction::Warn         => "warn",
+         FilterAction::Hide         => "hide",
+         FilterAction::Blur         => "blur",
+         FilterAction::Unknown(raw) => raw,
+      }
+   }
+}
+
+impl From<String> for FilterAction {
+   fn from(raw: String) -> FilterAction {
+      FilterAction::from_raw(raw)
+   }
+}
+
+impl From<FilterAction> for String {
+   fn from(value: FilterAction) -> String {
+      value.raw().to_string()
+   }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct FilterKeyword {
    pub id: FilterKeywordId,
    pub keyword: Option<String>,
    pub whole_word: Option<bool>,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct FilterKeywordId(pub String);
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct FilterStatus {
    pub id: FilterStatusId,
    pub status_id: StatusId,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct FilterStatusId(pub String);
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+/// `account_id`のアカウントが設定しているフィルタの一覧
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub struct FilterList {
+   pub account_id: AccountId,
+   pub filters: Vec<Filter>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct FilterResult {
    pub filter: Option<Filter>,
    pub keyword_matches: Vec<String>,
    pub status_matches: Vec<StatusId>,
 }
 
+/// `filters`のうち`status`に適用されるものを、サーバーが返すFilterResultと
+/// 同じ形で返す。
+///
+/// v1のフィルタしかないサーバーやストリーミングで受け取った投稿など、
+/// サーバーからFilterResultが返されない場合にクライアント側で
+/// フィルタを適用するためのもの
+pub fn apply_filters(
+   filters: &[Filter],
+   status: &NoCredentialStatus,
+   context: &FilterContext
+) -> Vec<FilterResult> {
+   let now = Utc::now();
+
+   filters.iter()
+      .filter_map(|filter| filter.apply(status, context, now))
+      .collect()
+}
+
+impl Filter {
+   /// `context`でこのフィルタを`status`に適用する。適用されない場合None。
+   ///
+   /// 本文、CW、投票の選択肢、メディアの説明文が検索される。
+   /// `status`がブーストの場合はブーストされた投稿に適用される
+   pub fn apply(
+      &self,
+      status: &NoCredentialStatus,
+      context: &FilterContext,
+      now: DateTime<Utc>
+   ) -> Option<FilterResult> {
+      if !self.context.contains(context) {
+         return None;
+      }
+
+      if self.expire_time.is_some_and(|time| time <= now) {
+         return None;
+      }
+
+      if let Some(boosted_status) = &status.boosted_status {
+         return self.apply(&boosted_status.get(), context, now);
+      }
+
+      let text = searchable_text(status);
+
+      let keyword_matches: Vec<String> = self.keywords.iter()
+         .filter(|keyword| keyword.matches(&text))
+         .filter_map(|keyword| keyword.keyword.clone())
+         .collect();
+
+      let status_matches: Vec<StatusId> = self.statuses.iter()
+         .filter(|filter_status| filter_status.status_id == status.id)
+         .map(|filter_status| filter_status.status_id.clone())
+         .collect();
+
+      if keyword_matches.is_empty() && status_matches.is_empty() {
+         return None;
+      }
+
+      let filter_result = FilterResult {
+         filter: Some(self.clone()),
+         keyword_matches,
+         status_matches,
+      };
+
+      Some(filter_result)
+   }
+}
+
+impl FilterKeyword {
+   /// `whole_word`が未指定の場合はサーバーのデフォルトと同じく単語単位で判定する。
+   ///
+   /// 単語単位の場合でも、日本語や中国語のように単語を空白で区切らない
+   /// 文字との境界は単語の境界とみなす
+   fn matches(&self, text: &[char]) -> bool {
+      let Some(keyword) = &self.keyword else { return false };
+      let keyword: Vec<char> = fold_case(keyword.trim());
+
+      if keyword.is_empty() || keyword.len() > text.len() {
+         return false;
+      }
+
+      let whole_word = self.whole_word.unwrap_or(true);
+
+      (0..=text.len() - keyword.len()).any(|start| {
+         let end = start + keyword.len();
+
+         text[start..end] == keyword[..]
+            && (!whole_word || is_whole_word(text, start, end))
+      })
+   }
+}
+
+/// 大文字小文字を区別せずに比較するため小文字に揃える。
+/// 文字の位置がずれないよう1文字は1文字に変換する
+fn fold_case(text: &str) -> Vec<char> {
+   text.chars()
+      .map(|c| c.to_lowercase().next().unwrap_or(c))
+      .collect()
+}
+
+fn searchable_text(status: &NoCredentialStatus) -> Vec<char> {
+   let mut texts: Vec<String> = vec![];
+
+   if let Some(spoiler_text) = &status.spoiler_text {
+      texts.push(spoiler_text.clone());
+   }
+
+   if let Some(content) = &status.content {
+      texts.push(html_to_plain_text(content));
+   }
+
+   if let Some(poll) = &status.poll {
+      let poll = poll.get();
+      let options: Vec<&str> = poll.poll_options.iter()
+         .filter_map(|option| option.title.as_deref())
+         .collect();
+      texts.push(options.join("\n\n"));
+   }
+
+   let descriptions: Vec<&str> = status.media_attachments.iter()
+      .filter_map(|media| media.description.as_deref())
+      .collect();
+   texts.push(descriptions.join("\n\n"));
+
+   fold_case(&texts.join("\n\n"))
+}
+
+fn is_whole_word(text: &[char], start: usize, end: usize) -> bool {
+   let before = start.checked_sub(1).map(|i| text[i]);
+   let after = text.get(end).copied();
+
+   is_word_boundary(before, text[start]) && is_word_boundary(after, text[end - 1])
+}
+
+/// `inside`はキーワードの端の文字、`outside`はそれに隣接する文字
+fn is_word_boundary(outside: Option<char>, inside: char) -> bool {
+   // キーワードの端が単語を構成しない文字の場合は境界を問わない
+   if !is_word_char(inside) {
+      return true;
+   }
+
+   match outside {
+      None => true,
+      Some(outside) => !is_word_char(outside) || is_cjk(inside) || is_cjk(outside),
+   }
+}
+
+fn is_word_char(c: char) -> bool {
+   c.is_alphanumeric() || c == '_'
+}
+
+/// 単語を空白で区切らない文字
+fn is_cjk(c: char) -> bool {
+   matches!(c,
+      '\u{3040}'..='\u{30FF}'     // ひらがな、カタカナ
+      | '\u{31F0}'..='\u{31FF}'   // カタカナ拡張
+      | '\u{3400}'..='\u{4DBF}'   // CJK統合漢字拡張A
+      | '\u{4E00}'..='\u{9FFF}'   // CJK統合漢字
+      | '\u{F900}'..='\u{FAFF}'   // CJK互換漢字
+      | '\u{FF66}'..='\u{FF9F}'   // 半角カタカナ
+      | '\u{20000}'..='\u{2FFFF}' // CJK統合漢字拡張B以降
+   )
+}
+
+/// [parse_html]でパースしたテキストを取り出す。
+/// 表示されないテキストは含まず、段落は空行で、箇条書きの項目は改行で区切る
+fn html_to_plain_text(html: &str) -> String {
+   let mut text = String::new();
+   push_plain_text(&mut text, &parse_html(html, &[], &[], &[]));
+   text
+}
+
+fn push_plain_text(text: &mut String, nodes: &[RichTextNode]) {
+   for node in nodes {
+      match node {
+         RichTextNode::Text(t) => text.push_str(t),
+         RichTextNode::Emoji(emoji) => {
+            text.push(':');
+            text.push_str(&emoji.shortcode);
+            text.push(':');
+         }
+         RichTextNode::Paragraph(children) => {
+            push_plain_text(text, children);
+            text.push_str("\n\n");
+         }
+         RichTextNode::ListItem(children) => {
+            push_plain_text(text, children);
+            text.push('\n');
+         }
+         RichTextNode::Link { children, .. }
+         | RichTextNode::Mention { children, .. }
+         | RichTextNode::Hashtag { children, .. }
+         | RichTextNode::Strong(children)
+         | RichTextNode::Emphasis(children)
+         | RichTextNode::Deleted(children)
+         | RichTextNode::Code(children)
+         | RichTextNode::Preformatted(children)
+         | RichTextNode::BlockQuote(children)
+         | RichTextNode::UnorderedList(children)
+         | RichTextNode::OrderedList { children, .. } => {
+            push_plain_text(text, children);
+         }
+      }
+   }
+}
+
 #[cfg(feature = "jvm")]
 convert_jvm_helper! {
    static FILTER_HELPER = impl struct FilterConvertHelper
@@ -137,9 +436,9 @@ impl<'local> CloneIntoJvm<'local, JvmFilter<'local>> for Filter {
          env,
          &self.id.0,
          &self.title,
-         &self.context.iter().map(|c| &c.0).collect::<Vec<_>>(),
+         &self.context.iter().map(|c| c.raw()).collect::<Vec<_>>(),
          &self.expire_time.map(|t| t.timestamp_millis()),
-         &self.filter_action.as_ref().map(|a| &a.0),
+         &self.filter_action.as_ref().map(|a| a.raw()),
          &self.keywords,
          &self.statuses,
       )
@@ -163,10 +462,10 @@ impl<'local> CloneFromJvm<'local, JvmFilter<'local>> for Filter {
       Filter {
          id: FilterId(raw_id),
          title,
-         context: raw_context.into_iter().map(FilterContext).collect(),
+         context: raw_context.into_iter().map(FilterContext::from_raw).collect(),
          expire_time: expire_time_epoch_millis
             .map(|time| DateTime::from_timestamp_millis(time).unwrap()),
-         filter_action: raw_filter_action.map(FilterAction),
+         filter_action: raw_filter_action.map(FilterAction::from_raw),
          keywords,
          statuses,
       }
@@ -360,3 +659,293 @@ impl<'local> CloneFromJvm<'local, JvmFilterResult<'local>> for FilterResult {
       }
    }
 }
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static FILTER_LIST_HELPER = impl struct FilterListConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/FilterList"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmFilterList<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+            Ljava/util/List;\
+         )V";
+
+      fn raw_instance_url<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawInstanceUrl",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn raw_account_local_id<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawAccountLocalId",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn filters<'local>(..) -> Vec<Filter>
+         where jvm_type: JvmList<'local, JvmFilter<'local>>,
+               jvm_getter_method: "getFilters",
+               jvm_return_type: "Ljava/util/List;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmFilterList<'local>> for FilterList {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmFilterList<'local> {
+      FILTER_LIST_HELPER.clone_into_jvm(
+         env,
+         self.account_id.instance_url.as_str(),
+         &self.account_id.local.0,
+         &self.filters,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmFilterList<'local>> for FilterList {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmFilterList<'local>
+   ) -> FilterList {
+      let raw_instance_url     = FILTER_LIST_HELPER.raw_instance_url    (env, jvm_instance);
+      let raw_account_local_id = FILTER_LIST_HELPER.raw_account_local_id(env, jvm_instance);
+      let filters              = FILTER_LIST_HELPER.filters             (env, jvm_instance);
+
+      FilterList {
+         account_id: AccountId {
+            instance_url: raw_instance_url.parse().unwrap(),
+            local: AccountLocalId(raw_account_local_id),
+         },
+         filters,
+      }
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use chrono::{DateTime, Duration, Utc};
+   use panoptiqon::cache::Cache;
+   use crate::status::{NoCredentialStatus, StatusId, StatusLocalId};
+   use super::{
+      Filter, FilterAction, FilterContext, FilterId, FilterKeyword,
+      FilterKeywordId, FilterStatus, FilterStatusId,
+   };
+
+   fn status(local_id: &str) -> NoCredentialStatus {
+      NoCredentialStatus {
+         id: StatusId {
+            instance_url: "https://filter.example.com/".parse().unwrap(),
+            local: StatusLocalId(local_id.to_string()),
+         },
+         uri: None,
+         created_time: None,
+         account: None,
+         content: None,
+         visibility: None,
+         is_sensitive: None,
+         spoiler_text: None,
+         media_attachments: vec![],
+         application: None,
+         mentions: vec![],
+         hashtags: vec![],
+         emojis: vec![],
+         boost_count: None,
+         favorite_count: None,
+         reply_count: None,
+         url: None,
+         replied_status_id: None,
+         replied_account_id: None,
+         boosted_status: None,
+         poll: None,
+         card: None,
+         language: None,
+         text: None,
+         edited_time: None,
+      }
+   }
+
+   fn filter(keywords: &[(&str, bool)]) -> Filter {
+      Filter {
+         id: FilterId("filter id".to_string()),
+         title: Some("title".to_string()),
+         context: vec![FilterContext::Home, FilterContext::Public],
+         expire_time: None,
+         filter_action: Some(FilterAction::Warn),
+         keywords: keywords.iter()
+            .map(|&(keyword, whole_word)| FilterKeyword {
+               id: FilterKeywordId(keyword.to_string()),
+               keyword: Some(keyword.to_string()),
+               whole_word: Some(whole_word),
+            })
+            .collect(),
+         statuses: vec![],
+      }
+   }
+
+   fn keyword_matches(filter: &Filter, content: &str) -> Vec<String> {
+      let status = NoCredentialStatus {
+         content: Some(content.to_string()),
+         ..status("status id")
+      };
+
+      filter.apply(&status, &FilterContext::Home, Utc::now())
+         .map(|result| result.keyword_matches)
+         .unwrap_or_default()
+   }
+
+   #[test]
+   fn whole_word() {
+      let filter = filter(&[("cat", true), ("dog", false), ("猫", true), ("#tag", true)]);
+
+      assert_eq!(vec!["cat"], keyword_matches(&filter, "<p>A CAT!</p>"));
+      assert!(keyword_matches(&filter, "<p>cats</p>").is_empty());
+      assert!(keyword_matches(&filter, "<p>concatenate</p>").is_empty());
+      assert_eq!(vec!["cat"], keyword_matches(&filter, "<p>a<br>cat</p>"));
+      assert_eq!(vec!["dog"], keyword_matches(&filter, "<p>hotdogs</p>"));
+      assert_eq!(vec!["cat"], keyword_matches(&filter, "<p>catが好き</p>"));
+      assert_eq!(vec!["猫"], keyword_matches(&filter, "<p>黒猫がいる</p>"));
+      assert_eq!(vec!["#tag"], keyword_matches(&filter, "<p>a#tag</p>"));
+      assert!(keyword_matches(&filter, "<p><a href=\"https://cat.example.com/\">link</a></p>").is_empty());
+   }
+
+   #[test]
+   fn character_reference() {
+      let filter = filter(&[("rock&roll", false), ("<3", false)]);
+
+      assert_eq!(vec!["rock&roll"], keyword_matches(&filter, "<p>rock&amp;roll</p>"));
+      assert_eq!(vec!["<3"], keyword_matches(&filter, "<p>&#60;3</p>"));
+      assert!(keyword_matches(&filter, "<p>rock&ampx;roll</p>").is_empty());
+   }
+
+   #[test]
+   fn invisible_text_is_not_searched() {
+      let filter = filter(&[("www", true), ("example.com", true), ("item", true)]);
+
+      let content = "<p><a href=\"https://www.example.com/path/to/page\">\
+         <span class=\"invisible\">https://www.</span>\
+         <span class=\"ellipsis\">example.com/path</span>\
+         <span class=\"invisible\">/to/page</span></a></p>";
+      assert_eq!(vec!["example.com"], keyword_matches(&filter, content));
+
+      assert_eq!(vec!["item"], keyword_matches(&filter, "<ul><li>first</li><li>item</li></ul>"));
+   }
+
+   #[test]
+   fn search_spoiler_text_poll_and_media() {
+      use crate::media_attachment::{MediaAttachment, MediaAttachmentId};
+      use crate::poll::{NoCredentialPoll, PollId, PollLocalId, PollOption};
+
+      let filter = filter(&[("spoiler", true), ("option", true), ("description", true)]);
+
+      let boosted_status = NoCredentialStatus {
+         spoiler_text: Some("spoiler".to_string()),
+         poll: Some(Cache::new(NoCredentialPoll {
+            id: PollId {
+               instance_url: "https://filter.example.com/".parse().unwrap(),
+               local: PollLocalId("poll id".to_string()),
+            },
+            expire_time: None,
+            is_expired: None,
+            allows_multiple_choices: None,
+            vote_count: None,
+            voter_count: None,
+            poll_options: vec![
+               PollOption { title: Some("option".to_string()), vote_count: None },
+            ],
+            emojis: vec![],
+         })),
+         media_attachments: vec![
+            MediaAttachment {
+               id: MediaAttachmentId("media id".to_string()),
+               url: None,
+               preview_url: None,
+               remote_url: None,
+               metadata: None,
+               description: Some("description".to_string()),
+               blurhash: None,
+            },
+         ],
+         ..status("status id")
+      };
+
+      let result = filter.apply(&boosted_status, &FilterContext::Home, Utc::now()).unwrap();
+      assert_eq!(Some(&filter), result.filter.as_ref());
+      assert_eq!(vec!["spoiler", "option", "description"], result.keyword_matches);
+      assert!(result.status_matches.is_empty());
+
+      let boost = NoCredentialStatus {
+         boosted_status: Some(Cache::new(boosted_status)),
+         ..status("boost id")
+      };
+      let result = filter.apply(&boost, &FilterContext::Home, Utc::now()).unwrap();
+      assert_eq!(vec!["spoiler", "option", "description"], result.keyword_matches);
+   }
+
+   #[test]
+   fn context_expire_time_and_statuses() {
+      use super::apply_filters;
+
+      let now = DateTime::from_timestamp(946684800, 0).unwrap();
+
+      let mut status_filter = filter(&[]);
+      status_filter.statuses = vec![
+         FilterStatus {
+            id: FilterStatusId("filter status id".to_string()),
+            status_id: status("filtered").id,
+         },
+      ];
+
+      let filtered = status("filtered");
+      let result = status_filter.apply(&filtered, &FilterContext::Public, now).unwrap();
+      assert!(result.keyword_matches.is_empty());
+      assert_eq!(vec![filtered.id.clone()], result.status_matches);
+
+      assert_eq!(None, status_filter.apply(&status("other"), &FilterContext::Public, now));
+      assert_eq!(None, status_filter.apply(&filtered, &FilterContext::Thread, now));
+
+      status_filter.expire_time = Some(now + Duration::seconds(1));
+      assert!(status_filter.apply(&filtered, &FilterContext::Public, now).is_some());
+      status_filter.expire_time = Some(now);
+      assert_eq!(None, status_filter.apply(&filtered, &FilterContext::Public, now));
+
+      let mut filters = vec![filter(&[("expired", true)]), filter(&[("keyword", true)])];
+      filters[0].expire_time = Some(now);
+
+      let status = NoCredentialStatus {
+         content: Some("<p>expired keyword</p>".to_string()),
+         ..status("status id")
+      };
+      let results = apply_filters(&filters, &status, &FilterContext::Home);
+      assert_eq!(1, results.len());
+      assert_eq!(vec!["keyword"], results[0].keyword_matches);
+   }
+
+   #[test]
+   fn context_and_action_serde_use_raw_value() {
+      let contexts = [
+         (FilterContext::Home,          "\"home\""),
+         (FilterContext::Notifications, "\"notifications\""),
+         (FilterContext::Public,        "\"public\""),
+         (FilterContext::Thread,        "\"thread\""),
+         (FilterContext::Account,       "\"account\""),
+         (FilterContext::Unknown("unknown".to_string()), "\"unknown\""),
+      ];
+
+      for (context, json) in contexts {
+         assert_eq!(json, serde_json::to_string(&context).unwrap());
+         assert_eq!(context, serde_json::from_str::<FilterContext>(json).unwrap());
+      }
+
+      let actions = [
+         (FilterAction::Warn, "\"warn\""),
+         (FilterAction::Hide, "\"hide\""),
+         (FilterAction::Blur, "\"blur\""),
+         (FilterAction::Unknown("unknown".to_string()), "\"unknown\""),
+      ];
+
+      for (action, json) in actions {
+         assert_eq!(json, serde_json::to_string(&action).unwrap());
+         assert_eq!(action, serde_json::from_str::<FilterAction>(json).unwrap());
+      }
+   }
+}
@@ -25,8 +25,11 @@ jvm_type! {
    JvmApplication,
    JvmCredentialAccount,
    JvmCustomEmoji,
+   JvmCustomEmojiCatalogue,
+   JvmEmojiTextRun,
    JvmFilter,
    JvmFilterKeyword,
+   JvmFilterList,
    JvmFilterResult,
    JvmFilterStatus,
    JvmInstance,
@@ -36,14 +39,27 @@ jvm_type! {
    JvmMediaAttachmentImageSize,
    JvmMediaAttachmentMetadata,
    JvmMediaAttachmentVideoSize,
+   JvmNotification,
+   JvmPage,
+   JvmPageCursor,
    JvmPoll,
    JvmPollNoCredential,
    JvmPollOption,
    JvmPreviewCard,
    JvmPreviewCardAuthor,
+   JvmRateLimitBudget,
    JvmRelationalAccount,
+   JvmRelationship,
+   JvmRichTextNode,
    JvmRole,
+   JvmSearchResult,
+   JvmSearchResultHashtag,
+   JvmSearchResultHashtagHistory,
    JvmStatus,
+   JvmStatusContext,
+   JvmStatusContextReplyTreeNode,
+   JvmStatusDraft,
+   JvmStatusDraftPoll,
    JvmStatusHashtag,
    JvmStatusId,
    JvmStatusMention,
@@ -20,10 +20,18 @@ pub mod custom_emoji;
 pub mod filter;
 pub mod instance;
 pub mod media_attachment;
+pub mod notification;
+pub mod page;
 pub mod poll;
 pub mod preview_card;
+pub mod rate_limit;
+pub mod relationship;
+pub mod rich_text;
 pub mod role;
+pub mod search;
 pub mod status;
+pub mod status_context;
+pub mod status_draft;
 pub mod token;
 mod cache;
 
@@ -36,12 +44,13 @@ mod jni_tests {
    use jni::objects::JObject;
    use ext_panoptiqon::repository_holder::RepositoryHolder;
    use panoptiqon::cache::Cache;
-   use panoptiqon::jvm_types::JvmCache;
+   use panoptiqon::jvm_types::{JvmCache, JvmList};
    use crate::account::Account;
    use crate::instance::Instance;
    use crate::jvm_types::{
-      JvmAccount, JvmCustomEmoji, JvmFilterResult, JvmInstance, JvmMediaAttachment,
-      JvmPoll, JvmPollNoCredential, JvmPreviewCard, JvmRole, JvmStatus,
+      JvmAccount, JvmCredentialAccount, JvmCustomEmoji, JvmFilterList,
+      JvmFilterResult, JvmInstance, JvmMediaAttachment, JvmPoll,
+      JvmPollNoCredential, JvmPreviewCard, JvmRichTextNode, JvmRole, JvmStatus,
       JvmStatusNoCredential,
    };
    use crate::poll::NoCredentialPoll;
@@ -357,6 +366,100 @@ mod jni_tests {
          .clone_into_jvm(&mut env)
    }
 
+   #[allow(non_upper_case_globals)]
+   static credentialAccount_visibility_toRust_instance_repo: RepositoryHolder<Instance> = RepositoryHolder::new();
+
+   #[allow(non_upper_case_globals)]
+   static credentialAccount_visibility_toRust_account_repo: RepositoryHolder<Account> = RepositoryHolder::new();
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_entity_ConvertJniTest_credentialAccount_1visibility_1toRust_00024createAccount<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>
+   ) -> JvmCache<'local, JvmAccount<'local>> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+
+      save_account(
+         &mut env,
+         &credentialAccount_visibility_toRust_account_repo,
+         &credentialAccount_visibility_toRust_instance_repo
+      ).clone_into_jvm(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_entity_ConvertJniTest_credentialAccount_1visibility_1toRust_00024assert<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      credential_accounts: JvmList<'local, JvmCredentialAccount<'local>>
+   ) {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+      use crate::account::CredentialAccount;
+      use crate::status::StatusVisibility;
+
+      let credential_accounts
+         = Vec::<CredentialAccount>::clone_from_jvm(&mut env, &credential_accounts);
+
+      assert_eq!(
+         vec![
+            Some(StatusVisibility::Public),
+            Some(StatusVisibility::Unlisted),
+            Some(StatusVisibility::Private),
+            Some(StatusVisibility::Direct),
+            Some(StatusVisibility::Unknown("unknown visibility".to_string())),
+            None,
+         ],
+         credential_accounts.into_iter()
+            .map(|a| a.default_post_visibility)
+            .collect::<Vec<_>>()
+      );
+   }
+
+   #[allow(non_upper_case_globals)]
+   static credentialAccount_visibility_fromRust_instance_repo: RepositoryHolder<Instance> = RepositoryHolder::new();
+
+   #[allow(non_upper_case_globals)]
+   static credentialAccount_visibility_fromRust_account_repo: RepositoryHolder<Account> = RepositoryHolder::new();
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_entity_ConvertJniTest_credentialAccount_1visibility_1fromRust_00024createCredentialAccounts<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>
+   ) -> JvmList<'local, JvmCredentialAccount<'local>> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+      use crate::account::CredentialAccount;
+      use crate::status::StatusVisibility;
+
+      let account = save_account(
+         &mut env,
+         &credentialAccount_visibility_fromRust_account_repo,
+         &credentialAccount_visibility_fromRust_instance_repo
+      );
+
+      let credential_accounts: Vec<_> = [
+         Some(StatusVisibility::Public),
+         Some(StatusVisibility::Unlisted),
+         Some(StatusVisibility::Private),
+         Some(StatusVisibility::Direct),
+         Some(StatusVisibility::Unknown("unknown visibility".to_string())),
+         None,
+      ]
+         .into_iter()
+         .map(|visibility| CredentialAccount {
+            id: account.get().id.clone(),
+            account: account.clone(),
+            raw_profile_note: None,
+            raw_profile_fields: vec![],
+            default_post_visibility: visibility,
+            default_post_sensitivity: None,
+            default_post_language: None,
+            follow_request_count: None,
+            role: None,
+         })
+         .collect();
+
+      credential_accounts.clone_into_jvm(&mut env)
+   }
+
    #[allow(non_upper_case_globals)]
    static customEmoji_toRust_instance_repo: RepositoryHolder<Instance> = RepositoryHolder::new();
 
@@ -523,12 +626,12 @@ mod jni_tests {
                id: FilterId("filter id".to_string()),
                title: Some("title".to_string()),
                context: vec![
-                  FilterContext("home".to_string()),
-                  FilterContext("public".to_string()),
-                  FilterContext("illegal context".to_string()),
+                  FilterContext::Home,
+                  FilterContext::Public,
+                  FilterContext::Unknown("illegal context".to_string()),
                ],
                expire_time: Some(Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap()),
-               filter_action: Some(FilterAction("hide".to_string())),
+               filter_action: Some(FilterAction::Hide),
                keywords: vec![
                   FilterKeyword {
                      id: FilterKeywordId("filter keyword id1".to_string()),
@@ -631,12 +734,12 @@ mod jni_tests {
             id: FilterId("filter id".to_string()),
             title: Some("title".to_string()),
             context: vec![
-               FilterContext("home".to_string()),
-               FilterContext("public".to_string()),
-               FilterContext("illegal context".to_string()),
+               FilterContext::Home,
+               FilterContext::Public,
+               FilterContext::Unknown("illegal context".to_string()),
             ],
             expire_time: Some(Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap()),
-            filter_action: Some(FilterAction("hide".to_string())),
+            filter_action: Some(FilterAction::Hide),
             keywords: vec![
                FilterKeyword {
                   id: FilterKeywordId("filter keyword id1".to_string()),
@@ -717,6 +820,68 @@ mod jni_tests {
       filter_result.clone_into_jvm(&mut env)
    }
 
+   fn enum_filter_list() -> crate::filter::FilterList {
+      use crate::account::{AccountId, AccountLocalId};
+      use crate::filter::{Filter, FilterAction, FilterContext, FilterId, FilterList};
+
+      let filter_actions = [
+         Some(FilterAction::Warn),
+         Some(FilterAction::Hide),
+         Some(FilterAction::Blur),
+         Some(FilterAction::Unknown("unknown action".to_string())),
+         None,
+      ];
+
+      FilterList {
+         account_id: AccountId {
+            instance_url: "https://example.com/instance/url".parse().unwrap(),
+            local: AccountLocalId("account id".to_string()),
+         },
+         filters: filter_actions.into_iter().enumerate()
+            .map(|(i, filter_action)| Filter {
+               id: FilterId(format!("filter id{i}")),
+               title: None,
+               context: vec![
+                  FilterContext::Home,
+                  FilterContext::Notifications,
+                  FilterContext::Public,
+                  FilterContext::Thread,
+                  FilterContext::Account,
+                  FilterContext::Unknown("unknown context".to_string()),
+               ],
+               expire_time: None,
+               filter_action,
+               keywords: vec![],
+               statuses: vec![],
+            })
+            .collect(),
+      }
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_entity_ConvertJniTest_filterList_1enums_1toRust_00024assert<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      filter_list: JvmFilterList<'local>
+   ) {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+      use crate::filter::FilterList;
+
+      let filter_list = FilterList::clone_from_jvm(&mut env, &filter_list);
+
+      assert_eq!(enum_filter_list(), filter_list);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_entity_ConvertJniTest_filterList_1enums_1fromRust_00024createFilterList<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>
+   ) -> JvmFilterList<'local> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+
+      enum_filter_list().clone_into_jvm(&mut env)
+   }
+
    #[no_mangle]
    extern "C" fn Java_com_wcaokaze_probosqis_mastodon_entity_ConvertJniTest_mediaAttachment_1image_1toRust_00024assert<'local>(
       mut env: JNIEnv<'local>,
@@ -1958,6 +2123,84 @@ mod jni_tests {
       preview_card.clone_into_jvm(&mut env)
    }
 
+   fn rich_text_nodes() -> Vec<crate::rich_text::RichTextNode> {
+      use crate::account::{AccountId, AccountLocalId};
+      use crate::rich_text::RichTextNode;
+
+      vec![
+         RichTextNode::Paragraph(vec![
+            RichTextNode::Mention {
+               account_id: Some(AccountId {
+                  instance_url: "https://example.com/instance/url".parse().unwrap(),
+                  local: AccountLocalId("account id".to_string()),
+               }),
+               acct: Some("acct".to_string()),
+               url: "https://example.com/@username".parse().unwrap(),
+               children: vec![RichTextNode::Text("@username".to_string())],
+            },
+            RichTextNode::Text(" ".to_string()),
+            RichTextNode::Mention {
+               account_id: None,
+               acct: None,
+               url: "https://example.com/@unknown".parse().unwrap(),
+               children: vec![RichTextNode::Text("@unknown".to_string())],
+            },
+            RichTextNode::Hashtag {
+               name: "hashtag".to_string(),
+               url: "https://example.com/tags/hashtag".parse().unwrap(),
+               children: vec![RichTextNode::Text("#hashtag".to_string())],
+            },
+            RichTextNode::Link {
+               url: "https://example.com/link".parse().unwrap(),
+               children: vec![RichTextNode::Text("example.com/link".to_string())],
+            },
+            RichTextNode::Strong(vec![RichTextNode::Text("strong".to_string())]),
+            RichTextNode::Emphasis(vec![RichTextNode::Text("emphasis".to_string())]),
+            RichTextNode::Deleted(vec![RichTextNode::Text("deleted".to_string())]),
+            RichTextNode::Code(vec![RichTextNode::Text("code".to_string())]),
+         ]),
+         RichTextNode::Preformatted(vec![RichTextNode::Text("preformatted".to_string())]),
+         RichTextNode::BlockQuote(vec![RichTextNode::Text("block quote".to_string())]),
+         RichTextNode::UnorderedList(vec![
+            RichTextNode::ListItem(vec![RichTextNode::Text("item".to_string())]),
+         ]),
+         RichTextNode::OrderedList {
+            start: Some(3),
+            children: vec![
+               RichTextNode::ListItem(vec![RichTextNode::Text("item".to_string())]),
+            ],
+         },
+         RichTextNode::OrderedList {
+            start: None,
+            children: vec![],
+         },
+      ]
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_entity_ConvertJniTest_richText_1toRust_00024assert<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      nodes: JvmList<'local, JvmRichTextNode<'local>>
+   ) {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+      use crate::rich_text::RichTextNode;
+
+      let nodes = Vec::<RichTextNode>::clone_from_jvm(&mut env, &nodes);
+
+      assert_eq!(rich_text_nodes(), nodes);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_entity_ConvertJniTest_richText_1fromRust_00024createNodes<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>
+   ) -> JvmList<'local, JvmRichTextNode<'local>> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+
+      rich_text_nodes().clone_into_jvm(&mut env)
+   }
+
    #[allow(non_upper_case_globals)]
    static role_toRust_instance_repo: RepositoryHolder<Instance> = RepositoryHolder::new();
 
@@ -2321,7 +2564,7 @@ mod jni_tests {
                Some(account)
             },
             content: Some("content".to_string()),
-            visibility: Some(StatusVisibility("public".to_string())),
+            visibility: Some(StatusVisibility::Public),
             is_sensitive: Some(true),
             spoiler_text: Some("spoilerText".to_string()),
             media_attachments: vec![
@@ -2603,10 +2846,10 @@ mod jni_tests {
                   filter: Some(Filter {
                      id: FilterId("filter id".to_string()),
                      title: Some("title".to_string()),
-                     context: vec![FilterContext("home".to_string())],
+                     context: vec![FilterContext::Home],
                      expire_time:
                         Some(Utc.with_ymd_and_hms(2000, 1, 5, 0, 0, 0).unwrap()),
-                     filter_action: Some(FilterAction("hide".to_string())),
+                     filter_action: Some(FilterAction::Hide),
                      keywords: vec![
                         FilterKeyword {
                            id: FilterKeywordId("filter keyword id".to_string()),
@@ -2882,7 +3125,7 @@ mod jni_tests {
             )
          ),
          content: Some("content".to_string()),
-         visibility: Some(StatusVisibility("public".to_string())),
+         visibility: Some(StatusVisibility::Public),
          is_sensitive: Some(true),
          spoiler_text: Some("spoilerText".to_string()),
          media_attachments: vec![
@@ -3098,10 +3341,10 @@ mod jni_tests {
                filter: Some(Filter {
                   id: FilterId("filter id".to_string()),
                   title: Some("title".to_string()),
-                  context: vec![FilterContext("home".to_string())],
+                  context: vec![FilterContext::Home],
                   expire_time:
                   Some(Utc.with_ymd_and_hms(2000, 1, 5, 0, 0, 0).unwrap()),
-                  filter_action: Some(FilterAction("hide".to_string())),
+                  filter_action: Some(FilterAction::Hide),
                   keywords: vec![
                      FilterKeyword {
                         id: FilterKeywordId("filter keyword id".to_string()),
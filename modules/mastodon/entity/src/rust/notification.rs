@@ -0,0 +1,214 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::{DateTime, Utc};
+use panoptiqon::cache::Cache;
+use serde::{Deserialize, Serialize};
+use url::Url;
+use crate::account::Account;
+use crate::status::Status;
+
+#[cfg(feature = "jvm")]
+use {
+   ext_panoptiqon::convert_jvm_helper,
+   jni::JNIEnv,
+   panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm},
+   panoptiqon::jvm_types::{JvmCache, JvmList, JvmLong, JvmNullable, JvmString},
+   crate::jvm_types::{JvmAccount, JvmNotification, JvmStatus},
+};
+
+/// 通知。
+///
+/// mastodon 4.3.0以降のグループ化された通知の場合、`accounts`に
+/// 通知を発生させたアカウントのうちいくつかが含まれる。
+/// グループ化されていない通知の場合`accounts`は1件になる
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+pub struct Notification {
+   pub id: NotificationId,
+   pub notification_type: NotificationType,
+   pub created_time: Option<DateTime<Utc>>,
+   #[serde(with = "ext_panoptiqon::cache_reference::vec")]
+   pub accounts: Vec<Cache<Account>>,
+   #[serde(with = "ext_panoptiqon::cache_reference::option")]
+   pub status: Option<Cache<Status>>,
+   pub group_key: Option<String>,
+   pub notification_count: Option<i64>,
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize, Serialize)]
+pub struct NotificationId {
+   pub instance_url: Url,
+   pub local: NotificationLocalId,
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize, Serialize)]
+pub struct NotificationLocalId(pub String);
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub enum NotificationType {
+   Mention,
+   Boost,
+   Favorite,
+   Follow,
+   FollowRequest,
+   Poll,
+   Update,
+   Status,
+   AdminSignUp,
+   AdminReport,
+   Unknown(String),
+}
+
+impl NotificationType {
+   pub fn from_raw(raw: String) -> NotificationType {
+      match raw.as_str() {
+         "mention"        => NotificationType::Mention,
+         "reblog"         => NotificationType::Boost,
+         "favourite"      => NotificationType::Favorite,
+         "follow"         => NotificationType::Follow,
+         "follow_request" => NotificationType::FollowRequest,
+         "poll"           => NotificationType::Poll,
+         "update"         => NotificationType::Update,
+         "status"         => NotificationType::Status,
+         "admin.sign_up"  => NotificationType::AdminSignUp,
+         "admin.report"   => NotificationType::AdminReport,
+         _ => NotificationType::Unknown(raw),
+      }
+   }
+
+   pub fn raw(&self) -> &str {
+      match self {
+         NotificationType::Mention       => "mention",
+         NotificationType::Boost         => "reblog",
+         NotificationType::Favorite      => "favourite",
+         NotificationType::Follow        => "follow",
+         NotificationType::FollowRequest => "follow_request",
+         NotificationType::Poll          => "poll",
+         NotificationType::Update        => "update",
+         NotificationType::Status        => "status",
+         NotificationType::AdminSignUp   => "admin.sign_up",
+         NotificationType::AdminReport   => "admin.report",
+         NotificationType::Unknown(raw)  => raw,
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static NOTIFICATION_HELPER = impl struct NotificationConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/Notification"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmNotification<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+            Ljava/lang/Long;\
+            Ljava/util/List;\
+            Lcom/wcaokaze/probosqis/panoptiqon/Cache;\
+            Ljava/lang/String;\
+            Ljava/lang/Long;\
+         )V";
+
+      fn raw_instance_url<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawInstanceUrl",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn raw_local_id<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawLocalId",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn raw_type<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawType",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn created_time_epoch_millis<'local>(..) -> Option<i64>
+         where jvm_type: JvmNullable<'local, JvmLong<'local>>,
+               jvm_getter_method: "getCreatedTimeEpochMillis",
+               jvm_return_type: "Ljava/lang/Long;";
+
+      fn accounts<'local>(..) -> Vec<Cache<Account>>
+         where jvm_type: JvmList<'local, JvmCache<'local, JvmAccount<'local>>>,
+               jvm_getter_method: "getAccounts",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn status<'local>(..) -> Option<Cache<Status>>
+         where jvm_type: JvmNullable<'local, JvmCache<'local, JvmStatus<'local>>>,
+               jvm_getter_method: "getStatus",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/panoptiqon/Cache;";
+
+      fn group_key<'local>(..) -> Option<String>
+         where jvm_type: JvmNullable<'local, JvmString<'local>>,
+               jvm_getter_method: "getGroupKey",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn notification_count<'local>(..) -> Option<i64>
+         where jvm_type: JvmNullable<'local, JvmLong<'local>>,
+               jvm_getter_method: "getNotificationCount",
+               jvm_return_type: "Ljava/lang/Long;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmNotification<'local>> for Notification {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmNotification<'local> {
+      NOTIFICATION_HELPER.clone_into_jvm(
+         env,
+         &self.id.instance_url.as_str(),
+         &self.id.local.0,
+         &self.notification_type.raw(),
+         &self.created_time.map(|t| t.timestamp_millis()),
+         &self.accounts,
+         &self.status,
+         &self.group_key,
+         &self.notification_count,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmNotification<'local>> for Notification {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmNotification<'local>
+   ) -> Notification {
+      let raw_instance_url          = NOTIFICATION_HELPER.raw_instance_url         (env, jvm_instance);
+      let raw_local_id              = NOTIFICATION_HELPER.raw_local_id             (env, jvm_instance);
+      let raw_type                  = NOTIFICATION_HELPER.raw_type                 (env, jvm_instance);
+      let created_time_epoch_millis = NOTIFICATION_HELPER.created_time_epoch_millis(env, jvm_instance);
+      let accounts                  = NOTIFICATION_HELPER.accounts                 (env, jvm_instance);
+      let status                    = NOTIFICATION_HELPER.status                   (env, jvm_instance);
+      let group_key                 = NOTIFICATION_HELPER.group_key                (env, jvm_instance);
+      let notification_count        = NOTIFICATION_HELPER.notification_count       (env, jvm_instance);
+
+      Notification {
+         id: NotificationId {
+            instance_url: raw_instance_url.parse().unwrap(),
+            local: NotificationLocalId(raw_local_id),
+         },
+         notification_type: NotificationType::from_raw(raw_type),
+         created_time: created_time_epoch_millis
+            .map(|time| DateTime::from_timestamp_millis(time).unwrap()),
+         accounts,
+         status,
+         group_key,
+         notification_count,
+      }
+   }
+}
@@ -0,0 +1,234 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "jvm")]
+use {
+   ext_panoptiqon::convert_jvm_helper,
+   jni::JNIEnv,
+   panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm},
+   panoptiqon::jvm_types::{JvmList, JvmNullable, JvmString},
+   crate::jvm_types::{JvmNotification, JvmPage, JvmPageCursor, JvmStatus},
+   crate::notification::Notification,
+   crate::status::Status,
+};
+
+/// タイムラインなどのページングされたリストの1ページ分
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+pub struct Page<T> {
+   pub items: Vec<T>,
+   /// このページより古い要素を取得するためのカーソル
+   pub next: Option<PageCursor>,
+   /// このページより新しい要素を取得するためのカーソル
+   pub prev: Option<PageCursor>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Default, Deserialize, Serialize)]
+pub struct PageCursor {
+   pub max_id: Option<String>,
+   pub since_id: Option<String>,
+   pub min_id: Option<String>,
+}
+
+// Kotlin側のPageは型パラメータが消去されるため、要素の型ごとに変換を実装する
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static STATUS_PAGE_HELPER = impl struct StatusPageConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/Page"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmPage<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+            Lcom/wcaokaze/probosqis/mastodon/entity/PageCursor;\
+            Lcom/wcaokaze/probosqis/mastodon/entity/PageCursor;\
+         )V";
+
+      fn items<'local>(..) -> Vec<Status>
+         where jvm_type: JvmList<'local, JvmStatus<'local>>,
+               jvm_getter_method: "getItems",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn next<'local>(..) -> Option<PageCursor>
+         where jvm_type: JvmNullable<'local, JvmPageCursor<'local>>,
+               jvm_getter_method: "getNext",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/PageCursor;";
+
+      fn prev<'local>(..) -> Option<PageCursor>
+         where jvm_type: JvmNullable<'local, JvmPageCursor<'local>>,
+               jvm_getter_method: "getPrev",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/PageCursor;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmPage<'local>> for Page<Status> {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmPage<'local> {
+      STATUS_PAGE_HELPER.clone_into_jvm(
+         env,
+         &self.items,
+         &self.next,
+         &self.prev,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmPage<'local>> for Page<Status> {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmPage<'local>
+   ) -> Page<Status> {
+      let items = STATUS_PAGE_HELPER.items(env, jvm_instance);
+      let next  = STATUS_PAGE_HELPER.next (env, jvm_instance);
+      let prev  = STATUS_PAGE_HELPER.prev (env, jvm_instance);
+
+      Page { items, next, prev }
+   }
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static NOTIFICATION_PAGE_HELPER = impl struct NotificationPageConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/Page"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmPage<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+            Lcom/wcaokaze/probosqis/mastodon/entity/PageCursor;\
+            Lcom/wcaokaze/probosqis/mastodon/entity/PageCursor;\
+         )V";
+
+      fn items<'local>(..) -> Vec<Notification>
+         where jvm_type: JvmList<'local, JvmNotification<'local>>,
+               jvm_getter_method: "getItems",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn next<'local>(..) -> Option<PageCursor>
+         where jvm_type: JvmNullable<'local, JvmPageCursor<'local>>,
+               jvm_getter_method: "getNext",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/PageCursor;";
+
+      fn prev<'local>(..) -> Option<PageCursor>
+         where jvm_type: JvmNullable<'local, JvmPageCursor<'local>>,
+               jvm_getter_method: "getPrev",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/PageCursor;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmPage<'local>> for Page<Notification> {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmPage<'local> {
+      NOTIFICATION_PAGE_HELPER.clone_into_jvm(
+         env,
+         &self.items,
+         &self.next,
+         &self.prev,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmPage<'local>> for Page<Notification> {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmPage<'local>
+   ) -> Page<Notification> {
+      let items = NOTIFICATION_PAGE_HELPER.items(env, jvm_instance);
+      let next  = NOTIFICATION_PAGE_HELPER.next (env, jvm_instance);
+      let prev  = NOTIFICATION_PAGE_HELPER.prev (env, jvm_instance);
+
+      Page { items, next, prev }
+   }
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static PAGE_CURSOR_HELPER = impl struct PageCursorConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/PageCursor"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmPageCursor<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+         )V";
+
+      fn max_id<'local>(..) -> Option<String>
+         where jvm_type: JvmNullable<'local, JvmString<'local>>,
+               jvm_getter_method: "getMaxId",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn since_id<'local>(..) -> Option<String>
+         where jvm_type: JvmNullable<'local, JvmString<'local>>,
+               jvm_getter_method: "getSinceId",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn min_id<'local>(..) -> Option<String>
+         where jvm_type: JvmNullable<'local, JvmString<'local>>,
+               jvm_getter_method: "getMinId",
+               jvm_return_type: "Ljava/lang/String;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmPageCursor<'local>> for PageCursor {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmPageCursor<'local> {
+      PAGE_CURSOR_HELPER.clone_into_jvm(
+         env,
+         &self.max_id,
+         &self.since_id,
+         &self.min_id,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmPageCursor<'local>> for PageCursor {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmPageCursor<'local>
+   ) -> PageCursor {
+      let max_id   = PAGE_CURSOR_HELPER.max_id  (env, jvm_instance);
+      let since_id = PAGE_CURSOR_HELPER.since_id(env, jvm_instance);
+      let min_id   = PAGE_CURSOR_HELPER.min_id  (env, jvm_instance);
+
+      PageCursor { max_id, since_id, min_id }
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use super::{Page, PageCursor};
+
+   #[test]
+   fn serde_round_trip() {
+      let page = Page {
+         items: vec!["1".to_string(), "2".to_string()],
+         next: Some(PageCursor {
+            max_id: Some("1".to_string()),
+            ..PageCursor::default()
+         }),
+         prev: Some(PageCursor {
+            min_id: Some("2".to_string()),
+            ..PageCursor::default()
+         }),
+      };
+
+      let json = serde_json::to_string(&page).unwrap();
+      assert_eq!(page, serde_json::from_str::<Page<String>>(&json).unwrap());
+   }
+}
@@ -15,10 +15,10 @@
  */
 
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
 use panoptiqon::cache::Cache;
-use crate::custom_emoji::CustomEmoji;
+use crate::custom_emoji::{self, CustomEmoji, EmojiTextRun};
 
 #[cfg(feature = "jvm")]
 use {
@@ -33,15 +33,16 @@ use {
    },
 };
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct Poll {
    pub id: PollId,
+   #[serde(with = "ext_panoptiqon::cache_reference")]
    pub no_credential: Cache<NoCredentialPoll>,
    pub is_voted: Option<bool>,
    pub voted_options: Vec<i64>,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct NoCredentialPoll {
    pub id: PollId,
    pub expire_time: Option<DateTime<Utc>>,
@@ -53,21 +54,29 @@ pub struct NoCredentialPoll {
    pub emojis: Vec<CustomEmoji>,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize, Serialize)]
 pub struct PollId {
    pub instance_url: Url,
    pub local: PollLocalId,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize, Serialize)]
 pub struct PollLocalId(pub String);
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct PollOption {
    pub title: Option<String>,
    pub vote_count: Option<i64>,
 }
 
+impl PollOption {
+   /// `emojis`には投票の`NoCredentialPoll::emojis`を渡す
+   pub fn parse_title(&self, emojis: &[CustomEmoji]) -> Option<Vec<EmojiTextRun>> {
+      let title = self.title.as_ref()?;
+      Some(custom_emoji::tokenize_emojis(title, emojis))
+   }
+}
+
 #[cfg(feature = "jvm")]
 convert_jvm_helper! {
    static POLL_HELPER = impl struct PollConvertHelper
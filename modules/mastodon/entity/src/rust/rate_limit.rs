@@ -0,0 +1,85 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "jvm")]
+use {
+   ext_panoptiqon::convert_jvm_helper,
+   jni::JNIEnv,
+   panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm},
+   crate::jvm_types::JvmRateLimitBudget,
+};
+
+/// アカウントごとのレート制限の状態。
+/// 残りが少ない場合はカラムの更新頻度を下げるなどに使う
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct RateLimitBudget {
+   pub limit: i64,
+   pub remaining: i64,
+   pub reset_time: DateTime<Utc>,
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static HELPER = impl struct RateLimitBudgetConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RateLimitBudget"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRateLimitBudget<'local>
+         where jvm_constructor: "(JJJ)V";
+
+      fn limit<'local>(..) -> i64
+         where jvm_getter_method: "getLimit",
+               jvm_return_type: "J";
+
+      fn remaining<'local>(..) -> i64
+         where jvm_getter_method: "getRemaining",
+               jvm_return_type: "J";
+
+      fn reset_time_epoch_millis<'local>(..) -> i64
+         where jvm_getter_method: "getResetTimeEpochMillis",
+               jvm_return_type: "J";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmRateLimitBudget<'local>> for RateLimitBudget {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmRateLimitBudget<'local> {
+      HELPER.clone_into_jvm(
+         env,
+         self.limit,
+         self.remaining,
+         self.reset_time.timestamp_millis(),
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmRateLimitBudget<'local>> for RateLimitBudget {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmRateLimitBudget<'local>
+   ) -> RateLimitBudget {
+      let limit                   = HELPER.limit                  (env, jvm_instance);
+      let remaining               = HELPER.remaining              (env, jvm_instance);
+      let reset_time_epoch_millis = HELPER.reset_time_epoch_millis(env, jvm_instance);
+
+      RateLimitBudget {
+         limit,
+         remaining,
+         reset_time: DateTime::from_timestamp_millis(reset_time_epoch_millis).unwrap(),
+      }
+   }
+}
@@ -0,0 +1,236 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use isolang::Language;
+use serde::{Deserialize, Serialize};
+use crate::account::AccountId;
+
+#[cfg(feature = "jvm")]
+use {
+   ext_panoptiqon::convert_jvm_helper,
+   jni::JNIEnv,
+   panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm},
+   panoptiqon::jvm_types::{JvmBoolean, JvmList, JvmNullable, JvmString},
+   crate::account::AccountLocalId,
+   crate::jvm_types::JvmRelationship,
+};
+
+/// ログイン中のアカウントから見た`id`のアカウントとの関係
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub struct Relationship {
+   pub id: AccountId,
+   pub is_following: Option<bool>,
+   pub is_showing_boosts: Option<bool>,
+   pub is_notifying: Option<bool>,
+   /// フォロー中のアカウントの投稿のうちホームタイムラインに表示する言語。
+   /// 空の場合すべての言語
+   pub languages: Vec<Language>,
+   pub is_followed_by: Option<bool>,
+   pub is_blocking: Option<bool>,
+   pub is_blocked_by: Option<bool>,
+   pub is_muting: Option<bool>,
+   pub is_muting_notifications: Option<bool>,
+   pub is_requested: Option<bool>,
+   pub is_requested_by: Option<bool>,
+   pub is_domain_blocking: Option<bool>,
+   pub is_endorsed: Option<bool>,
+   pub note: Option<String>,
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static HELPER = impl struct RelationshipConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/Relationship"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRelationship<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/Boolean;\
+            Ljava/util/List;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/String;\
+         )V";
+
+      fn raw_instance_url<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawInstanceUrl",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn raw_local_id<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawLocalId",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn is_following<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isFollowing",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn is_showing_boosts<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isShowingBoosts",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn is_notifying<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isNotifying",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn languages<'local>(..) -> Vec<String>
+         where jvm_type: JvmList<'local, JvmString<'local>>,
+               jvm_getter_method: "getLanguages",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn is_followed_by<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isFollowedBy",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn is_blocking<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isBlocking",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn is_blocked_by<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isBlockedBy",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn is_muting<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isMuting",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn is_muting_notifications<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isMutingNotifications",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn is_requested<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isRequested",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn is_requested_by<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isRequestedBy",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn is_domain_blocking<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isDomainBlocking",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn is_endorsed<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isEndorsed",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn note<'local>(..) -> Option<String>
+         where jvm_type: JvmNullable<'local, JvmString<'local>>,
+               jvm_getter_method: "getNote",
+               jvm_return_type: "Ljava/lang/String;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmRelationship<'local>> for Relationship {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmRelationship<'local> {
+      let languages: Vec<String> = self.languages.iter()
+         .filter_map(|l| l.to_639_1())
+         .map(|l| l.to_string())
+         .collect();
+
+      HELPER.clone_into_jvm(
+         env,
+         self.id.instance_url.as_str(),
+         &self.id.local.0,
+         &self.is_following,
+         &self.is_showing_boosts,
+         &self.is_notifying,
+         &languages,
+         &self.is_followed_by,
+         &self.is_blocking,
+         &self.is_blocked_by,
+         &self.is_muting,
+         &self.is_muting_notifications,
+         &self.is_requested,
+         &self.is_requested_by,
+         &self.is_domain_blocking,
+         &self.is_endorsed,
+         &self.note,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmRelationship<'local>> for Relationship {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmRelationship<'local>
+   ) -> Relationship {
+      let raw_instance_url        = HELPER.raw_instance_url       (env, jvm_instance);
+      let raw_local_id            = HELPER.raw_local_id           (env, jvm_instance);
+      let is_following            = HELPER.is_following           (env, jvm_instance);
+      let is_showing_boosts       = HELPER.is_showing_boosts      (env, jvm_instance);
+      let is_notifying            = HELPER.is_notifying           (env, jvm_instance);
+      let languages               = HELPER.languages              (env, jvm_instance);
+      let is_followed_by          = HELPER.is_followed_by         (env, jvm_instance);
+      let is_blocking             = HELPER.is_blocking            (env, jvm_instance);
+      let is_blocked_by           = HELPER.is_blocked_by          (env, jvm_instance);
+      let is_muting               = HELPER.is_muting              (env, jvm_instance);
+      let is_muting_notifications = HELPER.is_muting_notifications(env, jvm_instance);
+      let is_requested            = HELPER.is_requested           (env, jvm_instance);
+      let is_requested_by         = HELPER.is_requested_by        (env, jvm_instance);
+      let is_domain_blocking      = HELPER.is_domain_blocking     (env, jvm_instance);
+      let is_endorsed             = HELPER.is_endorsed            (env, jvm_instance);
+      let note                    = HELPER.note                   (env, jvm_instance);
+
+      Relationship {
+         id: AccountId {
+            instance_url: raw_instance_url.parse().unwrap(),
+            local: AccountLocalId(raw_local_id),
+         },
+         is_following,
+         is_showing_boosts,
+         is_notifying,
+         languages: languages.iter()
+            .filter_map(|code| Language::from_639_1(code))
+            .collect(),
+         is_followed_by,
+         is_blocking,
+         is_blocked_by,
+         is_muting,
+         is_muting_notifications,
+         is_requested,
+         is_requested_by,
+         is_domain_blocking,
+         is_endorsed,
+         note,
+      }
+   }
+}
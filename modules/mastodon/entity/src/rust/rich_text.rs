@@ -0,0 +1,1267 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+use crate::account::AccountId;
+use crate::custom_emoji::{tokenize_emojis, CustomEmoji, EmojiTextRun};
+use crate::status::{StatusHashtag, StatusMention};
+
+#[cfg(feature = "jvm")]
+use {
+   jni::JNIEnv,
+   ext_panoptiqon::convert_jvm_helper,
+   panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm},
+   panoptiqon::jvm_type::JvmType,
+   panoptiqon::jvm_types::{JvmList, JvmLong, JvmNullable, JvmString, JvmUnit},
+   crate::jvm_types::{JvmAccountId, JvmCustomEmoji, JvmRichTextNode},
+};
+
+/// 投稿の本文やプロフィールのHTMLをパースしたもの
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+pub enum RichTextNode {
+   Text(String),
+   Paragraph(Vec<RichTextNode>),
+   Link {
+      url: Url,
+      children: Vec<RichTextNode>,
+   },
+   Mention {
+      account_id: Option<AccountId>,
+      acct: Option<String>,
+      url: Url,
+      children: Vec<RichTextNode>,
+   },
+   Hashtag {
+      name: String,
+      url: Url,
+      children: Vec<RichTextNode>,
+   },
+   Emoji(CustomEmoji),
+   /// since mastodon 4.0.0
+   Strong(Vec<RichTextNode>),
+   /// since mastodon 4.0.0
+   Emphasis(Vec<RichTextNode>),
+   /// since mastodon 4.0.0
+   Deleted(Vec<RichTextNode>),
+   /// since mastodon 4.0.0
+   Code(Vec<RichTextNode>),
+   /// since mastodon 4.0.0
+   Preformatted(Vec<RichTextNode>),
+   /// since mastodon 4.0.0
+   BlockQuote(Vec<RichTextNode>),
+   /// since mastodon 4.0.0
+   UnorderedList(Vec<RichTextNode>),
+   /// since mastodon 4.0.0
+   OrderedList {
+      start: Option<i64>,
+      children: Vec<RichTextNode>,
+   },
+   /// since mastodon 4.0.0
+   ListItem(Vec<RichTextNode>),
+}
+
+/// MastodonのHTMLをパースする。
+///
+/// リンクは`mentions`、`hashtags`と照合してメンションとハッシュタグに、
+/// テキスト中の`:shortcode:`は`emojis`と照合してカスタム絵文字になる。
+/// `span.invisible`は除去され、`span.ellipsis`の後ろには`…`が付加される。
+/// 未対応のタグは取り除かれ、中身だけが残る
+pub fn parse_html(
+   html: &str,
+   mentions: &[StatusMention],
+   hashtags: &[StatusHashtag],
+   emojis: &[CustomEmoji],
+) -> Vec<RichTextNode> {
+   let mut builder = TreeBuilder {
+      mentions,
+      hashtags,
+      emojis,
+      stack: vec![Element {
+         tag_name: String::new(),
+         kind: ElementKind::Root,
+         children: vec![],
+      }],
+   };
+
+   for token in tokenize(html) {
+      match token {
+         Token::Text(text) => builder.push_text(&text),
+         Token::StartTag { name, attributes, is_self_closing } => {
+            builder.start_element(name, attributes, is_self_closing);
+         }
+         Token::EndTag(name) => builder.end_element(&name),
+      }
+   }
+
+   while builder.stack.len() > 1 {
+      builder.close_element();
+   }
+
+   builder.stack.pop().unwrap().children
+}
+
+enum Token {
+   Text(String),
+   StartTag {
+      name: String,
+      attributes: Vec<(String, String)>,
+      is_self_closing: bool,
+   },
+   EndTag(String),
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+   let mut tokens = vec![];
+   let mut text = String::new();
+   let mut rest = html;
+
+   while let Some(i) = rest.find('<') {
+      text.push_str(&decode_text(&rest[..i]));
+      rest = &rest[i..];
+
+      if let Some(comment) = rest.strip_prefix("<!--") {
+         rest = comment.find("-->").map(|end| &comment[end + 3..]).unwrap_or("");
+         continue;
+      }
+
+      match parse_tag(&rest[1..]) {
+         Some((token, remaining)) => {
+            if !text.is_empty() {
+               tokens.push(Token::Text(std::mem::take(&mut text)));
+            }
+            tokens.push(token);
+            rest = remaining;
+         }
+         None => {
+            text.push('<');
+            rest = &rest[1..];
+         }
+      }
+   }
+
+   text.push_str(&decode_text(rest));
+   if !text.is_empty() {
+      tokens.push(Token::Text(text));
+   }
+
+   tokens
+}
+
+/// `<`の直後から`>`までをパースする。タグとして不正な場合None
+fn parse_tag(tag: &str) -> Option<(Token, &str)> {
+   let (is_end_tag, mut rest) = match tag.strip_prefix('/') {
+      Some(rest) => (true, rest),
+      None => (false, tag),
+   };
+
+   let name_len = rest.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(rest.len());
+   if name_len == 0 {
+      return None;
+   }
+   let name = rest[..name_len].to_ascii_lowercase();
+   rest = &rest[name_len..];
+
+   let mut attributes = vec![];
+
+   loop {
+      rest = rest.trim_start();
+
+      let (is_self_closing, remaining) = if let Some(remaining) = rest.strip_prefix("/>") {
+         (true, remaining)
+      } else if let Some(remaining) = rest.strip_prefix('>') {
+         (false, remaining)
+      } else if rest.is_empty() {
+         return None;
+      } else {
+         let attr_name_len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/'))
+            .unwrap_or(rest.len());
+
+         if attr_name_len == 0 {
+            // 属性名のない`=`や`/`は無視する
+            rest = &rest[1..];
+            continue;
+         }
+
+         let attr_name = rest[..attr_name_len].to_ascii_lowercase();
+         rest = rest[attr_name_len..].trim_start();
+
+         let value = match rest.strip_prefix('=').map(str::trim_start) {
+            Some(value) if value.starts_with(['"', '\'']) => {
+               let quote = &value[..1];
+               let end = value[1..].find(quote)?;
+               rest = &value[end + 2..];
+               decode_text(&value[1..end + 1])
+            }
+            Some(value) => {
+               let end = value
+                  .find(|c: char| c.is_whitespace() || c == '>')
+                  .unwrap_or(value.len());
+               rest = &value[end..];
+               decode_text(&value[..end])
+            }
+            None => String::new(),
+         };
+
+         attributes.push((attr_name, value));
+         continue;
+      };
+
+      let token = if is_end_tag {
+         Token::EndTag(name)
+      } else {
+         Token::StartTag { name, attributes, is_self_closing }
+      };
+
+      return Some((token, remaining));
+   }
+}
+
+/// 文字参照をデコードする
+fn decode_text(text: &str) -> String {
+   let mut decoded = String::with_capacity(text.len());
+   let mut rest = text;
+
+   while let Some(i) = rest.find('&') {
+      decoded.push_str(&rest[..i]);
+      rest = &rest[i + 1..];
+
+      let reference = rest.find(';')
+         .filter(|&end| end <= 10)
+         .and_then(|end| Some((decode_character_reference(&rest[..end])?, end)));
+
+      match reference {
+         Some((c, end)) => {
+            decoded.push(c);
+            rest = &rest[end + 1..];
+         }
+         None => decoded.push('&'),
+      }
+   }
+
+   decoded.push_str(rest);
+   decoded
+}
+
+fn decode_character_reference(reference: &str) -> Option<char> {
+   match reference {
+      "amp"  => Some('&'),
+      "lt"   => Some('<'),
+      "gt"   => Some('>'),
+      "quot" => Some('"'),
+      "apos" => Some('\''),
+      "nbsp" => Some(' '),
+      _ => {
+         let code = reference.strip_prefix('#')?;
+         let code = match code.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => code.parse().ok()?,
+         };
+         char::from_u32(code)
+      }
+   }
+}
+
+enum ElementKind {
+   Root,
+   Paragraph,
+   Link {
+      href: String,
+      classes: Vec<String>,
+   },
+   Invisible,
+   Ellipsis,
+   Strong,
+   Emphasis,
+   Deleted,
+   Code,
+   Preformatted,
+   BlockQuote,
+   UnorderedList,
+   OrderedList {
+      start: Option<i64>,
+   },
+   ListItem,
+   /// 未対応のタグ。中身だけが親に追加される
+   Transparent,
+}
+
+struct Element {
+   tag_name: String,
+   kind: ElementKind,
+   children: Vec<RichTextNode>,
+}
+
+struct TreeBuilder<'a> {
+   mentions: &'a [StatusMention],
+   hashtags: &'a [StatusHashtag],
+   emojis: &'a [CustomEmoji],
+   stack: Vec<Element>,
+}
+
+impl TreeBuilder<'_> {
+   fn current_children(&mut self) -> &mut Vec<RichTextNode> {
+      &mut self.stack.last_mut().unwrap().children
+   }
+
+   fn start_element(
+      &mut self,
+      tag_name: String,
+      attributes: Vec<(String, String)>,
+      is_self_closing: bool,
+   ) {
+      if tag_name == "br" {
+         push_node(self.current_children(), RichTextNode::Text("\n".to_string()));
+         return;
+      }
+
+      if is_self_closing || is_void_element(&tag_name) {
+         return;
+      }
+
+      let attribute = |name: &str| {
+         attributes.iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+      };
+
+      let classes: Vec<String> = attribute("class")
+         .map(|class| class.split_whitespace().map(str::to_string).collect())
+         .unwrap_or_default();
+
+      let kind = match tag_name.as_str() {
+         "p" => ElementKind::Paragraph,
+         "a" => match attribute("href") {
+            Some(href) => ElementKind::Link { href: href.to_string(), classes },
+            None => ElementKind::Transparent,
+         },
+         "span" if classes.iter().any(|c| c == "invisible") => ElementKind::Invisible,
+         "span" if classes.iter().any(|c| c == "ellipsis") => ElementKind::Ellipsis,
+         "strong" | "b" => ElementKind::Strong,
+         "em" | "i" => ElementKind::Emphasis,
+         "del" | "s" => ElementKind::Deleted,
+         "code" => ElementKind::Code,
+         "pre" => ElementKind::Preformatted,
+         "blockquote" => ElementKind::BlockQuote,
+         "ul" => ElementKind::UnorderedList,
+         "ol" => ElementKind::OrderedList {
+            start: attribute("start").and_then(|start| start.trim().parse().ok()),
+         },
+         "li" => ElementKind::ListItem,
+         _ => ElementKind::Transparent,
+      };
+
+      self.stack.push(Element { tag_name, kind, children: vec![] });
+   }
+
+   /// 対応する開始タグがない終了タグは無視する。
+   /// 閉じられていない子孫の要素はここで閉じられる
+   fn end_element(&mut self, tag_name: &str) {
+      let Some(position) = self.stack.iter()
+         .skip(1)
+         .rposition(|element| element.tag_name == tag_name)
+      else {
+         return;
+      };
+
+      while self.stack.len() > position + 1 {
+         self.close_element();
+      }
+   }
+
+   fn close_element(&mut self) {
+      let element = self.stack.pop().unwrap();
+      let children = element.children;
+
+      let nodes = match element.kind {
+         ElementKind::Root => unreachable!(),
+         ElementKind::Paragraph     => vec![RichTextNode::Paragraph(children)],
+         ElementKind::Link { href, classes } => self.resolve_link(&href, &classes, children),
+         ElementKind::Invisible     => vec![],
+         ElementKind::Ellipsis      => {
+            let mut children = children;
+            push_node(&mut children, RichTextNode::Text("…".to_string()));
+            children
+         }
+         ElementKind::Strong        => vec![RichTextNode::Strong(children)],
+         ElementKind::Emphasis      => vec![RichTextNode::Emphasis(children)],
+         ElementKind::Deleted       => vec![RichTextNode::Deleted(children)],
+         ElementKind::Code          => vec![RichTextNode::Code(children)],
+         ElementKind::Preformatted  => vec![RichTextNode::Preformatted(children)],
+         ElementKind::BlockQuote    => vec![RichTextNode::BlockQuote(children)],
+         ElementKind::UnorderedList => vec![RichTextNode::UnorderedList(children)],
+         ElementKind::OrderedList { start } => vec![RichTextNode::OrderedList { start, children }],
+         ElementKind::ListItem      => vec![RichTextNode::ListItem(children)],
+         ElementKind::Transparent   => children,
+      };
+
+      let parent = self.current_children();
+      for node in nodes {
+         push_node(parent, node);
+      }
+   }
+
+   /// メンション、ハッシュタグの順に照合し、どちらでもなければ通常のリンクとする。
+   /// hrefがURLとして不正な場合はリンクにせず中身だけを返す
+   fn resolve_link(
+      &self,
+      href: &str,
+      classes: &[String],
+      children: Vec<RichTextNode>,
+   ) -> Vec<RichTextNode> {
+      let Ok(url) = href.parse::<Url>() else {
+         return children;
+      };
+
+      let mention = self.mentions.iter()
+         .find(|m| m.mentioned_account_url.as_ref() == Some(&url));
+
+      if let Some(mention) = mention {
+         return vec![RichTextNode::Mention {
+            account_id: mention.mentioned_account_id.clone(),
+            acct: mention.mentioned_account_acct.clone(),
+            url,
+            children,
+         }];
+      }
+
+      let has_class = |class: &str| classes.iter().any(|c| c == class);
+      let text = plain_text(&children);
+
+      if let Some(name) = text.strip_prefix('#') {
+         let hashtag = self.hashtags.iter().find(|h| {
+            h.url.as_ref() == Some(&url)
+               || h.name.as_ref().is_some_and(|n| n.eq_ignore_ascii_case(name))
+         });
+
+         if hashtag.is_some() || has_class("hashtag") {
+            return vec![RichTextNode::Hashtag {
+               name: hashtag.and_then(|h| h.name.clone())
+                  .unwrap_or_else(|| name.to_string()),
+               url,
+               children,
+            }];
+         }
+      }
+
+      if has_class("mention") && text.starts_with('@') {
+         return vec![RichTextNode::Mention {
+            account_id: None,
+            acct: None,
+            url,
+            children,
+         }];
+      }
+
+      vec![RichTextNode::Link { url, children }]
+   }
+
+   fn push_text(&mut self, text: &str) {
+      let nodes: Vec<_> = tokenize_emojis(text, self.emojis)
+         .into_iter()
+         .map(|run| match run {
+            EmojiTextRun::Text(text) => RichTextNode::Text(text),
+            EmojiTextRun::Emoji(emoji) => RichTextNode::Emoji(*emoji),
+         })
+         .collect();
+
+      let children = self.current_children();
+      for node in nodes {
+         push_node(children, node);
+      }
+   }
+}
+
+fn is_void_element(tag_name: &str) -> bool {
+   matches!(
+      tag_name,
+      "area" | "base" | "col" | "embed" | "hr" | "img" | "input" | "link"
+         | "meta" | "source" | "track" | "wbr"
+   )
+}
+
+/// 隣接するTextは結合し、空のTextは追加しない
+fn push_node(nodes: &mut Vec<RichTextNode>, node: RichTextNode) {
+   if let RichTextNode::Text(text) = &node {
+      if text.is_empty() {
+         return;
+      }
+
+      if let Some(RichTextNode::Text(last)) = nodes.last_mut() {
+         last.push_str(text);
+         return;
+      }
+   }
+
+   nodes.push(node);
+}
+
+fn plain_text(nodes: &[RichTextNode]) -> String {
+   let mut text = String::new();
+
+   for node in nodes {
+      match node {
+         RichTextNode::Text(t) => text.push_str(t),
+         RichTextNode::Emoji(emoji) => {
+            text.push(':');
+            text.push_str(&emoji.shortcode);
+            text.push(':');
+         }
+         RichTextNode::Paragraph(children)
+         | RichTextNode::Link { children, .. }
+         | RichTextNode::Mention { children, .. }
+         | RichTextNode::Hashtag { children, .. }
+         | RichTextNode::Strong(children)
+         | RichTextNode::Emphasis(children)
+         | RichTextNode::Deleted(children)
+         | RichTextNode::Code(children)
+         | RichTextNode::Preformatted(children)
+         | RichTextNode::BlockQuote(children)
+         | RichTextNode::UnorderedList(children)
+         | RichTextNode::OrderedList { children, .. }
+         | RichTextNode::ListItem(children) => {
+            text.push_str(&plain_text(children));
+         }
+      }
+   }
+
+   text
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static RICH_TEXT_TEXT_HELPER = impl struct RichTextTextConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Text"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/String;\
+         )V";
+
+      fn text<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getText",
+               jvm_return_type: "Ljava/lang/String;";
+   }
+
+   static RICH_TEXT_PARAGRAPH_HELPER = impl struct RichTextParagraphConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Paragraph"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+         )V";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+   }
+
+   static RICH_TEXT_LINK_HELPER = impl struct RichTextLinkConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Link"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/String;\
+            Ljava/util/List;\
+            Lkotlin/Unit;\
+         )V";
+
+      fn raw_url<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawUrl",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn dummy<'local>(..) -> Option<()>
+         where jvm_type: JvmNullable<'local, JvmUnit<'local>>,
+               jvm_getter_method: "getDummy",
+               jvm_return_type: "Lkotlin/Unit;";
+   }
+
+   static RICH_TEXT_MENTION_HELPER = impl struct RichTextMentionConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Mention"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Lcom/wcaokaze/probosqis/mastodon/entity/Account$Id;\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+            Ljava/util/List;\
+            Lkotlin/Unit;\
+         )V";
+
+      fn account_id<'local>(..) -> Option<AccountId>
+         where jvm_type: JvmNullable<'local, JvmAccountId<'local>>,
+               jvm_getter_method: "getAccountId",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/Account$Id;";
+
+      fn acct<'local>(..) -> Option<String>
+         where jvm_type: JvmNullable<'local, JvmString<'local>>,
+               jvm_getter_method: "getAcct",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn raw_url<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawUrl",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn dummy<'local>(..) -> Option<()>
+         where jvm_type: JvmNullable<'local, JvmUnit<'local>>,
+               jvm_getter_method: "getDummy",
+               jvm_return_type: "Lkotlin/Unit;";
+   }
+
+   static RICH_TEXT_HASHTAG_HELPER = impl struct RichTextHashtagConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Hashtag"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+            Ljava/util/List;\
+            Lkotlin/Unit;\
+         )V";
+
+      fn name<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getName",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn raw_url<'local>(..) -> String
+         where jvm_type: JvmString<'local>,
+               jvm_getter_method: "getRawUrl",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn dummy<'local>(..) -> Option<()>
+         where jvm_type: JvmNullable<'local, JvmUnit<'local>>,
+               jvm_getter_method: "getDummy",
+               jvm_return_type: "Lkotlin/Unit;";
+   }
+
+   static RICH_TEXT_EMOJI_HELPER = impl struct RichTextEmojiConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Emoji"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Lcom/wcaokaze/probosqis/mastodon/entity/CustomEmoji;\
+         )V";
+
+      fn emoji<'local>(..) -> CustomEmoji
+         where jvm_type: JvmCustomEmoji<'local>,
+               jvm_getter_method: "getEmoji",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/CustomEmoji;";
+   }
+
+   static RICH_TEXT_STRONG_HELPER = impl struct RichTextStrongConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Strong"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+         )V";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+   }
+
+   static RICH_TEXT_EMPHASIS_HELPER = impl struct RichTextEmphasisConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Emphasis"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+         )V";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+   }
+
+   static RICH_TEXT_DELETED_HELPER = impl struct RichTextDeletedConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Deleted"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+         )V";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+   }
+
+   static RICH_TEXT_CODE_HELPER = impl struct RichTextCodeConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Code"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+         )V";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+   }
+
+   static RICH_TEXT_PREFORMATTED_HELPER = impl struct RichTextPreformattedConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Preformatted"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+         )V";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+   }
+
+   static RICH_TEXT_BLOCK_QUOTE_HELPER = impl struct RichTextBlockQuoteConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$BlockQuote"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+         )V";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+   }
+
+   static RICH_TEXT_UNORDERED_LIST_HELPER = impl struct RichTextUnorderedListConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$UnorderedList"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+         )V";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+   }
+
+   static RICH_TEXT_ORDERED_LIST_HELPER = impl struct RichTextOrderedListConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$OrderedList"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/Long;\
+            Ljava/util/List;\
+         )V";
+
+      fn start<'local>(..) -> Option<i64>
+         where jvm_type: JvmNullable<'local, JvmLong<'local>>,
+               jvm_getter_method: "getStart",
+               jvm_return_type: "Ljava/lang/Long;";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+   }
+
+   static RICH_TEXT_LIST_ITEM_HELPER = impl struct RichTextListItemConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$ListItem"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmRichTextNode<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+         )V";
+
+      fn children<'local>(..) -> Vec<RichTextNode>
+         where jvm_type: JvmList<'local, JvmRichTextNode<'local>>,
+               jvm_getter_method: "getChildren",
+               jvm_return_type: "Ljava/util/List;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmRichTextNode<'local>> for RichTextNode {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmRichTextNode<'local> {
+      match self {
+         RichTextNode::Text(text) => {
+            RICH_TEXT_TEXT_HELPER.clone_into_jvm(env, text)
+         }
+
+         RichTextNode::Paragraph(children) => {
+            RICH_TEXT_PARAGRAPH_HELPER.clone_into_jvm(env, children)
+         }
+
+         RichTextNode::Link { url, children } => {
+            RICH_TEXT_LINK_HELPER.clone_into_jvm(
+               env,
+               &url.as_str(),
+               children,
+               &None::<()>,
+            )
+         }
+
+         RichTextNode::Mention { account_id, acct, url, children } => {
+            RICH_TEXT_MENTION_HELPER.clone_into_jvm(
+               env,
+               account_id,
+               acct,
+               &url.as_str(),
+               children,
+               &None::<()>,
+            )
+         }
+
+         RichTextNode::Hashtag { name, url, children } => {
+            RICH_TEXT_HASHTAG_HELPER.clone_into_jvm(
+               env,
+               name,
+               &url.as_str(),
+               children,
+               &None::<()>,
+            )
+         }
+
+         RichTextNode::Emoji(emoji) => {
+            RICH_TEXT_EMOJI_HELPER.clone_into_jvm(env, emoji)
+         }
+
+         RichTextNode::Strong(children) => {
+            RICH_TEXT_STRONG_HELPER.clone_into_jvm(env, children)
+         }
+
+         RichTextNode::Emphasis(children) => {
+            RICH_TEXT_EMPHASIS_HELPER.clone_into_jvm(env, children)
+         }
+
+         RichTextNode::Deleted(children) => {
+            RICH_TEXT_DELETED_HELPER.clone_into_jvm(env, children)
+         }
+
+         RichTextNode::Code(children) => {
+            RICH_TEXT_CODE_HELPER.clone_into_jvm(env, children)
+         }
+
+         RichTextNode::Preformatted(children) => {
+            RICH_TEXT_PREFORMATTED_HELPER.clone_into_jvm(env, children)
+         }
+
+         RichTextNode::BlockQuote(children) => {
+            RICH_TEXT_BLOCK_QUOTE_HELPER.clone_into_jvm(env, children)
+         }
+
+         RichTextNode::UnorderedList(children) => {
+            RICH_TEXT_UNORDERED_LIST_HELPER.clone_into_jvm(env, children)
+         }
+
+         RichTextNode::OrderedList { start, children } => {
+            RICH_TEXT_ORDERED_LIST_HELPER.clone_into_jvm(env, start, children)
+         }
+
+         RichTextNode::ListItem(children) => {
+            RICH_TEXT_LIST_ITEM_HELPER.clone_into_jvm(env, children)
+         }
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmRichTextNode<'local>> for RichTextNode {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmRichTextNode<'local>
+   ) -> RichTextNode {
+      if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Text"
+         )
+         .unwrap()
+      {
+         let text = RICH_TEXT_TEXT_HELPER.text(env, jvm_instance);
+         RichTextNode::Text(text)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Paragraph"
+         )
+         .unwrap()
+      {
+         let children = RICH_TEXT_PARAGRAPH_HELPER.children(env, jvm_instance);
+         RichTextNode::Paragraph(children)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Link"
+         )
+         .unwrap()
+      {
+         let raw_url  = RICH_TEXT_LINK_HELPER.raw_url (env, jvm_instance);
+         let children = RICH_TEXT_LINK_HELPER.children(env, jvm_instance);
+
+         RichTextNode::Link {
+            url: raw_url.parse().unwrap(),
+            children,
+         }
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Mention"
+         )
+         .unwrap()
+      {
+         let account_id = RICH_TEXT_MENTION_HELPER.account_id(env, jvm_instance);
+         let acct       = RICH_TEXT_MENTION_HELPER.acct      (env, jvm_instance);
+         let raw_url    = RICH_TEXT_MENTION_HELPER.raw_url   (env, jvm_instance);
+         let children   = RICH_TEXT_MENTION_HELPER.children  (env, jvm_instance);
+
+         RichTextNode::Mention {
+            account_id,
+            acct,
+            url: raw_url.parse().unwrap(),
+            children,
+         }
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Hashtag"
+         )
+         .unwrap()
+      {
+         let name     = RICH_TEXT_HASHTAG_HELPER.name    (env, jvm_instance);
+         let raw_url  = RICH_TEXT_HASHTAG_HELPER.raw_url (env, jvm_instance);
+         let children = RICH_TEXT_HASHTAG_HELPER.children(env, jvm_instance);
+
+         RichTextNode::Hashtag {
+            name,
+            url: raw_url.parse().unwrap(),
+            children,
+         }
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Emoji"
+         )
+         .unwrap()
+      {
+         let emoji = RICH_TEXT_EMOJI_HELPER.emoji(env, jvm_instance);
+         RichTextNode::Emoji(emoji)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Strong"
+         )
+         .unwrap()
+      {
+         let children = RICH_TEXT_STRONG_HELPER.children(env, jvm_instance);
+         RichTextNode::Strong(children)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Emphasis"
+         )
+         .unwrap()
+      {
+         let children = RICH_TEXT_EMPHASIS_HELPER.children(env, jvm_instance);
+         RichTextNode::Emphasis(children)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Deleted"
+         )
+         .unwrap()
+      {
+         let children = RICH_TEXT_DELETED_HELPER.children(env, jvm_instance);
+         RichTextNode::Deleted(children)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Code"
+         )
+         .unwrap()
+      {
+         let children = RICH_TEXT_CODE_HELPER.children(env, jvm_instance);
+         RichTextNode::Code(children)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$Preformatted"
+         )
+         .unwrap()
+      {
+         let children = RICH_TEXT_PREFORMATTED_HELPER.children(env, jvm_instance);
+         RichTextNode::Preformatted(children)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$BlockQuote"
+         )
+         .unwrap()
+      {
+         let children = RICH_TEXT_BLOCK_QUOTE_HELPER.children(env, jvm_instance);
+         RichTextNode::BlockQuote(children)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$UnorderedList"
+         )
+         .unwrap()
+      {
+         let children = RICH_TEXT_UNORDERED_LIST_HELPER.children(env, jvm_instance);
+         RichTextNode::UnorderedList(children)
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$OrderedList"
+         )
+         .unwrap()
+      {
+         let start    = RICH_TEXT_ORDERED_LIST_HELPER.start   (env, jvm_instance);
+         let children = RICH_TEXT_ORDERED_LIST_HELPER.children(env, jvm_instance);
+
+         RichTextNode::OrderedList { start, children }
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/RichTextNode$ListItem"
+         )
+         .unwrap()
+      {
+         let children = RICH_TEXT_LIST_ITEM_HELPER.children(env, jvm_instance);
+         RichTextNode::ListItem(children)
+      } else {
+         panic!("Unexpected class");
+      }
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use chrono::{TimeZone, Utc};
+   use panoptiqon::cache::Cache;
+   use crate::account::{AccountId, AccountLocalId};
+   use crate::custom_emoji::CustomEmoji;
+   use crate::instance::Instance;
+   use crate::status::{StatusHashtag, StatusMention};
+   use super::{parse_html, RichTextNode};
+
+   fn text(text: &str) -> RichTextNode {
+      RichTextNode::Text(text.to_string())
+   }
+
+   fn emoji(shortcode: &str) -> CustomEmoji {
+      let instance = Instance {
+         url: "https://rich-text.example.com/".parse().unwrap(),
+         version: "4.3.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      CustomEmoji {
+         instance: Cache::new(instance),
+         shortcode: shortcode.to_string(),
+         image_url: format!("https://rich-text.example.com/{shortcode}.png").parse().unwrap(),
+         static_image_url: None,
+         is_visible_in_picker: None,
+         category: None,
+      }
+   }
+
+   #[test]
+   fn links_are_resolved() {
+      let mention = StatusMention {
+         mentioned_account_id: Some(AccountId {
+            instance_url: "https://rich-text.example.com/".parse().unwrap(),
+            local: AccountLocalId("1".to_string()),
+         }),
+         mentioned_account_username: Some("wcaokaze".to_string()),
+         mentioned_account_url: Some("https://rich-text.example.com/@wcaokaze".parse().unwrap()),
+         mentioned_account_acct: Some("wcaokaze".to_string()),
+      };
+
+      let hashtag = StatusHashtag {
+         name: Some("probosqis".to_string()),
+         url: Some("https://rich-text.example.com/tags/probosqis".parse().unwrap()),
+      };
+
+      let html = "<p>\
+         <span class=\"h-card\" translate=\"no\">\
+            <a href=\"https://rich-text.example.com/@wcaokaze\" class=\"u-url mention\">@<span>wcaokaze</span></a>\
+         </span> \
+         <a href=\"https://rich-text.example.com/tags/Probosqis\" class=\"mention hashtag\" rel=\"tag\">#<span>Probosqis</span></a> \
+         <a href=\"https://example.com/a/very/long/path\" target=\"_blank\" rel=\"nofollow noopener\">\
+            <span class=\"invisible\">https://</span><span class=\"ellipsis\">example.com/a/very</span><span class=\"invisible\">/long/path</span>\
+         </a> \
+         <a href=\"https://other.example.com/@someone\" class=\"u-url mention\">@<span>someone</span></a>\
+      </p>";
+
+      let nodes = parse_html(html, &[mention], &[hashtag], &[]);
+
+      assert_eq!(
+         vec![
+            RichTextNode::Paragraph(vec![
+               RichTextNode::Mention {
+                  account_id: Some(AccountId {
+                     instance_url: "https://rich-text.example.com/".parse().unwrap(),
+                     local: AccountLocalId("1".to_string()),
+                  }),
+                  acct: Some("wcaokaze".to_string()),
+                  url: "https://rich-text.example.com/@wcaokaze".parse().unwrap(),
+                  children: vec![text("@wcaokaze")],
+               },
+               text(" "),
+               RichTextNode::Hashtag {
+                  name: "probosqis".to_string(),
+                  url: "https://rich-text.example.com/tags/Probosqis".parse().unwrap(),
+                  children: vec![text("#Probosqis")],
+               },
+               text(" "),
+               RichTextNode::Link {
+                  url: "https://example.com/a/very/long/path".parse().unwrap(),
+                  children: vec![text("example.com/a/very…")],
+               },
+               text(" "),
+               RichTextNode::Mention {
+                  account_id: None,
+                  acct: None,
+                  url: "https://other.example.com/@someone".parse().unwrap(),
+                  children: vec![text("@someone")],
+               },
+            ]),
+         ],
+         nodes
+      );
+   }
+
+   #[test]
+   fn formatting_elements() {
+      let html = "<p>a<br>b &amp; &lt;c&gt; &#x1F600;</p>\
+         <p><strong>strong</strong><em>em</em><del>del</del><code>code</code><u>u</u></p>\
+         <pre><code>fn main() {}\n</code></pre>\
+         <blockquote><p>quote</p></blockquote>\
+         <ul><li>x</li><li>y</li></ul>\
+         <ol start=\"3\"><li>z</li></ol>";
+
+      let nodes = parse_html(html, &[], &[], &[]);
+
+      assert_eq!(
+         vec![
+            RichTextNode::Paragraph(vec![text("a\nb & <c> 😀")]),
+            RichTextNode::Paragraph(vec![
+               RichTextNode::Strong(vec![text("strong")]),
+               RichTextNode::Emphasis(vec![text("em")]),
+               RichTextNode::Deleted(vec![text("del")]),
+               RichTextNode::Code(vec![text("code")]),
+               text("u"),
+            ]),
+            RichTextNode::Preformatted(vec![
+               RichTextNode::Code(vec![text("fn main() {}\n")]),
+            ]),
+            RichTextNode::BlockQuote(vec![
+               RichTextNode::Paragraph(vec![text("quote")]),
+            ]),
+            RichTextNode::UnorderedList(vec![
+               RichTextNode::ListItem(vec![text("x")]),
+               RichTextNode::ListItem(vec![text("y")]),
+            ]),
+            RichTextNode::OrderedList {
+               start: Some(3),
+               children: vec![
+                  RichTextNode::ListItem(vec![text("z")]),
+               ],
+            },
+         ],
+         nodes
+      );
+   }
+
+   #[test]
+   fn malformed_html() {
+      let html = "<p>a < b <strong>unclosed</p></em>c<!-- comment -->d";
+
+      let nodes = parse_html(html, &[], &[], &[]);
+
+      assert_eq!(
+         vec![
+            RichTextNode::Paragraph(vec![
+               text("a < b "),
+               RichTextNode::Strong(vec![text("unclosed")]),
+            ]),
+            text("cd"),
+         ],
+         nodes
+      );
+   }
+
+   #[test]
+   fn emojis() {
+      let nodes = parse_html(
+         "<p>:wave: at 12:30 :unknown::blobcat: :blobcat::wave:</p>",
+         &[],
+         &[],
+         &[emoji("wave"), emoji("blobcat")],
+      );
+
+      assert_eq!(
+         vec![
+            RichTextNode::Paragraph(vec![
+               RichTextNode::Emoji(emoji("wave")),
+               text(" at 12:30 :unknown:"),
+               RichTextNode::Emoji(emoji("blobcat")),
+               text(" "),
+               RichTextNode::Emoji(emoji("blobcat")),
+               RichTextNode::Emoji(emoji("wave")),
+            ]),
+         ],
+         nodes
+      );
+   }
+
+   #[test]
+   fn serde_round_trip() {
+      let nodes = parse_html(
+         "<p>hello <a href=\"https://rich-text.example.com/\">link</a></p>\
+          <ol start=\"3\"><li><strong>strong</strong></li></ol>",
+         &[],
+         &[],
+         &[],
+      );
+
+      let json = serde_json::to_string(&nodes).unwrap();
+      assert_eq!(nodes, serde_json::from_str::<Vec<RichTextNode>>(&json).unwrap());
+   }
+}
@@ -15,7 +15,7 @@
  */
 
 use panoptiqon::cache::Cache;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::instance::Instance;
 
 #[cfg(feature = "jvm")]
@@ -27,8 +27,9 @@ use {
    crate::jvm_types::{JvmInstance, JvmRole},
 };
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct Role {
+   #[serde(with = "ext_panoptiqon::cache_reference")]
    pub instance: Cache<Instance>,
    pub id: Option<RoleId>,
    pub name: Option<String>,
@@ -37,7 +38,7 @@ pub struct Role {
    pub is_highlighted: Option<bool>,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize, Serialize)]
 pub struct RoleId(pub String);
 
 #[cfg(feature = "jvm")]
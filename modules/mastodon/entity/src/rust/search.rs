@@ -0,0 +1,234 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::{DateTime, Utc};
+use panoptiqon::cache::Cache;
+use serde::{Deserialize, Serialize};
+use crate::account::Account;
+use crate::status::{Status, StatusHashtag};
+
+#[cfg(feature = "jvm")]
+use {
+   ext_panoptiqon::convert_jvm_helper,
+   jni::JNIEnv,
+   panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm},
+   panoptiqon::jvm_types::{JvmBoolean, JvmCache, JvmList, JvmLong, JvmNullable},
+   crate::jvm_types::{
+      JvmAccount, JvmSearchResult, JvmSearchResultHashtag,
+      JvmSearchResultHashtagHistory, JvmStatus, JvmStatusHashtag,
+   },
+};
+
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+pub struct SearchResult {
+   #[serde(with = "ext_panoptiqon::cache_reference::vec")]
+   pub accounts: Vec<Cache<Account>>,
+   pub statuses: Vec<Status>,
+   pub hashtags: Vec<SearchHashtag>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub struct SearchHashtag {
+   pub hashtag: StatusHashtag,
+   /// 新しい順の日ごとの使用状況
+   pub history: Vec<HashtagHistory>,
+   pub is_following: Option<bool>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub struct HashtagHistory {
+   pub day: Option<DateTime<Utc>>,
+   pub use_count: Option<u64>,
+   pub account_count: Option<u64>,
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static SEARCH_RESULT_HELPER = impl struct SearchResultConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/SearchResult"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmSearchResult<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+            Ljava/util/List;\
+            Ljava/util/List;\
+         )V";
+
+      fn accounts<'local>(..) -> Vec<Cache<Account>>
+         where jvm_type: JvmList<'local, JvmCache<'local, JvmAccount<'local>>>,
+               jvm_getter_method: "getAccounts",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn statuses<'local>(..) -> Vec<Status>
+         where jvm_type: JvmList<'local, JvmStatus<'local>>,
+               jvm_getter_method: "getStatuses",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn hashtags<'local>(..) -> Vec<SearchHashtag>
+         where jvm_type: JvmList<'local, JvmSearchResultHashtag<'local>>,
+               jvm_getter_method: "getHashtags",
+               jvm_return_type: "Ljava/util/List;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmSearchResult<'local>> for SearchResult {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmSearchResult<'local> {
+      SEARCH_RESULT_HELPER.clone_into_jvm(
+         env,
+         &self.accounts,
+         &self.statuses,
+         &self.hashtags,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmSearchResult<'local>> for SearchResult {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmSearchResult<'local>
+   ) -> SearchResult {
+      let accounts = SEARCH_RESULT_HELPER.accounts(env, jvm_instance);
+      let statuses = SEARCH_RESULT_HELPER.statuses(env, jvm_instance);
+      let hashtags = SEARCH_RESULT_HELPER.hashtags(env, jvm_instance);
+
+      SearchResult {
+         accounts,
+         statuses,
+         hashtags,
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static SEARCH_HASHTAG_HELPER = impl struct SearchHashtagConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/SearchResult$Hashtag"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmSearchResultHashtag<'local>
+         where jvm_constructor: "(\
+            Lcom/wcaokaze/probosqis/mastodon/entity/Status$Hashtag;\
+            Ljava/util/List;\
+            Ljava/lang/Boolean;\
+         )V";
+
+      fn hashtag<'local>(..) -> StatusHashtag
+         where jvm_type: JvmStatusHashtag<'local>,
+               jvm_getter_method: "getHashtag",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/Status$Hashtag;";
+
+      fn history<'local>(..) -> Vec<HashtagHistory>
+         where jvm_type: JvmList<'local, JvmSearchResultHashtagHistory<'local>>,
+               jvm_getter_method: "getHistory",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn is_following<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isFollowing",
+               jvm_return_type: "Ljava/lang/Boolean;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmSearchResultHashtag<'local>> for SearchHashtag {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmSearchResultHashtag<'local> {
+      SEARCH_HASHTAG_HELPER.clone_into_jvm(
+         env,
+         &self.hashtag,
+         &self.history,
+         &self.is_following,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmSearchResultHashtag<'local>> for SearchHashtag {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmSearchResultHashtag<'local>
+   ) -> SearchHashtag {
+      let hashtag      = SEARCH_HASHTAG_HELPER.hashtag     (env, jvm_instance);
+      let history      = SEARCH_HASHTAG_HELPER.history     (env, jvm_instance);
+      let is_following = SEARCH_HASHTAG_HELPER.is_following(env, jvm_instance);
+
+      SearchHashtag {
+         hashtag,
+         history,
+         is_following,
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static HASHTAG_HISTORY_HELPER = impl struct HashtagHistoryConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/SearchResult$HashtagHistory"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmSearchResultHashtagHistory<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/Long;\
+            Ljava/lang/Long;\
+            Ljava/lang/Long;\
+         )V";
+
+      fn day_epoch_millis<'local>(..) -> Option<i64>
+         where jvm_type: JvmNullable<'local, JvmLong<'local>>,
+               jvm_getter_method: "getDayEpochMillis",
+               jvm_return_type: "Ljava/lang/Long;";
+
+      fn use_count<'local>(..) -> Option<i64>
+         where jvm_type: JvmNullable<'local, JvmLong<'local>>,
+               jvm_getter_method: "getUseCount",
+               jvm_return_type: "Ljava/lang/Long;";
+
+      fn account_count<'local>(..) -> Option<i64>
+         where jvm_type: JvmNullable<'local, JvmLong<'local>>,
+               jvm_getter_method: "getAccountCount",
+               jvm_return_type: "Ljava/lang/Long;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmSearchResultHashtagHistory<'local>> for HashtagHistory {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmSearchResultHashtagHistory<'local> {
+      HASHTAG_HISTORY_HELPER.clone_into_jvm(
+         env,
+         &self.day.map(|t| t.timestamp_millis()),
+         &self.use_count    .map(|u| u as i64),
+         &self.account_count.map(|u| u as i64),
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmSearchResultHashtagHistory<'local>> for HashtagHistory {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmSearchResultHashtagHistory<'local>
+   ) -> HashtagHistory {
+      let day_epoch_millis = HASHTAG_HISTORY_HELPER.day_epoch_millis(env, jvm_instance);
+      let use_count        = HASHTAG_HISTORY_HELPER.use_count       (env, jvm_instance);
+      let account_count    = HASHTAG_HISTORY_HELPER.account_count   (env, jvm_instance);
+
+      HashtagHistory {
+         day: day_epoch_millis
+            .map(|time| DateTime::from_timestamp_millis(time).unwrap()),
+         use_count:     use_count    .map(|i| i as u64),
+         account_count: account_count.map(|i| i as u64),
+      }
+   }
+}
@@ -17,7 +17,7 @@
 use chrono::{DateTime, Utc};
 use isolang::Language;
 use panoptiqon::cache::Cache;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
 use crate::account::{Account, AccountId};
 use crate::application::Application;
@@ -26,6 +26,7 @@ use crate::filter::FilterResult;
 use crate::media_attachment::MediaAttachment;
 use crate::poll::{NoCredentialPoll, Poll};
 use crate::preview_card::PreviewCard;
+use crate::rich_text::{self, RichTextNode};
 
 #[cfg(feature = "jvm")]
 use {
@@ -42,10 +43,12 @@ use {
    },
 };
 
-#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
 pub struct Status {
    pub id: StatusId,
+   #[serde(with = "ext_panoptiqon::cache_reference")]
    pub no_credential: Cache<NoCredentialStatus>,
+   #[serde(with = "ext_panoptiqon::cache_reference::option")]
    pub boosted_status: Option<Cache<Status>>,
    pub poll: Option<Poll>,
    pub is_favorited: Option<bool>,
@@ -56,11 +59,12 @@ pub struct Status {
    pub filter_results: Vec<FilterResult>,
 }
 
-#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
 pub struct NoCredentialStatus {
    pub id: StatusId,
    pub uri: Option<String>,
    pub created_time: Option<DateTime<Utc>>,
+   #[serde(with = "ext_panoptiqon::cache_reference::option")]
    pub account: Option<Cache<Account>>,
    pub content: Option<String>,
    pub visibility: Option<StatusVisibility>,
@@ -77,7 +81,9 @@ pub struct NoCredentialStatus {
    pub url: Option<Url>,
    pub replied_status_id: Option<StatusId>,
    pub replied_account_id: Option<AccountId>,
+   #[serde(with = "ext_panoptiqon::cache_reference::option")]
    pub boosted_status: Option<Cache<NoCredentialStatus>>,
+   #[serde(with = "ext_panoptiqon::cache_reference::option")]
    pub poll: Option<Cache<NoCredentialPoll>>,
    pub card: Option<PreviewCard>,
    pub language: Option<Language>,
@@ -85,19 +91,68 @@ pub struct NoCredentialStatus {
    pub edited_time: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize, Serialize)]
 pub struct StatusId {
    pub instance_url: Url,
    pub local: StatusLocalId,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Deserialize, Serialize)]
 pub struct StatusLocalId(pub String);
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
-pub struct StatusVisibility(pub String);
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum StatusVisibility {
+   Public,
+   Unlisted,
+   Private,
+   Direct,
+   Unknown(String),
+}
+
+impl StatusVisibility {
+   pub fn from_raw(raw: String) -> StatusVisibility {
+      match raw.as_str() {
+         "public"   => StatusVisibility::Public,
+         "unlisted" => StatusVisibility::Unlisted,
+         "private"  => StatusVisibility::Private,
+         "direct"   => StatusVisibility::Direct,
+         _ => StatusVisibility::Unknown(raw),
+      }
+   }
+
+   pub fn raw(&self) -> &str {
+      match self {
+         StatusVisibility::Public       => "public",
+         StatusVisibility::Unlisted     => "unlisted",
+         StatusVisibility::Private      => "private",
+         StatusVisibility::Direct       => "direct",
+         StatusVisibility::Unknown(raw) => raw,
+      }
+   }
+}
+
+impl From<String> for StatusVisibility {
+   fn from(raw: String) -> StatusVisibility {
+      StatusVisibility::from_raw(raw)
+   }
+}
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+impl From<StatusVisibility> for String {
+   fn from(value: StatusVisibility) -> String {
+      value.raw().to_string()
+   }
+}
+
+impl NoCredentialStatus {
+   /// 本文のHTMLをパースする
+   pub fn parse_content(&self) -> Option<Vec<RichTextNode>> {
+      let content = self.content.as_ref()?;
+      Some(rich_text::parse_html(content, &self.mentions, &self.hashtags, &self.emojis))
+   }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct StatusMention {
    pub mentioned_account_id: Option<AccountId>,
    pub mentioned_account_username: Option<String>,
@@ -105,7 +160,7 @@ pub struct StatusMention {
    pub mentioned_account_acct: Option<String>,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct StatusHashtag {
    pub name: Option<String>,
    pub url: Option<Url>,
@@ -422,7 +477,7 @@ impl<'local> CloneIntoJvm<'local, JvmStatusNoCredential<'local>> for NoCredentia
          &self.created_time.map(|t| t.timestamp_millis()),
          &self.account,
          &self.content,
-         &self.visibility.as_ref().map(|v| &v.0),
+         &self.visibility.as_ref().map(|v| v.raw()),
          &self.is_sensitive,
          &self.spoiler_text,
          &self.media_attachments,
@@ -493,7 +548,7 @@ impl<'local> CloneFromJvm<'local, JvmStatusNoCredential<'local>> for NoCredentia
             .map(|t| DateTime::from_timestamp_millis(t).unwrap()),
          account,
          content,
-         visibility: raw_visibility.map(StatusVisibility),
+         visibility: raw_visibility.map(StatusVisibility::from_raw),
          is_sensitive,
          spoiler_text,
          media_attachments,
@@ -723,3 +778,24 @@ impl<'local> CloneFromJvm<'local, JvmStatusHashtag<'local>> for StatusHashtag {
       }
    }
 }
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use super::StatusVisibility;
+
+   #[test]
+   fn visibility_serde_uses_raw_value() {
+      let visibilities = [
+         (StatusVisibility::Public,   "\"public\""),
+         (StatusVisibility::Unlisted, "\"unlisted\""),
+         (StatusVisibility::Private,  "\"private\""),
+         (StatusVisibility::Direct,   "\"direct\""),
+         (StatusVisibility::Unknown("local".to_string()), "\"local\""),
+      ];
+
+      for (visibility, json) in visibilities {
+         assert_eq!(json, serde_json::to_string(&visibility).unwrap());
+         assert_eq!(visibility, serde_json::from_str::<StatusVisibility>(json).unwrap());
+      }
+   }
+}
@@ -0,0 +1,254 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use panoptiqon::cache::Cache;
+use serde::{Deserialize, Serialize};
+use crate::status::{Status, StatusId};
+
+#[cfg(feature = "jvm")]
+use {
+   ext_panoptiqon::convert_jvm_helper,
+   jni::JNIEnv,
+   panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm},
+   panoptiqon::jvm_type::JvmType,
+   panoptiqon::jvm_types::{JvmCache, JvmList},
+   crate::jvm_types::{
+      JvmStatus, JvmStatusContext, JvmStatusContextReplyTreeNode, JvmStatusId,
+   },
+};
+
+/// 投稿の前後のスレッド
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+pub struct StatusContext {
+   /// 返信先を辿った投稿。古い順
+   #[serde(with = "ext_panoptiqon::cache_reference::vec")]
+   pub ancestors: Vec<Cache<Status>>,
+   /// 返信を辿った投稿。深さ優先順
+   #[serde(with = "ext_panoptiqon::cache_reference::vec")]
+   pub descendants: Vec<Cache<Status>>,
+   /// `ancestors`の先頭から始まる返信ツリー
+   pub reply_tree: ReplyTreeNode,
+   /// 対象の投稿に同じアカウントが続けて返信した投稿。古い順。
+   /// 対象の投稿自身は含まない
+   #[serde(with = "ext_panoptiqon::cache_reference::vec")]
+   pub self_reply_chain: Vec<Cache<Status>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+pub enum ReplyTreeNode {
+   Status {
+      #[serde(with = "ext_panoptiqon::cache_reference")]
+      status: Cache<Status>,
+      replies: Vec<ReplyTreeNode>,
+      /// `reply_count`のうち`replies`に含まれなかった返信の数
+      missing_reply_count: u64,
+   },
+   /// 公開範囲やブロック、削除などにより取得できなかった投稿。
+   ///
+   /// 返信先が取得できなかった返信は、取得できなかった投稿ごとにまとめて
+   /// 対象の投稿の`replies`の末尾に置かれる
+   Missing {
+      status_id: StatusId,
+      replies: Vec<ReplyTreeNode>,
+   },
+}
+
+impl ReplyTreeNode {
+   pub fn replies(&self) -> &[ReplyTreeNode] {
+      match self {
+         ReplyTreeNode::Status  { replies, .. } => replies,
+         ReplyTreeNode::Missing { replies, .. } => replies,
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static STATUS_CONTEXT_HELPER = impl struct StatusContextConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/StatusContext"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmStatusContext<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+            Ljava/util/List;\
+            Lcom/wcaokaze/probosqis/mastodon/entity/StatusContext$ReplyTreeNode;\
+            Ljava/util/List;\
+         )V";
+
+      fn ancestors<'local>(..) -> Vec<Cache<Status>>
+         where jvm_type: JvmList<'local, JvmCache<'local, JvmStatus<'local>>>,
+               jvm_getter_method: "getAncestors",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn descendants<'local>(..) -> Vec<Cache<Status>>
+         where jvm_type: JvmList<'local, JvmCache<'local, JvmStatus<'local>>>,
+               jvm_getter_method: "getDescendants",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn reply_tree<'local>(..) -> ReplyTreeNode
+         where jvm_type: JvmStatusContextReplyTreeNode<'local>,
+               jvm_getter_method: "getReplyTree",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/StatusContext$ReplyTreeNode;";
+
+      fn self_reply_chain<'local>(..) -> Vec<Cache<Status>>
+         where jvm_type: JvmList<'local, JvmCache<'local, JvmStatus<'local>>>,
+               jvm_getter_method: "getSelfReplyChain",
+               jvm_return_type: "Ljava/util/List;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmStatusContext<'local>> for StatusContext {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmStatusContext<'local> {
+      STATUS_CONTEXT_HELPER.clone_into_jvm(
+         env,
+         &self.ancestors,
+         &self.descendants,
+         &self.reply_tree,
+         &self.self_reply_chain,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmStatusContext<'local>> for StatusContext {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmStatusContext<'local>
+   ) -> StatusContext {
+      let ancestors        = STATUS_CONTEXT_HELPER.ancestors       (env, jvm_instance);
+      let descendants      = STATUS_CONTEXT_HELPER.descendants     (env, jvm_instance);
+      let reply_tree       = STATUS_CONTEXT_HELPER.reply_tree      (env, jvm_instance);
+      let self_reply_chain = STATUS_CONTEXT_HELPER.self_reply_chain(env, jvm_instance);
+
+      StatusContext {
+         ancestors,
+         descendants,
+         reply_tree,
+         self_reply_chain,
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static REPLY_TREE_STATUS_HELPER = impl struct ReplyTreeStatusConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/StatusContext$ReplyTreeNode$Status"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmStatusContextReplyTreeNode<'local>
+         where jvm_constructor: "(\
+            Lcom/wcaokaze/probosqis/panoptiqon/Cache;\
+            Ljava/util/List;\
+            J\
+         )V";
+
+      fn status<'local>(..) -> Cache<Status>
+         where jvm_type: JvmCache<'local, JvmStatus<'local>>,
+               jvm_getter_method: "getStatus",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/panoptiqon/Cache;";
+
+      fn replies<'local>(..) -> Vec<ReplyTreeNode>
+         where jvm_type: JvmList<'local, JvmStatusContextReplyTreeNode<'local>>,
+               jvm_getter_method: "getReplies",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn missing_reply_count<'local>(..) -> i64
+         where jvm_getter_method: "getMissingReplyCount",
+               jvm_return_type: "J";
+   }
+
+   static REPLY_TREE_MISSING_HELPER = impl struct ReplyTreeMissingConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/StatusContext$ReplyTreeNode$Missing"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmStatusContextReplyTreeNode<'local>
+         where jvm_constructor: "(\
+            Lcom/wcaokaze/probosqis/mastodon/entity/Status$Id;\
+            Ljava/util/List;\
+         )V";
+
+      fn status_id<'local>(..) -> StatusId
+         where jvm_type: JvmStatusId<'local>,
+               jvm_getter_method: "getStatusId",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/Status$Id;";
+
+      fn replies<'local>(..) -> Vec<ReplyTreeNode>
+         where jvm_type: JvmList<'local, JvmStatusContextReplyTreeNode<'local>>,
+               jvm_getter_method: "getReplies",
+               jvm_return_type: "Ljava/util/List;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmStatusContextReplyTreeNode<'local>> for ReplyTreeNode {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmStatusContextReplyTreeNode<'local> {
+      match self {
+         ReplyTreeNode::Status { status, replies, missing_reply_count } => {
+            REPLY_TREE_STATUS_HELPER.clone_into_jvm(
+               env,
+               status,
+               replies,
+               *missing_reply_count as i64,
+            )
+         }
+
+         ReplyTreeNode::Missing { status_id, replies } => {
+            REPLY_TREE_MISSING_HELPER.clone_into_jvm(env, status_id, replies)
+         }
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmStatusContextReplyTreeNode<'local>> for ReplyTreeNode {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmStatusContextReplyTreeNode<'local>
+   ) -> ReplyTreeNode {
+      if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/StatusContext$ReplyTreeNode$Status"
+         )
+         .unwrap()
+      {
+         let status              = REPLY_TREE_STATUS_HELPER.status             (env, jvm_instance);
+         let replies             = REPLY_TREE_STATUS_HELPER.replies            (env, jvm_instance);
+         let missing_reply_count = REPLY_TREE_STATUS_HELPER.missing_reply_count(env, jvm_instance);
+
+         ReplyTreeNode::Status {
+            status,
+            replies,
+            missing_reply_count: missing_reply_count as u64,
+         }
+      } else if env
+         .is_instance_of(
+            jvm_instance.j_object(),
+            "com/wcaokaze/probosqis/mastodon/entity/StatusContext$ReplyTreeNode$Missing"
+         )
+         .unwrap()
+      {
+         let status_id = REPLY_TREE_MISSING_HELPER.status_id(env, jvm_instance);
+         let replies   = REPLY_TREE_MISSING_HELPER.replies  (env, jvm_instance);
+
+         ReplyTreeNode::Missing {
+            status_id,
+            replies,
+         }
+      } else {
+         panic!("Unexpected class");
+      }
+   }
+}
@@ -0,0 +1,272 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+use isolang::Language;
+use serde::{Deserialize, Serialize};
+use crate::media_attachment::MediaAttachmentId;
+use crate::status::{StatusId, StatusVisibility};
+
+#[cfg(feature = "jvm")]
+use {
+   ext_panoptiqon::convert_jvm_helper,
+   jni::JNIEnv,
+   panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm},
+   panoptiqon::jvm_types::{JvmBoolean, JvmList, JvmNullable, JvmString, JvmUnit},
+   crate::jvm_types::{JvmStatusDraft, JvmStatusDraftPoll, JvmStatusId},
+};
+
+/// 投稿、編集するStatusの内容。
+/// 新規投稿時に`None`の項目はCredentialAccountのデフォルト値が使われる
+#[derive(Debug, PartialEq, Clone, Default, Deserialize, Serialize)]
+pub struct StatusDraft {
+   pub text: Option<String>,
+   pub spoiler_text: Option<String>,
+   pub visibility: Option<StatusVisibility>,
+   pub is_sensitive: Option<bool>,
+   pub language: Option<Language>,
+   pub replied_status_id: Option<StatusId>,
+   pub media_attachment_ids: Vec<MediaAttachmentId>,
+   pub poll: Option<PollDraft>,
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+pub struct PollDraft {
+   pub options: Vec<String>,
+   pub duration: Duration,
+   pub allows_multiple_choices: bool,
+   pub hides_totals: bool,
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static STATUS_DRAFT_HELPER = impl struct StatusDraftConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/StatusDraft"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmStatusDraft<'local>
+         where jvm_constructor: "(\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+            Ljava/lang/String;\
+            Ljava/lang/Boolean;\
+            Ljava/lang/String;\
+            Lcom/wcaokaze/probosqis/mastodon/entity/Status$Id;\
+            Ljava/util/List;\
+            Lcom/wcaokaze/probosqis/mastodon/entity/StatusDraft$Poll;\
+            Lkotlin/Unit;\
+         )V";
+
+      fn text<'local>(..) -> Option<String>
+         where jvm_type: JvmNullable<'local, JvmString<'local>>,
+               jvm_getter_method: "getText",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn spoiler_text<'local>(..) -> Option<String>
+         where jvm_type: JvmNullable<'local, JvmString<'local>>,
+               jvm_getter_method: "getSpoilerText",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn raw_visibility<'local>(..) -> Option<String>
+         where jvm_type: JvmNullable<'local, JvmString<'local>>,
+               jvm_getter_method: "getRawVisibility",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn is_sensitive<'local>(..) -> Option<bool>
+         where jvm_type: JvmNullable<'local, JvmBoolean<'local>>,
+               jvm_getter_method: "isSensitive",
+               jvm_return_type: "Ljava/lang/Boolean;";
+
+      fn language<'local>(..) -> Option<String>
+         where jvm_type: JvmNullable<'local, JvmString<'local>>,
+               jvm_getter_method: "getLanguage",
+               jvm_return_type: "Ljava/lang/String;";
+
+      fn replied_status_id<'local>(..) -> Option<StatusId>
+         where jvm_type: JvmNullable<'local, JvmStatusId<'local>>,
+               jvm_getter_method: "getRepliedStatusId",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/Status$Id;";
+
+      fn raw_media_attachment_ids<'local>(..) -> Vec<String>
+         where jvm_type: JvmList<'local, JvmString<'local>>,
+               jvm_getter_method: "getRawMediaAttachmentIds",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn poll<'local>(..) -> Option<PollDraft>
+         where jvm_type: JvmNullable<'local, JvmStatusDraftPoll<'local>>,
+               jvm_getter_method: "getPoll",
+               jvm_return_type: "Lcom/wcaokaze/probosqis/mastodon/entity/StatusDraft$Poll;";
+
+      fn dummy<'local>(..) -> Option<()>
+         where jvm_type: JvmNullable<'local, JvmUnit<'local>>,
+               jvm_getter_method: "getDummy",
+               jvm_return_type: "Lkotlin/Unit;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmStatusDraft<'local>> for StatusDraft {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmStatusDraft<'local> {
+      STATUS_DRAFT_HELPER.clone_into_jvm(
+         env,
+         &self.text,
+         &self.spoiler_text,
+         &self.visibility.as_ref().map(|v| v.raw()),
+         &self.is_sensitive,
+         &self.language.and_then(|l| l.to_639_1()),
+         &self.replied_status_id,
+         &self.media_attachment_ids.iter().map(|id| id.0.as_str()).collect::<Vec<_>>(),
+         &self.poll,
+         &None::<()>,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmStatusDraft<'local>> for StatusDraft {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmStatusDraft<'local>
+   ) -> StatusDraft {
+      let text                     = STATUS_DRAFT_HELPER.text                    (env, jvm_instance);
+      let spoiler_text             = STATUS_DRAFT_HELPER.spoiler_text            (env, jvm_instance);
+      let raw_visibility           = STATUS_DRAFT_HELPER.raw_visibility          (env, jvm_instance);
+      let is_sensitive             = STATUS_DRAFT_HELPER.is_sensitive            (env, jvm_instance);
+      let language                 = STATUS_DRAFT_HELPER.language                (env, jvm_instance);
+      let replied_status_id        = STATUS_DRAFT_HELPER.replied_status_id       (env, jvm_instance);
+      let raw_media_attachment_ids = STATUS_DRAFT_HELPER.raw_media_attachment_ids(env, jvm_instance);
+      let poll                     = STATUS_DRAFT_HELPER.poll                    (env, jvm_instance);
+
+      StatusDraft {
+         text,
+         spoiler_text,
+         visibility: raw_visibility.map(StatusVisibility::from_raw),
+         is_sensitive,
+         // 不正な言語コードの場合はCredentialAccountのデフォルト値を使う
+         language: language.and_then(|code| Language::from_639_1(&code)),
+         replied_status_id,
+         media_attachment_ids: raw_media_attachment_ids.into_iter()
+            .map(MediaAttachmentId)
+            .collect(),
+         poll,
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+convert_jvm_helper! {
+   static POLL_DRAFT_HELPER = impl struct PollDraftConvertHelper
+      where jvm_class: "com/wcaokaze/probosqis/mastodon/entity/StatusDraft$Poll"
+   {
+      fn clone_into_jvm<'local>(..) -> JvmStatusDraftPoll<'local>
+         where jvm_constructor: "(\
+            Ljava/util/List;\
+            J\
+            Z\
+            Z\
+            Lkotlin/Unit;\
+         )V";
+
+      fn options<'local>(..) -> Vec<String>
+         where jvm_type: JvmList<'local, JvmString<'local>>,
+               jvm_getter_method: "getOptions",
+               jvm_return_type: "Ljava/util/List;";
+
+      fn duration_seconds<'local>(..) -> i64
+         where jvm_getter_method: "getDurationSeconds",
+               jvm_return_type: "J";
+
+      fn allows_multiple_choices<'local>(..) -> bool
+         where jvm_getter_method: "getAllowsMultipleChoices",
+               jvm_return_type: "Z";
+
+      fn hides_totals<'local>(..) -> bool
+         where jvm_getter_method: "getHidesTotals",
+               jvm_return_type: "Z";
+
+      fn dummy<'local>(..) -> Option<()>
+         where jvm_type: JvmNullable<'local, JvmUnit<'local>>,
+               jvm_getter_method: "getDummy",
+               jvm_return_type: "Lkotlin/Unit;";
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneIntoJvm<'local, JvmStatusDraftPoll<'local>> for PollDraft {
+   fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmStatusDraftPoll<'local> {
+      POLL_DRAFT_HELPER.clone_into_jvm(
+         env,
+         &self.options,
+         self.duration.as_secs() as i64,
+         self.allows_multiple_choices,
+         self.hides_totals,
+         &None::<()>,
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+impl<'local> CloneFromJvm<'local, JvmStatusDraftPoll<'local>> for PollDraft {
+   fn clone_from_jvm(
+      env: &mut JNIEnv<'local>,
+      jvm_instance: &JvmStatusDraftPoll<'local>
+   ) -> PollDraft {
+      let options                 = POLL_DRAFT_HELPER.options                (env, jvm_instance);
+      let duration_seconds        = POLL_DRAFT_HELPER.duration_seconds       (env, jvm_instance);
+      let allows_multiple_choices = POLL_DRAFT_HELPER.allows_multiple_choices(env, jvm_instance);
+      let hides_totals            = POLL_DRAFT_HELPER.hides_totals           (env, jvm_instance);
+
+      PollDraft {
+         options,
+         duration: Duration::from_secs(duration_seconds.max(0) as u64),
+         allows_multiple_choices,
+         hides_totals,
+      }
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use std::time::Duration;
+   use isolang::Language;
+   use crate::media_attachment::MediaAttachmentId;
+   use crate::status::{StatusId, StatusLocalId, StatusVisibility};
+   use super::{PollDraft, StatusDraft};
+
+   #[test]
+   fn serde_round_trip() {
+      let draft = StatusDraft {
+         text: Some("text".to_string()),
+         spoiler_text: Some("spoiler".to_string()),
+         visibility: Some(StatusVisibility::Unlisted),
+         is_sensitive: Some(true),
+         language: Some(Language::Jpn),
+         replied_status_id: Some(StatusId {
+            instance_url: "https://status-draft.example.com/".parse().unwrap(),
+            local: StatusLocalId("1".to_string()),
+         }),
+         media_attachment_ids: vec![MediaAttachmentId("2".to_string())],
+         poll: Some(PollDraft {
+            options: vec!["a".to_string(), "b".to_string()],
+            duration: Duration::from_secs(60 * 60),
+            allows_multiple_choices: true,
+            hides_totals: false,
+         }),
+      };
+
+      let json = serde_json::to_string(&draft).unwrap();
+      assert_eq!(draft, serde_json::from_str::<StatusDraft>(&json).unwrap());
+   }
+}
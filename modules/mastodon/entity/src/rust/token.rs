@@ -16,7 +16,7 @@
 
 use chrono::{DateTime, Utc};
 use panoptiqon::cache::Cache;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::account::{AccountId, CredentialAccount};
 use crate::instance::Instance;
 
@@ -29,9 +29,11 @@ use {
    crate::jvm_types::{JvmCredentialAccount, JvmInstance, JvmToken},
 };
 
-#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct Token {
+   #[serde(with = "ext_panoptiqon::cache_reference")]
    pub instance: Cache<Instance>,
+   #[serde(with = "ext_panoptiqon::cache_reference::option")]
    pub account: Option<Cache<CredentialAccount>>,
    pub account_id: AccountId,
    pub access_token: String,
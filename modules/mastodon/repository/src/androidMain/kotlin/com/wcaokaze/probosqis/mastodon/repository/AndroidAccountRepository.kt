@@ -17,9 +17,119 @@
 package com.wcaokaze.probosqis.mastodon.repository
 
 import com.wcaokaze.probosqis.entity.Image
+import com.wcaokaze.probosqis.ext.kotlin.webapi.WebApiCallback
+import com.wcaokaze.probosqis.ext.kotlin.webapi.awaitWebApi
 import com.wcaokaze.probosqis.mastodon.entity.Account
+import com.wcaokaze.probosqis.mastodon.entity.RelationalAccount
+import com.wcaokaze.probosqis.mastodon.entity.Relationship
+import com.wcaokaze.probosqis.mastodon.entity.Status
+import com.wcaokaze.probosqis.mastodon.entity.Token
 import com.wcaokaze.probosqis.panoptiqon.Cache
+import kotlin.time.Duration
 
 class AndroidAccountRepository : AccountRepository {
-   external override fun getAccountIcon(account: Account): Cache<Image?>
+   external override fun getAccountIcon(token: Token, account: Account): Cache<Image?>
+
+   external override fun getAccount(token: Token, accountId: Account.Id): Cache<Account>
+
+   external override fun lookupAccount(token: Token, acct: String): Cache<Account>
+
+   external override fun getAccountStatuses(
+      token: Token,
+      accountId: Account.Id,
+      excludesReplies: Boolean,
+      excludesBoosts: Boolean,
+      isOnlyMedia: Boolean,
+      isOnlyPinned: Boolean,
+      tagged: String?
+   ): List<Status>
+
+   external override fun getFollowers(
+      token: Token,
+      accountId: Account.Id
+   ): List<Cache<Account>>
+
+   external override fun getFollowees(
+      token: Token,
+      accountId: Account.Id
+   ): List<Cache<Account>>
+
+   external override fun getRelationships(
+      token: Token,
+      accountIds: List<Account.Id>
+   ): List<Cache<Relationship>>
+
+   external override fun follow(
+      token: Token,
+      accountId: Account.Id,
+      showsBoosts: Boolean?,
+      notifies: Boolean?,
+      languages: List<String>
+   ): Cache<Relationship>
+
+   external override fun unfollow(token: Token, accountId: Account.Id): Cache<Relationship>
+
+   external override fun block(token: Token, accountId: Account.Id): Cache<Relationship>
+
+   external override fun unblock(token: Token, accountId: Account.Id): Cache<Relationship>
+
+   override fun mute(
+      token: Token,
+      account: Cache<Account>,
+      mutesNotifications: Boolean,
+      duration: Duration?
+   ): RelationalAccount {
+      return postMute(token, account, mutesNotifications, duration?.inWholeSeconds)
+   }
+
+   private external fun postMute(
+      token: Token,
+      account: Cache<Account>,
+      mutesNotifications: Boolean,
+      durationSeconds: Long?
+   ): RelationalAccount
+
+   external override fun unmute(token: Token, accountId: Account.Id): Cache<Relationship>
+
+   external override fun setNote(
+      token: Token,
+      accountId: Account.Id,
+      note: String?
+   ): Cache<Relationship>
+
+   override suspend fun getAccountAsync(token: Token, accountId: Account.Id): Cache<Account>
+      = awaitWebApi { getAccountAsync(token, accountId, it) }
+
+   override suspend fun followAsync(
+      token: Token,
+      accountId: Account.Id,
+      showsBoosts: Boolean?,
+      notifies: Boolean?,
+      languages: List<String>
+   ): Cache<Relationship>
+      = awaitWebApi { followAsync(token, accountId, showsBoosts, notifies, languages, it) }
+
+   override suspend fun unfollowAsync(token: Token, accountId: Account.Id): Cache<Relationship>
+      = awaitWebApi { unfollowAsync(token, accountId, it) }
+
+   private external fun getAccountAsync(
+      token: Token,
+      accountId: Account.Id,
+      callback: WebApiCallback<Cache<Account>>
+   )
+
+   private external fun followAsync(
+      token: Token,
+      accountId: Account.Id,
+      showsBoosts: Boolean?,
+      notifies: Boolean?,
+      languages: List<String>,
+      callback: WebApiCallback<Cache<Relationship>>
+   )
+
+   private external fun unfollowAsync(
+      token: Token,
+      accountId: Account.Id,
+      callback: WebApiCallback<Cache<Relationship>>
+   )
 }
@@ -0,0 +1,171 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.mastodon.entity.Filter
+import com.wcaokaze.probosqis.mastodon.entity.FilterList
+import com.wcaokaze.probosqis.mastodon.entity.Status
+import com.wcaokaze.probosqis.mastodon.entity.Token
+import com.wcaokaze.probosqis.panoptiqon.Cache
+import kotlin.time.Duration
+
+class AndroidFilterRepository : FilterRepository {
+   external override fun getFilters(token: Token): Cache<FilterList>
+
+   external override fun loadFilters(token: Token): Cache<FilterList>?
+
+   override fun createFilter(
+      token: Token,
+      title: String,
+      context: List<Filter.Context>,
+      filterAction: Filter.Action,
+      expireDuration: Duration?,
+      keywords: List<Pair<String, Boolean>>
+   ): Filter {
+      return postFilter(
+         token,
+         title,
+         context.map(Filter.Context::raw),
+         filterAction.raw,
+         expireDuration?.inWholeSeconds,
+         keywords.map { it.first },
+         keywords.map { it.second }
+      )
+   }
+
+   private external fun postFilter(
+      token: Token,
+      title: String,
+      rawContext: List<String>,
+      rawFilterAction: String,
+      expireDurationSeconds: Long?,
+      keywords: List<String>,
+      wholeWords: List<Boolean>
+   ): Filter
+
+   override fun updateFilter(
+      token: Token,
+      filterId: Filter.Id,
+      title: String?,
+      context: List<Filter.Context>?,
+      filterAction: Filter.Action?,
+      expireDuration: Duration?,
+      removesExpireTime: Boolean
+   ): Filter {
+      return putFilter(
+         token,
+         filterId.raw,
+         title,
+         context?.map(Filter.Context::raw),
+         filterAction?.raw,
+         expireDuration?.inWholeSeconds,
+         removesExpireTime
+      )
+   }
+
+   private external fun putFilter(
+      token: Token,
+      rawFilterId: String,
+      title: String?,
+      rawContext: List<String>?,
+      rawFilterAction: String?,
+      expireDurationSeconds: Long?,
+      removesExpireTime: Boolean
+   ): Filter
+
+   override fun deleteFilter(token: Token, filterId: Filter.Id) {
+      deleteFilterById(token, filterId.raw)
+   }
+
+   private external fun deleteFilterById(token: Token, rawFilterId: String)
+
+   override fun addKeyword(
+      token: Token,
+      filterId: Filter.Id,
+      keyword: String,
+      wholeWord: Boolean
+   ): Filter.Keyword {
+      return postKeyword(token, filterId.raw, keyword, wholeWord)
+   }
+
+   private external fun postKeyword(
+      token: Token,
+      rawFilterId: String,
+      keyword: String,
+      wholeWord: Boolean
+   ): Filter.Keyword
+
+   override fun updateKeyword(
+      token: Token,
+      filterId: Filter.Id,
+      keywordId: Filter.Keyword.Id,
+      keyword: String?,
+      wholeWord: Boolean?
+   ): Filter.Keyword {
+      return putKeyword(token, filterId.raw, keywordId.raw, keyword, wholeWord)
+   }
+
+   private external fun putKeyword(
+      token: Token,
+      rawFilterId: String,
+      rawKeywordId: String,
+      keyword: String?,
+      wholeWord: Boolean?
+   ): Filter.Keyword
+
+   override fun removeKeyword(
+      token: Token,
+      filterId: Filter.Id,
+      keywordId: Filter.Keyword.Id
+   ) {
+      deleteKeywordById(token, filterId.raw, keywordId.raw)
+   }
+
+   private external fun deleteKeywordById(
+      token: Token,
+      rawFilterId: String,
+      rawKeywordId: String
+   )
+
+   override fun addStatus(
+      token: Token,
+      filterId: Filter.Id,
+      statusId: Status.Id
+   ): Filter.FilterStatus {
+      return postFilterStatus(token, filterId.raw, statusId)
+   }
+
+   private external fun postFilterStatus(
+      token: Token,
+      rawFilterId: String,
+      statusId: Status.Id
+   ): Filter.FilterStatus
+
+   override fun removeStatus(
+      token: Token,
+      filterId: Filter.Id,
+      filterStatusId: Filter.FilterStatus.Id
+   ) {
+      deleteFilterStatusById(token, filterId.raw, filterStatusId.raw)
+   }
+
+   private external fun deleteFilterStatusById(
+      token: Token,
+      rawFilterId: String,
+      rawFilterStatusId: String
+   )
+}
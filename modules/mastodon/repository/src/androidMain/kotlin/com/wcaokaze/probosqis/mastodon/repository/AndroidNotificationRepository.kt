@@ -0,0 +1,58 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.ext.kotlin.webapi.WebApiCallback
+import com.wcaokaze.probosqis.ext.kotlin.webapi.awaitWebApi
+import com.wcaokaze.probosqis.mastodon.entity.Notification
+import com.wcaokaze.probosqis.mastodon.entity.Page
+import com.wcaokaze.probosqis.mastodon.entity.PageCursor
+import com.wcaokaze.probosqis.mastodon.entity.Token
+
+class AndroidNotificationRepository : NotificationRepository {
+   override fun getNotifications(
+      token: Token,
+      cursor: PageCursor?,
+      limit: Int?
+   ): Page<Notification> {
+      return loadNotifications(token, cursor, limit?.toLong())
+   }
+
+   private external fun loadNotifications(
+      token: Token,
+      cursor: PageCursor?,
+      limit: Long?
+   ): Page<Notification>
+
+   external override fun dismiss(token: Token, notification: Notification)
+
+   external override fun clear(token: Token)
+
+   override suspend fun getNotificationsAsync(
+      token: Token,
+      cursor: PageCursor?,
+      limit: Int?
+   ): Page<Notification>
+      = awaitWebApi { loadNotificationsAsync(token, cursor, limit?.toLong(), it) }
+
+   private external fun loadNotificationsAsync(
+      token: Token,
+      cursor: PageCursor?,
+      limit: Long?,
+      callback: WebApiCallback<Page<Notification>>
+   )
+}
@@ -0,0 +1,38 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import android.content.Context
+import java.io.File
+
+/**
+ * ステータスやアカウントなどのキャッシュをディスクに永続化し、
+ * アプリの再起動後に復元できるようにする。
+ * 各Repositoryを使用するより前に呼ぶ必要がある。
+ */
+object AndroidPersistentCache {
+   fun initialize(context: Context) {
+      val dir = File(context.cacheDir, "mastodon")
+      initialize(dir.absolutePath)
+   }
+
+   private external fun initialize(directory: String)
+
+   /** Repositoryを経由せずに変更されたキャッシュをディスクに書き込む。 */
+   external fun flush()
+}
@@ -0,0 +1,244 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.ext.kotlin.webapi.WebApiCallback
+import com.wcaokaze.probosqis.ext.kotlin.webapi.awaitWebApi
+import com.wcaokaze.probosqis.mastodon.entity.Poll
+import com.wcaokaze.probosqis.mastodon.entity.Status
+import com.wcaokaze.probosqis.mastodon.entity.StatusContext
+import com.wcaokaze.probosqis.mastodon.entity.StatusDraft
+import com.wcaokaze.probosqis.mastodon.entity.Token
+import com.wcaokaze.probosqis.panoptiqon.Cache
+
+class AndroidStatusRepository : StatusRepository {
+   external override fun favorite(token: Token, statusId: Status.Id): Cache<Status>
+
+   external override fun unfavorite(token: Token, statusId: Status.Id): Cache<Status>
+
+   override fun boost(
+      token: Token,
+      statusId: Status.Id,
+      visibility: Status.Visibility?
+   ): Cache<Status> {
+      return postBoost(token, statusId, visibility?.raw)
+   }
+
+   private external fun postBoost(
+      token: Token,
+      statusId: Status.Id,
+      rawVisibility: String?
+   ): Cache<Status>
+
+   external override fun unboost(token: Token, statusId: Status.Id): Cache<Status>
+
+   external override fun bookmark(token: Token, statusId: Status.Id): Cache<Status>
+
+   external override fun unbookmark(token: Token, statusId: Status.Id): Cache<Status>
+
+   external override fun pin(token: Token, statusId: Status.Id): Cache<Status>
+
+   external override fun unpin(token: Token, statusId: Status.Id): Cache<Status>
+
+   external override fun mute(token: Token, statusId: Status.Id): Cache<Status>
+
+   external override fun unmute(token: Token, statusId: Status.Id): Cache<Status>
+
+   external override fun refreshPoll(token: Token, poll: Poll): Poll
+
+   override fun vote(token: Token, poll: Poll, choices: List<Int>): Poll {
+      return postVote(token, poll, choices.map { it.toLong() })
+   }
+
+   private external fun postVote(token: Token, poll: Poll, choices: List<Long>): Poll
+
+   external override fun postStatus(
+      token: Token,
+      draft: StatusDraft,
+      idempotencyKey: String?
+   ): Cache<Status>
+
+   external override fun editStatus(
+      token: Token,
+      statusId: Status.Id,
+      draft: StatusDraft
+   ): Cache<Status>
+
+   external override fun deleteStatus(token: Token, statusId: Status.Id): StatusDraft
+
+   external override fun getContext(token: Token, status: Cache<Status>): StatusContext
+
+   override suspend fun favoriteAsync(token: Token, statusId: Status.Id): Cache<Status>
+      = awaitWebApi { favoriteAsync(token, statusId, it) }
+
+   override suspend fun unfavoriteAsync(token: Token, statusId: Status.Id): Cache<Status>
+      = awaitWebApi { unfavoriteAsync(token, statusId, it) }
+
+   override suspend fun boostAsync(
+      token: Token,
+      statusId: Status.Id,
+      visibility: Status.Visibility?
+   ): Cache<Status>
+      = awaitWebApi { postBoostAsync(token, statusId, visibility?.raw, it) }
+
+   override suspend fun unboostAsync(token: Token, statusId: Status.Id): Cache<Status>
+      = awaitWebApi { unboostAsync(token, statusId, it) }
+
+   override suspend fun bookmarkAsync(token: Token, statusId: Status.Id): Cache<Status>
+      = awaitWebApi { bookmarkAsync(token, statusId, it) }
+
+   override suspend fun unbookmarkAsync(token: Token, statusId: Status.Id): Cache<Status>
+      = awaitWebApi { unbookmarkAsync(token, statusId, it) }
+
+   override suspend fun pinAsync(token: Token, statusId: Status.Id): Cache<Status>
+      = awaitWebApi { pinAsync(token, statusId, it) }
+
+   override suspend fun unpinAsync(token: Token, statusId: Status.Id): Cache<Status>
+      = awaitWebApi { unpinAsync(token, statusId, it) }
+
+   override suspend fun muteAsync(token: Token, statusId: Status.Id): Cache<Status>
+      = awaitWebApi { muteAsync(token, statusId, it) }
+
+   override suspend fun unmuteAsync(token: Token, statusId: Status.Id): Cache<Status>
+      = awaitWebApi { unmuteAsync(token, statusId, it) }
+
+   override suspend fun refreshPollAsync(token: Token, poll: Poll): Poll
+      = awaitWebApi { refreshPollAsync(token, poll, it) }
+
+   override suspend fun voteAsync(token: Token, poll: Poll, choices: List<Int>): Poll
+      = awaitWebApi { postVoteAsync(token, poll, choices.map { c -> c.toLong() }, it) }
+
+   override suspend fun postStatusAsync(
+      token: Token,
+      draft: StatusDraft,
+      idempotencyKey: String?
+   ): Cache<Status>
+      = awaitWebApi { postStatusAsync(token, draft, idempotencyKey, it) }
+
+   override suspend fun editStatusAsync(
+      token: Token,
+      statusId: Status.Id,
+      draft: StatusDraft
+   ): Cache<Status>
+      = awaitWebApi { editStatusAsync(token, statusId, draft, it) }
+
+   override suspend fun deleteStatusAsync(token: Token, statusId: Status.Id): StatusDraft
+      = awaitWebApi { deleteStatusAsync(token, statusId, it) }
+
+   override suspend fun getContextAsync(token: Token, status: Cache<Status>): StatusContext
+      = awaitWebApi { getContextAsync(token, status, it) }
+
+   private external fun favoriteAsync(
+      token: Token,
+      statusId: Status.Id,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun unfavoriteAsync(
+      token: Token,
+      statusId: Status.Id,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun postBoostAsync(
+      token: Token,
+      statusId: Status.Id,
+      rawVisibility: String?,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun unboostAsync(
+      token: Token,
+      statusId: Status.Id,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun bookmarkAsync(
+      token: Token,
+      statusId: Status.Id,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun unbookmarkAsync(
+      token: Token,
+      statusId: Status.Id,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun pinAsync(
+      token: Token,
+      statusId: Status.Id,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun unpinAsync(
+      token: Token,
+      statusId: Status.Id,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun muteAsync(
+      token: Token,
+      statusId: Status.Id,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun unmuteAsync(
+      token: Token,
+      statusId: Status.Id,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun refreshPollAsync(
+      token: Token,
+      poll: Poll,
+      callback: WebApiCallback<Poll>
+   )
+
+   private external fun postVoteAsync(
+      token: Token,
+      poll: Poll,
+      choices: List<Long>,
+      callback: WebApiCallback<Poll>
+   )
+
+   private external fun postStatusAsync(
+      token: Token,
+      draft: StatusDraft,
+      idempotencyKey: String?,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun editStatusAsync(
+      token: Token,
+      statusId: Status.Id,
+      draft: StatusDraft,
+      callback: WebApiCallback<Cache<Status>>
+   )
+
+   private external fun deleteStatusAsync(
+      token: Token,
+      statusId: Status.Id,
+      callback: WebApiCallback<StatusDraft>
+   )
+
+   private external fun getContextAsync(
+      token: Token,
+      status: Cache<Status>,
+      callback: WebApiCallback<StatusContext>
+   )
+}
@@ -16,9 +16,268 @@
 
 package com.wcaokaze.probosqis.mastodon.repository
 
+import com.wcaokaze.probosqis.ext.kotlin.webapi.WebApiCallback
+import com.wcaokaze.probosqis.ext.kotlin.webapi.awaitWebApi
+import com.wcaokaze.probosqis.mastodon.entity.Page
+import com.wcaokaze.probosqis.mastodon.entity.PageCursor
+import com.wcaokaze.probosqis.mastodon.entity.RateLimitBudget
 import com.wcaokaze.probosqis.mastodon.entity.Status
 import com.wcaokaze.probosqis.mastodon.entity.Token
 
 class AndroidTimelineRepository : TimelineRepository {
    external override fun getHomeTimeline(token: Token): List<Status>
+
+   external override fun getPublicTimeline(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean
+   ): List<Status>
+
+   external override fun getHashtagTimeline(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>
+   ): List<Status>
+
+   external override fun getListTimeline(token: Token, listId: String): List<Status>
+
+   external override fun loadOlderHomeTimeline(
+      token: Token,
+      cursor: PageCursor
+   ): Page<Status>
+
+   external override fun loadNewerHomeTimeline(
+      token: Token,
+      cursor: PageCursor
+   ): Page<Status>
+
+   external override fun loadOlderPublicTimeline(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      cursor: PageCursor
+   ): Page<Status>
+
+   external override fun loadNewerPublicTimeline(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      cursor: PageCursor
+   ): Page<Status>
+
+   external override fun loadOlderHashtagTimeline(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      cursor: PageCursor
+   ): Page<Status>
+
+   external override fun loadNewerHashtagTimeline(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      cursor: PageCursor
+   ): Page<Status>
+
+   external override fun loadOlderListTimeline(
+      token: Token,
+      listId: String,
+      cursor: PageCursor
+   ): Page<Status>
+
+   external override fun loadNewerListTimeline(
+      token: Token,
+      listId: String,
+      cursor: PageCursor
+   ): Page<Status>
+
+   override suspend fun getHomeTimelineAsync(token: Token): List<Status>
+      = awaitWebApi { getHomeTimelineAsync(token, it) }
+
+   override suspend fun getPublicTimelineAsync(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean
+   ): List<Status>
+      = awaitWebApi { getPublicTimelineAsync(token, isLocal, isRemote, isOnlyMedia, it) }
+
+   override suspend fun getHashtagTimelineAsync(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>
+   ): List<Status>
+      = awaitWebApi { getHashtagTimelineAsync(token, hashtag, any, all, none, it) }
+
+   override suspend fun getListTimelineAsync(token: Token, listId: String): List<Status>
+      = awaitWebApi { getListTimelineAsync(token, listId, it) }
+
+   override suspend fun loadOlderHomeTimelineAsync(
+      token: Token,
+      cursor: PageCursor
+   ): Page<Status>
+      = awaitWebApi { loadOlderHomeTimelineAsync(token, cursor, it) }
+
+   override suspend fun loadNewerHomeTimelineAsync(
+      token: Token,
+      cursor: PageCursor
+   ): Page<Status>
+      = awaitWebApi { loadNewerHomeTimelineAsync(token, cursor, it) }
+
+   override suspend fun loadOlderPublicTimelineAsync(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      cursor: PageCursor
+   ): Page<Status>
+      = awaitWebApi { loadOlderPublicTimelineAsync(token, isLocal, isRemote, isOnlyMedia, cursor, it) }
+
+   override suspend fun loadNewerPublicTimelineAsync(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      cursor: PageCursor
+   ): Page<Status>
+      = awaitWebApi { loadNewerPublicTimelineAsync(token, isLocal, isRemote, isOnlyMedia, cursor, it) }
+
+   override suspend fun loadOlderHashtagTimelineAsync(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      cursor: PageCursor
+   ): Page<Status>
+      = awaitWebApi { loadOlderHashtagTimelineAsync(token, hashtag, any, all, none, cursor, it) }
+
+   override suspend fun loadNewerHashtagTimelineAsync(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      cursor: PageCursor
+   ): Page<Status>
+      = awaitWebApi { loadNewerHashtagTimelineAsync(token, hashtag, any, all, none, cursor, it) }
+
+   override suspend fun loadOlderListTimelineAsync(
+      token: Token,
+      listId: String,
+      cursor: PageCursor
+   ): Page<Status>
+      = awaitWebApi { loadOlderListTimelineAsync(token, listId, cursor, it) }
+
+   override suspend fun loadNewerListTimelineAsync(
+      token: Token,
+      listId: String,
+      cursor: PageCursor
+   ): Page<Status>
+      = awaitWebApi { loadNewerListTimelineAsync(token, listId, cursor, it) }
+
+   external override fun getRateLimitBudget(token: Token): RateLimitBudget?
+
+   private external fun getHomeTimelineAsync(
+      token: Token,
+      callback: WebApiCallback<List<Status>>
+   )
+
+   private external fun getPublicTimelineAsync(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      callback: WebApiCallback<List<Status>>
+   )
+
+   private external fun getHashtagTimelineAsync(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      callback: WebApiCallback<List<Status>>
+   )
+
+   private external fun getListTimelineAsync(
+      token: Token,
+      listId: String,
+      callback: WebApiCallback<List<Status>>
+   )
+
+   private external fun loadOlderHomeTimelineAsync(
+      token: Token,
+      cursor: PageCursor,
+      callback: WebApiCallback<Page<Status>>
+   )
+
+   private external fun loadNewerHomeTimelineAsync(
+      token: Token,
+      cursor: PageCursor,
+      callback: WebApiCallback<Page<Status>>
+   )
+
+   private external fun loadOlderPublicTimelineAsync(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      cursor: PageCursor,
+      callback: WebApiCallback<Page<Status>>
+   )
+
+   private external fun loadNewerPublicTimelineAsync(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      cursor: PageCursor,
+      callback: WebApiCallback<Page<Status>>
+   )
+
+   private external fun loadOlderHashtagTimelineAsync(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      cursor: PageCursor,
+      callback: WebApiCallback<Page<Status>>
+   )
+
+   private external fun loadNewerHashtagTimelineAsync(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      cursor: PageCursor,
+      callback: WebApiCallback<Page<Status>>
+   )
+
+   private external fun loadOlderListTimelineAsync(
+      token: Token,
+      listId: String,
+      cursor: PageCursor,
+      callback: WebApiCallback<Page<Status>>
+   )
+
+   private external fun loadNewerListTimelineAsync(
+      token: Token,
+      listId: String,
+      cursor: PageCursor,
+      callback: WebApiCallback<Page<Status>>
+   )
 }
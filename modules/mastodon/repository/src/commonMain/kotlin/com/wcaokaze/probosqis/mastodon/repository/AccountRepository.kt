@@ -18,12 +18,141 @@ package com.wcaokaze.probosqis.mastodon.repository
 
 import com.wcaokaze.probosqis.entity.Image
 import com.wcaokaze.probosqis.mastodon.entity.Account
+import com.wcaokaze.probosqis.mastodon.entity.RelationalAccount
+import com.wcaokaze.probosqis.mastodon.entity.Relationship
+import com.wcaokaze.probosqis.mastodon.entity.Status
+import com.wcaokaze.probosqis.mastodon.entity.Token
 import com.wcaokaze.probosqis.panoptiqon.Cache
 import java.io.IOException
+import kotlin.time.Duration
 
 interface AccountRepository {
    /**
+    * [token]のインスタンスのレート制限の残りが少ない場合、
+    * ダウンロードは後回しにされる
+    *
     * @throws IOException
     */
-   fun getAccountIcon(account: Account): Cache<Image?>
+   fun getAccountIcon(token: Token, account: Account): Cache<Image?>
+
+   /**
+    * @throws IOException
+    */
+   fun getAccount(token: Token, accountId: Account.Id): Cache<Account>
+
+   /**
+    * @param acct
+    *   ローカルのアカウントの場合`username`、
+    *   リモートのアカウントの場合`username@domain`
+    * @throws IOException
+    */
+   fun lookupAccount(token: Token, acct: String): Cache<Account>
+
+   /**
+    * @throws IOException
+    */
+   fun getAccountStatuses(
+      token: Token,
+      accountId: Account.Id,
+      excludesReplies: Boolean = false,
+      excludesBoosts: Boolean = false,
+      isOnlyMedia: Boolean = false,
+      isOnlyPinned: Boolean = false,
+      tagged: String? = null
+   ): List<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun getFollowers(token: Token, accountId: Account.Id): List<Cache<Account>>
+
+   /**
+    * [accountId]のアカウントがフォローしているアカウントを取得する
+    * @throws IOException
+    */
+   fun getFollowees(token: Token, accountId: Account.Id): List<Cache<Account>>
+
+   /**
+    * @throws IOException
+    */
+   fun getRelationships(
+      token: Token,
+      accountIds: List<Account.Id>
+   ): List<Cache<Relationship>>
+
+   /**
+    * @param showsBoosts
+    *   フォローしたアカウントのブーストをホームタイムラインに表示するかどうか
+    * @param notifies
+    *   フォローしたアカウントが投稿したときに通知を受け取るかどうか
+    * @param languages
+    *   ホームタイムラインに表示する投稿の言語。空の場合すべての言語
+    * @throws IOException
+    */
+   fun follow(
+      token: Token,
+      accountId: Account.Id,
+      showsBoosts: Boolean? = null,
+      notifies: Boolean? = null,
+      languages: List<String> = emptyList()
+   ): Cache<Relationship>
+
+   /**
+    * @throws IOException
+    */
+   fun unfollow(token: Token, accountId: Account.Id): Cache<Relationship>
+
+   /**
+    * @throws IOException
+    */
+   fun block(token: Token, accountId: Account.Id): Cache<Relationship>
+
+   /**
+    * @throws IOException
+    */
+   fun unblock(token: Token, accountId: Account.Id): Cache<Relationship>
+
+   /**
+    * @param duration nullの場合無期限にミュートする
+    * @throws IOException
+    * @throws IllegalArgumentException [duration]が1秒未満の場合
+    */
+   fun mute(
+      token: Token,
+      account: Cache<Account>,
+      mutesNotifications: Boolean = true,
+      duration: Duration? = null
+   ): RelationalAccount
+
+   /**
+    * @throws IOException
+    */
+   fun unmute(token: Token, accountId: Account.Id): Cache<Relationship>
+
+   /**
+    * アカウントに自分だけが見られるメモを設定する。nullの場合メモを削除する
+    * @throws IOException
+    */
+   fun setNote(
+      token: Token,
+      accountId: Account.Id,
+      note: String?
+   ): Cache<Relationship>
+
+   /*
+    * 以下はタイムライン上でよく使う操作の非同期版。
+    * レスポンスを待つ間JVMのスレッドもRust側のスレッドも占有しない
+    */
+
+   suspend fun getAccountAsync(token: Token, accountId: Account.Id): Cache<Account>
+
+   suspend fun followAsync(
+      token: Token,
+      accountId: Account.Id,
+      showsBoosts: Boolean? = null,
+      notifies: Boolean? = null,
+      languages: List<String> = emptyList()
+   ): Cache<Relationship>
+
+   suspend fun unfollowAsync(token: Token, accountId: Account.Id): Cache<Relationship>
 }
@@ -0,0 +1,119 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.mastodon.entity.Filter
+import com.wcaokaze.probosqis.mastodon.entity.FilterList
+import com.wcaokaze.probosqis.mastodon.entity.Status
+import com.wcaokaze.probosqis.mastodon.entity.Token
+import com.wcaokaze.probosqis.panoptiqon.Cache
+import java.io.IOException
+import kotlin.time.Duration
+
+/**
+ * ログイン中のアカウントのフィルタを管理する。
+ *
+ * フィルタはアカウントごとに[FilterList]としてキャッシュされ、
+ * このRepositoryによる変更はキャッシュにも反映される
+ */
+interface FilterRepository {
+   /**
+    * @throws IOException
+    */
+   fun getFilters(token: Token): Cache<FilterList>
+
+   /**
+    * キャッシュされているフィルタ。まだ[getFilters]で取得していない場合null
+    */
+   fun loadFilters(token: Token): Cache<FilterList>?
+
+   /**
+    * @param expireDuration nullの場合無期限
+    * @param keywords キーワードと[Filter.Keyword.wholeWord]の組
+    * @throws IOException
+    */
+   fun createFilter(
+      token: Token,
+      title: String,
+      context: List<Filter.Context>,
+      filterAction: Filter.Action,
+      expireDuration: Duration? = null,
+      keywords: List<Pair<String, Boolean>> = emptyList()
+   ): Filter
+
+   /**
+    * nullを指定した項目は変更しない。
+    *
+    * @param removesExpireTime trueの場合無期限にする。[expireDuration]は無視される
+    * @throws IOException
+    */
+   fun updateFilter(
+      token: Token,
+      filterId: Filter.Id,
+      title: String? = null,
+      context: List<Filter.Context>? = null,
+      filterAction: Filter.Action? = null,
+      expireDuration: Duration? = null,
+      removesExpireTime: Boolean = false
+   ): Filter
+
+   /**
+    * @throws IOException
+    */
+   fun deleteFilter(token: Token, filterId: Filter.Id)
+
+   /**
+    * @throws IOException
+    */
+   fun addKeyword(
+      token: Token,
+      filterId: Filter.Id,
+      keyword: String,
+      wholeWord: Boolean
+   ): Filter.Keyword
+
+   /**
+    * nullを指定した項目は変更しない
+    * @throws IOException
+    */
+   fun updateKeyword(
+      token: Token,
+      filterId: Filter.Id,
+      keywordId: Filter.Keyword.Id,
+      keyword: String? = null,
+      wholeWord: Boolean? = null
+   ): Filter.Keyword
+
+   /**
+    * @throws IOException
+    */
+   fun removeKeyword(token: Token, filterId: Filter.Id, keywordId: Filter.Keyword.Id)
+
+   /**
+    * @throws IOException
+    */
+   fun addStatus(token: Token, filterId: Filter.Id, statusId: Status.Id): Filter.FilterStatus
+
+   /**
+    * @throws IOException
+    */
+   fun removeStatus(
+      token: Token,
+      filterId: Filter.Id,
+      filterStatusId: Filter.FilterStatus.Id
+   )
+}
@@ -0,0 +1,59 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.ext.kotlin.Url
+import kotlin.time.Duration
+import kotlin.time.Duration.Companion.seconds
+
+data class HttpClientSettings(
+   val connectTimeout: Duration = 10.seconds,
+   /**
+    * リクエスト全体のタイムアウト。ストリーミングには適用されない
+    */
+   val readTimeout: Duration = 30.seconds,
+   /**
+    * `http://`, `https://`, `socks5://`, `socks5h://`のプロキシ。
+    * nullの場合はプロキシを使用しない
+    */
+   val proxyUrl: Url? = null,
+)
+
+/**
+ * Rust側で共有しているHTTPクライアントの設定。
+ * 設定画面で変更された値を実行中に反映する
+ */
+interface HttpClientRegistry {
+   /**
+    * @throws com.wcaokaze.probosqis.ext.kotlin.webapi.InvalidUrlException
+    *   プロキシのURLが不正な場合
+    * @throws IllegalArgumentException
+    *   タイムアウトが0以下の場合
+    */
+   fun setDefaultSettings(settings: HttpClientSettings)
+
+   /**
+    * [instanceUrl]へのリクエストにのみ使用する設定を変更する。
+    * nullの場合はデフォルトの設定に戻す
+    *
+    * @throws com.wcaokaze.probosqis.ext.kotlin.webapi.InvalidUrlException
+    *   インスタンスかプロキシのURLが不正な場合
+    * @throws IllegalArgumentException
+    *   タイムアウトが0以下の場合
+    */
+   fun setInstanceSettings(instanceUrl: Url, settings: HttpClientSettings?)
+}
@@ -0,0 +1,61 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.mastodon.entity.MediaAttachment
+import com.wcaokaze.probosqis.mastodon.entity.Token
+import java.io.IOException
+
+fun interface MediaUploadProgressListener {
+   /**
+    * アップロード中のスレッドから呼ばれる
+    */
+   fun onProgress(sentBytes: Long, totalBytes: Long)
+}
+
+interface MediaRepository {
+   /**
+    * メディアをアップロードする。
+    *
+    * サーバー側の処理に時間がかかる場合は処理が完了するまで待機し、
+    * [url][MediaAttachment.url]が取得できる状態のMediaAttachmentを返す。
+    *
+    * @param mimeType nullの場合サーバーがファイルの内容から判定する
+    * @throws IOException
+    */
+   fun uploadMedia(
+      token: Token,
+      fileName: String,
+      file: ByteArray,
+      mimeType: String? = null,
+      description: String? = null,
+      focus: MediaAttachment.ImageFocus? = null,
+      onProgress: MediaUploadProgressListener? = null
+   ): MediaAttachment
+
+   /**
+    * 代替テキストやフォーカスを変更する。
+    * Statusに添付する前のメディアのみ変更できる
+    * @throws IOException
+    */
+   fun updateMedia(
+      token: Token,
+      mediaId: MediaAttachment.Id,
+      description: String?,
+      focus: MediaAttachment.ImageFocus?
+   ): MediaAttachment
+}
@@ -0,0 +1,59 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.mastodon.entity.Notification
+import com.wcaokaze.probosqis.mastodon.entity.Page
+import com.wcaokaze.probosqis.mastodon.entity.PageCursor
+import com.wcaokaze.probosqis.mastodon.entity.Token
+import java.io.IOException
+
+interface NotificationRepository {
+   /**
+    * mastodon 4.3.0以降のサーバーではグループ化された通知を取得する。
+    *
+    * @param cursor 前回取得したページの[Page.next]または[Page.prev]。
+    *   nullの場合最新のページを取得する
+    * @throws IOException
+    */
+   fun getNotifications(
+      token: Token,
+      cursor: PageCursor? = null,
+      limit: Int? = null
+   ): Page<Notification>
+
+   /**
+    * グループ化された通知の場合はグループ内の通知がすべて削除される
+    * @throws IOException
+    */
+   fun dismiss(token: Token, notification: Notification)
+
+   /**
+    * @throws IOException
+    */
+   fun clear(token: Token)
+
+   /**
+    * [getNotifications]の非同期版。
+    * レスポンスを待つ間JVMのスレッドもRust側のスレッドも占有しない
+    */
+   suspend fun getNotificationsAsync(
+      token: Token,
+      cursor: PageCursor? = null,
+      limit: Int? = null
+   ): Page<Notification>
+}
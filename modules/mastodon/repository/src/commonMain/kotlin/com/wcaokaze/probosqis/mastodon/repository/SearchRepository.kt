@@ -0,0 +1,73 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.mastodon.entity.Account
+import com.wcaokaze.probosqis.mastodon.entity.SearchResult
+import com.wcaokaze.probosqis.mastodon.entity.Token
+import com.wcaokaze.probosqis.panoptiqon.Cache
+import java.io.IOException
+
+interface SearchRepository {
+   /**
+    * @param type nullの場合すべての種類を検索する
+    * @param resolves URLやacctで指定されたリモートのアカウントや投稿を
+    *   インスタンスに取得させる
+    * @param accountId 指定した場合そのアカウントの投稿のみ検索する
+    * @param offset 2ページ目以降を取得する場合、取得済みの件数を指定する
+    * @throws IOException
+    */
+   fun search(
+      token: Token,
+      query: String,
+      type: SearchType? = null,
+      resolves: Boolean = false,
+      isOnlyFollowing: Boolean = false,
+      accountId: Account.Id? = null,
+      excludesUnreviewed: Boolean = false,
+      offset: Int? = null,
+      limit: Int? = null
+   ): SearchResult
+
+   /**
+    * 他のサーバーの投稿やプロフィールのURL、または`@username@domain`形式の
+    * acctを、ログイン中のインスタンス上の投稿またはアカウントに解決する。
+    *
+    * 返される投稿やアカウントのidはログイン中のインスタンスのものなので、
+    * そのままお気に入りやフォローなどの操作に使える。
+    *
+    * @return 見つからなかった場合null
+    * @throws IOException
+    */
+   fun resolve(token: Token, query: String): ResolvedObject?
+}
+
+enum class SearchType(val raw: String) {
+   ACCOUNT("accounts"),
+   STATUS("statuses"),
+   HASHTAG("hashtags"),
+}
+
+sealed class ResolvedObject {
+   data class Account(
+      val account: Cache<com.wcaokaze.probosqis.mastodon.entity.Account>
+   ) : ResolvedObject()
+
+   data class Status(
+      val status: Cache<com.wcaokaze.probosqis.mastodon.entity.Status>
+   ) : ResolvedObject()
+}
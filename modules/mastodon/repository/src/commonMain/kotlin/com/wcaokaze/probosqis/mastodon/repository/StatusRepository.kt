@@ -0,0 +1,178 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.mastodon.entity.Poll
+import com.wcaokaze.probosqis.mastodon.entity.Status
+import com.wcaokaze.probosqis.mastodon.entity.StatusContext
+import com.wcaokaze.probosqis.mastodon.entity.StatusDraft
+import com.wcaokaze.probosqis.mastodon.entity.Token
+import com.wcaokaze.probosqis.panoptiqon.Cache
+import java.io.IOException
+
+interface StatusRepository {
+   /**
+    * @throws IOException
+    */
+   fun favorite(token: Token, statusId: Status.Id): Cache<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun unfavorite(token: Token, statusId: Status.Id): Cache<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun boost(
+      token: Token,
+      statusId: Status.Id,
+      visibility: Status.Visibility? = null
+   ): Cache<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun unboost(token: Token, statusId: Status.Id): Cache<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun bookmark(token: Token, statusId: Status.Id): Cache<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun unbookmark(token: Token, statusId: Status.Id): Cache<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun pin(token: Token, statusId: Status.Id): Cache<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun unpin(token: Token, statusId: Status.Id): Cache<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun mute(token: Token, statusId: Status.Id): Cache<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun unmute(token: Token, statusId: Status.Id): Cache<Status>
+
+   /**
+    * 投票の最新の結果を取得する。
+    * @throws IOException
+    */
+   fun refreshPoll(token: Token, poll: Poll): Poll
+
+   /**
+    * @param choices 投票する選択肢のインデックス
+    * @throws IOException 締め切られた投票や、複数選択できない投票に複数の
+    *   選択肢を指定した場合など、投票できない場合も含む
+    */
+   fun vote(token: Token, poll: Poll, choices: List<Int>): Poll
+
+   /**
+    * @param idempotencyKey 同じキーでの投稿の重複を防ぐ
+    * @throws IOException
+    */
+   fun postStatus(
+      token: Token,
+      draft: StatusDraft,
+      idempotencyKey: String? = null
+   ): Cache<Status>
+
+   /**
+    * @throws IOException
+    */
+   fun editStatus(token: Token, statusId: Status.Id, draft: StatusDraft): Cache<Status>
+
+   /**
+    * @return 削除したStatusを再編集するための下書き
+    * @throws IOException
+    */
+   fun deleteStatus(token: Token, statusId: Status.Id): StatusDraft
+
+   /**
+    * @throws IOException
+    */
+   fun getContext(token: Token, status: Cache<Status>): StatusContext
+
+   /*
+    * 以下は非同期版。
+    * レスポンスを待つ間JVMのスレッドもRust側のスレッドも占有しない
+    */
+
+   suspend fun favoriteAsync(token: Token, statusId: Status.Id): Cache<Status>
+
+   suspend fun unfavoriteAsync(token: Token, statusId: Status.Id): Cache<Status>
+
+   suspend fun boostAsync(
+      token: Token,
+      statusId: Status.Id,
+      visibility: Status.Visibility? = null
+   ): Cache<Status>
+
+   suspend fun unboostAsync(token: Token, statusId: Status.Id): Cache<Status>
+
+   suspend fun bookmarkAsync(token: Token, statusId: Status.Id): Cache<Status>
+
+   suspend fun unbookmarkAsync(token: Token, statusId: Status.Id): Cache<Status>
+
+   suspend fun pinAsync(token: Token, statusId: Status.Id): Cache<Status>
+
+   suspend fun unpinAsync(token: Token, statusId: Status.Id): Cache<Status>
+
+   suspend fun muteAsync(token: Token, statusId: Status.Id): Cache<Status>
+
+   suspend fun unmuteAsync(token: Token, statusId: Status.Id): Cache<Status>
+
+   suspend fun refreshPollAsync(token: Token, poll: Poll): Poll
+
+   /**
+    * @param choices 投票する選択肢のインデックス
+    */
+   suspend fun voteAsync(token: Token, poll: Poll, choices: List<Int>): Poll
+
+   /**
+    * @param idempotencyKey 同じキーでの投稿の重複を防ぐ
+    */
+   suspend fun postStatusAsync(
+      token: Token,
+      draft: StatusDraft,
+      idempotencyKey: String? = null
+   ): Cache<Status>
+
+   suspend fun editStatusAsync(
+      token: Token,
+      statusId: Status.Id,
+      draft: StatusDraft
+   ): Cache<Status>
+
+   /**
+    * @return 削除したStatusを再編集するための下書き
+    */
+   suspend fun deleteStatusAsync(token: Token, statusId: Status.Id): StatusDraft
+
+   suspend fun getContextAsync(token: Token, status: Cache<Status>): StatusContext
+}
@@ -16,9 +16,164 @@
 
 package com.wcaokaze.probosqis.mastodon.repository
 
+import com.wcaokaze.probosqis.mastodon.entity.Page
+import com.wcaokaze.probosqis.mastodon.entity.PageCursor
+import com.wcaokaze.probosqis.mastodon.entity.RateLimitBudget
 import com.wcaokaze.probosqis.mastodon.entity.Status
 import com.wcaokaze.probosqis.mastodon.entity.Token
 
 interface TimelineRepository {
    fun getHomeTimeline(token: Token): List<Status>
+
+   fun getPublicTimeline(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean
+   ): List<Status>
+
+   fun getHashtagTimeline(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>
+   ): List<Status>
+
+   fun getListTimeline(token: Token, listId: String): List<Status>
+
+   /*
+    * 以下はページングして読み込む版。
+    * loadOlderには前回取得したページの[Page.next]を、
+    * loadNewerには[Page.prev]を渡す。
+    * 最初のページはloadOlderに`PageCursor()`を渡して取得する
+    */
+
+   fun loadOlderHomeTimeline(token: Token, cursor: PageCursor): Page<Status>
+
+   fun loadNewerHomeTimeline(token: Token, cursor: PageCursor): Page<Status>
+
+   fun loadOlderPublicTimeline(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      cursor: PageCursor
+   ): Page<Status>
+
+   fun loadNewerPublicTimeline(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      cursor: PageCursor
+   ): Page<Status>
+
+   fun loadOlderHashtagTimeline(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      cursor: PageCursor
+   ): Page<Status>
+
+   fun loadNewerHashtagTimeline(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      cursor: PageCursor
+   ): Page<Status>
+
+   fun loadOlderListTimeline(token: Token, listId: String, cursor: PageCursor): Page<Status>
+
+   fun loadNewerListTimeline(token: Token, listId: String, cursor: PageCursor): Page<Status>
+
+   /*
+    * 以下は上記の非同期版。
+    * レスポンスを待つ間JVMのスレッドもRust側のスレッドも占有しないため、
+    * 多数のカラムを同時に読み込む場合にはこちらを使う
+    */
+
+   suspend fun getHomeTimelineAsync(token: Token): List<Status>
+
+   suspend fun getPublicTimelineAsync(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean
+   ): List<Status>
+
+   suspend fun getHashtagTimelineAsync(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>
+   ): List<Status>
+
+   suspend fun getListTimelineAsync(token: Token, listId: String): List<Status>
+
+   suspend fun loadOlderHomeTimelineAsync(
+      token: Token,
+      cursor: PageCursor
+   ): Page<Status>
+
+   suspend fun loadNewerHomeTimelineAsync(
+      token: Token,
+      cursor: PageCursor
+   ): Page<Status>
+
+   suspend fun loadOlderPublicTimelineAsync(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      cursor: PageCursor
+   ): Page<Status>
+
+   suspend fun loadNewerPublicTimelineAsync(
+      token: Token,
+      isLocal: Boolean,
+      isRemote: Boolean,
+      isOnlyMedia: Boolean,
+      cursor: PageCursor
+   ): Page<Status>
+
+   suspend fun loadOlderHashtagTimelineAsync(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      cursor: PageCursor
+   ): Page<Status>
+
+   suspend fun loadNewerHashtagTimelineAsync(
+      token: Token,
+      hashtag: String,
+      any: List<String>,
+      all: List<String>,
+      none: List<String>,
+      cursor: PageCursor
+   ): Page<Status>
+
+   suspend fun loadOlderListTimelineAsync(
+      token: Token,
+      listId: String,
+      cursor: PageCursor
+   ): Page<Status>
+
+   suspend fun loadNewerListTimelineAsync(
+      token: Token,
+      listId: String,
+      cursor: PageCursor
+   ): Page<Status>
+
+   /**
+    * このアカウントのレート制限の残り。まだ一度もリクエストしていない場合などはnull
+    */
+   fun getRateLimitBudget(token: Token): RateLimitBudget?
 }
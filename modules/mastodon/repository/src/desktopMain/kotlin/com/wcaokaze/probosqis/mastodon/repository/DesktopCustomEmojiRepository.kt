@@ -0,0 +1,38 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.mastodon.entity.CustomEmoji
+import com.wcaokaze.probosqis.mastodon.entity.CustomEmojiCatalogue
+import com.wcaokaze.probosqis.mastodon.entity.Instance
+import com.wcaokaze.probosqis.panoptiqon.Cache
+
+class DesktopCustomEmojiRepository : CustomEmojiRepository {
+   external override fun getCustomEmojis(
+      instance: Cache<Instance>
+   ): Cache<CustomEmojiCatalogue>
+
+   external override fun refreshCustomEmojis(
+      instance: Cache<Instance>
+   ): Cache<CustomEmojiCatalogue>
+
+   external override fun searchCustomEmojis(
+      instance: Cache<Instance>,
+      query: String,
+      limit: Int
+   ): List<CustomEmoji>
+}
@@ -0,0 +1,68 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.ext.kotlin.Url
+
+/**
+ * @param appVersion User-Agentに含めるアプリのバージョン
+ */
+class DesktopHttpClientRegistry(
+   private val appVersion: String?
+) : HttpClientRegistry {
+   init {
+      setDefaultSettings(HttpClientSettings())
+   }
+
+   override fun setDefaultSettings(settings: HttpClientSettings) {
+      applyDefaultSettings(
+         settings.connectTimeout.inWholeMilliseconds,
+         settings.readTimeout.inWholeMilliseconds,
+         settings.proxyUrl?.raw,
+         appVersion
+      )
+   }
+
+   override fun setInstanceSettings(instanceUrl: Url, settings: HttpClientSettings?) {
+      if (settings == null) {
+         resetInstanceSettings(instanceUrl.raw)
+      } else {
+         applyInstanceSettings(
+            instanceUrl.raw,
+            settings.connectTimeout.inWholeMilliseconds,
+            settings.readTimeout.inWholeMilliseconds,
+            settings.proxyUrl?.raw
+         )
+      }
+   }
+
+   private external fun applyDefaultSettings(
+      connectTimeoutMillis: Long,
+      readTimeoutMillis: Long,
+      proxyUrl: String?,
+      appVersion: String?
+   )
+
+   private external fun applyInstanceSettings(
+      instanceUrl: String,
+      connectTimeoutMillis: Long,
+      readTimeoutMillis: Long,
+      proxyUrl: String?
+   )
+
+   private external fun resetInstanceSettings(instanceUrl: String)
+}
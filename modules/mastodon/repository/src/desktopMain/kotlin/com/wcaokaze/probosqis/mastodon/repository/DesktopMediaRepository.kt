@@ -0,0 +1,60 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.mastodon.entity.MediaAttachment
+import com.wcaokaze.probosqis.mastodon.entity.Token
+
+class DesktopMediaRepository : MediaRepository {
+   override fun uploadMedia(
+      token: Token,
+      fileName: String,
+      file: ByteArray,
+      mimeType: String?,
+      description: String?,
+      focus: MediaAttachment.ImageFocus?,
+      onProgress: MediaUploadProgressListener?
+   ): MediaAttachment {
+      return postMedia(token, fileName, mimeType, file, description, focus, onProgress)
+   }
+
+   private external fun postMedia(
+      token: Token,
+      fileName: String,
+      mimeType: String?,
+      file: ByteArray,
+      description: String?,
+      focus: MediaAttachment.ImageFocus?,
+      onProgress: MediaUploadProgressListener?
+   ): MediaAttachment
+
+   override fun updateMedia(
+      token: Token,
+      mediaId: MediaAttachment.Id,
+      description: String?,
+      focus: MediaAttachment.ImageFocus?
+   ): MediaAttachment {
+      return putMedia(token, mediaId.raw, description, focus)
+   }
+
+   private external fun putMedia(
+      token: Token,
+      rawMediaId: String,
+      description: String?,
+      focus: MediaAttachment.ImageFocus?
+   ): MediaAttachment
+}
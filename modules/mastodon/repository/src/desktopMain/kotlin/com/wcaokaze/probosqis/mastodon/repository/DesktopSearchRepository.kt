@@ -0,0 +1,61 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+package com.wcaokaze.probosqis.mastodon.repository
+
+import com.wcaokaze.probosqis.mastodon.entity.Account
+import com.wcaokaze.probosqis.mastodon.entity.SearchResult
+import com.wcaokaze.probosqis.mastodon.entity.Token
+
+class DesktopSearchRepository : SearchRepository {
+   override fun search(
+      token: Token,
+      query: String,
+      type: SearchType?,
+      resolves: Boolean,
+      isOnlyFollowing: Boolean,
+      accountId: Account.Id?,
+      excludesUnreviewed: Boolean,
+      offset: Int?,
+      limit: Int?
+   ): SearchResult {
+      return loadSearchResult(
+         token,
+         query,
+         type?.raw,
+         resolves,
+         isOnlyFollowing,
+         accountId,
+         excludesUnreviewed,
+         offset?.toLong(),
+         limit?.toLong()
+      )
+   }
+
+   private external fun loadSearchResult(
+      token: Token,
+      query: String,
+      rawType: String?,
+      resolves: Boolean,
+      isOnlyFollowing: Boolean,
+      accountId: Account.Id?,
+      excludesUnreviewed: Boolean,
+      offset: Long?,
+      limit: Long?
+   ): SearchResult
+
+   external override fun resolve(token: Token, query: String): ResolvedObject?
+}
@@ -14,51 +14,81 @@
  * limitations under the License.
  */
 
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use ext_reqwest::transport::{ReqwestTransport, Transport};
 use foundation_entity::image_bytes::ImageBytes;
-use ext_reqwest::CLIENT;
-use mastodon_entity::account::Account;
+use isolang::Language;
+use mastodon_entity::account::{Account, AccountId, RelationalAccount};
+use mastodon_entity::page::{Page, PageCursor};
+use mastodon_entity::relationship::Relationship;
+use mastodon_entity::status::Status;
+use mastodon_entity::token::Token;
+use mastodon_webapi::entity::account::Account as ApiAccount;
+use mastodon_webapi::entity::relationship::Relationship as ApiRelationship;
+use mastodon_webapi::entity::status::Status as ApiStatus;
+use mastodon_webapi::api::accounts::AccountStatusesParams;
+use mastodon_webapi::pagination::{Page as ApiPage, PageParams};
 
 #[cfg(not(feature = "jvm"))]
 use std::marker::PhantomData;
 
 #[cfg(feature = "jvm")]
-use jni::JNIEnv;
+use jni::{JavaVM, JNIEnv};
 use panoptiqon::cache::Cache;
 
 pub struct AccountRepository<'jni> {
    #[cfg(not(feature = "jvm"))]
    env: PhantomData<&'jni ()>,
    #[cfg(feature = "jvm")]
-   env: JNIEnv<'jni>
+   env: JNIEnv<'jni>,
+   transport: Arc<dyn Transport>,
 }
 
 impl AccountRepository<'_> {
    #[cfg(not(feature = "jvm"))]
    pub fn new() -> AccountRepository<'static> {
       AccountRepository {
-         env: PhantomData
+         env: PhantomData,
+         transport: Arc::new(ReqwestTransport),
       }
    }
 
    #[cfg(feature = "jvm")]
    pub fn new<'jni>(env: &JNIEnv<'jni>) -> AccountRepository<'jni> {
       AccountRepository {
-         env: unsafe { env.unsafe_clone() }
+         env: unsafe { env.unsafe_clone() },
+         transport: Arc::new(ReqwestTransport),
       }
    }
 
+   /// アイコンのダウンロードは`token`のインスタンスのレート制限の残りが
+   /// 少なければ後回しにされる
    pub fn get_account_icon(
       &mut self,
+      token: &Token,
       account: Account
    ) -> anyhow::Result<Cache<ImageBytes>> {
       use crate::cache;
+      use ext_reqwest::error::{ResponseExt, WebApiError};
+      use ext_reqwest::rate_limit::{self, RateLimitedSend};
 
       let icon_url = account.avatar_image_url
          .ok_or(anyhow::anyhow!("no avatar image url"))?;
 
-      let bytes = CLIENT.get(icon_url.clone())
-         .send()?
-         .bytes()?;
+      // アイコンはタイムラインの表示に必須ではないため、
+      // レート制限の残りが少なければ後回しにする。
+      // アイコンはメディア用の別ホストにあることが多いので、
+      // インスタンスのレート制限の残りを参照する
+      let instance_url = token.instance.get().url.clone();
+      let bytes = rate_limit::non_urgent_for(&instance_url, Some(&token.access_token), || {
+         self.transport.get(icon_url.clone())
+            .send_rate_limited()?
+            .check_status()?
+            .bytes()
+            .map_err(WebApiError::from)
+      })?;
 
       let image_bytes = ImageBytes::new(icon_url, bytes);
 
@@ -68,54 +98,1647 @@ impl AccountRepository<'_> {
 
       Ok(icon_cache)
    }
+
+   pub fn get_account(
+      &mut self,
+      token: &Token,
+      account_id: &AccountId
+   ) -> anyhow::Result<Cache<Account>> {
+      use mastodon_webapi::api::accounts;
+
+      let api_account = accounts::get_account(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         Some(&token.access_token),
+         &account_id.local.0
+      )?;
+
+      self.save_account(token, api_account)
+   }
+
+   /// `acct`はローカルのアカウントの場合`username`、
+   /// リモートのアカウントの場合`username@domain`。
+   /// メンションからプロフィールを開く場合などに使う
+   pub fn lookup_account(
+      &mut self,
+      token: &Token,
+      acct: &str
+   ) -> anyhow::Result<Cache<Account>> {
+      use mastodon_webapi::api::accounts;
+
+      let acct = acct.strip_prefix('@').unwrap_or(acct);
+
+      let api_account = accounts::get_lookup(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         Some(&token.access_token),
+         acct
+      )?;
+
+      self.save_account(token, api_account)
+   }
+
+   /// `cursor`には前回取得したページの`next`または`prev`を渡す。
+   /// `None`の場合最新のページを取得する
+   pub fn get_account_statuses(
+      &mut self,
+      token: &Token,
+      account_id: &AccountId,
+      excludes_replies: bool,
+      excludes_boosts: bool,
+      is_only_media: bool,
+      is_only_pinned: bool,
+      tagged: Option<&str>,
+      cursor: Option<&PageCursor>,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Status>> {
+      use mastodon_webapi::api::accounts;
+
+      let api_page = accounts::get_statuses(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         Some(&token.access_token),
+         &account_id.local.0,
+         &AccountStatusesParams {
+            only_media: Some(is_only_media),
+            exclude_replies: Some(excludes_replies),
+            exclude_reblogs: Some(excludes_boosts),
+            pinned: Some(is_only_pinned),
+            tagged,
+         },
+         &page_params(cursor, limit)
+      )?;
+
+      self.convert_status_page(token, api_page)
+   }
+
+   /// `cursor`には前回取得したページの`next`または`prev`を渡す。
+   /// `None`の場合最新のページを取得する
+   pub fn get_followers(
+      &mut self,
+      token: &Token,
+      account_id: &AccountId,
+      cursor: Option<&PageCursor>,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Cache<Account>>> {
+      use mastodon_webapi::api::accounts;
+
+      let api_page = accounts::get_followers(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         Some(&token.access_token),
+         &account_id.local.0,
+         &page_params(cursor, limit)
+      )?;
+
+      self.convert_account_page(token, api_page)
+   }
+
+   /// `account_id`のアカウントがフォローしているアカウントを取得する。
+   ///
+   /// `cursor`には前回取得したページの`next`または`prev`を渡す。
+   /// `None`の場合最新のページを取得する
+   pub fn get_followees(
+      &mut self,
+      token: &Token,
+      account_id: &AccountId,
+      cursor: Option<&PageCursor>,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Cache<Account>>> {
+      use mastodon_webapi::api::accounts;
+
+      let api_page = accounts::get_following(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         Some(&token.access_token),
+         &account_id.local.0,
+         &page_params(cursor, limit)
+      )?;
+
+      self.convert_account_page(token, api_page)
+   }
+
+   pub fn get_relationships(
+      &mut self,
+      token: &Token,
+      account_ids: &[AccountId]
+   ) -> anyhow::Result<Vec<Cache<Relationship>>> {
+      use mastodon_webapi::api::accounts;
+
+      let ids: Vec<&str> = account_ids.iter().map(|id| id.local.0.as_str()).collect();
+
+      let api_relationships = accounts::get_relationships(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &ids,
+         /* with_suspended = */ None
+      )?;
+
+      api_relationships.into_iter()
+         .map(|r| self.save_relationship(token, r))
+         .collect()
+   }
+
+   /// * `shows_boosts` - フォローしたアカウントのブーストをホームタイムラインに
+   ///   表示するかどうか
+   /// * `notifies` - フォローしたアカウントが投稿したときに通知を受け取るかどうか
+   /// * `languages` - ホームタイムラインに表示する投稿の言語。空の場合すべての言語
+   pub fn follow(
+      &mut self,
+      token: &Token,
+      account_id: &AccountId,
+      shows_boosts: Option<bool>,
+      notifies: Option<bool>,
+      languages: &[Language]
+   ) -> anyhow::Result<Cache<Relationship>> {
+      use mastodon_webapi::api::accounts;
+
+      let languages: Vec<&str> = languages.iter()
+         .filter_map(|l| l.to_639_1())
+         .collect();
+
+      let api_relationship = accounts::post_follow(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &account_id.local.0,
+         shows_boosts,
+         notifies,
+         &languages
+      )?;
+
+      self.save_relationship(token, api_relationship)
+   }
+
+   pub fn unfollow(
+      &mut self,
+      token: &Token,
+      account_id: &AccountId
+   ) -> anyhow::Result<Cache<Relationship>> {
+      use mastodon_webapi::api::accounts;
+
+      let api_relationship = accounts::post_unfollow(
+         self.transport.as_ref(), &token.instance.get().url, &token.access_token, &account_id.local.0
+      )?;
+
+      self.save_relationship(token, api_relationship)
+   }
+
+   pub fn block(
+      &mut self,
+      token: &Token,
+      account_id: &AccountId
+   ) -> anyhow::Result<Cache<Relationship>> {
+      use mastodon_webapi::api::accounts;
+
+      let api_relationship = accounts::post_block(
+         self.transport.as_ref(), &token.instance.get().url, &token.access_token, &account_id.local.0
+      )?;
+
+      self.save_relationship(token, api_relationship)
+   }
+
+   pub fn unblock(
+      &mut self,
+      token: &Token,
+      account_id: &AccountId
+   ) -> anyhow::Result<Cache<Relationship>> {
+      use mastodon_webapi::api::accounts;
+
+      let api_relationship = accounts::post_unblock(
+         self.transport.as_ref(), &token.instance.get().url, &token.access_token, &account_id.local.0
+      )?;
+
+      self.save_relationship(token, api_relationship)
+   }
+
+   /// `duration`がNoneの場合無期限にミュートする。
+   ///
+   /// ミュートの期限はサーバーのミュート一覧から取得する。
+   /// 取得できない場合は端末の時刻から計算する。
+   ///
+   /// 更新されたRelationshipはキャッシュに保存される
+   pub fn mute(
+      &mut self,
+      token: &Token,
+      account: Cache<Account>,
+      mutes_notifications: bool,
+      duration: Option<Duration>
+   ) -> anyhow::Result<RelationalAccount> {
+      use mastodon_webapi::api::accounts;
+
+      let account_id = account.get().id.clone();
+
+      let api_relationship = accounts::post_mute(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &account_id.local.0,
+         Some(mutes_notifications),
+         duration.map(|d| d.as_secs())
+      )?;
+
+      let mute_expire_time = duration.map(|d|
+         self.fetch_mute_expire_time(token, &account_id)
+            .unwrap_or_else(|| Utc::now() + d)
+      );
+
+      self.save_relationship(token, api_relationship)?;
+
+      Ok(RelationalAccount { account, mute_expire_time })
+   }
+
+   /// ミュートした直後のアカウントはミュート一覧の先頭ページに含まれる
+   fn fetch_mute_expire_time(
+      &self,
+      token: &Token,
+      account_id: &AccountId
+   ) -> Option<DateTime<Utc>> {
+      use mastodon_webapi::api::mutes;
+
+      let page = mutes::get_mutes(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         None, None, None
+      ).ok()?;
+
+      let muted_account = page.items.into_iter()
+         .find(|a| a.id.as_deref() == Some(account_id.local.0.as_str()))?;
+
+      let mute_expires_at = muted_account.mute_expires_at?;
+      DateTime::parse_from_rfc3339(&mute_expires_at).ok()
+         .map(|time| time.to_utc())
+   }
+
+   pub fn unmute(
+      &mut self,
+      token: &Token,
+      account_id: &AccountId
+   ) -> anyhow::Result<Cache<Relationship>> {
+      use mastodon_webapi::api::accounts;
+
+      let api_relationship = accounts::post_unmute(
+         self.transport.as_ref(), &token.instance.get().url, &token.access_token, &account_id.local.0
+      )?;
+
+      self.save_relationship(token, api_relationship)
+   }
+
+   /// アカウントに自分だけが見られるメモを設定する。Noneの場合メモを削除する
+   pub fn set_note(
+      &mut self,
+      token: &Token,
+      account_id: &AccountId,
+      note: Option<&str>
+   ) -> anyhow::Result<Cache<Relationship>> {
+      use mastodon_webapi::api::accounts;
+
+      let api_relationship = accounts::post_note(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &account_id.local.0,
+         note
+      )?;
+
+      self.save_relationship(token, api_relationship)
+   }
+
+   fn save_account(
+      &mut self,
+      token: &Token,
+      api_account: ApiAccount
+   ) -> anyhow::Result<Cache<Account>> {
+      use crate::cache;
+      use crate::conversion;
+
+      let mut account_repo = cache::account::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let account = conversion::account::from_api(
+         #[cfg(feature = "jvm")] &mut self.env,
+         token.instance.clone(),
+         api_account,
+         &mut account_repo
+      )?;
+
+      Ok(account_repo.save(account))
+   }
+
+   fn convert_account_page(
+      &mut self,
+      token: &Token,
+      api_page: ApiPage<ApiAccount>
+   ) -> anyhow::Result<Page<Cache<Account>>> {
+      use crate::cache;
+      use crate::conversion;
+
+      let ApiPage { items, next, prev } = api_page;
+
+      let mut account_repo = cache::account::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut accounts = Vec::with_capacity(items.len());
+      for api_account in items {
+         let Ok(account) = conversion::account::from_api(
+            #[cfg(feature = "jvm")] &mut self.env,
+            token.instance.clone(),
+            api_account,
+            &mut account_repo
+         ) else { continue; };
+
+         accounts.push(account_repo.save(account));
+      }
+
+      let page = Page {
+         items: accounts,
+         next: next.map(conversion::page::from_api),
+         prev: prev.map(conversion::page::from_api),
+      };
+
+      Ok(page)
+   }
+
+   fn convert_status_page(
+      &mut self,
+      token: &Token,
+      api_page: ApiPage<ApiStatus>
+   ) -> anyhow::Result<Page<Status>> {
+      use crate::cache;
+      use crate::conversion;
+
+      let ApiPage { items, next, prev } = api_page;
+
+      let mut account_repo = cache::account::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut status_repo = cache::status::status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_status_repo = cache::status::no_credential_status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_poll_repo = cache::poll::no_credential_poll_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let statuses = items.into_iter()
+         .flat_map(|api_status|
+            conversion::status::from_api(
+               #[cfg(feature = "jvm")] &mut self.env,
+               token.instance.clone(),
+               api_status,
+               &mut account_repo,
+               &mut status_repo,
+               &mut no_credential_status_repo,
+               &mut no_credential_poll_repo
+            )
+         )
+         .collect();
+
+      let page = Page {
+         items: statuses,
+         next: next.map(conversion::page::from_api),
+         prev: prev.map(conversion::page::from_api),
+      };
+
+      Ok(page)
+   }
+
+   fn save_relationship(
+      &mut self,
+      token: &Token,
+      api_relationship: ApiRelationship
+   ) -> anyhow::Result<Cache<Relationship>> {
+      use crate::cache;
+      use crate::conversion;
+
+      let relationship = conversion::relationship::from_api(
+         token.instance.clone(),
+         api_relationship
+      )?;
+
+      let relationship_cache = cache::relationship::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?
+         .save(relationship);
+
+      Ok(relationship_cache)
+   }
+}
+
+/// [AccountRepository]の非同期版。
+/// タイムライン上でよく使う操作のみを持つ
+pub struct AsyncAccountRepository {
+   #[cfg(feature = "jvm")]
+   vm: JavaVM,
+   transport: Arc<dyn Transport>,
+}
+
+#[cfg(not(feature = "jvm"))]
+impl Default for AsyncAccountRepository {
+   fn default() -> AsyncAccountRepository {
+      AsyncAccountRepository::new()
+   }
+}
+
+impl AsyncAccountRepository {
+   #[cfg(not(feature = "jvm"))]
+   pub fn new() -> AsyncAccountRepository {
+      AsyncAccountRepository {
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   #[cfg(feature = "jvm")]
+   pub fn new(env: &JNIEnv) -> jni::errors::Result<AsyncAccountRepository> {
+      Ok(AsyncAccountRepository {
+         vm: env.get_java_vm()?,
+         transport: Arc::new(ReqwestTransport),
+      })
+   }
+
+   pub async fn get_account(
+      &self,
+      token: &Token,
+      account_id: &AccountId
+   ) -> anyhow::Result<Cache<Account>> {
+      use mastodon_webapi::api::accounts;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_account = accounts::get_account_async(
+         self.transport.as_ref(),
+         &instance_url,
+         Some(&token.access_token),
+         &account_id.local.0
+      ).await?;
+
+      self.with_repository(|repository| repository.save_account(token, api_account))
+   }
+
+   /// [AccountRepository::follow]の非同期版
+   pub async fn follow(
+      &self,
+      token: &Token,
+      account_id: &AccountId,
+      shows_boosts: Option<bool>,
+      notifies: Option<bool>,
+      languages: &[Language]
+   ) -> anyhow::Result<Cache<Relationship>> {
+      use mastodon_webapi::api::accounts;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let languages: Vec<&str> = languages.iter()
+         .filter_map(|l| l.to_639_1())
+         .collect();
+
+      let api_relationship = accounts::post_follow_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &account_id.local.0,
+         shows_boosts,
+         notifies,
+         &languages
+      ).await?;
+
+      self.with_repository(|repository| repository.save_relationship(token, api_relationship))
+   }
+
+   pub async fn unfollow(
+      &self,
+      token: &Token,
+      account_id: &AccountId
+   ) -> anyhow::Result<Cache<Relationship>> {
+      use mastodon_webapi::api::accounts;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_relationship = accounts::post_unfollow_async(
+         self.transport.as_ref(), &instance_url, &token.access_token, &account_id.local.0
+      ).await?;
+
+      self.with_repository(|repository| repository.save_relationship(token, api_relationship))
+   }
+
+   /// レスポンスを受け取ったスレッドでそのままキャッシュに変換する
+   fn with_repository<T>(
+      &self,
+      f: impl FnOnce(&mut AccountRepository) -> anyhow::Result<T>
+   ) -> anyhow::Result<T> {
+      #[cfg(not(feature = "jvm"))]
+      {
+         f(&mut AccountRepository::new())
+      }
+
+      #[cfg(feature = "jvm")]
+      {
+         // ランタイムのスレッドはJVMにアタッチしたままにし、
+         // ローカル参照はフレームごとに解放する
+         let mut env = self.vm.attach_current_thread_as_daemon()?;
+         env.with_local_frame(16, |env| f(&mut AccountRepository::new(env)))
+      }
+   }
+}
+
+fn page_params(
+   cursor: Option<&PageCursor>,
+   limit: Option<u32>
+) -> PageParams<'_> {
+   PageParams {
+      max_id: cursor.and_then(|c| c.max_id.as_deref()),
+      since_id: cursor.and_then(|c| c.since_id.as_deref()),
+      min_id: cursor.and_then(|c| c.min_id.as_deref()),
+      limit,
+   }
 }
 
 #[cfg(feature = "jvm")]
 mod jvm {
+   use std::future::Future;
+   use std::time::Duration;
    use jni::JNIEnv;
    use jni::objects::JObject;
+   use jni::sys::jboolean;
    use foundation_entity::jvm_types::JvmImage;
-   use mastodon_entity::jvm_types::JvmAccount;
-   use panoptiqon::jvm_types::{JvmCache, JvmNullable};
+   use mastodon_entity::account::{Account, AccountId};
+   use mastodon_entity::jvm_types::{
+      JvmAccount, JvmAccountId, JvmRelationalAccount, JvmRelationship, JvmStatus,
+      JvmToken,
+   };
+   use mastodon_entity::page::{Page, PageCursor};
+   use mastodon_entity::relationship::Relationship;
+   use mastodon_entity::token::Token;
+   use panoptiqon::cache::Cache;
+   use panoptiqon::jvm_type::JvmType;
+   use panoptiqon::jvm_types::{
+      JvmBoolean, JvmCache, JvmList, JvmLong, JvmNullable, JvmString,
+   };
+   use crate::cache;
+   use super::{AccountRepository, AsyncAccountRepository};
 
    #[no_mangle]
    extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_getAccountIcon<'local>(
       mut env: JNIEnv<'local>,
       _obj: JObject<'local>,
+      token: JvmToken<'local>,
       account: JvmAccount<'local>
    ) -> JvmCache<'local, JvmNullable<'local, JvmImage<'local>>> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
 
-      get_account_icon(&mut env, account)
-         .unwrap_or_throw_io_exception(&mut env)
+      get_account_icon(&mut env, token, account)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    #[no_mangle]
    extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_getAccountIcon<'local>(
       mut env: JNIEnv<'local>,
       _obj: JObject<'local>,
+      token: JvmToken<'local>,
       account: JvmAccount<'local>
    ) -> JvmCache<'local, JvmNullable<'local, JvmImage<'local>>> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
 
-      get_account_icon(&mut env, account)
-         .unwrap_or_throw_io_exception(&mut env)
+      get_account_icon(&mut env, token, account)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    fn get_account_icon<'local>(
       env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
       account: JvmAccount<'local>
    ) -> anyhow::Result<JvmCache<'local, JvmNullable<'local, JvmImage<'local>>>> {
-      use mastodon_entity::account::Account;
       use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
-      use crate::account_repository::AccountRepository;
 
+      let token = clone_token_from_jvm(env, &token)?;
       let account = Account::clone_from_jvm(env, &account);
 
       let mut account_repository = AccountRepository::new(env);
 
-      let icon = account_repository.get_account_icon(account)?;
+      let icon = account_repository.get_account_icon(&token, account)?;
       let icon = icon.clone_into_jvm(env);
       Ok(icon)
    }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_getAccount<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmCache<'local, JvmAccount<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_account(&mut env, token, account_id)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_getAccount<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmCache<'local, JvmAccount<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_account(&mut env, token, account_id)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_account<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> anyhow::Result<JvmCache<'local, JvmAccount<'local>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut account_repository = AccountRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let account_id = AccountId::clone_from_jvm(env, &account_id);
+
+      let account = account_repository.get_account(&token, &account_id)?;
+      Ok(account.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_lookupAccount<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      acct: JvmString<'local>
+   ) -> JvmCache<'local, JvmAccount<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      lookup_account(&mut env, token, acct)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_lookupAccount<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      acct: JvmString<'local>
+   ) -> JvmCache<'local, JvmAccount<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      lookup_account(&mut env, token, acct)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn lookup_account<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      acct: JvmString<'local>
+   ) -> anyhow::Result<JvmCache<'local, JvmAccount<'local>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut account_repository = AccountRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let acct = String::clone_from_jvm(env, &acct);
+
+      let account = account_repository.lookup_account(&token, &acct)?;
+      Ok(account.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_getAccountStatuses<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      excludes_replies: jboolean,
+      excludes_boosts: jboolean,
+      is_only_media: jboolean,
+      is_only_pinned: jboolean,
+      tagged: JvmNullable<'local, JvmString<'local>>
+   ) -> JvmList<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_account_statuses(
+         &mut env, token, account_id, excludes_replies, excludes_boosts,
+         is_only_media, is_only_pinned, tagged
+      ).unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_getAccountStatuses<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      excludes_replies: jboolean,
+      excludes_boosts: jboolean,
+      is_only_media: jboolean,
+      is_only_pinned: jboolean,
+      tagged: JvmNullable<'local, JvmString<'local>>
+   ) -> JvmList<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_account_statuses(
+         &mut env, token, account_id, excludes_replies, excludes_boosts,
+         is_only_media, is_only_pinned, tagged
+      ).unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_account_statuses<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      excludes_replies: jboolean,
+      excludes_boosts: jboolean,
+      is_only_media: jboolean,
+      is_only_pinned: jboolean,
+      tagged: JvmNullable<'local, JvmString<'local>>
+   ) -> anyhow::Result<JvmList<'local, JvmStatus<'local>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut account_repository = AccountRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let account_id = AccountId::clone_from_jvm(env, &account_id);
+      let tagged = Option::<String>::clone_from_jvm(env, &tagged);
+
+      let page = account_repository.get_account_statuses(
+         &token,
+         &account_id,
+         excludes_replies != 0,
+         excludes_boosts != 0,
+         is_only_media != 0,
+         is_only_pinned != 0,
+         tagged.as_deref(),
+         None,
+         None
+      )?;
+      Ok(page.items.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_getFollowers<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmList<'local, JvmCache<'local, JvmAccount<'local>>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_account_list(&mut env, token, account_id, AccountRepository::get_followers)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_getFollowers<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmList<'local, JvmCache<'local, JvmAccount<'local>>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_account_list(&mut env, token, account_id, AccountRepository::get_followers)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_getFollowees<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmList<'local, JvmCache<'local, JvmAccount<'local>>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_account_list(&mut env, token, account_id, AccountRepository::get_followees)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_getFollowees<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmList<'local, JvmCache<'local, JvmAccount<'local>>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_account_list(&mut env, token, account_id, AccountRepository::get_followees)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_account_list<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      action: impl FnOnce(&mut AccountRepository<'local>, &Token, &AccountId, Option<&PageCursor>, Option<u32>) -> anyhow::Result<Page<Cache<Account>>>
+   ) -> anyhow::Result<JvmList<'local, JvmCache<'local, JvmAccount<'local>>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut account_repository = AccountRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let account_id = AccountId::clone_from_jvm(env, &account_id);
+
+      let page = action(&mut account_repository, &token, &account_id, None, None)?;
+      Ok(page.items.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_getRelationships<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_ids: JvmList<'local, JvmAccountId<'local>>
+   ) -> JvmList<'local, JvmCache<'local, JvmRelationship<'local>>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_relationships(&mut env, token, account_ids)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_getRelationships<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_ids: JvmList<'local, JvmAccountId<'local>>
+   ) -> JvmList<'local, JvmCache<'local, JvmRelationship<'local>>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_relationships(&mut env, token, account_ids)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_relationships<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      account_ids: JvmList<'local, JvmAccountId<'local>>
+   ) -> anyhow::Result<JvmList<'local, JvmCache<'local, JvmRelationship<'local>>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut account_repository = AccountRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let account_ids = Vec::<AccountId>::clone_from_jvm(env, &account_ids);
+
+      let relationships = account_repository.get_relationships(&token, &account_ids)?;
+      Ok(relationships.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_follow<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      shows_boosts: JvmNullable<'local, JvmBoolean<'local>>,
+      notifies: JvmNullable<'local, JvmBoolean<'local>>,
+      languages: JvmList<'local, JvmString<'local>>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      follow(&mut env, token, account_id, shows_boosts, notifies, languages)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_follow<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      shows_boosts: JvmNullable<'local, JvmBoolean<'local>>,
+      notifies: JvmNullable<'local, JvmBoolean<'local>>,
+      languages: JvmList<'local, JvmString<'local>>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      follow(&mut env, token, account_id, shows_boosts, notifies, languages)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn follow<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      shows_boosts: JvmNullable<'local, JvmBoolean<'local>>,
+      notifies: JvmNullable<'local, JvmBoolean<'local>>,
+      languages: JvmList<'local, JvmString<'local>>
+   ) -> anyhow::Result<JvmCache<'local, JvmRelationship<'local>>> {
+      use isolang::Language;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut account_repository = AccountRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let account_id = AccountId::clone_from_jvm(env, &account_id);
+      let shows_boosts = Option::<bool>::clone_from_jvm(env, &shows_boosts);
+      let notifies = Option::<bool>::clone_from_jvm(env, &notifies);
+      let languages: Vec<Language> = Vec::<String>::clone_from_jvm(env, &languages)
+         .iter()
+         .filter_map(|code| Language::from_639_1(code))
+         .collect();
+
+      let relationship = account_repository.follow(
+         &token, &account_id, shows_boosts, notifies, &languages
+      )?;
+      Ok(relationship.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_unfollow<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, account_id, AccountRepository::unfollow)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_unfollow<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, account_id, AccountRepository::unfollow)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_block<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, account_id, AccountRepository::block)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_block<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, account_id, AccountRepository::block)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_unblock<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, account_id, AccountRepository::unblock)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_unblock<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, account_id, AccountRepository::unblock)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_unmute<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, account_id, AccountRepository::unmute)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_unmute<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, account_id, AccountRepository::unmute)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_postMute<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account: JvmCache<'local, JvmAccount<'local>>,
+      mutes_notifications: jboolean,
+      duration_seconds: JvmNullable<'local, JvmLong<'local>>
+   ) -> JvmRelationalAccount<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let duration = match mute_duration_from_jvm(&mut env, &duration_seconds) {
+         Ok(duration) => duration,
+         Err(message) => return throw_illegal_argument(&mut env, message),
+      };
+
+      mute(&mut env, token, account, mutes_notifications, duration)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_postMute<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account: JvmCache<'local, JvmAccount<'local>>,
+      mutes_notifications: jboolean,
+      duration_seconds: JvmNullable<'local, JvmLong<'local>>
+   ) -> JvmRelationalAccount<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let duration = match mute_duration_from_jvm(&mut env, &duration_seconds) {
+         Ok(duration) => duration,
+         Err(message) => return throw_illegal_argument(&mut env, message),
+      };
+
+      mute(&mut env, token, account, mutes_notifications, duration)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn mute<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      account: JvmCache<'local, JvmAccount<'local>>,
+      mutes_notifications: jboolean,
+      duration: Option<Duration>
+   ) -> anyhow::Result<JvmRelationalAccount<'local>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut account_repository = AccountRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let account = Cache::<Account>::clone_from_jvm(env, &account);
+
+      let relational_account = account_repository.mute(
+         &token, account, mutes_notifications != 0, duration
+      )?;
+      Ok(relational_account.clone_into_jvm(env))
+   }
+
+   /// サーバーは0秒を無期限として扱うため、0以下の値はエラーにする
+   fn mute_duration_from_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      duration_seconds: &JvmNullable<'local, JvmLong<'local>>
+   ) -> Result<Option<Duration>, String> {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      match Option::<i64>::clone_from_jvm(env, duration_seconds) {
+         None => Ok(None),
+         Some(seconds) if seconds > 0 => Ok(Some(Duration::from_secs(seconds as u64))),
+         Some(seconds) => Err(format!("mute duration must be positive: {seconds}s")),
+      }
+   }
+
+   fn throw_illegal_argument<'local, J>(
+      env: &mut JNIEnv<'local>,
+      message: String
+   ) -> J
+      where J: JvmType<'local> + 'local
+   {
+      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use jni::objects::JThrowable;
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+      use panoptiqon::jvm_types::JvmException;
+
+      Err(message).unwrap_or_throw_exception(env, |env, message| {
+         let message: JvmString = message.clone_into_jvm(env);
+         let exception = env.new_object(
+            "java/lang/IllegalArgumentException", "(Ljava/lang/String;)V",
+            &[message.j_string().into()]
+         ).unwrap();
+
+         JvmException::from_j_throwable(JThrowable::from(exception))
+      })
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_setNote<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      note: JvmNullable<'local, JvmString<'local>>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      set_note(&mut env, token, account_id, note)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_setNote<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      note: JvmNullable<'local, JvmString<'local>>
+   ) -> JvmCache<'local, JvmRelationship<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      set_note(&mut env, token, account_id, note)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn set_note<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      note: JvmNullable<'local, JvmString<'local>>
+   ) -> anyhow::Result<JvmCache<'local, JvmRelationship<'local>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut account_repository = AccountRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let account_id = AccountId::clone_from_jvm(env, &account_id);
+      let note = Option::<String>::clone_from_jvm(env, &note);
+
+      let relationship = account_repository.set_note(&token, &account_id, note.as_deref())?;
+      Ok(relationship.clone_into_jvm(env))
+   }
+
+   fn call<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      action: impl FnOnce(&mut AccountRepository<'local>, &Token, &AccountId) -> anyhow::Result<Cache<Relationship>>
+   ) -> anyhow::Result<JvmCache<'local, JvmRelationship<'local>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut account_repository = AccountRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let account_id = AccountId::clone_from_jvm(env, &account_id);
+      let relationship = action(&mut account_repository, &token, &account_id)?;
+      Ok(relationship.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_getAccountAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_account_action(
+         &mut env, token, account_id, callback,
+         |repository, token, account_id| async move {
+            repository.get_account(&token, &account_id).await
+         },
+         account_into_jvm
+      );
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_getAccountAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_account_action(
+         &mut env, token, account_id, callback,
+         |repository, token, account_id| async move {
+            repository.get_account(&token, &account_id).await
+         },
+         account_into_jvm
+      );
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_followAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      shows_boosts: JvmNullable<'local, JvmBoolean<'local>>,
+      notifies: JvmNullable<'local, JvmBoolean<'local>>,
+      languages: JvmList<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      follow_async(&mut env, token, account_id, shows_boosts, notifies, languages, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_followAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      shows_boosts: JvmNullable<'local, JvmBoolean<'local>>,
+      notifies: JvmNullable<'local, JvmBoolean<'local>>,
+      languages: JvmList<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      follow_async(&mut env, token, account_id, shows_boosts, notifies, languages, callback);
+   }
+
+   fn follow_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      shows_boosts: JvmNullable<'local, JvmBoolean<'local>>,
+      notifies: JvmNullable<'local, JvmBoolean<'local>>,
+      languages: JvmList<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      use isolang::Language;
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let shows_boosts = Option::<bool>::clone_from_jvm(env, &shows_boosts);
+      let notifies = Option::<bool>::clone_from_jvm(env, &notifies);
+      let languages: Vec<Language> = Vec::<String>::clone_from_jvm(env, &languages)
+         .iter()
+         .filter_map(|code| Language::from_639_1(code))
+         .collect();
+
+      spawn_account_action(
+         env, token, account_id, callback,
+         |repository, token, account_id| async move {
+            repository.follow(&token, &account_id, shows_boosts, notifies, &languages).await
+         },
+         relationship_into_jvm
+      );
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidAccountRepository_unfollowAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_account_action(
+         &mut env, token, account_id, callback,
+         |repository, token, account_id| async move {
+            repository.unfollow(&token, &account_id).await
+         },
+         relationship_into_jvm
+      );
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopAccountRepository_unfollowAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_account_action(
+         &mut env, token, account_id, callback,
+         |repository, token, account_id| async move {
+            repository.unfollow(&token, &account_id).await
+         },
+         relationship_into_jvm
+      );
+   }
+
+   /// `action`を共有のランタイムで実行し、結果を`into_jvm`で変換して`callback`に渡す。
+   /// JNI関数はリクエストの完了を待たずに戻る
+   fn spawn_account_action<'local, T, F, C>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      account_id: JvmAccountId<'local>,
+      callback: JObject<'local>,
+      action: impl FnOnce(AsyncAccountRepository, Token, AccountId) -> F,
+      into_jvm: C
+   )
+      where T: Send + 'static,
+            F: Future<Output = anyhow::Result<T>> + Send + 'static,
+            C: for<'a> FnOnce(&mut JNIEnv<'a>, T) -> JObject<'a> + Send + 'static
+   {
+      use ext_reqwest::callback::WebApiCallback;
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let result = (|| {
+         let repository = AsyncAccountRepository::new(env)?;
+         let token = clone_token_from_jvm(env, &token)?;
+         let account_id = AccountId::clone_from_jvm(env, &account_id);
+         let callback = WebApiCallback::new(env, &callback)?;
+
+         callback.spawn(action(repository, token, account_id), into_jvm);
+
+         anyhow::Ok(())
+      })();
+
+      if let Err(e) = result {
+         throw_web_api_exception(env, &e);
+      }
+   }
+
+   fn account_into_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      account: Cache<Account>
+   ) -> JObject<'local> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+
+      let account: JvmCache<JvmAccount> = account.clone_into_jvm(env);
+      account.into_j_object()
+   }
+
+   fn relationship_into_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      relationship: Cache<Relationship>
+   ) -> JObject<'local> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+
+      let relationship: JvmCache<JvmRelationship> = relationship.clone_into_jvm(env);
+      relationship.into_j_object()
+   }
+
+   fn clone_token_from_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      token: &JvmToken<'local>
+   ) -> anyhow::Result<Token> {
+      let instance = token.instance(env);
+      let instance = cache::instance::clone_from_jvm(env, &instance)?;
+      Ok(Token::clone_from_jvm(env, token, instance))
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use chrono::{TimeZone, Utc};
+   use mastodon_entity::account::{AccountId, AccountLocalId};
+   use mastodon_entity::token::Token;
+   use crate::fixture;
+   use super::AccountRepository;
+
+   fn token() -> Token {
+      fixture::token("https://relationship.example.com/", "4.3.0")
+   }
+
+   #[test]
+   fn delay_icon_while_instance_rate_limited() {
+      use std::time::{Duration, Instant};
+      use chrono::TimeDelta;
+
+      let token = Token {
+         access_token: "icon access token".to_string(),
+         ..token()
+      };
+      let reset_time = (Utc::now() + TimeDelta::seconds(2)).to_rfc3339();
+
+      let mut repository = AccountRepository::new();
+      repository.transport = fixture::replay_with(
+         "account/icon_rate_limited.json",
+         &[("{reset_time}", &reset_time)]
+      );
+
+      // インスタンスのレート制限を使い切った状態にする
+      let account = repository
+         .get_account(&token, &AccountId {
+            instance_url: token.instance.get().url.clone(),
+            local: AccountLocalId("icon account id".to_string()),
+         })
+         .unwrap();
+
+      let start = Instant::now();
+      let icon = repository
+         .get_account_icon(&token, account.get().clone())
+         .unwrap();
+
+      assert!(start.elapsed() >= Duration::from_secs(1));
+      assert_eq!(
+         "https://media.example.com/accounts/avatars/icon.png",
+         icon.get().url.as_str()
+      );
+   }
+
+   #[test]
+   fn follow_updates_relationship_cache() {
+      use isolang::Language;
+
+      let mut repository = AccountRepository::new();
+      repository.transport = fixture::replay("account/follow.json");
+      let token = token();
+      let account_id = AccountId {
+         instance_url: token.instance.get().url.clone(),
+         local: AccountLocalId("followee id".to_string()),
+      };
+
+      let relationships = repository
+         .get_relationships(&token, std::slice::from_ref(&account_id)).unwrap();
+      assert_eq!(1, relationships.len());
+      assert_eq!(account_id, relationships[0].get().id);
+      assert_eq!(Some(false), relationships[0].get().is_following);
+      assert_eq!(vec![Language::Jpn], relationships[0].get().languages);
+      assert_eq!(None, relationships[0].get().note);
+
+      let relationship = repository
+         .follow(&token, &account_id, Some(true), None, &[Language::Jpn])
+         .unwrap();
+      assert_eq!(Some(true), relationship.get().is_following);
+
+      // get_relationshipsで取得済みのキャッシュも更新される
+      assert_eq!(Some(true), relationships[0].get().is_following);
+   }
+
+   #[test]
+   fn mute_fills_expire_time() {
+      use std::time::Duration;
+      use mastodon_entity::account::Account;
+      use crate::cache;
+
+      let mut repository = AccountRepository::new();
+      repository.transport = fixture::replay("account/mute.json");
+      let token = token();
+
+      let account = Account {
+         instance: token.instance.clone(),
+         id: AccountId {
+            instance_url: token.instance.get().url.clone(),
+            local: AccountLocalId("muted account id".to_string()),
+         },
+         username: None,
+         acct: None,
+         url: None,
+         display_name: None,
+         profile_note: None,
+         avatar_image_url: None,
+         avatar_static_image_url: None,
+         header_image_url: None,
+         header_static_image_url: None,
+         is_locked: None,
+         profile_fields: vec![],
+         emojis_in_profile: vec![],
+         is_bot: None,
+         is_group: None,
+         is_discoverable: None,
+         is_noindex: None,
+         moved_to: None,
+         is_suspended: None,
+         is_limited: None,
+         created_time: None,
+         last_status_post_time: None,
+         status_count: None,
+         follower_count: None,
+         followee_count: None,
+      };
+      let account = cache::account::repo().write().unwrap().save(account);
+
+      // サーバーのミュート一覧の期限を使う
+      let relational_account = repository
+         .mute(&token, account.clone(), true, Some(Duration::from_secs(3600)))
+         .unwrap();
+
+      assert_eq!(account, relational_account.account);
+      assert_eq!(
+         Some(Utc.with_ymd_and_hms(2000, 1, 1, 1, 0, 0).unwrap()),
+         relational_account.mute_expire_time
+      );
+
+      let relationship = cache::relationship::repo().read().unwrap()
+         .load(account.get().id.clone())
+         .unwrap();
+      assert_eq!(Some(true), relationship.get().is_muting);
+
+      // ミュート一覧に含まれない場合は端末の時刻から計算する
+      let before = Utc::now();
+      let relational_account = repository
+         .mute(&token, account.clone(), true, Some(Duration::from_secs(3600)))
+         .unwrap();
+      let after = Utc::now();
+
+      let mute_expire_time = relational_account.mute_expire_time.unwrap();
+      assert!(before + Duration::from_secs(3600) <= mute_expire_time);
+      assert!(mute_expire_time <= after + Duration::from_secs(3600));
+
+      let relational_account = repository
+         .mute(&token, account, true, None)
+         .unwrap();
+      assert_eq!(None, relational_account.mute_expire_time);
+   }
+
+   #[test]
+   fn lookup_account_from_mention() {
+      let mut repository = AccountRepository::new();
+      repository.transport = fixture::replay("account/lookup.json");
+      let token = token();
+
+      let account = repository
+         .lookup_account(&token, "@username@remote.example.com")
+         .unwrap();
+
+      assert_eq!(
+         AccountId {
+            instance_url: token.instance.get().url.clone(),
+            local: AccountLocalId("remote account id".to_string()),
+         },
+         account.get().id
+      );
+      assert_eq!(Some("username@remote.example.com"), account.get().acct.as_deref());
+   }
+
+   #[test]
+   fn get_followers_with_cursor() {
+      use mastodon_entity::page::PageCursor;
+
+      let mut repository = AccountRepository::new();
+      repository.transport = fixture::replay("account/followers.json");
+      let token = token();
+      let account_id = AccountId {
+         instance_url: token.instance.get().url.clone(),
+         local: AccountLocalId("account id".to_string()),
+      };
+
+      let cursor = PageCursor {
+         max_id: Some("12345".to_string()),
+         since_id: None,
+         min_id: None,
+      };
+
+      let page = repository
+         .get_followers(&token, &account_id, Some(&cursor), Some(40))
+         .unwrap();
+
+      assert_eq!(
+         vec!["follower id 1", "follower id 2"],
+         page.items.iter().map(|a| a.get().id.local.0.clone()).collect::<Vec<_>>()
+      );
+      assert_eq!(
+         Some(PageCursor {
+            max_id: Some("12300".to_string()),
+            since_id: None,
+            min_id: None,
+         }),
+         page.next
+      );
+      assert_eq!(None, page.prev);
+   }
+
+   #[test]
+   fn follow_async_updates_relationship_cache() {
+      use ext_reqwest::runtime::RUNTIME;
+      use isolang::Language;
+      use super::AsyncAccountRepository;
+
+      let transport = fixture::replay("account/follow.json");
+
+      let mut repository = AccountRepository::new();
+      repository.transport = transport.clone();
+      let mut async_repository = AsyncAccountRepository::new();
+      async_repository.transport = transport;
+
+      let token = token();
+      let account_id = AccountId {
+         instance_url: token.instance.get().url.clone(),
+         local: AccountLocalId("followee id".to_string()),
+      };
+
+      let relationships = repository
+         .get_relationships(&token, std::slice::from_ref(&account_id)).unwrap();
+      assert_eq!(Some(false), relationships[0].get().is_following);
+
+      let relationship = RUNTIME
+         .block_on(async_repository.follow(&token, &account_id, Some(true), None, &[Language::Jpn]))
+         .unwrap();
+      assert_eq!(Some(true), relationship.get().is_following);
+
+      // 同期版で取得済みのキャッシュも更新される
+      assert_eq!(Some(true), relationships[0].get().is_following);
+   }
 }
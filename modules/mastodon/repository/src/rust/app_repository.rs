@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use std::sync::Arc;
+use ext_reqwest::transport::{ReqwestTransport, Transport};
 use mastodon_entity::account::CredentialAccount;
 use mastodon_entity::application::Application;
 use mastodon_entity::instance::Instance;
@@ -31,7 +33,8 @@ pub struct AppRepository<'jni> {
    #[cfg(not(feature = "jvm"))]
    env: PhantomData<&'jni ()>,
    #[cfg(feature = "jvm")]
-   env: JNIEnv<'jni>
+   env: JNIEnv<'jni>,
+   transport: Arc<dyn Transport>,
 }
 
 impl AppRepository<'_> {
@@ -41,14 +44,16 @@ impl AppRepository<'_> {
    #[cfg(not(feature = "jvm"))]
    pub fn new() -> AppRepository<'static> {
       AppRepository {
-         env: PhantomData
+         env: PhantomData,
+         transport: Arc::new(ReqwestTransport),
       }
    }
 
    #[cfg(feature = "jvm")]
    pub fn new<'jni>(env: &JNIEnv<'jni>) -> AppRepository<'jni> {
       AppRepository {
-         env: unsafe { env.unsafe_clone() }
+         env: unsafe { env.unsafe_clone() },
+         transport: Arc::new(ReqwestTransport),
       }
    }
 
@@ -57,7 +62,6 @@ impl AppRepository<'_> {
       instance: Instance,
       redirect_uri: &str
    ) -> anyhow::Result<Application> {
-      use ext_reqwest::CLIENT;
       use mastodon_webapi::api::apps;
       use semver::Version;
       use crate::cache;
@@ -68,7 +72,7 @@ impl AppRepository<'_> {
 
       let api_application  = if instance_version < Version::new(4, 3, 0) {
          apps::post_apps_v0(
-            &CLIENT, &instance.url,
+            self.transport.as_ref(), &instance.url,
             /* client_name = */ "Probosqis",
             /* redirect_uris = */ redirect_uri,
             /* scopes = */ Some("read write push"),
@@ -76,7 +80,7 @@ impl AppRepository<'_> {
          )?
       } else {
          apps::post_apps_v4_3_0(
-            &CLIENT, &instance.url,
+            self.transport.as_ref(), &instance.url,
             /* client_name = */ "Probosqis",
             /* redirect_uris = */ &[
                Self::ANDROID_REDIRECT_URI,
@@ -128,12 +132,11 @@ impl AppRepository<'_> {
       client_secret: &str,
       redirect_uri: &str
    ) -> anyhow::Result<Token> {
-      use ext_reqwest::CLIENT;
       use mastodon_webapi::api::oauth;
       use crate::conversion;
 
       let api_token = oauth::post_token(
-         &CLIENT,
+         self.transport.as_ref(),
          /* instance_base_url */ &instance_cache.get().url,
          /* grant_type = */ "authorization_code",
          /* code = */ Some(code),
@@ -165,13 +168,12 @@ impl AppRepository<'_> {
       instance: &Cache<Instance>,
       access_token: &str
    ) -> anyhow::Result<Cache<CredentialAccount>> {
-      use ext_reqwest::CLIENT;
       use mastodon_webapi::api::accounts;
       use crate::cache;
       use crate::conversion;
 
       let api_credential_account = accounts::get_verify_credentials(
-         &CLIENT,
+         self.transport.as_ref(),
          /* instance_base_url = */ &instance.get().url,
          access_token
       )?;
@@ -206,11 +208,11 @@ mod jvm {
       _obj: JObject<'local>,
       instance: JvmInstance<'local>
    ) -> JvmApplication<'local> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
       use super::AppRepository;
 
       post_app(&mut env, instance, AppRepository::DESKTOP_REDIRECT_URI)
-         .unwrap_or_throw_io_exception(&mut env)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    #[no_mangle]
@@ -219,11 +221,11 @@ mod jvm {
       _obj: JObject<'local>,
       instance: JvmInstance<'local>
    ) -> JvmApplication<'local> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
       use super::AppRepository;
 
       post_app(&mut env, instance, AppRepository::ANDROID_REDIRECT_URI)
-         .unwrap_or_throw_io_exception(&mut env)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    fn post_app<'local>(
@@ -248,11 +250,11 @@ mod jvm {
       instance: JvmCache<'local, JvmInstance<'local>>,
       client_id: JvmString<'local>
    ) -> JvmString<'local> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
       use super::AppRepository;
 
       get_authorize_url(&mut env, instance, client_id, AppRepository::DESKTOP_REDIRECT_URI)
-         .unwrap_or_throw_io_exception(&mut env)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    #[no_mangle]
@@ -262,11 +264,11 @@ mod jvm {
       instance: JvmCache<'local, JvmInstance<'local>>,
       client_id: JvmString<'local>
    ) -> JvmString<'local> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
       use super::AppRepository;
 
       get_authorize_url(&mut env, instance, client_id, AppRepository::ANDROID_REDIRECT_URI)
-         .unwrap_or_throw_io_exception(&mut env)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    fn get_authorize_url<'local>(
@@ -300,11 +302,11 @@ mod jvm {
       client_id: JvmString<'local>,
       client_secret: JvmString<'local>
    ) -> JvmToken<'local> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
       use super::AppRepository;
 
       get_token(&mut env, instance, code, client_id, client_secret, AppRepository::DESKTOP_REDIRECT_URI)
-         .unwrap_or_throw_io_exception(&mut env)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    #[no_mangle]
@@ -316,11 +318,11 @@ mod jvm {
       client_id: JvmString<'local>,
       client_secret: JvmString<'local>
    ) -> JvmToken<'local> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
       use super::AppRepository;
 
       get_token(&mut env, instance, code, client_id, client_secret, AppRepository::ANDROID_REDIRECT_URI)
-         .unwrap_or_throw_io_exception(&mut env)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    fn get_token<'local>(
@@ -356,10 +358,10 @@ mod jvm {
       _obj: JObject<'local>,
       token: JvmToken<'local>
    ) -> JvmCache<'local, JvmCredentialAccount<'local>> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
 
       get_credential_account(&mut env, token)
-         .unwrap_or_throw_io_exception(&mut env)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    #[no_mangle]
@@ -368,10 +370,10 @@ mod jvm {
       _obj: JObject<'local>,
       token: JvmToken<'local>
    ) -> JvmCache<'local, JvmCredentialAccount<'local>> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
 
       get_credential_account(&mut env, token)
-         .unwrap_or_throw_io_exception(&mut env)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    fn get_credential_account<'local>(
@@ -395,51 +397,19 @@ mod jvm {
 
 #[cfg(all(test, not(feature = "jvm")))]
 mod test {
-   use mastodon_webapi::entity::application::Application;
+   use crate::fixture;
    use super::AppRepository;
 
-   fn dummy_application() -> Application {
-      Application {
-         name: "app name".to_string(),
-         website: None,
-         scopes: None,
-         redirect_uris: None,
-         redirect_uri: None,
-         vapid_key: None,
-         client_id: None,
-         client_secret: None,
-         client_secret_expires_at: None,
-      }
-   }
-
    #[test]
    fn switch_function_by_instance_version() {
-      use std::sync::{Arc, Mutex};
       use chrono::DateTime;
       use mastodon_entity::instance::Instance;
-      use mastodon_webapi::api::apps;
       use url::Url;
 
+      // 4.3.0未満ではredirect_uris、4.3.0以降ではredirect_uris[]として
+      // 送信されることをfixtureのフォームで確かめる
       let mut repository = AppRepository::new();
-
-      let v0_called     = Arc::new(Mutex::new(false));
-      let v4_3_0_called = Arc::new(Mutex::new(false));
-
-      {
-         let v0_called = v0_called.clone();
-         apps::inject_post_apps_v0(move |_, _, _, _, _, _| {
-            *v0_called.lock().unwrap() = true;
-            Ok(dummy_application())
-         });
-      }
-
-      {
-         let v4_3_0_called = v4_3_0_called.clone();
-         apps::inject_post_apps_v4_3_0(move |_, _, _, _, _, _| {
-            *v4_3_0_called.lock().unwrap() = true;
-            Ok(dummy_application())
-         });
-      }
+      repository.transport = fixture::replay("app/post_app_by_version.json");
 
       let instance = |version: &'static str| Instance {
          url: Url::parse("https://example.com/").unwrap(),
@@ -447,61 +417,10 @@ mod test {
          version_checked_time: DateTime::UNIX_EPOCH
       };
 
-      {
-         let _application = repository
-            .post_app(instance("4.1.0"), AppRepository::ANDROID_REDIRECT_URI);
-         assert_eq!(true,  *v0_called    .lock().unwrap());
-         assert_eq!(false, *v4_3_0_called.lock().unwrap());
-      }
-
-      *v0_called    .lock().unwrap() = false;
-      *v4_3_0_called.lock().unwrap() = false;
-
-      {
-         let _application = repository
-            .post_app(instance("4.2.0"), AppRepository::ANDROID_REDIRECT_URI);
-         assert_eq!(true,  *v0_called    .lock().unwrap());
-         assert_eq!(false, *v4_3_0_called.lock().unwrap());
-      }
-
-      *v0_called    .lock().unwrap() = false;
-      *v4_3_0_called.lock().unwrap() = false;
-
-      {
-         let _application = repository
-            .post_app(instance("4.2.9"), AppRepository::ANDROID_REDIRECT_URI);
-         assert_eq!(true,  *v0_called    .lock().unwrap());
-         assert_eq!(false, *v4_3_0_called.lock().unwrap());
-      }
-
-      *v0_called    .lock().unwrap() = false;
-      *v4_3_0_called.lock().unwrap() = false;
-
-      {
-         let _application = repository
-            .post_app(instance("4.3.0"), AppRepository::ANDROID_REDIRECT_URI);
-         assert_eq!(false, *v0_called    .lock().unwrap());
-         assert_eq!(true,  *v4_3_0_called.lock().unwrap());
-      }
-
-      *v0_called    .lock().unwrap() = false;
-      *v4_3_0_called.lock().unwrap() = false;
-
-      {
-         let _application = repository
-            .post_app(instance("4.3.1"), AppRepository::ANDROID_REDIRECT_URI);
-         assert_eq!(false, *v0_called    .lock().unwrap());
-         assert_eq!(true,  *v4_3_0_called.lock().unwrap());
-      }
-
-      *v0_called    .lock().unwrap() = false;
-      *v4_3_0_called.lock().unwrap() = false;
-
-      {
-         let _application = repository
-            .post_app(instance("4.4.0"), AppRepository::ANDROID_REDIRECT_URI);
-         assert_eq!(false, *v0_called    .lock().unwrap());
-         assert_eq!(true,  *v4_3_0_called.lock().unwrap());
+      for version in ["4.1.0", "4.2.0", "4.2.9", "4.3.0", "4.3.1", "4.4.0"] {
+         repository
+            .post_app(instance(version), AppRepository::ANDROID_REDIRECT_URI)
+            .unwrap();
       }
    }
 
@@ -509,10 +428,10 @@ mod test {
    fn account_conversion_uses_newer_redirect_uris_field() {
       use chrono::DateTime;
       use mastodon_entity::instance::Instance;
-      use mastodon_webapi::api::apps;
       use url::Url;
 
       let mut repository = AppRepository::new();
+      repository.transport = fixture::replay("app/post_app_redirect_uris.json");
 
       let instance = Instance {
          url: Url::parse("https://example.com/").unwrap(),
@@ -520,22 +439,6 @@ mod test {
          version_checked_time: DateTime::UNIX_EPOCH,
       };
 
-      apps::inject_post_apps_v0(|_, _, _, _, _, _| {
-         Ok(
-            Application {
-               redirect_uris: Some(vec![
-                  "https://example.com/callback/newer1".to_string(),
-                  "https://example.com/callback/newer2".to_string(),
-               ]),
-               redirect_uri: Some(
-                  "https://example.com/callback/older1\n\
-                   https://example.com/callback/older2".to_string()
-               ),
-               ..dummy_application()
-            }
-         )
-      });
-
       let application = repository
          .post_app(instance.clone(), "https://example.com/callback");
 
@@ -547,19 +450,6 @@ mod test {
          application.unwrap().redirect_uris
       );
 
-      apps::inject_post_apps_v0(|_, _, _, _, _, _| {
-         Ok(
-            Application {
-               redirect_uris: None,
-               redirect_uri: Some(
-                  "https://example.com/callback/older1\n\
-                   https://example.com/callback/older2".to_string()
-               ),
-               ..dummy_application()
-            }
-         )
-      });
-
       let application = repository
          .post_app(instance.clone(), "https://example.com/callback");
 
@@ -576,16 +466,11 @@ mod test {
    fn authorize_url() {
       use chrono::{TimeZone, Utc};
       use mastodon_entity::instance::Instance;
-      use mastodon_webapi::api::oauth;
       use url::Url;
       use crate::cache;
 
       let repository = AppRepository::new();
 
-      oauth::inject_get_authorize_url(|instance_base_url, _, _, _, _, _, _|
-         Ok(instance_base_url.join("oauth/authorize")?)
-      );
-
       let instance = Instance {
          url: "https://example.com/".parse().unwrap(),
          version: "0.0.0".to_string(),
@@ -601,7 +486,13 @@ mod test {
       ).unwrap();
 
       assert_eq!(
-         Url::parse("https://example.com/oauth/authorize").unwrap(),
+         Url::parse(
+            "https://example.com/oauth/authorize\
+               ?response_type=code\
+               &client_id=client_id\
+               &redirect_uri=redirect_uri\
+               &scope=read+write+push"
+         ).unwrap(),
          authorize_url
       );
    }
@@ -618,120 +509,10 @@ mod test {
       use mastodon_entity::instance::Instance;
       use mastodon_entity::status::StatusVisibility;
       use mastodon_entity::token::Token;
-      use mastodon_webapi::api::accounts;
-      use mastodon_webapi::api::oauth;
-      use mastodon_webapi::entity::account::{
-         Account as ApiAccount,
-         AccountField as ApiAccountField,
-         CredentialAccountSource as ApiCredentialAccountSource,
-      };
-      use mastodon_webapi::entity::custom_emoji::CustomEmoji as ApiCustomEmoji;
-      use mastodon_webapi::entity::token::Token as ApiToken;
       use crate::cache;
 
       let mut repository = AppRepository::new();
-
-      oauth::inject_post_token(|_, _, _, _, _, _, _, _|
-         Ok(
-            ApiToken {
-               access_token: "access_token".to_string(),
-               token_type: "token_type".to_string(),
-               scope: "scope".to_string(),
-               created_at: 0,
-            }
-         )
-      );
-
-      accounts::inject_get_verify_credentials(|_, _, _|
-         Ok(
-            ApiAccount {
-               id: Some("account id".to_string()),
-               username: Some("username".to_string()),
-               acct: Some("acct".to_string()),
-               url: Some("https://example.com/url".to_string()),
-               display_name: Some("display_name".to_string()),
-               note: Some("note".to_string()),
-               avatar: Some("https://example.com/avatar/image/url".to_string()),
-               avatar_static: Some("https://example.com/avatar/static/image/url".to_string()),
-               header: Some("https://example.com/header/image/url".to_string()),
-               header_static: Some("https://example.com/header/static/image/url".to_string()),
-               locked: Some(false),
-               fields: Some(vec![
-                  ApiAccountField {
-                     name: Some("name".to_string()),
-                     value: Some("value".to_string()),
-                     verified_at: Some("2000-01-02T00:00:00.000Z".to_string()),
-                  },
-               ]),
-               emojis: Some(vec![
-                  ApiCustomEmoji {
-                     shortcode: Some("shortcode".to_string()),
-                     url: Some("https://example.com/custom/emoji/url".to_string()),
-                     static_url: Some("https://example.com/custom/emoji/static/url".to_string()),
-                     visible_in_picker: Some(false),
-                     category: Some("category".to_string()),
-                  },
-               ]),
-               bot: Some(true),
-               group: Some(false),
-               discoverable: Some(true),
-               noindex: Some(false),
-               moved: Some(Box::new(ApiAccount {
-                  id: Some("moved account id".to_string()),
-                  username: None,
-                  acct: None,
-                  url: None,
-                  display_name: None,
-                  note: None,
-                  avatar: None,
-                  avatar_static: None,
-                  header: None,
-                  header_static: None,
-                  locked: None,
-                  fields: None,
-                  emojis: None,
-                  bot: None,
-                  group: None,
-                  discoverable: None,
-                  noindex: None,
-                  moved: None,
-                  suspended: None,
-                  limited: None,
-                  created_at: None,
-                  last_status_at: None,
-                  statuses_count: None,
-                  followers_count: None,
-                  following_count: None,
-                  source: None,
-                  role: None,
-                  mute_expires_at: None,
-               })),
-               suspended: Some(false),
-               limited: Some(false),
-               created_at: Some("2000-01-02T00:00:00.000Z".to_string()),
-               last_status_at: Some("2000-01-02T00:00:00.000Z".to_string()),
-               statuses_count: Some(10000),
-               followers_count: Some(100),
-               following_count: Some(1000),
-               source: Some(ApiCredentialAccountSource {
-                  note: Some("note".to_string()),
-                  fields: Some(vec![
-                     ApiAccountField {
-                        name: Some("name".to_string()),
-                        value: Some("value".to_string()),
-                        verified_at: Some("2000-01-02T00:00:00.000Z".to_string()),
-                     },
-                  ]),
-                  privacy: Some("public".to_string()),
-                  sensitive: Some(false),
-                  language: Some("ja".to_string()),
-                  follow_requests_count: Some(1),
-               }),
-               role: None,
-               mute_expires_at: None,
-            }
-         )
-      );
+      repository.transport = fixture::replay("app/token.json");
 
       let instance = Instance {
          url: "https://example.com/".parse().unwrap(),
@@ -857,7 +638,7 @@ mod test {
                            verified_time: Some(Utc.with_ymd_and_hms(2000, 1, 2, 0, 0, 0).unwrap()),
                         },
                      ],
-                     default_post_visibility: Some(StatusVisibility("public".to_string())),
+                     default_post_visibility: Some(StatusVisibility::Public),
                      default_post_sensitivity: Some(false),
                      default_post_language: Some(Language::from_639_1("ja").unwrap()),
                      follow_request_count: Some(1),
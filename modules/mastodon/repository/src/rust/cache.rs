@@ -14,19 +14,35 @@
  * limitations under the License.
  */
 
+/// `ext_panoptiqon::cache_reference`でキーとしてシリアライズされたCacheを
+/// デシリアライズする際にこれらのRepositoryから探すようにする
+pub(crate) fn register_cache_references() {
+   use ext_panoptiqon::cache_reference::register;
+
+   register(instance::repo());
+   register(account::repo());
+   register(account::credential_account_repo());
+   register(poll::no_credential_poll_repo());
+   register(status::no_credential_status_repo());
+   register(status::status_repo());
+}
+
 pub mod instance {
+   use ext_panoptiqon::persistence::Persistence;
    use ext_panoptiqon::repository_holder::RepositoryHolder;
    use mastodon_entity::instance::Instance;
-   use panoptiqon::cache::Cache;
    
    #[cfg(feature = "jvm")]
    use {
       jni::JNIEnv,
       mastodon_entity::jvm_types::JvmInstance,
+      panoptiqon::cache::Cache,
       panoptiqon::jvm_types::JvmCache,
    };
 
-   static REPO: RepositoryHolder<Instance> = RepositoryHolder::new();
+   static REPO: RepositoryHolder<Instance> = RepositoryHolder::with_persistence(
+      Persistence::json("instances", 1024 * 1024)
+   );
 
    pub fn repo() -> &'static RepositoryHolder<Instance> {
       &REPO
@@ -64,12 +80,15 @@ pub mod instance {
 }
 
 pub mod account {
-   use ext_panoptiqon::repository_holder::RepositoryHolder;
+   use ext_panoptiqon::persistence::Persistence;
+   use ext_panoptiqon::repository_holder::{RepositoryHolder, RepositoryWriteGuard};
    use mastodon_entity::account::{Account, CredentialAccount};
 
-   pub type Repository = panoptiqon::repository::Repository<Account>;
+   pub type Repository<'a> = RepositoryWriteGuard<'a, Account>;
 
-   static REPO: RepositoryHolder<Account> = RepositoryHolder::new();
+   static REPO: RepositoryHolder<Account> = RepositoryHolder::with_persistence(
+      Persistence::json("accounts", 16 * 1024 * 1024)
+   );
    
    static CREDENTIAL_ACCOUNT_REPO: RepositoryHolder<CredentialAccount>
       = RepositoryHolder::new();
@@ -84,44 +103,127 @@ pub mod account {
 }
 
 pub mod account_icon {
+   use bytes::Bytes;
    use foundation_entity::image_bytes::ImageBytes;
+   use ext_panoptiqon::persistence::Persistence;
    use ext_panoptiqon::repository_holder::RepositoryHolder;
+   use url::Url;
 
-   static REPO: RepositoryHolder<ImageBytes> = RepositoryHolder::new();
+   static REPO: RepositoryHolder<ImageBytes> = RepositoryHolder::with_persistence(
+      Persistence::new(
+         "account_icons",
+         64 * 1024 * 1024,
+         encode_key,
+         encode,
+         decode,
+      )
+   );
 
    pub fn repo() -> &'static RepositoryHolder<ImageBytes> {
       &REPO
    }
+
+   fn encode_key(url: &Url) -> anyhow::Result<String> {
+      Ok(url.to_string())
+   }
+
+   /// JSONでは画像のバイト列が数値の配列になってしまうため、
+   /// `[URL長 u32][URL][画像のバイト列]`の形式で保存する
+   fn encode(image: &ImageBytes) -> anyhow::Result<Vec<u8>> {
+      let url = image.url.as_str();
+      let url_len = u32::try_from(url.len())?;
+      let image_bytes = &image.image_bytes.0;
+
+      let mut bytes = Vec::with_capacity(4 + url.len() + image_bytes.len());
+      bytes.extend_from_slice(&url_len.to_le_bytes());
+      bytes.extend_from_slice(url.as_bytes());
+      bytes.extend_from_slice(image_bytes);
+      Ok(bytes)
+   }
+
+   fn decode(bytes: &[u8]) -> anyhow::Result<ImageBytes> {
+      let url_len = bytes.get(..4)
+         .ok_or_else(|| anyhow::anyhow!("unexpected end of image bytes"))?;
+      let url_len = u32::from_le_bytes(url_len.try_into()?) as usize;
+
+      let url = bytes.get(4..4 + url_len)
+         .ok_or_else(|| anyhow::anyhow!("unexpected end of image bytes"))?;
+      let url = Url::parse(std::str::from_utf8(url)?)?;
+
+      let image_bytes = Bytes::copy_from_slice(&bytes[4 + url_len..]);
+      Ok(ImageBytes::new(url, image_bytes))
+   }
 }
 
-pub mod poll {
+pub mod custom_emoji {
+   use ext_panoptiqon::repository_holder::RepositoryHolder;
+   use mastodon_entity::custom_emoji::CustomEmojiCatalogue;
+
+   static REPO: RepositoryHolder<CustomEmojiCatalogue> = RepositoryHolder::new();
+
+   pub fn repo() -> &'static RepositoryHolder<CustomEmojiCatalogue> {
+      &REPO
+   }
+}
+
+pub mod filter {
    use ext_panoptiqon::repository_holder::RepositoryHolder;
+   use mastodon_entity::filter::FilterList;
+
+   static REPO: RepositoryHolder<FilterList> = RepositoryHolder::new();
+
+   pub fn repo() -> &'static RepositoryHolder<FilterList> {
+      &REPO
+   }
+}
+
+pub mod poll {
+   use ext_panoptiqon::persistence::Persistence;
+   use ext_panoptiqon::repository_holder::{RepositoryHolder, RepositoryWriteGuard};
    use mastodon_entity::poll::NoCredentialPoll;
 
-   pub type NoCredentialPollRepository
-      = panoptiqon::repository::Repository<NoCredentialPoll>;
+   pub type NoCredentialPollRepository<'a>
+      = RepositoryWriteGuard<'a, NoCredentialPoll>;
 
    static NO_CREDENTIAL_POLL_REPO: RepositoryHolder<NoCredentialPoll>
-      = RepositoryHolder::new();
+      = RepositoryHolder::with_persistence(
+         Persistence::json("no_credential_polls", 4 * 1024 * 1024)
+      );
 
    pub fn no_credential_poll_repo() -> &'static RepositoryHolder<NoCredentialPoll> {
       &NO_CREDENTIAL_POLL_REPO
    }
 }
 
-pub mod status {
+pub mod relationship {
    use ext_panoptiqon::repository_holder::RepositoryHolder;
+   use mastodon_entity::relationship::Relationship;
+
+   static REPO: RepositoryHolder<Relationship> = RepositoryHolder::new();
+
+   pub fn repo() -> &'static RepositoryHolder<Relationship> {
+      &REPO
+   }
+}
+
+pub mod status {
+   use ext_panoptiqon::persistence::Persistence;
+   use ext_panoptiqon::repository_holder::{RepositoryHolder, RepositoryWriteGuard};
    use mastodon_entity::status::{NoCredentialStatus, Status};
 
-   pub type StatusRepository = panoptiqon::repository::Repository<Status>;
+   pub type StatusRepository<'a> = RepositoryWriteGuard<'a, Status>;
 
-   pub type NoCredentialStatusRepository
-      = panoptiqon::repository::Repository<NoCredentialStatus>;
+   pub type NoCredentialStatusRepository<'a>
+      = RepositoryWriteGuard<'a, NoCredentialStatus>;
 
-   static REPO: RepositoryHolder<Status> = RepositoryHolder::new();
+   static REPO: RepositoryHolder<Status> = RepositoryHolder::with_persistence(
+      Persistence::json("statuses", 32 * 1024 * 1024)
+   );
 
    static NO_CREDENTIAL_REPO: RepositoryHolder<NoCredentialStatus>
-      = RepositoryHolder::new();
+      = RepositoryHolder::with_persistence(
+         Persistence::json("no_credential_statuses", 64 * 1024 * 1024)
+      );
 
    pub fn status_repo() -> &'static RepositoryHolder<Status> {
       &REPO
@@ -132,3 +234,75 @@ pub mod status {
       &NO_CREDENTIAL_REPO
    }
 }
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use chrono::{TimeZone, Utc};
+   use mastodon_entity::instance::Instance;
+   use mastodon_entity::status::Status;
+   use mastodon_webapi::entity::status::Status as ApiStatus;
+   use serde_json::json;
+   use url::Url;
+   use crate::conversion;
+
+   #[test]
+   fn cache_references_are_serialized_as_keys() {
+      let instance = Instance {
+         url: Url::parse("https://cache.example.com/").unwrap(),
+         version: "4.3.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+      let instance_cache = super::instance::repo().write().unwrap().save(instance);
+
+      let api_status: ApiStatus = serde_json::from_value(json!({
+         "id": "1",
+         "content": "<p>boost</p>",
+         "account": { "id": "10", "username": "wcaokaze" },
+         "reblog": {
+            "id": "0",
+            "content": "<p>content</p>",
+            "account": { "id": "11", "username": "probosqis" },
+         },
+      })).unwrap();
+
+      let status = {
+         let mut account_repo = super::account::repo().write().unwrap();
+         let mut status_repo = super::status::status_repo().write().unwrap();
+         let mut no_credential_status_repo
+            = super::status::no_credential_status_repo().write().unwrap();
+         let mut no_credential_poll_repo
+            = super::poll::no_credential_poll_repo().write().unwrap();
+
+         let status = conversion::status::from_api(
+            instance_cache,
+            api_status,
+            &mut account_repo,
+            &mut status_repo,
+            &mut no_credential_status_repo,
+            &mut no_credential_poll_repo,
+         ).unwrap();
+
+         status_repo.save(status)
+      };
+
+      let serialized = serde_json::to_value(&*status.get()).unwrap();
+      let status_id = |local: &str| json!({
+         "instance_url": "https://cache.example.com/",
+         "local": local,
+      });
+      assert_eq!(status_id("1"), serialized["no_credential"]);
+      assert_eq!(status_id("0"), serialized["boosted_status"]);
+
+      super::register_cache_references();
+      let deserialized: Status = serde_json::from_value(serialized).unwrap();
+      assert_eq!(*status.get(), deserialized);
+
+      let boosted_status = deserialized.boosted_status.unwrap();
+      let boosted_account = boosted_status.get().no_credential.get()
+         .account.clone().unwrap();
+      assert_eq!(
+         Some("probosqis".to_string()),
+         boosted_account.get().username
+      );
+   }
+}
@@ -120,7 +120,7 @@ pub fn credential_account_from_api(
          .flatten()
          .flat_map(|f| profile_field_from_api(f))
          .collect();
-      default_post_visibility = source.privacy.map(StatusVisibility);
+      default_post_visibility = source.privacy.map(StatusVisibility::from_raw);
       default_post_sensitivity = source.sensitive;
       default_post_language
          = source.language.and_then(|code| Language::from_639_1(&code));
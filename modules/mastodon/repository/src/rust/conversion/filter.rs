@@ -42,7 +42,7 @@ pub fn from_api(
          .flatten()
          .map(|mut c| {
             c.make_ascii_lowercase();
-            FilterContext(c)
+            FilterContext::from_raw(c)
          })
          .collect(),
       expire_time: expires_at
@@ -50,7 +50,7 @@ pub fn from_api(
          .map(|time| time.to_utc()),
       filter_action: filter_action.map(|mut a| {
          a.make_ascii_lowercase();
-         FilterAction(a)
+         FilterAction::from_raw(a)
       }),
       keywords: keywords.into_iter()
          .flatten()
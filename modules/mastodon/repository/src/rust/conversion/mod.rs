@@ -19,8 +19,13 @@ pub(crate) mod application;
 pub(crate) mod custom_emoji;
 pub(crate) mod filter;
 pub(crate) mod media_attachment;
+pub(crate) mod notification;
+pub(crate) mod page;
 pub(crate) mod poll;
 pub(crate) mod preview_card;
+pub(crate) mod rate_limit;
+pub(crate) mod relationship;
 pub(crate) mod role;
+pub(crate) mod search;
 pub(crate) mod status;
 pub(crate) mod token;
@@ -0,0 +1,191 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use mastodon_entity::account::Account;
+use mastodon_entity::instance::Instance;
+use mastodon_entity::notification::Notification;
+use mastodon_entity::status::Status;
+use mastodon_webapi::entity::notification::{
+   GroupedNotificationsResults as ApiGroupedNotificationsResults,
+   Notification as ApiNotification,
+   NotificationGroup as ApiNotificationGroup,
+};
+use panoptiqon::cache::Cache;
+use crate::cache;
+
+#[cfg(feature = "jvm")]
+use jni::JNIEnv;
+
+pub fn from_api(
+   #[cfg(feature = "jvm")] env: &mut JNIEnv,
+   instance: Cache<Instance>,
+   entity: ApiNotification,
+   account_cache_repository: &mut cache::account::Repository,
+   status_cache_repository: &mut cache::status::StatusRepository,
+   no_credential_status_cache_repository:
+      &mut cache::status::NoCredentialStatusRepository,
+   no_credential_poll_repository: &mut cache::poll::NoCredentialPollRepository,
+) -> anyhow::Result<Notification> {
+   use anyhow::Context;
+   use chrono::DateTime;
+   use mastodon_entity::notification::{
+      NotificationId, NotificationLocalId, NotificationType,
+   };
+   use crate::conversion;
+
+   let ApiNotification { id, r#type, group_key, created_at, account, status } = entity;
+
+   let notification = Notification {
+      id: NotificationId {
+         instance_url: instance.get().url.clone(),
+         local: NotificationLocalId(id.context("No notification id")?),
+      },
+      notification_type: NotificationType::from_raw(
+         r#type.context("No notification type")?
+      ),
+      created_time: created_at
+         .and_then(|time| DateTime::parse_from_rfc3339(&time).ok())
+         .map(|time| time.to_utc()),
+      accounts: account
+         .and_then(|acc|
+            conversion::account::from_api(
+               #[cfg(feature = "jvm")] env,
+               instance.clone(),
+               acc,
+               account_cache_repository
+            ).ok()
+         )
+         .map(|acc| account_cache_repository.save(acc))
+         .into_iter()
+         .collect(),
+      status: status
+         .and_then(|status|
+            conversion::status::from_api(
+               #[cfg(feature = "jvm")] env,
+               instance.clone(),
+               status,
+               account_cache_repository,
+               status_cache_repository,
+               no_credential_status_cache_repository,
+               no_credential_poll_repository
+            ).ok()
+         )
+         .map(|status| status_cache_repository.save(status)),
+      group_key,
+      notification_count: Some(1),
+   };
+
+   Ok(notification)
+}
+
+/// mastodon 4.3.0以降のグループ化された通知を変換する。
+/// レスポンスに含まれるAccountとStatusはキャッシュに保存される
+pub fn grouped_from_api(
+   #[cfg(feature = "jvm")] env: &mut JNIEnv,
+   instance: Cache<Instance>,
+   entity: ApiGroupedNotificationsResults,
+   account_cache_repository: &mut cache::account::Repository,
+   status_cache_repository: &mut cache::status::StatusRepository,
+   no_credential_status_cache_repository:
+      &mut cache::status::NoCredentialStatusRepository,
+   no_credential_poll_repository: &mut cache::poll::NoCredentialPollRepository,
+) -> anyhow::Result<Vec<Notification>> {
+   use crate::conversion;
+
+   let ApiGroupedNotificationsResults {
+      accounts, statuses, notification_groups
+   } = entity;
+
+   let mut account_caches: HashMap<String, Cache<Account>> = HashMap::new();
+   for acc in accounts.unwrap_or(vec![]) {
+      let Ok(acc) = conversion::account::from_api(
+         #[cfg(feature = "jvm")] env,
+         instance.clone(),
+         acc,
+         account_cache_repository
+      ) else { continue; };
+
+      let id = acc.id.local.0.clone();
+      account_caches.insert(id, account_cache_repository.save(acc));
+   }
+
+   let mut status_caches: HashMap<String, Cache<Status>> = HashMap::new();
+   for status in statuses.unwrap_or(vec![]) {
+      let Ok(status) = conversion::status::from_api(
+         #[cfg(feature = "jvm")] env,
+         instance.clone(),
+         status,
+         account_cache_repository,
+         status_cache_repository,
+         no_credential_status_cache_repository,
+         no_credential_poll_repository
+      ) else { continue; };
+
+      let id = status.id.local.0.clone();
+      status_caches.insert(id, status_cache_repository.save(status));
+   }
+
+   let notifications = notification_groups.unwrap_or(vec![])
+      .into_iter()
+      .flat_map(|group|
+         group_from_api(instance.clone(), group, &account_caches, &status_caches)
+      )
+      .collect();
+
+   Ok(notifications)
+}
+
+fn group_from_api(
+   instance: Cache<Instance>,
+   entity: ApiNotificationGroup,
+   accounts: &HashMap<String, Cache<Account>>,
+   statuses: &HashMap<String, Cache<Status>>,
+) -> anyhow::Result<Notification> {
+   use anyhow::Context;
+   use chrono::DateTime;
+   use mastodon_entity::notification::{
+      NotificationId, NotificationLocalId, NotificationType,
+   };
+
+   let ApiNotificationGroup {
+      group_key, notifications_count, r#type, most_recent_notification_id,
+      latest_page_notification_at, sample_account_ids, status_id, ..
+   } = entity;
+
+   let notification = Notification {
+      id: NotificationId {
+         instance_url: instance.get().url.clone(),
+         local: NotificationLocalId(
+            most_recent_notification_id.context("No notification id")?
+         ),
+      },
+      notification_type: NotificationType::from_raw(
+         r#type.context("No notification type")?
+      ),
+      created_time: latest_page_notification_at
+         .and_then(|time| DateTime::parse_from_rfc3339(&time).ok())
+         .map(|time| time.to_utc()),
+      accounts: sample_account_ids.unwrap_or(vec![]).iter()
+         .flat_map(|id| accounts.get(id).cloned())
+         .collect(),
+      status: status_id.and_then(|id| statuses.get(&id).cloned()),
+      group_key,
+      notification_count: notifications_count,
+   };
+
+   Ok(notification)
+}
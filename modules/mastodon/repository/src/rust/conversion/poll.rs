@@ -36,7 +36,7 @@ pub fn from_api(
 
    let ApiPoll {
       id, expires_at, expired, multiple, votes_count, voters_count, options,
-      emojis, voted, own_voted,
+      emojis, voted, own_votes,
    } = entity;
 
    let id = PollId {
@@ -68,7 +68,7 @@ pub fn from_api(
       id: id.clone(),
       no_credential: no_credential_poll,
       is_voted: voted,
-      voted_options: own_voted.unwrap_or(vec![]),
+      voted_options: own_votes.unwrap_or(vec![]),
    };
 
    Ok(poll)
@@ -0,0 +1,27 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use ext_reqwest::rate_limit::RateLimitBudget as ApiRateLimitBudget;
+use mastodon_entity::rate_limit::RateLimitBudget;
+
+pub fn from_api(entity: ApiRateLimitBudget) -> RateLimitBudget {
+   let ApiRateLimitBudget { limit, remaining, reset_time } = entity;
+
+   RateLimitBudget {
+      limit: limit.into(),
+      remaining: remaining.into(),
+      reset_time,
+   }
+}
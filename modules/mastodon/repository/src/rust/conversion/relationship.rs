@@ -0,0 +1,61 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use mastodon_entity::instance::Instance;
+use mastodon_entity::relationship::Relationship;
+use mastodon_webapi::entity::relationship::Relationship as ApiRelationship;
+use panoptiqon::cache::Cache;
+
+pub fn from_api(
+   instance: Cache<Instance>,
+   entity: ApiRelationship
+) -> anyhow::Result<Relationship> {
+   use anyhow::Context;
+   use isolang::Language;
+   use mastodon_entity::account::{AccountId, AccountLocalId};
+
+   let ApiRelationship {
+      id, following, showing_reblogs, notifying, languages, followed_by,
+      blocking, blocked_by, muting, muting_notifications, requested,
+      requested_by, domain_blocking, endorsed, note,
+   } = entity;
+
+   let relationship = Relationship {
+      id: AccountId {
+         instance_url: instance.get().url.clone(),
+         local: AccountLocalId(id.context("No account id")?),
+      },
+      is_following: following,
+      is_showing_boosts: showing_reblogs,
+      is_notifying: notifying,
+      languages: languages.unwrap_or_default()
+         .iter()
+         .filter_map(|code| Language::from_639_1(code))
+         .collect(),
+      is_followed_by: followed_by,
+      is_blocking: blocking,
+      is_blocked_by: blocked_by,
+      is_muting: muting,
+      is_muting_notifications: muting_notifications,
+      is_requested: requested,
+      is_requested_by: requested_by,
+      is_domain_blocking: domain_blocking,
+      is_endorsed: endorsed,
+      note: note.filter(|note| !note.is_empty()),
+   };
+
+   Ok(relationship)
+}
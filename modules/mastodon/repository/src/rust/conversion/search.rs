@@ -0,0 +1,119 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use mastodon_entity::instance::Instance;
+use mastodon_entity::search::{HashtagHistory, SearchHashtag, SearchResult};
+use mastodon_webapi::entity::search::{
+   Search as ApiSearch, Tag as ApiTag, TagHistory as ApiTagHistory,
+};
+use panoptiqon::cache::Cache;
+use crate::cache;
+
+#[cfg(feature = "jvm")]
+use jni::JNIEnv;
+
+pub fn from_api(
+   #[cfg(feature = "jvm")] env: &mut JNIEnv,
+   instance: Cache<Instance>,
+   entity: ApiSearch,
+   account_cache_repository: &mut cache::account::Repository,
+   status_cache_repository: &mut cache::status::StatusRepository,
+   no_credential_status_cache_repository:
+      &mut cache::status::NoCredentialStatusRepository,
+   no_credential_poll_repository: &mut cache::poll::NoCredentialPollRepository,
+) -> anyhow::Result<SearchResult> {
+   use crate::conversion;
+
+   let ApiSearch { accounts, statuses, hashtags } = entity;
+
+   let mut account_caches = vec![];
+   for api_account in accounts.into_iter().flatten() {
+      let Ok(account) = conversion::account::from_api(
+         #[cfg(feature = "jvm")] env,
+         instance.clone(),
+         api_account,
+         account_cache_repository
+      ) else { continue; };
+
+      account_caches.push(account_cache_repository.save(account));
+   }
+
+   let statuses = statuses.into_iter()
+      .flatten()
+      .flat_map(|api_status|
+         conversion::status::from_api(
+            #[cfg(feature = "jvm")] env,
+            instance.clone(),
+            api_status,
+            account_cache_repository,
+            status_cache_repository,
+            no_credential_status_cache_repository,
+            no_credential_poll_repository
+         )
+      )
+      .collect();
+
+   let hashtags = hashtags.into_iter()
+      .flatten()
+      .flat_map(hashtag_from_api)
+      .collect();
+
+   let search_result = SearchResult {
+      accounts: account_caches,
+      statuses,
+      hashtags,
+   };
+
+   Ok(search_result)
+}
+
+pub fn hashtag_from_api(
+   entity: ApiTag
+) -> anyhow::Result<SearchHashtag> {
+   use mastodon_webapi::entity::status::StatusTag as ApiStatusTag;
+   use crate::conversion;
+
+   let ApiTag { name, url, history, following } = entity;
+
+   let hashtag = SearchHashtag {
+      hashtag: conversion::status::tag_from_api(ApiStatusTag { name, url })?,
+      history: history.into_iter()
+         .flatten()
+         .flat_map(history_from_api)
+         .collect(),
+      is_following: following,
+   };
+
+   Ok(hashtag)
+}
+
+fn history_from_api(
+   entity: ApiTagHistory
+) -> anyhow::Result<HashtagHistory> {
+   use chrono::DateTime;
+
+   let ApiTagHistory { day, uses, accounts } = entity;
+
+   let history = HashtagHistory {
+      day: day
+         .and_then(|day| day.parse().ok())
+         .and_then(|day| DateTime::from_timestamp(day, 0)),
+      use_count: uses.and_then(|uses| uses.parse().ok()),
+      account_count: accounts.and_then(|accounts| accounts.parse().ok()),
+   };
+
+   Ok(history)
+}
@@ -16,6 +16,7 @@
 
 use mastodon_entity::instance::Instance;
 use mastodon_entity::status::{Status, StatusHashtag, StatusMention};
+use mastodon_entity::status_draft::StatusDraft;
 use panoptiqon::cache::Cache;
 use crate::cache;
 
@@ -97,7 +98,7 @@ pub fn from_api(
          )
          .map(|acc| account_cache_repository.save(acc)),
       content,
-      visibility: visibility.map(StatusVisibility),
+      visibility: visibility.map(StatusVisibility::from_raw),
       is_sensitive: sensitive,
       spoiler_text,
       media_attachments: media_attachments.unwrap_or(vec![]).into_iter()
@@ -207,3 +208,67 @@ pub fn tag_from_api(
 
    Ok(status_tag)
 }
+
+/// 削除したStatusから削除して再編集用の下書きを作る
+pub fn draft_from_api(
+   instance: Cache<Instance>,
+   entity: ApiStatus
+) -> anyhow::Result<StatusDraft> {
+   use std::time::Duration;
+   use chrono::DateTime;
+   use isolang::Language;
+   use mastodon_entity::media_attachment::MediaAttachmentId;
+   use mastodon_entity::status::{StatusId, StatusLocalId, StatusVisibility};
+   use mastodon_entity::status_draft::PollDraft;
+   use mastodon_webapi::entity::poll::Poll as ApiPoll;
+
+   let ApiStatus {
+      created_at, visibility, sensitive, spoiler_text, media_attachments,
+      in_reply_to_id, poll, language, text, ..
+   } = entity;
+
+   let poll = poll.map(|poll| {
+      let ApiPoll { expires_at, multiple, options, .. } = poll;
+
+      let created_time = created_at.as_ref()
+         .and_then(|time| DateTime::parse_from_rfc3339(time).ok());
+      let expire_time = expires_at
+         .and_then(|time| DateTime::parse_from_rfc3339(&time).ok());
+
+      // 投稿時に指定された期間はAPIから取得できないため、
+      // 投稿日時と締切日時から逆算する。不明な場合は1日とする
+      let duration = created_time.zip(expire_time)
+         .and_then(|(created, expire)| (expire - created).to_std().ok())
+         .unwrap_or(Duration::from_secs(24 * 60 * 60));
+
+      PollDraft {
+         options: options.unwrap_or(vec![]).into_iter()
+            .flat_map(|option| option.title)
+            .collect(),
+         duration,
+         allows_multiple_choices: multiple.unwrap_or(false),
+         hides_totals: false,
+      }
+   });
+
+   let draft = StatusDraft {
+      text,
+      spoiler_text,
+      visibility: visibility.map(StatusVisibility::from_raw),
+      is_sensitive: sensitive,
+      language: language.and_then(|code| Language::from_639_1(&code)),
+      replied_status_id: in_reply_to_id.map(|id|
+         StatusId {
+            instance_url: instance.get().url.clone(),
+            local: StatusLocalId(id)
+         }
+      ),
+      media_attachment_ids: media_attachments.unwrap_or(vec![]).into_iter()
+         .flat_map(|media| media.id)
+         .map(MediaAttachmentId)
+         .collect(),
+      poll,
+   };
+
+   Ok(draft)
+}
@@ -0,0 +1,316 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use chrono::{TimeDelta, Utc};
+use ext_reqwest::transport::{ReqwestTransport, Transport};
+use mastodon_entity::custom_emoji::{CustomEmoji, CustomEmojiCatalogue};
+use mastodon_entity::instance::Instance;
+use panoptiqon::cache::Cache;
+
+#[cfg(not(feature = "jvm"))]
+use std::marker::PhantomData;
+
+#[cfg(feature = "jvm")]
+use jni::JNIEnv;
+
+/// キャッシュしたカスタム絵文字の一覧を取得し直すまでの期間
+const CATALOGUE_TTL: TimeDelta = TimeDelta::hours(1);
+
+/// サーバーごとのカスタム絵文字の一覧を管理する
+pub struct CustomEmojiRepository<'jni> {
+   #[cfg(not(feature = "jvm"))]
+   env: PhantomData<&'jni ()>,
+   #[cfg(feature = "jvm")]
+   env: JNIEnv<'jni>,
+   transport: Arc<dyn Transport>,
+}
+
+impl CustomEmojiRepository<'_> {
+   #[cfg(not(feature = "jvm"))]
+   pub fn new() -> CustomEmojiRepository<'static> {
+      CustomEmojiRepository {
+         env: PhantomData,
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   #[cfg(feature = "jvm")]
+   pub fn new<'jni>(env: &JNIEnv<'jni>) -> CustomEmojiRepository<'jni> {
+      CustomEmojiRepository {
+         env: unsafe { env.unsafe_clone() },
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   /// キャッシュが古くなっていなければキャッシュを返し、
+   /// そうでなければサーバーから取得し直す
+   pub fn get_custom_emojis(
+      &mut self,
+      instance: &Cache<Instance>
+   ) -> anyhow::Result<Cache<CustomEmojiCatalogue>> {
+      use crate::cache;
+
+      let instance_url = instance.get().url.clone();
+
+      let cached_catalogue = cache::custom_emoji::repo()
+         .read(#[cfg(feature = "jvm")] &mut self.env)?
+         .load(instance_url)
+         .ok();
+
+      if let Some(catalogue) = cached_catalogue {
+         if !catalogue.get().is_expired(CATALOGUE_TTL, Utc::now()) {
+            return Ok(catalogue);
+         }
+      }
+
+      self.refresh_custom_emojis(instance)
+   }
+
+   /// キャッシュの有無にかかわらずサーバーから取得する
+   pub fn refresh_custom_emojis(
+      &mut self,
+      instance: &Cache<Instance>
+   ) -> anyhow::Result<Cache<CustomEmojiCatalogue>> {
+      use mastodon_webapi::api::custom_emojis;
+      use crate::cache;
+      use crate::conversion;
+
+      let instance_url = instance.get().url.clone();
+
+      let api_custom_emojis = custom_emojis::get_custom_emojis(self.transport.as_ref(), &instance_url)?;
+
+      // shortcodeやURLが欠けている絵文字は使えないため、一覧全体を失敗にせず除外する
+      let emojis = api_custom_emojis.into_iter()
+         .filter_map(|e| conversion::custom_emoji::from_api(instance.clone(), e).ok())
+         .collect();
+
+      let catalogue = CustomEmojiCatalogue {
+         instance: instance.clone(),
+         emojis,
+         fetched_time: Utc::now(),
+      };
+
+      let catalogue = cache::custom_emoji::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?
+         .save(catalogue);
+
+      Ok(catalogue)
+   }
+
+   /// 投稿作成時の入力補完のためにshortcodeを検索する。
+   /// 検索の仕様は`CustomEmojiCatalogue::search`を参照
+   pub fn search_custom_emojis(
+      &mut self,
+      instance: &Cache<Instance>,
+      query: &str,
+      limit: usize
+   ) -> anyhow::Result<Vec<CustomEmoji>> {
+      let catalogue = self.get_custom_emojis(instance)?;
+      let emojis = catalogue.get().search(query, limit);
+      Ok(emojis)
+   }
+}
+
+#[cfg(feature = "jvm")]
+mod jvm {
+   use jni::JNIEnv;
+   use jni::objects::JObject;
+   use jni::sys::jint;
+   use mastodon_entity::jvm_types::{JvmCustomEmoji, JvmCustomEmojiCatalogue, JvmInstance};
+   use panoptiqon::jvm_types::{JvmCache, JvmList, JvmString};
+   use crate::cache;
+   use super::CustomEmojiRepository;
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidCustomEmojiRepository_getCustomEmojis<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      instance: JvmCache<'local, JvmInstance<'local>>
+   ) -> JvmCache<'local, JvmCustomEmojiCatalogue<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_custom_emojis(&mut env, instance)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopCustomEmojiRepository_getCustomEmojis<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      instance: JvmCache<'local, JvmInstance<'local>>
+   ) -> JvmCache<'local, JvmCustomEmojiCatalogue<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_custom_emojis(&mut env, instance)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_custom_emojis<'local>(
+      env: &mut JNIEnv<'local>,
+      instance: JvmCache<'local, JvmInstance<'local>>
+   ) -> anyhow::Result<JvmCache<'local, JvmCustomEmojiCatalogue<'local>>> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+
+      let mut custom_emoji_repository = CustomEmojiRepository::new(env);
+
+      let instance = cache::instance::clone_from_jvm(env, &instance)?;
+      let catalogue = custom_emoji_repository.get_custom_emojis(&instance)?;
+      Ok(catalogue.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidCustomEmojiRepository_refreshCustomEmojis<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      instance: JvmCache<'local, JvmInstance<'local>>
+   ) -> JvmCache<'local, JvmCustomEmojiCatalogue<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      refresh_custom_emojis(&mut env, instance)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopCustomEmojiRepository_refreshCustomEmojis<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      instance: JvmCache<'local, JvmInstance<'local>>
+   ) -> JvmCache<'local, JvmCustomEmojiCatalogue<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      refresh_custom_emojis(&mut env, instance)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn refresh_custom_emojis<'local>(
+      env: &mut JNIEnv<'local>,
+      instance: JvmCache<'local, JvmInstance<'local>>
+   ) -> anyhow::Result<JvmCache<'local, JvmCustomEmojiCatalogue<'local>>> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+
+      let mut custom_emoji_repository = CustomEmojiRepository::new(env);
+
+      let instance = cache::instance::clone_from_jvm(env, &instance)?;
+      let catalogue = custom_emoji_repository.refresh_custom_emojis(&instance)?;
+      Ok(catalogue.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidCustomEmojiRepository_searchCustomEmojis<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      instance: JvmCache<'local, JvmInstance<'local>>,
+      query: JvmString<'local>,
+      limit: jint
+   ) -> JvmList<'local, JvmCustomEmoji<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      search_custom_emojis(&mut env, instance, query, limit)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopCustomEmojiRepository_searchCustomEmojis<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      instance: JvmCache<'local, JvmInstance<'local>>,
+      query: JvmString<'local>,
+      limit: jint
+   ) -> JvmList<'local, JvmCustomEmoji<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      search_custom_emojis(&mut env, instance, query, limit)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn search_custom_emojis<'local>(
+      env: &mut JNIEnv<'local>,
+      instance: JvmCache<'local, JvmInstance<'local>>,
+      query: JvmString<'local>,
+      limit: jint
+   ) -> anyhow::Result<JvmList<'local, JvmCustomEmoji<'local>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut custom_emoji_repository = CustomEmojiRepository::new(env);
+
+      let instance = cache::instance::clone_from_jvm(env, &instance)?;
+      let query = String::clone_from_jvm(env, &query);
+      let limit = usize::try_from(limit)?;
+
+      let emojis = custom_emoji_repository.search_custom_emojis(&instance, &query, limit)?;
+      Ok(emojis.clone_into_jvm(env))
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use chrono::{TimeZone, Utc};
+   use mastodon_entity::instance::Instance;
+   use panoptiqon::cache::Cache;
+   use crate::fixture;
+   use super::CustomEmojiRepository;
+
+   fn instance() -> Cache<Instance> {
+      use crate::cache;
+
+      let instance = Instance {
+         url: "https://custom-emoji.example.com/".parse().unwrap(),
+         version: "4.3.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      cache::instance::repo().write().unwrap().save(instance)
+   }
+
+   #[test]
+   fn catalogue_is_cached_until_expired() {
+      use chrono::TimeDelta;
+      use mastodon_entity::custom_emoji::CustomEmojiCatalogue;
+      use crate::cache;
+
+      let transport = fixture::replay("custom_emoji/custom_emojis.json");
+      let mut repository = CustomEmojiRepository::new();
+      repository.transport = transport.clone();
+      let instance = instance();
+
+      let catalogue = repository.get_custom_emojis(&instance).unwrap();
+      assert_eq!(1, transport.requests().len());
+      assert_eq!(
+         vec!["blobcat"],
+         catalogue.get().emojis.iter().map(|e| e.shortcode.as_str()).collect::<Vec<_>>()
+      );
+
+      repository.get_custom_emojis(&instance).unwrap();
+      assert_eq!(1, transport.requests().len());
+
+      let emojis = repository.search_custom_emojis(&instance, "blob", 10).unwrap();
+      assert_eq!(1, transport.requests().len());
+      assert_eq!("blobcat", emojis[0].shortcode);
+
+      let expired_catalogue = CustomEmojiCatalogue {
+         fetched_time: Utc::now() - TimeDelta::hours(2),
+         ..catalogue.get().clone()
+      };
+      cache::custom_emoji::repo().write().unwrap().save(expired_catalogue);
+
+      repository.get_custom_emojis(&instance).unwrap();
+      assert_eq!(2, transport.requests().len());
+
+      repository.refresh_custom_emojis(&instance).unwrap();
+      assert_eq!(3, transport.requests().len());
+   }
+}
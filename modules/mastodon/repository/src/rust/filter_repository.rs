@@ -0,0 +1,993 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+use ext_reqwest::transport::{ReqwestTransport, Transport};
+use mastodon_entity::filter::{
+   Filter, FilterAction, FilterContext, FilterId, FilterKeyword, FilterKeywordId,
+   FilterList, FilterStatus, FilterStatusId,
+};
+use mastodon_entity::status::StatusId;
+use mastodon_entity::token::Token;
+use panoptiqon::cache::Cache;
+
+#[cfg(not(feature = "jvm"))]
+use std::marker::PhantomData;
+
+#[cfg(feature = "jvm")]
+use jni::JNIEnv;
+
+/// ログイン中のアカウントのフィルタを管理する。
+///
+/// フィルタはアカウントごとに`FilterList`としてキャッシュされ、
+/// このRepositoryによる変更はキャッシュにも反映される
+pub struct FilterRepository<'jni> {
+   #[cfg(not(feature = "jvm"))]
+   env: PhantomData<&'jni ()>,
+   #[cfg(feature = "jvm")]
+   env: JNIEnv<'jni>,
+   transport: Arc<dyn Transport>,
+}
+
+impl FilterRepository<'_> {
+   #[cfg(not(feature = "jvm"))]
+   pub fn new() -> FilterRepository<'static> {
+      FilterRepository {
+         env: PhantomData,
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   #[cfg(feature = "jvm")]
+   pub fn new<'jni>(env: &JNIEnv<'jni>) -> FilterRepository<'jni> {
+      FilterRepository {
+         env: unsafe { env.unsafe_clone() },
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   pub fn get_filters(
+      &mut self,
+      token: &Token
+   ) -> anyhow::Result<Cache<FilterList>> {
+      use mastodon_webapi::api::filters;
+      use crate::cache;
+      use crate::conversion;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_filters = filters::get_filters(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token
+      )?;
+
+      let filters = api_filters.into_iter()
+         .map(|f| conversion::filter::from_api(instance_url.clone(), f))
+         .collect::<anyhow::Result<Vec<_>>>()?;
+
+      let filter_list = FilterList {
+         account_id: token.account_id.clone(),
+         filters,
+      };
+
+      let filter_list = cache::filter::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?
+         .save(filter_list);
+
+      Ok(filter_list)
+   }
+
+   /// キャッシュされているフィルタ。まだ`get_filters`で取得していない場合None
+   pub fn load_filters(
+      &mut self,
+      token: &Token
+   ) -> anyhow::Result<Option<Cache<FilterList>>> {
+      use crate::cache;
+
+      let filter_list = cache::filter::repo()
+         .read(#[cfg(feature = "jvm")] &mut self.env)?
+         .load(token.account_id.clone())
+         .ok();
+
+      Ok(filter_list)
+   }
+
+   /// * `expire_duration` - Noneの場合無期限
+   /// * `keywords` - キーワードと`whole_word`の組
+   pub fn create_filter(
+      &mut self,
+      token: &Token,
+      title: &str,
+      context: &[FilterContext],
+      filter_action: &FilterAction,
+      expire_duration: Option<Duration>,
+      keywords: &[(&str, bool)]
+   ) -> anyhow::Result<Filter> {
+      use mastodon_webapi::api::filters::{self, PostFilterParams};
+      use crate::conversion;
+
+      let instance_url = token.instance.get().url.clone();
+      let context: Vec<&str> = context.iter().map(|c| c.raw()).collect();
+
+      let api_filter = filters::post_filter(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &PostFilterParams {
+            title,
+            context: &context,
+            filter_action: Some(filter_action.raw()),
+            expires_in: expire_duration.map(|d| d.as_secs()),
+            keywords,
+         }
+      )?;
+
+      let filter = conversion::filter::from_api(instance_url, api_filter)?;
+
+      self.update_cache(token, |filters| filters.push(filter.clone()))?;
+
+      Ok(filter)
+   }
+
+   /// Noneを指定した項目は変更しない。
+   ///
+   /// * `expire_duration` - `Some(None)`の場合無期限にする
+   pub fn update_filter(
+      &mut self,
+      token: &Token,
+      filter_id: &FilterId,
+      title: Option<&str>,
+      context: Option<&[FilterContext]>,
+      filter_action: Option<&FilterAction>,
+      expire_duration: Option<Option<Duration>>
+   ) -> anyhow::Result<Filter> {
+      use anyhow::bail;
+      use mastodon_webapi::api::filters::{self, PutFilterParams};
+      use crate::conversion;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let context: Vec<&str> = context.unwrap_or_default()
+         .iter()
+         .map(|c| c.raw())
+         .collect();
+
+      if context.is_empty() && title.is_none() && filter_action.is_none()
+         && expire_duration.is_none()
+      {
+         bail!("nothing to update");
+      }
+
+      let api_filter = filters::put_filter(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &filter_id.0,
+         &PutFilterParams {
+            title,
+            context: &context,
+            filter_action: filter_action.map(|a| a.raw()),
+            expires_in: expire_duration.map(|d| d.map(|d| d.as_secs())),
+         }
+      )?;
+
+      let filter = conversion::filter::from_api(instance_url, api_filter)?;
+
+      self.update_cache(token, |filters| {
+         for f in filters.iter_mut().filter(|f| f.id == filter.id) {
+            *f = filter.clone();
+         }
+      })?;
+
+      Ok(filter)
+   }
+
+   pub fn delete_filter(
+      &mut self,
+      token: &Token,
+      filter_id: &FilterId
+   ) -> anyhow::Result<()> {
+      use mastodon_webapi::api::filters;
+
+      filters::delete_filter(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &filter_id.0
+      )?;
+
+      self.update_cache(token, |filters| filters.retain(|f| &f.id != filter_id))
+   }
+
+   pub fn add_keyword(
+      &mut self,
+      token: &Token,
+      filter_id: &FilterId,
+      keyword: &str,
+      whole_word: bool
+   ) -> anyhow::Result<FilterKeyword> {
+      use mastodon_webapi::api::filters;
+      use crate::conversion;
+
+      let api_keyword = filters::post_filter_keyword(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &filter_id.0,
+         keyword,
+         Some(whole_word)
+      )?;
+
+      let keyword = conversion::filter::filter_keyword_from_api(api_keyword)?;
+
+      self.update_filter_cache(token, filter_id, |filter| {
+         filter.keywords.push(keyword.clone());
+      })?;
+
+      Ok(keyword)
+   }
+
+   /// Noneを指定した項目は変更しない
+   pub fn update_keyword(
+      &mut self,
+      token: &Token,
+      filter_id: &FilterId,
+      keyword_id: &FilterKeywordId,
+      keyword: Option<&str>,
+      whole_word: Option<bool>
+   ) -> anyhow::Result<FilterKeyword> {
+      use mastodon_webapi::api::filters;
+      use crate::conversion;
+
+      let api_keyword = filters::put_filter_keyword(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &keyword_id.0,
+         keyword,
+         whole_word
+      )?;
+
+      let keyword = conversion::filter::filter_keyword_from_api(api_keyword)?;
+
+      self.update_filter_cache(token, filter_id, |filter| {
+         for k in filter.keywords.iter_mut().filter(|k| k.id == keyword.id) {
+            *k = keyword.clone();
+         }
+      })?;
+
+      Ok(keyword)
+   }
+
+   pub fn remove_keyword(
+      &mut self,
+      token: &Token,
+      filter_id: &FilterId,
+      keyword_id: &FilterKeywordId
+   ) -> anyhow::Result<()> {
+      use mastodon_webapi::api::filters;
+
+      filters::delete_filter_keyword(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &keyword_id.0
+      )?;
+
+      self.update_filter_cache(token, filter_id, |filter| {
+         filter.keywords.retain(|k| &k.id != keyword_id);
+      })
+   }
+
+   pub fn add_status(
+      &mut self,
+      token: &Token,
+      filter_id: &FilterId,
+      status_id: &StatusId
+   ) -> anyhow::Result<FilterStatus> {
+      use mastodon_webapi::api::filters;
+      use crate::conversion;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = filters::post_filter_status(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &filter_id.0,
+         &status_id.local.0
+      )?;
+
+      let status = conversion::filter::filter_status_from_api(instance_url, api_status)?;
+
+      self.update_filter_cache(token, filter_id, |filter| {
+         filter.statuses.push(status.clone());
+      })?;
+
+      Ok(status)
+   }
+
+   pub fn remove_status(
+      &mut self,
+      token: &Token,
+      filter_id: &FilterId,
+      filter_status_id: &FilterStatusId
+   ) -> anyhow::Result<()> {
+      use mastodon_webapi::api::filters;
+
+      filters::delete_filter_status(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &filter_status_id.0
+      )?;
+
+      self.update_filter_cache(token, filter_id, |filter| {
+         filter.statuses.retain(|s| &s.id != filter_status_id);
+      })
+   }
+
+   /// キャッシュされている`FilterList`を更新する。
+   /// まだ取得していない場合は何もしない
+   fn update_cache(
+      &mut self,
+      token: &Token,
+      update: impl FnOnce(&mut Vec<Filter>)
+   ) -> anyhow::Result<()> {
+      use crate::cache;
+
+      let mut repo = cache::filter::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let Ok(filter_list) = repo.load(token.account_id.clone()) else {
+         return Ok(());
+      };
+
+      let mut filter_list = filter_list.get().clone();
+      update(&mut filter_list.filters);
+      repo.save(filter_list);
+
+      Ok(())
+   }
+
+   fn update_filter_cache(
+      &mut self,
+      token: &Token,
+      filter_id: &FilterId,
+      update: impl FnOnce(&mut Filter)
+   ) -> anyhow::Result<()> {
+      self.update_cache(token, |filters| {
+         if let Some(filter) = filters.iter_mut().find(|f| &f.id == filter_id) {
+            update(filter);
+         }
+      })
+   }
+}
+
+#[cfg(feature = "jvm")]
+mod jvm {
+   use std::time::Duration;
+   use jni::JNIEnv;
+   use jni::objects::JObject;
+   use jni::sys::jboolean;
+   use mastodon_entity::filter::FilterId;
+   use mastodon_entity::jvm_types::{
+      JvmFilter, JvmFilterKeyword, JvmFilterList, JvmFilterStatus, JvmStatusId,
+      JvmToken,
+   };
+   use mastodon_entity::token::Token;
+   use panoptiqon::jvm_types::{
+      JvmBoolean, JvmCache, JvmList, JvmLong, JvmNullable, JvmString,
+   };
+   use crate::cache;
+   use super::FilterRepository;
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidFilterRepository_getFilters<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>
+   ) -> JvmCache<'local, JvmFilterList<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_filters(&mut env, token)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopFilterRepository_getFilters<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>
+   ) -> JvmCache<'local, JvmFilterList<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_filters(&mut env, token)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_filters<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>
+   ) -> anyhow::Result<JvmCache<'local, JvmFilterList<'local>>> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+
+      let mut filter_repository = FilterRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let filter_list = filter_repository.get_filters(&token)?;
+      Ok(filter_list.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidFilterRepository_loadFilters<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>
+   ) -> JvmNullable<'local, JvmCache<'local, JvmFilterList<'local>>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      load_filters(&mut env, token)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopFilterRepository_loadFilters<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>
+   ) -> JvmNullable<'local, JvmCache<'local, JvmFilterList<'local>>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      load_filters(&mut env, token)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn load_filters<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>
+   ) -> anyhow::Result<JvmNullable<'local, JvmCache<'local, JvmFilterList<'local>>>> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+
+      let mut filter_repository = FilterRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let filter_list = filter_repository.load_filters(&token)?;
+      Ok(filter_list.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidFilterRepository_postFilter<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      title: JvmString<'local>,
+      raw_context: JvmList<'local, JvmString<'local>>,
+      raw_filter_action: JvmString<'local>,
+      expire_duration_seconds: JvmNullable<'local, JvmLong<'local>>,
+      keywords: JvmList<'local, JvmString<'local>>,
+      whole_words: JvmList<'local, JvmBoolean<'local>>
+   ) -> JvmFilter<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      create_filter(&mut env, token, title, raw_context, raw_filter_action, expire_duration_seconds, keywords, whole_words)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopFilterRepository_postFilter<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      title: JvmString<'local>,
+      raw_context: JvmList<'local, JvmString<'local>>,
+      raw_filter_action: JvmString<'local>,
+      expire_duration_seconds: JvmNullable<'local, JvmLong<'local>>,
+      keywords: JvmList<'local, JvmString<'local>>,
+      whole_words: JvmList<'local, JvmBoolean<'local>>
+   ) -> JvmFilter<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      create_filter(&mut env, token, title, raw_context, raw_filter_action, expire_duration_seconds, keywords, whole_words)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn create_filter<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      title: JvmString<'local>,
+      raw_context: JvmList<'local, JvmString<'local>>,
+      raw_filter_action: JvmString<'local>,
+      expire_duration_seconds: JvmNullable<'local, JvmLong<'local>>,
+      keywords: JvmList<'local, JvmString<'local>>,
+      whole_words: JvmList<'local, JvmBoolean<'local>>
+   ) -> anyhow::Result<JvmFilter<'local>> {
+      use anyhow::ensure;
+      use mastodon_entity::filter::{FilterAction, FilterContext};
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut filter_repository = FilterRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let title = String::clone_from_jvm(env, &title);
+      let context: Vec<FilterContext> = Vec::<String>::clone_from_jvm(env, &raw_context)
+         .into_iter()
+         .map(FilterContext::from_raw)
+         .collect();
+      let filter_action = FilterAction::from_raw(String::clone_from_jvm(env, &raw_filter_action));
+      let expire_duration = duration_from_jvm(env, &expire_duration_seconds)?;
+      let keywords = Vec::<String>::clone_from_jvm(env, &keywords);
+      let whole_words = Vec::<bool>::clone_from_jvm(env, &whole_words);
+
+      ensure!(keywords.len() == whole_words.len(), "keywords and whole_words differ in length");
+
+      let keywords: Vec<(&str, bool)> = keywords.iter()
+         .map(String::as_str)
+         .zip(whole_words)
+         .collect();
+
+      let filter = filter_repository.create_filter(
+         &token, &title, &context, &filter_action, expire_duration, &keywords
+      )?;
+      Ok(filter.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidFilterRepository_putFilter<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      title: JvmNullable<'local, JvmString<'local>>,
+      raw_context: JvmNullable<'local, JvmList<'local, JvmString<'local>>>,
+      raw_filter_action: JvmNullable<'local, JvmString<'local>>,
+      expire_duration_seconds: JvmNullable<'local, JvmLong<'local>>,
+      removes_expire_time: jboolean
+   ) -> JvmFilter<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      update_filter(&mut env, token, raw_filter_id, title, raw_context, raw_filter_action, expire_duration_seconds, removes_expire_time)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopFilterRepository_putFilter<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      title: JvmNullable<'local, JvmString<'local>>,
+      raw_context: JvmNullable<'local, JvmList<'local, JvmString<'local>>>,
+      raw_filter_action: JvmNullable<'local, JvmString<'local>>,
+      expire_duration_seconds: JvmNullable<'local, JvmLong<'local>>,
+      removes_expire_time: jboolean
+   ) -> JvmFilter<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      update_filter(&mut env, token, raw_filter_id, title, raw_context, raw_filter_action, expire_duration_seconds, removes_expire_time)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn update_filter<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      title: JvmNullable<'local, JvmString<'local>>,
+      raw_context: JvmNullable<'local, JvmList<'local, JvmString<'local>>>,
+      raw_filter_action: JvmNullable<'local, JvmString<'local>>,
+      expire_duration_seconds: JvmNullable<'local, JvmLong<'local>>,
+      removes_expire_time: jboolean
+   ) -> anyhow::Result<JvmFilter<'local>> {
+      use mastodon_entity::filter::{FilterAction, FilterContext};
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut filter_repository = FilterRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let filter_id = FilterId(String::clone_from_jvm(env, &raw_filter_id));
+      let title = Option::<String>::clone_from_jvm(env, &title);
+      let context: Option<Vec<FilterContext>>
+         = Option::<Vec<String>>::clone_from_jvm(env, &raw_context)
+            .map(|context| context.into_iter().map(FilterContext::from_raw).collect());
+      let filter_action = Option::<String>::clone_from_jvm(env, &raw_filter_action)
+         .map(FilterAction::from_raw);
+
+      let expire_duration = if removes_expire_time != 0 {
+         Some(None)
+      } else {
+         duration_from_jvm(env, &expire_duration_seconds)?.map(Some)
+      };
+
+      let filter = filter_repository.update_filter(
+         &token,
+         &filter_id,
+         title.as_deref(),
+         context.as_deref(),
+         filter_action.as_ref(),
+         expire_duration
+      )?;
+      Ok(filter.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidFilterRepository_deleteFilterById<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>
+   ) {
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      if let Err(e) = delete_filter(&mut env, token, raw_filter_id) {
+         throw_web_api_exception(&mut env, &e);
+      }
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopFilterRepository_deleteFilterById<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>
+   ) {
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      if let Err(e) = delete_filter(&mut env, token, raw_filter_id) {
+         throw_web_api_exception(&mut env, &e);
+      }
+   }
+
+   fn delete_filter<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>
+   ) -> anyhow::Result<()> {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let mut filter_repository = FilterRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let filter_id = FilterId(String::clone_from_jvm(env, &raw_filter_id));
+      filter_repository.delete_filter(&token, &filter_id)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidFilterRepository_postKeyword<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      keyword: JvmString<'local>,
+      whole_word: jboolean
+   ) -> JvmFilterKeyword<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      add_keyword(&mut env, token, raw_filter_id, keyword, whole_word)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopFilterRepository_postKeyword<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      keyword: JvmString<'local>,
+      whole_word: jboolean
+   ) -> JvmFilterKeyword<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      add_keyword(&mut env, token, raw_filter_id, keyword, whole_word)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn add_keyword<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      keyword: JvmString<'local>,
+      whole_word: jboolean
+   ) -> anyhow::Result<JvmFilterKeyword<'local>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut filter_repository = FilterRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let filter_id = FilterId(String::clone_from_jvm(env, &raw_filter_id));
+      let keyword = String::clone_from_jvm(env, &keyword);
+
+      let keyword = filter_repository.add_keyword(
+         &token, &filter_id, &keyword, whole_word != 0
+      )?;
+      Ok(keyword.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidFilterRepository_putKeyword<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      raw_keyword_id: JvmString<'local>,
+      keyword: JvmNullable<'local, JvmString<'local>>,
+      whole_word: JvmNullable<'local, JvmBoolean<'local>>
+   ) -> JvmFilterKeyword<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      update_keyword(&mut env, token, raw_filter_id, raw_keyword_id, keyword, whole_word)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopFilterRepository_putKeyword<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      raw_keyword_id: JvmString<'local>,
+      keyword: JvmNullable<'local, JvmString<'local>>,
+      whole_word: JvmNullable<'local, JvmBoolean<'local>>
+   ) -> JvmFilterKeyword<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      update_keyword(&mut env, token, raw_filter_id, raw_keyword_id, keyword, whole_word)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn update_keyword<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      raw_keyword_id: JvmString<'local>,
+      keyword: JvmNullable<'local, JvmString<'local>>,
+      whole_word: JvmNullable<'local, JvmBoolean<'local>>
+   ) -> anyhow::Result<JvmFilterKeyword<'local>> {
+      use mastodon_entity::filter::FilterKeywordId;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut filter_repository = FilterRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let filter_id = FilterId(String::clone_from_jvm(env, &raw_filter_id));
+      let keyword_id = FilterKeywordId(String::clone_from_jvm(env, &raw_keyword_id));
+      let keyword = Option::<String>::clone_from_jvm(env, &keyword);
+      let whole_word = Option::<bool>::clone_from_jvm(env, &whole_word);
+
+      let keyword = filter_repository.update_keyword(
+         &token, &filter_id, &keyword_id, keyword.as_deref(), whole_word
+      )?;
+      Ok(keyword.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidFilterRepository_deleteKeywordById<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      raw_keyword_id: JvmString<'local>
+   ) {
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      if let Err(e) = remove_keyword(&mut env, token, raw_filter_id, raw_keyword_id) {
+         throw_web_api_exception(&mut env, &e);
+      }
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopFilterRepository_deleteKeywordById<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      raw_keyword_id: JvmString<'local>
+   ) {
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      if let Err(e) = remove_keyword(&mut env, token, raw_filter_id, raw_keyword_id) {
+         throw_web_api_exception(&mut env, &e);
+      }
+   }
+
+   fn remove_keyword<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      raw_keyword_id: JvmString<'local>
+   ) -> anyhow::Result<()> {
+      use mastodon_entity::filter::FilterKeywordId;
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let mut filter_repository = FilterRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let filter_id = FilterId(String::clone_from_jvm(env, &raw_filter_id));
+      let keyword_id = FilterKeywordId(String::clone_from_jvm(env, &raw_keyword_id));
+      filter_repository.remove_keyword(&token, &filter_id, &keyword_id)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidFilterRepository_postFilterStatus<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmFilterStatus<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      add_status(&mut env, token, raw_filter_id, status_id)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopFilterRepository_postFilterStatus<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmFilterStatus<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      add_status(&mut env, token, raw_filter_id, status_id)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn add_status<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> anyhow::Result<JvmFilterStatus<'local>> {
+      use mastodon_entity::status::StatusId;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut filter_repository = FilterRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let filter_id = FilterId(String::clone_from_jvm(env, &raw_filter_id));
+      let status_id = StatusId::clone_from_jvm(env, &status_id);
+
+      let filter_status = filter_repository.add_status(&token, &filter_id, &status_id)?;
+      Ok(filter_status.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidFilterRepository_deleteFilterStatusById<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      raw_filter_status_id: JvmString<'local>
+   ) {
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      if let Err(e) = remove_status(&mut env, token, raw_filter_id, raw_filter_status_id) {
+         throw_web_api_exception(&mut env, &e);
+      }
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopFilterRepository_deleteFilterStatusById<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      raw_filter_status_id: JvmString<'local>
+   ) {
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      if let Err(e) = remove_status(&mut env, token, raw_filter_id, raw_filter_status_id) {
+         throw_web_api_exception(&mut env, &e);
+      }
+   }
+
+   fn remove_status<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      raw_filter_id: JvmString<'local>,
+      raw_filter_status_id: JvmString<'local>
+   ) -> anyhow::Result<()> {
+      use mastodon_entity::filter::FilterStatusId;
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let mut filter_repository = FilterRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let filter_id = FilterId(String::clone_from_jvm(env, &raw_filter_id));
+      let filter_status_id = FilterStatusId(String::clone_from_jvm(env, &raw_filter_status_id));
+      filter_repository.remove_status(&token, &filter_id, &filter_status_id)
+   }
+
+   fn duration_from_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      seconds: &JvmNullable<'local, JvmLong<'local>>
+   ) -> anyhow::Result<Option<Duration>> {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      Option::<i64>::clone_from_jvm(env, seconds)
+         .map(|seconds| anyhow::Ok(Duration::from_secs(u64::try_from(seconds)?)))
+         .transpose()
+   }
+
+   fn clone_token_from_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      token: &JvmToken<'local>
+   ) -> anyhow::Result<Token> {
+      let instance = token.instance(env);
+      let instance = cache::instance::clone_from_jvm(env, &instance)?;
+      Ok(Token::clone_from_jvm(env, token, instance))
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use mastodon_entity::token::Token;
+   use crate::fixture;
+   use super::FilterRepository;
+
+   fn token() -> Token {
+      fixture::token("https://filter.example.com/", "4.3.0")
+   }
+
+   #[test]
+   fn changes_are_reflected_in_cached_filters() {
+      use std::time::Duration;
+      use mastodon_entity::filter::{
+         FilterAction, FilterContext, FilterId, FilterKeywordId,
+      };
+
+      let mut repository = FilterRepository::new();
+      repository.transport = fixture::replay("filter/changes.json");
+      let token = token();
+
+      assert_eq!(None, repository.load_filters(&token).unwrap());
+
+      let filter_list = repository.get_filters(&token).unwrap();
+      assert_eq!(token.account_id, filter_list.get().account_id);
+      assert_eq!(1, filter_list.get().filters.len());
+      assert_eq!(Some(filter_list.clone()), repository.load_filters(&token).unwrap());
+
+      let created = repository
+         .create_filter(
+            &token, "title 2", &[FilterContext::Thread, FilterContext::Account],
+            &FilterAction::Hide, Some(Duration::from_secs(3600)),
+            &[("new keyword", false)]
+         )
+         .unwrap();
+      assert_eq!(FilterId("2".to_string()), created.id);
+      assert_eq!(2, filter_list.get().filters.len());
+
+      let filter_id = FilterId("1".to_string());
+      repository.add_keyword(&token, &filter_id, "added", true).unwrap();
+      repository.remove_keyword(&token, &filter_id, &FilterKeywordId("10".to_string())).unwrap();
+      assert_eq!(
+         vec![Some("added".to_string())],
+         filter_list.get().filters[0].keywords.iter()
+            .map(|k| k.keyword.clone())
+            .collect::<Vec<_>>()
+      );
+
+      repository.delete_filter(&token, &FilterId("2".to_string())).unwrap();
+      assert_eq!(
+         vec![filter_id],
+         filter_list.get().filters.iter().map(|f| f.id.clone()).collect::<Vec<_>>()
+      );
+   }
+}
@@ -0,0 +1,77 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::sync::Arc;
+use chrono::{TimeZone, Utc};
+use ext_reqwest::replay::ReplayTransport;
+use mastodon_entity::account::{AccountId, AccountLocalId};
+use mastodon_entity::instance::Instance;
+use mastodon_entity::token::Token;
+use url::Url;
+
+/// `src/rustTest/fixtures/`にあるfixtureファイルを読み込む
+pub fn replay(name: &str) -> Arc<ReplayTransport> {
+   Arc::new(ReplayTransport::load(path(name)))
+}
+
+/// fixture中の`{instance_url}`を置き換えて読み込む。
+/// テスト中に起動したサーバーなどURLが実行時に決まる場合に使う
+pub fn replay_with_instance_url(name: &str, instance_url: &Url) -> Arc<ReplayTransport> {
+   replay_with(name, &[("{instance_url}", instance_url.as_str())])
+}
+
+/// fixture中のプレースホルダを置き換えて読み込む。
+/// 時刻など実行時に決まる値を埋め込む場合に使う
+pub fn replay_with(name: &str, replacements: &[(&str, &str)]) -> Arc<ReplayTransport> {
+   let json = replacements.iter()
+      .fold(fs::read_to_string(path(name)).unwrap(), |json, (from, to)| {
+         json.replace(from, to)
+      });
+
+   Arc::new(ReplayTransport::from_json(&json))
+}
+
+/// `url`のインスタンスのトークンを作る。インスタンスはキャッシュに保存される
+pub fn token(url: &str, version: &str) -> Token {
+   use crate::cache;
+
+   let instance = Instance {
+      url: url.parse().unwrap(),
+      version: version.to_string(),
+      version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+   };
+
+   let instance_cache = cache::instance::repo().write().unwrap().save(instance);
+   let instance_url = instance_cache.get().url.clone();
+
+   Token {
+      instance: instance_cache,
+      account: None,
+      account_id: AccountId {
+         instance_url,
+         local: AccountLocalId("credential account id".to_string()),
+      },
+      access_token: "access token".to_string(),
+      token_type: "token type".to_string(),
+      scope: "scope".to_string(),
+      created_at: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+   }
+}
+
+fn path(name: &str) -> String {
+   format!("{}/src/rustTest/fixtures/{name}", env!("CARGO_MANIFEST_DIR"))
+}
@@ -0,0 +1,200 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+#[cfg(feature = "jvm")]
+mod jvm {
+   use std::time::Duration;
+
+   use ext_reqwest::client_registry::{self, ClientSettings};
+   use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+   use jni::JNIEnv;
+   use jni::objects::JObject;
+   use jni::sys::jlong;
+   use panoptiqon::convert_jvm::CloneFromJvm;
+   use panoptiqon::jvm_types::{JvmNullable, JvmString};
+   use url::Url;
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidHttpClientRegistry_applyDefaultSettings<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      connect_timeout_millis: jlong,
+      read_timeout_millis: jlong,
+      proxy_url: JvmNullable<'local, JvmString<'local>>,
+      app_version: JvmNullable<'local, JvmString<'local>>
+   ) {
+      apply_default_settings(&mut env, connect_timeout_millis, read_timeout_millis, proxy_url, app_version);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopHttpClientRegistry_applyDefaultSettings<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      connect_timeout_millis: jlong,
+      read_timeout_millis: jlong,
+      proxy_url: JvmNullable<'local, JvmString<'local>>,
+      app_version: JvmNullable<'local, JvmString<'local>>
+   ) {
+      apply_default_settings(&mut env, connect_timeout_millis, read_timeout_millis, proxy_url, app_version);
+   }
+
+   fn apply_default_settings<'local>(
+      env: &mut JNIEnv<'local>,
+      connect_timeout_millis: jlong,
+      read_timeout_millis: jlong,
+      proxy_url: JvmNullable<'local, JvmString<'local>>,
+      app_version: JvmNullable<'local, JvmString<'local>>
+   ) {
+      if !check_timeouts(env, connect_timeout_millis, read_timeout_millis) {
+         return;
+      }
+
+      let result = (|| {
+         let settings = ClientSettings {
+            app_version: Option::<String>::clone_from_jvm(env, &app_version),
+            ..settings_from_jvm(env, connect_timeout_millis, read_timeout_millis, proxy_url)?
+         };
+         client_registry::set_default_settings(settings)?;
+         anyhow::Ok(())
+      })();
+
+      if let Err(e) = result {
+         throw_web_api_exception(env, &e);
+      }
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidHttpClientRegistry_applyInstanceSettings<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      instance_url: JvmString<'local>,
+      connect_timeout_millis: jlong,
+      read_timeout_millis: jlong,
+      proxy_url: JvmNullable<'local, JvmString<'local>>
+   ) {
+      apply_instance_settings(&mut env, instance_url, connect_timeout_millis, read_timeout_millis, proxy_url);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopHttpClientRegistry_applyInstanceSettings<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      instance_url: JvmString<'local>,
+      connect_timeout_millis: jlong,
+      read_timeout_millis: jlong,
+      proxy_url: JvmNullable<'local, JvmString<'local>>
+   ) {
+      apply_instance_settings(&mut env, instance_url, connect_timeout_millis, read_timeout_millis, proxy_url);
+   }
+
+   fn apply_instance_settings<'local>(
+      env: &mut JNIEnv<'local>,
+      instance_url: JvmString<'local>,
+      connect_timeout_millis: jlong,
+      read_timeout_millis: jlong,
+      proxy_url: JvmNullable<'local, JvmString<'local>>
+   ) {
+      if !check_timeouts(env, connect_timeout_millis, read_timeout_millis) {
+         return;
+      }
+
+      let result = (|| {
+         let instance_url = parse_url(String::clone_from_jvm(env, &instance_url))?;
+         let settings = settings_from_jvm(env, connect_timeout_millis, read_timeout_millis, proxy_url)?;
+         client_registry::set_instance_settings(&instance_url, Some(settings))?;
+         anyhow::Ok(())
+      })();
+
+      if let Err(e) = result {
+         throw_web_api_exception(env, &e);
+      }
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidHttpClientRegistry_resetInstanceSettings<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      instance_url: JvmString<'local>
+   ) {
+      reset_instance_settings(&mut env, instance_url);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopHttpClientRegistry_resetInstanceSettings<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      instance_url: JvmString<'local>
+   ) {
+      reset_instance_settings(&mut env, instance_url);
+   }
+
+   fn reset_instance_settings<'local>(
+      env: &mut JNIEnv<'local>,
+      instance_url: JvmString<'local>
+   ) {
+      let result = parse_url(String::clone_from_jvm(env, &instance_url))
+         .and_then(|url| Ok(client_registry::set_instance_settings(&url, None)?));
+
+      if let Err(e) = result {
+         throw_web_api_exception(env, &e);
+      }
+   }
+
+   /// タイムアウトが正の値であることを確認する。
+   /// 0以下の場合は`java.lang.IllegalArgumentException`をthrowしてfalseを返す
+   fn check_timeouts(
+      env: &mut JNIEnv,
+      connect_timeout_millis: jlong,
+      read_timeout_millis: jlong
+   ) -> bool {
+      if connect_timeout_millis > 0 && read_timeout_millis > 0 {
+         return true;
+      }
+
+      let message = format!(
+         "timeouts must be positive: connectTimeout={connect_timeout_millis}ms, readTimeout={read_timeout_millis}ms"
+      );
+      env.throw_new("java/lang/IllegalArgumentException", message).unwrap();
+      false
+   }
+
+   fn settings_from_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      connect_timeout_millis: jlong,
+      read_timeout_millis: jlong,
+      proxy_url: JvmNullable<'local, JvmString<'local>>
+   ) -> anyhow::Result<ClientSettings> {
+      let proxy = Option::<String>::clone_from_jvm(env, &proxy_url)
+         .map(parse_url)
+         .transpose()?;
+
+      // アプリのバージョンはデフォルトの設定から引き継ぐ
+      let settings = ClientSettings {
+         connect_timeout: Duration::from_millis(connect_timeout_millis as u64),
+         read_timeout: Duration::from_millis(read_timeout_millis as u64),
+         proxy,
+         app_version: client_registry::default_settings().app_version,
+      };
+
+      Ok(settings)
+   }
+
+   fn parse_url(url: String) -> anyhow::Result<Url> {
+      use ext_reqwest::error::WebApiError;
+
+      let parsed = url.parse().map_err(|_| WebApiError::InvalidUrl(url))?;
+      Ok(parsed)
+   }
+}
@@ -16,6 +16,18 @@
 
 pub mod account_repository;
 pub mod app_repository;
+pub mod custom_emoji_repository;
+pub mod filter_repository;
+pub mod http_client_registry;
+pub mod media_repository;
+pub mod notification_repository;
+pub mod persistent_cache;
+pub mod search_repository;
+pub mod status_repository;
+pub mod streaming_repository;
 pub mod timeline_repository;
 pub(crate) mod cache;
 pub(crate) mod conversion;
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod fixture;
@@ -0,0 +1,451 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::{self, Read};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use ext_reqwest::transport::{ReqwestTransport, Transport};
+use mastodon_entity::media_attachment::{ImageFocus, MediaAttachment, MediaAttachmentId};
+use mastodon_entity::token::Token;
+
+pub struct MediaRepository {
+   poll_interval: Duration,
+   poll_timeout: Duration,
+   transport: Arc<dyn Transport>,
+}
+
+/// アップロードするメディア
+pub struct MediaUpload {
+   pub file_name: String,
+   /// Noneの場合サーバーがファイルの内容から判定する
+   pub mime_type: Option<String>,
+   pub file: Vec<u8>,
+   pub description: Option<String>,
+   pub focus: Option<ImageFocus>,
+}
+
+/// アップロード中のメディアをキャンセルするためのハンドル。
+/// Cloneして別スレッドから[MediaUploadCanceller::cancel]を呼ぶ
+#[derive(Clone, Default)]
+pub struct MediaUploadCanceller(Arc<AtomicBool>);
+
+impl MediaUploadCanceller {
+   pub fn new() -> MediaUploadCanceller {
+      MediaUploadCanceller::default()
+   }
+
+   pub fn cancel(&self) {
+      self.0.store(true, Ordering::Relaxed);
+   }
+
+   pub fn is_cancelled(&self) -> bool {
+      self.0.load(Ordering::Relaxed)
+   }
+}
+
+impl Default for MediaRepository {
+   fn default() -> MediaRepository {
+      MediaRepository::new()
+   }
+}
+
+impl MediaRepository {
+   pub fn new() -> MediaRepository {
+      MediaRepository {
+         poll_interval: Duration::from_secs(1),
+         poll_timeout: Duration::from_secs(5 * 60),
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   /// メディアをアップロードする。
+   ///
+   /// サーバー側の処理に時間がかかる場合は処理が完了するまで待機し、
+   /// `url`が取得できる状態のMediaAttachmentを返す。
+   ///
+   /// `on_progress`には送信済みのバイト数とファイルサイズが渡される
+   pub fn upload_media(
+      &mut self,
+      token: &Token,
+      media: MediaUpload,
+      on_progress: impl FnMut(u64, u64) + Send + 'static,
+      canceller: &MediaUploadCanceller
+   ) -> anyhow::Result<MediaAttachment> {
+      use anyhow::bail;
+      use mastodon_webapi::api::media;
+      use crate::conversion;
+
+      let MediaUpload { file_name, mime_type, file, description, focus } = media;
+      let file_size = file.len() as u64;
+
+      let reader = ProgressReader {
+         inner: io::Cursor::new(file),
+         total: file_size,
+         on_progress,
+         canceller: canceller.clone(),
+      };
+
+      let file = media::MediaFile {
+         file_name: &file_name,
+         mime_type: mime_type.as_deref(),
+         reader: Box::new(reader),
+         size: file_size,
+      };
+
+      let api_media_attachment = media::post_media_v2(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         file,
+         description.as_deref(),
+         focus.as_ref().map(focus_to_api).as_deref()
+      );
+
+      if canceller.is_cancelled() {
+         bail!("media upload is cancelled");
+      }
+
+      let mut media_attachment
+         = conversion::media_attachment::from_api(api_media_attachment?)?;
+
+      let mut elapsed = Duration::ZERO;
+
+      while media_attachment.url.is_none() {
+         if elapsed >= self.poll_timeout {
+            bail!("media processing timed out: {}", media_attachment.id.0);
+         }
+
+         std::thread::sleep(self.poll_interval);
+         elapsed += self.poll_interval;
+
+         if canceller.is_cancelled() {
+            bail!("media upload is cancelled");
+         }
+
+         let api_media_attachment = media::get_media(
+            self.transport.as_ref(),
+            &token.instance.get().url,
+            &token.access_token,
+            &media_attachment.id.0
+         )?;
+
+         media_attachment = conversion::media_attachment::from_api(api_media_attachment)?;
+      }
+
+      Ok(media_attachment)
+   }
+
+   /// 代替テキストやフォーカスを変更する。
+   /// Statusに添付する前のメディアのみ変更できる
+   pub fn update_media(
+      &mut self,
+      token: &Token,
+      id: &MediaAttachmentId,
+      description: Option<&str>,
+      focus: Option<&ImageFocus>
+   ) -> anyhow::Result<MediaAttachment> {
+      use mastodon_webapi::api::media;
+      use crate::conversion;
+
+      let api_media_attachment = media::put_media(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &id.0,
+         description,
+         focus.map(focus_to_api).as_deref()
+      )?;
+
+      conversion::media_attachment::from_api(api_media_attachment)
+   }
+}
+
+fn focus_to_api(focus: &ImageFocus) -> String {
+   format!("{},{}", focus.x, focus.y)
+}
+
+struct ProgressReader<R, F> {
+   inner: R,
+   total: u64,
+   on_progress: F,
+   canceller: MediaUploadCanceller,
+}
+
+impl<R: Read + io::Seek, F: FnMut(u64, u64)> Read for ProgressReader<R, F> {
+   fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+      if self.canceller.is_cancelled() {
+         return Err(io::Error::other("media upload is cancelled"));
+      }
+
+      let size = self.inner.read(buf)?;
+      let position = self.inner.stream_position()?;
+      (self.on_progress)(position, self.total);
+      Ok(size)
+   }
+}
+
+#[cfg(feature = "jvm")]
+mod jvm {
+   use jni::JNIEnv;
+   use jni::objects::{JByteArray, JObject};
+   use mastodon_entity::jvm_types::{
+      JvmMediaAttachment, JvmMediaAttachmentImageFocus, JvmToken,
+   };
+   use mastodon_entity::token::Token;
+   use panoptiqon::jvm_types::{JvmNullable, JvmString};
+   use crate::cache;
+   use super::{MediaRepository, MediaUpload, MediaUploadCanceller};
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidMediaRepository_postMedia<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      file_name: JvmString<'local>,
+      mime_type: JvmNullable<'local, JvmString<'local>>,
+      file: JByteArray<'local>,
+      description: JvmNullable<'local, JvmString<'local>>,
+      focus: JvmNullable<'local, JvmMediaAttachmentImageFocus<'local>>,
+      progress_listener: JObject<'local>
+   ) -> JvmMediaAttachment<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      upload_media(&mut env, token, file_name, mime_type, file, description, focus, progress_listener)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopMediaRepository_postMedia<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      file_name: JvmString<'local>,
+      mime_type: JvmNullable<'local, JvmString<'local>>,
+      file: JByteArray<'local>,
+      description: JvmNullable<'local, JvmString<'local>>,
+      focus: JvmNullable<'local, JvmMediaAttachmentImageFocus<'local>>,
+      progress_listener: JObject<'local>
+   ) -> JvmMediaAttachment<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      upload_media(&mut env, token, file_name, mime_type, file, description, focus, progress_listener)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn upload_media<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      file_name: JvmString<'local>,
+      mime_type: JvmNullable<'local, JvmString<'local>>,
+      file: JByteArray<'local>,
+      description: JvmNullable<'local, JvmString<'local>>,
+      focus: JvmNullable<'local, JvmMediaAttachmentImageFocus<'local>>,
+      progress_listener: JObject<'local>
+   ) -> anyhow::Result<JvmMediaAttachment<'local>> {
+      use jni::objects::JValue;
+      use mastodon_entity::media_attachment::ImageFocus;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut media_repository = MediaRepository::new();
+
+      let token = clone_token_from_jvm(env, &token)?;
+
+      let media = MediaUpload {
+         file_name: String::clone_from_jvm(env, &file_name),
+         mime_type: Option::<String>::clone_from_jvm(env, &mime_type),
+         file: env.convert_byte_array(&file)?,
+         description: Option::<String>::clone_from_jvm(env, &description),
+         focus: Option::<ImageFocus>::clone_from_jvm(env, &focus),
+      };
+
+      // 送信はJVMにアタッチされていないスレッドで行われることがあるため、
+      // リスナーはGlobalRefで保持し、呼び出す際にアタッチする
+      let progress_listener = if progress_listener.is_null() {
+         None
+      } else {
+         Some((env.get_java_vm()?, env.new_global_ref(&progress_listener)?))
+      };
+
+      let on_progress = move |sent_bytes: u64, total_bytes: u64| {
+         let Some((vm, listener)) = &progress_listener else { return; };
+         let Ok(mut env) = vm.attach_current_thread_as_daemon() else { return; };
+
+         let _ = env.call_method(
+            listener, "onProgress", "(JJ)V",
+            &[JValue::Long(sent_bytes as i64), JValue::Long(total_bytes as i64)]
+         );
+
+         if env.exception_check().unwrap_or(false) {
+            let _ = env.exception_describe();
+            let _ = env.exception_clear();
+         }
+      };
+
+      let media_attachment = media_repository.upload_media(
+         &token, media, on_progress, &MediaUploadCanceller::new()
+      )?;
+
+      Ok(media_attachment.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidMediaRepository_putMedia<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_media_id: JvmString<'local>,
+      description: JvmNullable<'local, JvmString<'local>>,
+      focus: JvmNullable<'local, JvmMediaAttachmentImageFocus<'local>>
+   ) -> JvmMediaAttachment<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      update_media(&mut env, token, raw_media_id, description, focus)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopMediaRepository_putMedia<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      raw_media_id: JvmString<'local>,
+      description: JvmNullable<'local, JvmString<'local>>,
+      focus: JvmNullable<'local, JvmMediaAttachmentImageFocus<'local>>
+   ) -> JvmMediaAttachment<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      update_media(&mut env, token, raw_media_id, description, focus)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn update_media<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      raw_media_id: JvmString<'local>,
+      description: JvmNullable<'local, JvmString<'local>>,
+      focus: JvmNullable<'local, JvmMediaAttachmentImageFocus<'local>>
+   ) -> anyhow::Result<JvmMediaAttachment<'local>> {
+      use mastodon_entity::media_attachment::{ImageFocus, MediaAttachmentId};
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut media_repository = MediaRepository::new();
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let media_id = MediaAttachmentId(String::clone_from_jvm(env, &raw_media_id));
+      let description = Option::<String>::clone_from_jvm(env, &description);
+      let focus = Option::<ImageFocus>::clone_from_jvm(env, &focus);
+
+      let media_attachment = media_repository.update_media(
+         &token, &media_id, description.as_deref(), focus.as_ref()
+      )?;
+
+      Ok(media_attachment.clone_into_jvm(env))
+   }
+
+   fn clone_token_from_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      token: &JvmToken<'local>
+   ) -> anyhow::Result<Token> {
+      let instance = token.instance(env);
+      let instance = cache::instance::clone_from_jvm(env, &instance)?;
+      Ok(Token::clone_from_jvm(env, token, instance))
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use std::time::Duration;
+   use mastodon_entity::token::Token;
+   use crate::fixture;
+   use super::{MediaRepository, MediaUpload, MediaUploadCanceller};
+
+   fn token() -> Token {
+      fixture::token("https://example.com/", "0.0.0")
+   }
+
+   #[test]
+   fn upload_media_polls_until_processed() {
+      use std::sync::{Arc, Mutex};
+      use mastodon_entity::media_attachment::ImageFocus;
+
+      let transport = fixture::replay("media/upload.json");
+      let mut repository = MediaRepository::new();
+      repository.transport = transport.clone();
+      repository.poll_interval = Duration::ZERO;
+
+      let progress = Arc::new(Mutex::new(vec![]));
+
+      let media = MediaUpload {
+         file_name: "image.png".to_string(),
+         mime_type: Some("image/png".to_string()),
+         file: vec![0; 1024],
+         description: Some("description".to_string()),
+         focus: Some(ImageFocus { x: 0.5, y: -0.25 }),
+      };
+
+      let media_attachment = repository.upload_media(
+         &token(),
+         media,
+         {
+            let progress = progress.clone();
+            move |sent, total| progress.lock().unwrap().push((sent, total))
+         },
+         &MediaUploadCanceller::new()
+      ).unwrap();
+
+      assert_eq!(4, transport.requests().len());
+      assert_eq!(
+         Some("https://example.com/media".parse().unwrap()),
+         media_attachment.url
+      );
+      assert_eq!(Some(&(1024, 1024)), progress.lock().unwrap().last());
+
+      let request = &transport.requests()[0];
+      let content_type = request.headers["content-type"].to_str().unwrap();
+      assert!(content_type.starts_with("multipart/form-data; boundary="));
+
+      let body = String::from_utf8_lossy(request.body.as_deref().unwrap());
+      assert!(body.contains(
+         "name=\"file\"; filename=\"image.png\"\r\nContent-Type: image/png\r\n\r\n"
+      ));
+      assert!(body.contains("name=\"description\"\r\n\r\ndescription\r\n"));
+      assert!(body.contains("name=\"focus\"\r\n\r\n0.5,-0.25\r\n"));
+   }
+
+   #[test]
+   fn cancel_upload() {
+      // ファイルを読み切る前にキャンセルされるのでリクエストは完了しない
+      let mut repository = MediaRepository::new();
+      repository.transport = fixture::replay("media/cancel.json");
+      repository.poll_interval = Duration::ZERO;
+
+      let canceller = MediaUploadCanceller::new();
+      canceller.cancel();
+
+      let media = MediaUpload {
+         file_name: "image.png".to_string(),
+         mime_type: None,
+         file: vec![0; 1024],
+         description: None,
+         focus: None,
+      };
+
+      let result = repository.upload_media(&token(), media, |_, _| {}, &canceller);
+
+      assert!(result.is_err());
+   }
+}
@@ -0,0 +1,637 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use ext_reqwest::transport::{ReqwestTransport, Transport};
+use mastodon_entity::notification::Notification;
+use mastodon_entity::page::{Page, PageCursor};
+use mastodon_entity::token::Token;
+use mastodon_webapi::entity::notification::{
+   GroupedNotificationsResults, Notification as ApiNotification,
+};
+use mastodon_webapi::api::notifications::{NotificationsV1Params, NotificationsV2Params};
+use mastodon_webapi::pagination::{Page as ApiPage, PageParams, Paged as ApiPaged};
+use semver::Version;
+
+#[cfg(not(feature = "jvm"))]
+use std::marker::PhantomData;
+
+#[cfg(feature = "jvm")]
+use jni::{JavaVM, JNIEnv};
+
+pub struct NotificationRepository<'jni> {
+   #[cfg(not(feature = "jvm"))]
+   env: PhantomData<&'jni ()>,
+   #[cfg(feature = "jvm")]
+   env: JNIEnv<'jni>,
+   transport: Arc<dyn Transport>,
+}
+
+impl NotificationRepository<'_> {
+   #[cfg(not(feature = "jvm"))]
+   pub fn new() -> NotificationRepository<'static> {
+      NotificationRepository {
+         env: PhantomData,
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   #[cfg(feature = "jvm")]
+   pub fn new<'jni>(env: &JNIEnv<'jni>) -> NotificationRepository<'jni> {
+      NotificationRepository {
+         env: unsafe { env.unsafe_clone() },
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   /// mastodon 4.3.0以降のサーバーではグループ化された通知を取得する。
+   ///
+   /// `cursor`には前回取得したページの`next`または`prev`を渡す。
+   /// `None`の場合最新のページを取得する
+   pub fn get_notifications(
+      &mut self,
+      token: &Token,
+      cursor: Option<&PageCursor>,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Notification>> {
+      use mastodon_webapi::api::notifications;
+
+      let page = PageParams {
+         max_id: cursor.and_then(|c| c.max_id.as_deref()),
+         since_id: cursor.and_then(|c| c.since_id.as_deref()),
+         min_id: cursor.and_then(|c| c.min_id.as_deref()),
+         limit,
+      };
+
+      if supports_grouped_notifications(token) {
+         let api_paged = notifications::get_notifications_v2(
+            self.transport.as_ref(),
+            &token.instance.get().url,
+            &token.access_token,
+            &NotificationsV2Params {
+               grouped_types: GROUPED_TYPES,
+               ..NotificationsV2Params::default()
+            },
+            &page
+         )?;
+
+         self.convert_grouped_page(token, api_paged)
+      } else {
+         let api_page = notifications::get_notifications_v1(
+            self.transport.as_ref(),
+            &token.instance.get().url,
+            &token.access_token,
+            &NotificationsV1Params::default(),
+            &page
+         )?;
+
+         self.convert_page(token, api_page)
+      }
+   }
+
+   /// グループ化された通知の場合はグループ内の通知がすべて削除される
+   pub fn dismiss(
+      &mut self,
+      token: &Token,
+      notification: &Notification
+   ) -> anyhow::Result<()> {
+      use mastodon_webapi::api::notifications;
+
+      match notification.group_key {
+         Some(ref group_key) if supports_grouped_notifications(token) => {
+            notifications::post_dismiss_v2(
+               self.transport.as_ref(),
+               &token.instance.get().url,
+               &token.access_token,
+               group_key
+            )?;
+         }
+         _ => {
+            notifications::post_dismiss_v1(
+               self.transport.as_ref(),
+               &token.instance.get().url,
+               &token.access_token,
+               &notification.id.local.0
+            )?;
+         }
+      }
+
+      Ok(())
+   }
+
+   pub fn clear(&mut self, token: &Token) -> anyhow::Result<()> {
+      use mastodon_webapi::api::notifications;
+
+      notifications::post_clear(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token
+      )?;
+
+      Ok(())
+   }
+
+   fn convert_grouped_page(
+      &mut self,
+      token: &Token,
+      api_paged: ApiPaged<GroupedNotificationsResults>
+   ) -> anyhow::Result<Page<Notification>> {
+      use crate::cache;
+      use crate::conversion;
+
+      let ApiPaged { body, next, prev } = api_paged;
+
+      let mut account_repo = cache::account::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut status_repo = cache::status::status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_status_repo = cache::status::no_credential_status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_poll_repo = cache::poll::no_credential_poll_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let notifications = conversion::notification::grouped_from_api(
+         #[cfg(feature = "jvm")] &mut self.env,
+         token.instance.clone(),
+         body,
+         &mut account_repo,
+         &mut status_repo,
+         &mut no_credential_status_repo,
+         &mut no_credential_poll_repo
+      )?;
+
+      let page = Page {
+         items: notifications,
+         next: next.map(conversion::page::from_api),
+         prev: prev.map(conversion::page::from_api),
+      };
+
+      Ok(page)
+   }
+
+   fn convert_page(
+      &mut self,
+      token: &Token,
+      api_page: ApiPage<ApiNotification>
+   ) -> anyhow::Result<Page<Notification>> {
+      use crate::cache;
+      use crate::conversion;
+
+      let ApiPage { items, next, prev } = api_page;
+
+      let mut account_repo = cache::account::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut status_repo = cache::status::status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_status_repo = cache::status::no_credential_status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_poll_repo = cache::poll::no_credential_poll_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let notifications = items.into_iter()
+         .flat_map(|api_notification|
+            conversion::notification::from_api(
+               #[cfg(feature = "jvm")] &mut self.env,
+               token.instance.clone(),
+               api_notification,
+               &mut account_repo,
+               &mut status_repo,
+               &mut no_credential_status_repo,
+               &mut no_credential_poll_repo
+            )
+         )
+         .collect();
+
+      let page = Page {
+         items: notifications,
+         next: next.map(conversion::page::from_api),
+         prev: prev.map(conversion::page::from_api),
+      };
+
+      Ok(page)
+   }
+}
+
+/// [NotificationRepository]の非同期版
+pub struct AsyncNotificationRepository {
+   #[cfg(feature = "jvm")]
+   vm: JavaVM,
+   transport: Arc<dyn Transport>,
+}
+
+#[cfg(not(feature = "jvm"))]
+impl Default for AsyncNotificationRepository {
+   fn default() -> AsyncNotificationRepository {
+      AsyncNotificationRepository::new()
+   }
+}
+
+impl AsyncNotificationRepository {
+   #[cfg(not(feature = "jvm"))]
+   pub fn new() -> AsyncNotificationRepository {
+      AsyncNotificationRepository {
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   #[cfg(feature = "jvm")]
+   pub fn new(env: &JNIEnv) -> jni::errors::Result<AsyncNotificationRepository> {
+      Ok(AsyncNotificationRepository {
+         vm: env.get_java_vm()?,
+         transport: Arc::new(ReqwestTransport),
+      })
+   }
+
+   /// [NotificationRepository::get_notifications]の非同期版
+   pub async fn get_notifications(
+      &self,
+      token: &Token,
+      cursor: Option<&PageCursor>,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Notification>> {
+      use mastodon_webapi::api::notifications;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let page = PageParams {
+         max_id: cursor.and_then(|c| c.max_id.as_deref()),
+         since_id: cursor.and_then(|c| c.since_id.as_deref()),
+         min_id: cursor.and_then(|c| c.min_id.as_deref()),
+         limit,
+      };
+
+      if supports_grouped_notifications(token) {
+         let api_paged = notifications::get_notifications_v2_async(
+            self.transport.as_ref(),
+            &instance_url,
+            &token.access_token,
+            &NotificationsV2Params {
+               grouped_types: GROUPED_TYPES,
+               ..NotificationsV2Params::default()
+            },
+            &page
+         ).await?;
+
+         self.with_repository(|repository| repository.convert_grouped_page(token, api_paged))
+      } else {
+         let api_page = notifications::get_notifications_v1_async(
+            self.transport.as_ref(),
+            &instance_url,
+            &token.access_token,
+            &NotificationsV1Params::default(),
+            &page
+         ).await?;
+
+         self.with_repository(|repository| repository.convert_page(token, api_page))
+      }
+   }
+
+   /// レスポンスを受け取ったスレッドでそのままキャッシュに変換する
+   fn with_repository<T>(
+      &self,
+      f: impl FnOnce(&mut NotificationRepository) -> anyhow::Result<T>
+   ) -> anyhow::Result<T> {
+      #[cfg(not(feature = "jvm"))]
+      {
+         f(&mut NotificationRepository::new())
+      }
+
+      #[cfg(feature = "jvm")]
+      {
+         // ランタイムのスレッドはJVMにアタッチしたままにし、
+         // ローカル参照はフレームごとに解放する
+         let mut env = self.vm.attach_current_thread_as_daemon()?;
+         env.with_local_frame(64, |env| f(&mut NotificationRepository::new(env)))
+      }
+   }
+}
+
+/// グループ化して取得する通知の種類
+const GROUPED_TYPES: &[&str] = &["favourite", "reblog", "follow"];
+
+fn supports_grouped_notifications(token: &Token) -> bool {
+   let instance_version = Version::parse(&token.instance.get().version)
+      .unwrap_or(Version::new(0, 0, 0));
+
+   instance_version >= Version::new(4, 3, 0)
+}
+
+#[cfg(feature = "jvm")]
+mod jvm {
+   use jni::JNIEnv;
+   use jni::objects::JObject;
+   use mastodon_entity::jvm_types::{JvmNotification, JvmPage, JvmPageCursor, JvmToken};
+   use mastodon_entity::token::Token;
+   use panoptiqon::jvm_types::{JvmLong, JvmNullable};
+   use crate::cache;
+   use super::{AsyncNotificationRepository, NotificationRepository};
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidNotificationRepository_loadNotifications<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmNullable<'local, JvmPageCursor<'local>>,
+      limit: JvmNullable<'local, JvmLong<'local>>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_notifications(&mut env, token, cursor, limit)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopNotificationRepository_loadNotifications<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmNullable<'local, JvmPageCursor<'local>>,
+      limit: JvmNullable<'local, JvmLong<'local>>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_notifications(&mut env, token, cursor, limit)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_notifications<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmNullable<'local, JvmPageCursor<'local>>,
+      limit: JvmNullable<'local, JvmLong<'local>>
+   ) -> anyhow::Result<JvmPage<'local>> {
+      use mastodon_entity::page::PageCursor;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut notification_repository = NotificationRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let cursor = Option::<PageCursor>::clone_from_jvm(env, &cursor);
+      let limit = Option::<i64>::clone_from_jvm(env, &limit)
+         .map(u32::try_from)
+         .transpose()?;
+
+      let page = notification_repository.get_notifications(&token, cursor.as_ref(), limit)?;
+      Ok(page.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidNotificationRepository_dismiss<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      notification: JvmNotification<'local>
+   ) {
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      if let Err(e) = dismiss(&mut env, token, notification) {
+         throw_web_api_exception(&mut env, &e);
+      }
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopNotificationRepository_dismiss<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      notification: JvmNotification<'local>
+   ) {
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      if let Err(e) = dismiss(&mut env, token, notification) {
+         throw_web_api_exception(&mut env, &e);
+      }
+   }
+
+   fn dismiss<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      notification: JvmNotification<'local>
+   ) -> anyhow::Result<()> {
+      use mastodon_entity::notification::Notification;
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let mut notification_repository = NotificationRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let notification = Notification::clone_from_jvm(env, &notification);
+      notification_repository.dismiss(&token, &notification)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidNotificationRepository_clear<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>
+   ) {
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      if let Err(e) = clear(&mut env, token) {
+         throw_web_api_exception(&mut env, &e);
+      }
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopNotificationRepository_clear<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>
+   ) {
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      if let Err(e) = clear(&mut env, token) {
+         throw_web_api_exception(&mut env, &e);
+      }
+   }
+
+   fn clear<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>
+   ) -> anyhow::Result<()> {
+      let mut notification_repository = NotificationRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      notification_repository.clear(&token)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidNotificationRepository_loadNotificationsAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmNullable<'local, JvmPageCursor<'local>>,
+      limit: JvmNullable<'local, JvmLong<'local>>,
+      callback: JObject<'local>
+   ) {
+      get_notifications_async(&mut env, token, cursor, limit, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopNotificationRepository_loadNotificationsAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmNullable<'local, JvmPageCursor<'local>>,
+      limit: JvmNullable<'local, JvmLong<'local>>,
+      callback: JObject<'local>
+   ) {
+      get_notifications_async(&mut env, token, cursor, limit, callback);
+   }
+
+   fn get_notifications_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmNullable<'local, JvmPageCursor<'local>>,
+      limit: JvmNullable<'local, JvmLong<'local>>,
+      callback: JObject<'local>
+   ) {
+      use ext_reqwest::callback::WebApiCallback;
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+      use mastodon_entity::page::PageCursor;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+      use panoptiqon::jvm_type::JvmType;
+
+      let result = (|| {
+         let repository = AsyncNotificationRepository::new(env)?;
+         let token = clone_token_from_jvm(env, &token)?;
+         let cursor = Option::<PageCursor>::clone_from_jvm(env, &cursor);
+         let limit = Option::<i64>::clone_from_jvm(env, &limit)
+            .map(u32::try_from)
+            .transpose()?;
+         let callback = WebApiCallback::new(env, &callback)?;
+
+         let future = async move {
+            repository.get_notifications(&token, cursor.as_ref(), limit).await
+         };
+
+         callback.spawn(future, |env, page| {
+            let page: JvmPage = page.clone_into_jvm(env);
+            page.into_j_object()
+         });
+
+         anyhow::Ok(())
+      })();
+
+      if let Err(e) = result {
+         throw_web_api_exception(env, &e);
+      }
+   }
+
+   fn clone_token_from_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      token: &JvmToken<'local>
+   ) -> anyhow::Result<Token> {
+      let instance = token.instance(env);
+      let instance = cache::instance::clone_from_jvm(env, &instance)?;
+      Ok(Token::clone_from_jvm(env, token, instance))
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use mastodon_entity::token::Token;
+   use crate::fixture;
+   use super::NotificationRepository;
+
+   fn token(version: &str) -> Token {
+      fixture::token("https://notification.example.com/", version)
+   }
+
+   #[test]
+   fn switch_endpoint_by_instance_version() {
+      use mastodon_entity::notification::{
+         NotificationId, NotificationLocalId, NotificationType,
+      };
+      use mastodon_entity::status::{StatusId, StatusLocalId};
+
+      let transport = fixture::replay("notification/switch_endpoint.json");
+      let mut repository = NotificationRepository::new();
+      repository.transport = transport.clone();
+
+      let token_v1 = token("4.2.9");
+      let page = repository.get_notifications(&token_v1, None, None).unwrap();
+      assert_eq!("/api/v1/notifications", transport.requests()[0].url.path());
+
+      assert_eq!(1, page.items.len());
+      assert_eq!(
+         NotificationId {
+            instance_url: token_v1.instance.get().url.clone(),
+            local: NotificationLocalId("notification id".to_string()),
+         },
+         page.items[0].id
+      );
+      assert_eq!(NotificationType::Mention, page.items[0].notification_type);
+      assert_eq!(
+         Some(StatusId {
+            instance_url: token_v1.instance.get().url.clone(),
+            local: StatusLocalId("mentioned status id".to_string()),
+         }),
+         page.items[0].status.as_ref().map(|s| s.get().id.clone())
+      );
+
+      let token_v2 = token("4.3.0");
+      let page = repository.get_notifications(&token_v2, None, None).unwrap();
+      assert_eq!("/api/v2/notifications", transport.requests()[1].url.path());
+
+      assert_eq!(2, page.items.len());
+      assert_eq!(NotificationType::Favorite, page.items[0].notification_type);
+      assert_eq!(Some(3), page.items[0].notification_count);
+      assert_eq!(Some("favourite-123".to_string()), page.items[0].group_key);
+      assert!(page.items[0].accounts.is_empty());
+      assert_eq!(
+         Some(StatusId {
+            instance_url: token_v2.instance.get().url.clone(),
+            local: StatusLocalId("favourited status id".to_string()),
+         }),
+         page.items[0].status.as_ref().map(|s| s.get().id.clone())
+      );
+      assert_eq!(
+         NotificationType::Unknown("admin.something".to_string()),
+         page.items[1].notification_type
+      );
+   }
+
+   #[test]
+   fn get_notifications_async_switches_endpoint() {
+      use ext_reqwest::runtime::RUNTIME;
+      use mastodon_entity::notification::NotificationType;
+      use super::AsyncNotificationRepository;
+
+      let transport = fixture::replay("notification/switch_endpoint.json");
+      let mut repository = AsyncNotificationRepository::new();
+      repository.transport = transport.clone();
+
+      let page = RUNTIME
+         .block_on(repository.get_notifications(&token("4.2.9"), None, None))
+         .unwrap();
+      assert_eq!("/api/v1/notifications", transport.requests()[0].url.path());
+      assert_eq!(1, page.items.len());
+      assert_eq!(NotificationType::Mention, page.items[0].notification_type);
+
+      let page = RUNTIME
+         .block_on(repository.get_notifications(&token("4.3.0"), None, None))
+         .unwrap();
+      assert_eq!("/api/v2/notifications", transport.requests()[1].url.path());
+      assert_eq!(2, page.items.len());
+      assert_eq!(Some("favourite-123".to_string()), page.items[0].group_key);
+   }
+}
@@ -0,0 +1,109 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::path::PathBuf;
+
+#[cfg(feature = "jvm")]
+use jni::JNIEnv;
+
+/// インスタンス、ステータス、アカウント、投票、アカウントのアイコンの
+/// キャッシュを`directory`に永続化し、前回の起動時に保存されたものを復元する。
+///
+/// いずれかのRepositoryを使用するより前に呼ぶ必要がある。
+/// キャッシュは参照先のCacheをキーとして保存しているため、
+/// 参照される側のRepositoryから順に復元する。
+pub fn initialize(
+   #[cfg(feature = "jvm")] env: &mut JNIEnv,
+   directory: impl Into<PathBuf>
+) -> anyhow::Result<()> {
+   use crate::cache;
+
+   ext_panoptiqon::persistence::set_directory(directory);
+   cache::register_cache_references();
+
+   cache::instance::repo().read(#[cfg(feature = "jvm")] env)?;
+   cache::account::repo().read(#[cfg(feature = "jvm")] env)?;
+   cache::poll::no_credential_poll_repo().read(#[cfg(feature = "jvm")] env)?;
+   cache::status::no_credential_status_repo().read(#[cfg(feature = "jvm")] env)?;
+   cache::status::status_repo().read(#[cfg(feature = "jvm")] env)?;
+
+   Ok(())
+}
+
+/// Repositoryを経由せずに変更されたキャッシュをディスクに書き込む
+pub fn flush() {
+   use crate::cache;
+
+   cache::instance::repo().flush();
+   cache::account::repo().flush();
+   cache::account_icon::repo().flush();
+   cache::poll::no_credential_poll_repo().flush();
+   cache::status::no_credential_status_repo().flush();
+   cache::status::status_repo().flush();
+}
+
+#[cfg(feature = "jvm")]
+mod jvm {
+   use jni::JNIEnv;
+   use jni::objects::JObject;
+   use panoptiqon::jvm_types::JvmString;
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidPersistentCache_initialize<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      directory: JvmString<'local>
+   ) {
+      initialize(&mut env, directory);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopPersistentCache_initialize<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      directory: JvmString<'local>
+   ) {
+      initialize(&mut env, directory);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidPersistentCache_flush<'local>(
+      _env: JNIEnv<'local>,
+      _obj: JObject<'local>
+   ) {
+      super::flush();
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopPersistentCache_flush<'local>(
+      _env: JNIEnv<'local>,
+      _obj: JObject<'local>
+   ) {
+      super::flush();
+   }
+
+   fn initialize<'local>(
+      env: &mut JNIEnv<'local>,
+      directory: JvmString<'local>
+   ) {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let directory = String::clone_from_jvm(env, &directory);
+
+      // キャッシュを復元できなくても起動は続ける
+      let _ = super::initialize(env, directory);
+   }
+}
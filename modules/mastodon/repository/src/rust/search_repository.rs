@@ -0,0 +1,563 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use ext_reqwest::transport::{ReqwestTransport, Transport};
+use mastodon_entity::account::{Account, AccountId};
+use mastodon_entity::search::SearchResult;
+use mastodon_entity::status::Status;
+use mastodon_entity::token::Token;
+use panoptiqon::cache::Cache;
+use url::Url;
+
+#[cfg(not(feature = "jvm"))]
+use std::marker::PhantomData;
+
+#[cfg(feature = "jvm")]
+use jni::JNIEnv;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum SearchType {
+   Account,
+   Status,
+   Hashtag,
+}
+
+impl SearchType {
+   fn raw(&self) -> &'static str {
+      match self {
+         SearchType::Account => "accounts",
+         SearchType::Status  => "statuses",
+         SearchType::Hashtag => "hashtags",
+      }
+   }
+}
+
+/// URLやacctをログイン中のインスタンス上のアカウントまたは投稿に解決した結果
+#[derive(Debug, PartialEq, Clone)]
+pub enum ResolvedObject {
+   Account(Cache<Account>),
+   Status(Cache<Status>),
+}
+
+pub struct SearchRepository<'jni> {
+   #[cfg(not(feature = "jvm"))]
+   env: PhantomData<&'jni ()>,
+   #[cfg(feature = "jvm")]
+   env: JNIEnv<'jni>,
+   transport: Arc<dyn Transport>,
+}
+
+impl SearchRepository<'_> {
+   #[cfg(not(feature = "jvm"))]
+   pub fn new() -> SearchRepository<'static> {
+      SearchRepository {
+         env: PhantomData,
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   #[cfg(feature = "jvm")]
+   pub fn new<'jni>(env: &JNIEnv<'jni>) -> SearchRepository<'jni> {
+      SearchRepository {
+         env: unsafe { env.unsafe_clone() },
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   /// * `search_type` - Noneの場合すべての種類を検索する
+   /// * `resolves` - URLやacctで指定されたリモートのアカウントや投稿を
+   ///   インスタンスに取得させる
+   /// * `account_id` - 指定した場合そのアカウントの投稿のみ検索する
+   /// * `offset` - 2ページ目以降を取得する場合、取得済みの件数を指定する
+   pub fn search(
+      &mut self,
+      token: &Token,
+      query: &str,
+      search_type: Option<SearchType>,
+      resolves: bool,
+      is_only_following: bool,
+      account_id: Option<&AccountId>,
+      excludes_unreviewed: bool,
+      offset: Option<u32>,
+      limit: Option<u32>
+   ) -> anyhow::Result<SearchResult> {
+      use mastodon_webapi::api::search::{self, SearchParams};
+      use crate::cache;
+      use crate::conversion;
+
+      let api_search = search::get_search_v2(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         Some(&token.access_token),
+         query,
+         &SearchParams {
+            r#type: search_type.map(|t| t.raw()),
+            resolve: Some(resolves),
+            following: Some(is_only_following),
+            account_id: account_id.map(|id| id.local.0.as_str()),
+            exclude_unreviewed: Some(excludes_unreviewed),
+            limit,
+            offset,
+            ..SearchParams::default()
+         }
+      )?;
+
+      let mut account_repo = cache::account::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut status_repo = cache::status::status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_status_repo = cache::status::no_credential_status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_poll_repo = cache::poll::no_credential_poll_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      conversion::search::from_api(
+         #[cfg(feature = "jvm")] &mut self.env,
+         token.instance.clone(),
+         api_search,
+         &mut account_repo,
+         &mut status_repo,
+         &mut no_credential_status_repo,
+         &mut no_credential_poll_repo
+      )
+   }
+
+   /// 他のサーバーの投稿やプロフィールのURL、または`@username@domain`形式の
+   /// acctを、ログイン中のインスタンス上の投稿またはアカウントに解決する。
+   ///
+   /// URLの場合は投稿、アカウントの順に検索し、uriまたはurlが一致するものを
+   /// 返す。それ以外の場合はacctとしてアカウントを検索する。
+   ///
+   /// 返される投稿やアカウントのidはログイン中のインスタンスのものなので、
+   /// そのままお気に入りやフォローなどの操作に使える。
+   /// 見つからなかった場合None
+   pub fn resolve(
+      &mut self,
+      token: &Token,
+      query: &str
+   ) -> anyhow::Result<Option<ResolvedObject>> {
+      let query = query.trim();
+
+      let url = Url::parse(query).ok()
+         .filter(|url| matches!(url.scheme(), "http" | "https"));
+
+      let Some(url) = url else {
+         let accounts = self.resolve_search(token, query, SearchType::Account)?
+            .accounts;
+         let resolved = accounts.into_iter().next().map(ResolvedObject::Account);
+         return Ok(resolved);
+      };
+
+      if let Some(status) = self.resolve_status_url(token, query, &url)? {
+         return Ok(Some(ResolvedObject::Status(status)));
+      }
+
+      let accounts = self.resolve_search(token, query, SearchType::Account)?
+         .accounts;
+      let resolved = accounts.into_iter()
+         .find(|account| account.get().url.as_ref() == Some(&url))
+         .map(ResolvedObject::Account);
+      Ok(resolved)
+   }
+
+   fn resolve_status_url(
+      &mut self,
+      token: &Token,
+      query: &str,
+      url: &Url
+   ) -> anyhow::Result<Option<Cache<Status>>> {
+      use crate::cache;
+
+      let statuses = self.resolve_search(token, query, SearchType::Status)?
+         .statuses;
+
+      let status = statuses.into_iter().find(|status| {
+         let no_credential = status.no_credential.get();
+         no_credential.uri.as_deref() == Some(query)
+            || no_credential.url.as_ref() == Some(url)
+      });
+
+      let Some(status) = status else { return Ok(None); };
+
+      let status = cache::status::status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?
+         .save(status);
+
+      Ok(Some(status))
+   }
+
+   fn resolve_search(
+      &mut self,
+      token: &Token,
+      query: &str,
+      search_type: SearchType
+   ) -> anyhow::Result<SearchResult> {
+      self.search(
+         token,
+         query,
+         Some(search_type),
+         /* resolves = */ true,
+         /* is_only_following = */ false,
+         /* account_id = */ None,
+         /* excludes_unreviewed = */ false,
+         /* offset = */ None,
+         /* limit = */ None
+      )
+   }
+}
+
+#[cfg(feature = "jvm")]
+mod jvm {
+   use ext_panoptiqon::convert_jvm_helper;
+   use jni::JNIEnv;
+   use jni::objects::JObject;
+   use jni::sys::jboolean;
+   use mastodon_entity::jvm_types::{
+      JvmAccount, JvmAccountId, JvmSearchResult, JvmStatus, JvmToken,
+   };
+   use mastodon_entity::account::Account;
+   use mastodon_entity::status::Status;
+   use mastodon_entity::token::Token;
+   use panoptiqon::cache::Cache;
+   use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+   use panoptiqon::jvm_type;
+   use panoptiqon::jvm_types::{JvmCache, JvmLong, JvmNullable, JvmString};
+   use crate::cache;
+   use super::{ResolvedObject, SearchRepository, SearchType};
+
+   jvm_type! {
+      JvmResolvedObject,
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidSearchRepository_loadSearchResult<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      query: JvmString<'local>,
+      raw_search_type: JvmNullable<'local, JvmString<'local>>,
+      resolves: jboolean,
+      is_only_following: jboolean,
+      account_id: JvmNullable<'local, JvmAccountId<'local>>,
+      excludes_unreviewed: jboolean,
+      offset: JvmNullable<'local, JvmLong<'local>>,
+      limit: JvmNullable<'local, JvmLong<'local>>
+   ) -> JvmSearchResult<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      search(
+         &mut env, token, query, raw_search_type, resolves, is_only_following,
+         account_id, excludes_unreviewed, offset, limit
+      )
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopSearchRepository_loadSearchResult<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      query: JvmString<'local>,
+      raw_search_type: JvmNullable<'local, JvmString<'local>>,
+      resolves: jboolean,
+      is_only_following: jboolean,
+      account_id: JvmNullable<'local, JvmAccountId<'local>>,
+      excludes_unreviewed: jboolean,
+      offset: JvmNullable<'local, JvmLong<'local>>,
+      limit: JvmNullable<'local, JvmLong<'local>>
+   ) -> JvmSearchResult<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      search(
+         &mut env, token, query, raw_search_type, resolves, is_only_following,
+         account_id, excludes_unreviewed, offset, limit
+      )
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn search<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      query: JvmString<'local>,
+      raw_search_type: JvmNullable<'local, JvmString<'local>>,
+      resolves: jboolean,
+      is_only_following: jboolean,
+      account_id: JvmNullable<'local, JvmAccountId<'local>>,
+      excludes_unreviewed: jboolean,
+      offset: JvmNullable<'local, JvmLong<'local>>,
+      limit: JvmNullable<'local, JvmLong<'local>>
+   ) -> anyhow::Result<JvmSearchResult<'local>> {
+      use mastodon_entity::account::AccountId;
+
+      let mut search_repository = SearchRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let query = String::clone_from_jvm(env, &query);
+      let search_type = Option::<String>::clone_from_jvm(env, &raw_search_type)
+         .map(|raw| search_type_from_raw(&raw))
+         .transpose()?;
+      let account_id = Option::<AccountId>::clone_from_jvm(env, &account_id);
+      let offset = Option::<i64>::clone_from_jvm(env, &offset)
+         .map(u32::try_from)
+         .transpose()?;
+      let limit = Option::<i64>::clone_from_jvm(env, &limit)
+         .map(u32::try_from)
+         .transpose()?;
+
+      let search_result = search_repository.search(
+         &token,
+         &query,
+         search_type,
+         resolves != 0,
+         is_only_following != 0,
+         account_id.as_ref(),
+         excludes_unreviewed != 0,
+         offset,
+         limit
+      )?;
+      Ok(search_result.clone_into_jvm(env))
+   }
+
+   fn search_type_from_raw(raw: &str) -> anyhow::Result<SearchType> {
+      use anyhow::bail;
+
+      let search_type = match raw {
+         "accounts" => SearchType::Account,
+         "statuses" => SearchType::Status,
+         "hashtags" => SearchType::Hashtag,
+         _ => bail!("unknown search type: {raw}"),
+      };
+
+      Ok(search_type)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidSearchRepository_resolve<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      query: JvmString<'local>
+   ) -> JvmNullable<'local, JvmResolvedObject<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      resolve(&mut env, token, query)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopSearchRepository_resolve<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      query: JvmString<'local>
+   ) -> JvmNullable<'local, JvmResolvedObject<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      resolve(&mut env, token, query)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn resolve<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      query: JvmString<'local>
+   ) -> anyhow::Result<JvmNullable<'local, JvmResolvedObject<'local>>> {
+      let mut search_repository = SearchRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let query = String::clone_from_jvm(env, &query);
+      let resolved = search_repository.resolve(&token, &query)?;
+      Ok(resolved.clone_into_jvm(env))
+   }
+
+   convert_jvm_helper! {
+      static RESOLVED_ACCOUNT_HELPER = impl struct ResolvedAccountConvertHelper
+         where jvm_class: "com/wcaokaze/probosqis/mastodon/repository/ResolvedObject$Account"
+      {
+         fn clone_into_jvm<'local>(..) -> JvmResolvedObject<'local>
+            where jvm_constructor: "(Lcom/wcaokaze/probosqis/panoptiqon/Cache;)V";
+
+         fn account<'local>(..) -> Cache<Account>
+            where jvm_type: JvmCache<'local, JvmAccount<'local>>,
+                  jvm_getter_method: "getAccount",
+                  jvm_return_type: "Lcom/wcaokaze/probosqis/panoptiqon/Cache;";
+      }
+
+      static RESOLVED_STATUS_HELPER = impl struct ResolvedStatusConvertHelper
+         where jvm_class: "com/wcaokaze/probosqis/mastodon/repository/ResolvedObject$Status"
+      {
+         fn clone_into_jvm<'local>(..) -> JvmResolvedObject<'local>
+            where jvm_constructor: "(Lcom/wcaokaze/probosqis/panoptiqon/Cache;)V";
+
+         fn status<'local>(..) -> Cache<Status>
+            where jvm_type: JvmCache<'local, JvmStatus<'local>>,
+                  jvm_getter_method: "getStatus",
+                  jvm_return_type: "Lcom/wcaokaze/probosqis/panoptiqon/Cache;";
+      }
+   }
+
+   impl<'local> CloneIntoJvm<'local, JvmResolvedObject<'local>> for ResolvedObject {
+      fn clone_into_jvm(&self, env: &mut JNIEnv<'local>) -> JvmResolvedObject<'local> {
+         match self {
+            ResolvedObject::Account(account) => {
+               RESOLVED_ACCOUNT_HELPER.clone_into_jvm(env, account)
+            }
+
+            ResolvedObject::Status(status) => {
+               RESOLVED_STATUS_HELPER.clone_into_jvm(env, status)
+            }
+         }
+      }
+   }
+
+   impl<'local> CloneFromJvm<'local, JvmResolvedObject<'local>> for ResolvedObject {
+      fn clone_from_jvm(
+         env: &mut JNIEnv<'local>,
+         jvm_instance: &JvmResolvedObject<'local>
+      ) -> ResolvedObject {
+         use panoptiqon::jvm_type::JvmType;
+
+         if env
+            .is_instance_of(
+               jvm_instance.j_object(),
+               "com/wcaokaze/probosqis/mastodon/repository/ResolvedObject$Account"
+            )
+            .unwrap()
+         {
+            let account = RESOLVED_ACCOUNT_HELPER.account(env, jvm_instance);
+            ResolvedObject::Account(account)
+         } else {
+            let status = RESOLVED_STATUS_HELPER.status(env, jvm_instance);
+            ResolvedObject::Status(status)
+         }
+      }
+   }
+
+   fn clone_token_from_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      token: &JvmToken<'local>
+   ) -> anyhow::Result<Token> {
+      let instance = token.instance(env);
+      let instance = cache::instance::clone_from_jvm(env, &instance)?;
+      Ok(Token::clone_from_jvm(env, token, instance))
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use mastodon_entity::account::{AccountId, AccountLocalId};
+   use mastodon_entity::status::{StatusId, StatusLocalId};
+   use mastodon_entity::token::Token;
+   use crate::fixture;
+   use super::{ResolvedObject, SearchRepository};
+
+   fn token() -> Token {
+      fixture::token("https://search.example.com/", "4.3.0")
+   }
+
+   #[test]
+   fn resolve_remote_url_to_local_id() {
+      let mut repository = SearchRepository::new();
+      repository.transport = fixture::replay("search/resolve.json");
+      let token = token();
+
+      let resolved = repository
+         .resolve(&token, " https://remote.example.com/@username/123 ")
+         .unwrap();
+      let Some(ResolvedObject::Status(status)) = resolved else {
+         panic!("{resolved:?}");
+      };
+      assert_eq!(
+         StatusId {
+            instance_url: token.instance.get().url.clone(),
+            local: StatusLocalId("local status id".to_string()),
+         },
+         status.get().id
+      );
+
+      let resolved = repository
+         .resolve(&token, "@username@remote.example.com")
+         .unwrap();
+      let Some(ResolvedObject::Account(account)) = resolved else {
+         panic!("{resolved:?}");
+      };
+      assert_eq!(
+         AccountId {
+            instance_url: token.instance.get().url.clone(),
+            local: AccountLocalId("local account id".to_string()),
+         },
+         account.get().id
+      );
+
+      let resolved = repository
+         .resolve(&token, "https://remote.example.com/@username")
+         .unwrap();
+      let Some(ResolvedObject::Account(account)) = resolved else {
+         panic!("{resolved:?}");
+      };
+      assert_eq!(
+         AccountId {
+            instance_url: token.instance.get().url.clone(),
+            local: AccountLocalId("local account id".to_string()),
+         },
+         account.get().id
+      );
+
+      // URLが一致しない検索結果は無視する
+      let resolved = repository
+         .resolve(&token, "https://remote.example.com/unknown")
+         .unwrap();
+      assert_eq!(None, resolved);
+   }
+
+   #[test]
+   fn search_hashtags() {
+      use chrono::DateTime;
+      use mastodon_entity::search::HashtagHistory;
+      use super::SearchType;
+
+      let mut repository = SearchRepository::new();
+      repository.transport = fixture::replay("search/hashtags.json");
+      let token = token();
+
+      let result = repository
+         .search(
+            &token, "probosqis", Some(SearchType::Hashtag), false, true, None,
+            false, Some(40), Some(20)
+         )
+         .unwrap();
+
+      assert!(result.accounts.is_empty());
+      assert!(result.statuses.is_empty());
+      assert_eq!(1, result.hashtags.len());
+      assert_eq!(Some("probosqis"), result.hashtags[0].hashtag.name.as_deref());
+      assert_eq!(Some(true), result.hashtags[0].is_following);
+      assert_eq!(
+         vec![
+            HashtagHistory {
+               day: DateTime::from_timestamp(946684800, 0),
+               use_count: Some(12),
+               account_count: Some(3),
+            },
+         ],
+         result.hashtags[0].history
+      );
+   }
+}
@@ -0,0 +1,2838 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use ext_reqwest::transport::{ReqwestTransport, Transport};
+use mastodon_entity::account::AccountId;
+use mastodon_entity::poll::{Poll, PollId};
+use mastodon_entity::status::{Status, StatusId, StatusVisibility};
+use mastodon_entity::status_context::{ReplyTreeNode, StatusContext};
+use mastodon_entity::status_draft::StatusDraft;
+use mastodon_entity::token::Token;
+use mastodon_webapi::api::statuses::{PollParams, StatusParams};
+use mastodon_webapi::entity::context::Context as ApiContext;
+use mastodon_webapi::entity::poll::Poll as ApiPoll;
+use mastodon_webapi::entity::status::Status as ApiStatus;
+use panoptiqon::cache::Cache;
+
+#[cfg(not(feature = "jvm"))]
+use std::marker::PhantomData;
+
+#[cfg(feature = "jvm")]
+use jni::{JavaVM, JNIEnv};
+
+pub struct StatusRepository<'jni> {
+   #[cfg(not(feature = "jvm"))]
+   env: PhantomData<&'jni ()>,
+   #[cfg(feature = "jvm")]
+   env: JNIEnv<'jni>,
+   transport: Arc<dyn Transport>,
+}
+
+impl StatusRepository<'_> {
+   #[cfg(not(feature = "jvm"))]
+   pub fn new() -> StatusRepository<'static> {
+      StatusRepository {
+         env: PhantomData,
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   #[cfg(feature = "jvm")]
+   pub fn new<'jni>(env: &JNIEnv<'jni>) -> StatusRepository<'jni> {
+      StatusRepository {
+         env: unsafe { env.unsafe_clone() },
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   /// `draft`で指定されなかった公開範囲、センシティブ、言語は
+   /// CredentialAccountのデフォルト値が使われる。
+   ///
+   /// `idempotency_key`を指定すると、同じキーで再送した場合に
+   /// 二重に投稿されることを防げる
+   pub fn post_status(
+      &mut self,
+      token: &Token,
+      draft: &StatusDraft,
+      idempotency_key: Option<&str>
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let draft = self.fill_post_defaults(token, draft)?;
+      let media_ids = media_ids(&draft);
+      let poll_options = poll_options(&draft);
+      let params = post_params(&draft, &media_ids, &poll_options);
+
+      let api_status = statuses::post_status(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         idempotency_key,
+         &params
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   /// 公開範囲とリプライ先は編集できないため`draft`の値は無視される
+   pub fn edit_status(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId,
+      draft: &StatusDraft
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let media_ids = media_ids(draft);
+      let poll_options = poll_options(draft);
+      let params = edit_params(draft, &media_ids, &poll_options);
+
+      let api_status = statuses::put_status(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0,
+         &params
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   /// 削除したStatusを削除して再編集するための下書きを返す
+   pub fn delete_status(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<StatusDraft> {
+      use mastodon_webapi::api::statuses;
+      use crate::conversion;
+
+      let api_status = statuses::delete_status(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0
+      )?;
+
+      conversion::status::draft_from_api(token.instance.clone(), api_status)
+   }
+
+   pub fn favorite(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let api_status = statuses::post_favourite(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub fn unfavorite(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let api_status = statuses::post_unfavourite(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub fn boost(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId,
+      visibility: Option<&StatusVisibility>
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let api_status = statuses::post_reblog(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0,
+         visibility.map(|v| v.raw())
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub fn unboost(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let api_status = statuses::post_unreblog(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub fn bookmark(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let api_status = statuses::post_bookmark(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub fn unbookmark(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let api_status = statuses::post_unbookmark(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub fn pin(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let api_status = statuses::post_pin(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub fn unpin(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let api_status = statuses::post_unpin(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub fn mute(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let api_status = statuses::post_mute(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub fn unmute(
+      &mut self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let api_status = statuses::post_unmute(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &status_id.local.0
+      )?;
+
+      self.save_status(token, api_status)
+   }
+
+   /// 投票の最新の結果を取得する。
+   /// キャッシュされている`NoCredentialPoll`も更新される
+   pub fn refresh_poll(
+      &mut self,
+      token: &Token,
+      poll_id: &PollId
+   ) -> anyhow::Result<Poll> {
+      use mastodon_webapi::api::polls;
+
+      let api_poll = polls::get_poll(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         Some(&token.access_token),
+         &poll_id.local.0
+      )?;
+
+      self.save_poll(token, api_poll)
+   }
+
+   /// `choices`は投票する選択肢のインデックス。
+   /// キャッシュされている`NoCredentialPoll`も更新される。
+   ///
+   /// 締め切られた投票や複数選択できない投票に複数の選択肢を指定した場合など、
+   /// 投票できない場合はリクエストを送信せずにErrを返す
+   pub fn vote(
+      &mut self,
+      token: &Token,
+      poll: &Poll,
+      choices: &[usize]
+   ) -> anyhow::Result<Poll> {
+      use mastodon_webapi::api::polls;
+
+      check_choices(poll, choices)?;
+      let choices: Vec<u64> = choices.iter().map(|&c| c as u64).collect();
+
+      let api_poll = polls::post_votes(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         &token.access_token,
+         &poll.id.local.0,
+         &choices
+      )?;
+
+      self.save_poll(token, api_poll)
+   }
+
+   /// `status`の前後のスレッドを取得する。
+   ///
+   /// `status`がブーストの場合はブーストされた投稿のスレッドになる
+   pub fn get_context(
+      &mut self,
+      token: &Token,
+      status: &Cache<Status>
+   ) -> anyhow::Result<StatusContext> {
+      use mastodon_webapi::api::statuses;
+
+      let status = status.get().boosted_status.clone()
+         .unwrap_or_else(|| status.clone());
+
+      let api_context = statuses::get_context(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         Some(&token.access_token),
+         &status.get().id.local.0
+      )?;
+
+      self.save_context(token, &status, api_context)
+   }
+
+   /// `status`はブーストではなく、`api_context`を取得した投稿
+   fn save_context(
+      &mut self,
+      token: &Token,
+      status: &Cache<Status>,
+      api_context: ApiContext
+   ) -> anyhow::Result<StatusContext> {
+      use crate::cache;
+      use crate::conversion;
+
+      let mut account_repo = cache::account::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut status_repo = cache::status::status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_status_repo = cache::status::no_credential_status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_poll_repo = cache::poll::no_credential_poll_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut convert = |api_statuses: Option<Vec<ApiStatus>>| {
+         api_statuses.unwrap_or_default()
+            .into_iter()
+            .flat_map(|s| {
+               let status = conversion::status::from_api(
+                  #[cfg(feature = "jvm")] &mut self.env,
+                  token.instance.clone(),
+                  s,
+                  &mut account_repo,
+                  &mut status_repo,
+                  &mut no_credential_status_repo,
+                  &mut no_credential_poll_repo
+               ).ok()?;
+
+               Some(status_repo.save(status))
+            })
+            .collect::<Vec<_>>()
+      };
+
+      let ancestors = convert(api_context.ancestors);
+      let descendants = convert(api_context.descendants);
+
+      let reply_tree = build_reply_tree(status, &ancestors, &descendants);
+      let self_reply_chain = find_self_reply_chain(status, &descendants);
+
+      Ok(StatusContext { ancestors, descendants, reply_tree, self_reply_chain })
+   }
+
+   /// `draft`で指定されなかった公開範囲、センシティブ、言語を
+   /// CredentialAccountのデフォルト値で補う
+   fn fill_post_defaults(
+      &mut self,
+      token: &Token,
+      draft: &StatusDraft
+   ) -> anyhow::Result<StatusDraft> {
+      use crate::cache;
+
+      let credential_account = cache::account::credential_account_repo()
+         .read(#[cfg(feature = "jvm")] &mut self.env)?
+         .load(token.account_id.clone())
+         .ok();
+
+      Ok(StatusDraft {
+         visibility: draft.visibility.clone().or_else(||
+            credential_account.as_ref()?.get().default_post_visibility.clone()
+         ),
+         is_sensitive: draft.is_sensitive.or_else(||
+            credential_account.as_ref()?.get().default_post_sensitivity
+         ),
+         language: draft.language.or_else(||
+            credential_account.as_ref()?.get().default_post_language
+         ),
+         ..draft.clone()
+      })
+   }
+
+   fn save_poll(
+      &mut self,
+      token: &Token,
+      api_poll: ApiPoll
+   ) -> anyhow::Result<Poll> {
+      use crate::cache;
+      use crate::conversion;
+
+      let mut no_credential_poll_repo = cache::poll::no_credential_poll_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      conversion::poll::from_api(
+         token.instance.clone(),
+         api_poll,
+         &mut no_credential_poll_repo
+      )
+   }
+
+   fn save_status(
+      &mut self,
+      token: &Token,
+      api_status: ApiStatus
+   ) -> anyhow::Result<Cache<Status>> {
+      use crate::cache;
+      use crate::conversion;
+
+      let mut account_repo = cache::account::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut status_repo = cache::status::status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_status_repo = cache::status::no_credential_status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_poll_repo = cache::poll::no_credential_poll_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let status = conversion::status::from_api(
+         #[cfg(feature = "jvm")] &mut self.env,
+         token.instance.clone(),
+         api_status,
+         &mut account_repo,
+         &mut status_repo,
+         &mut no_credential_status_repo,
+         &mut no_credential_poll_repo
+      )?;
+
+      Ok(status_repo.save(status))
+   }
+}
+
+/// [StatusRepository]の非同期版
+pub struct AsyncStatusRepository {
+   #[cfg(feature = "jvm")]
+   vm: JavaVM,
+   transport: Arc<dyn Transport>,
+}
+
+#[cfg(not(feature = "jvm"))]
+impl Default for AsyncStatusRepository {
+   fn default() -> AsyncStatusRepository {
+      AsyncStatusRepository::new()
+   }
+}
+
+impl AsyncStatusRepository {
+   #[cfg(not(feature = "jvm"))]
+   pub fn new() -> AsyncStatusRepository {
+      AsyncStatusRepository {
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   #[cfg(feature = "jvm")]
+   pub fn new(env: &JNIEnv) -> jni::errors::Result<AsyncStatusRepository> {
+      Ok(AsyncStatusRepository {
+         vm: env.get_java_vm()?,
+         transport: Arc::new(ReqwestTransport),
+      })
+   }
+
+   pub async fn favorite(
+      &self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_favourite_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub async fn unfavorite(
+      &self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_unfavourite_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub async fn boost(
+      &self,
+      token: &Token,
+      status_id: &StatusId,
+      visibility: Option<&StatusVisibility>
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_reblog_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0,
+         visibility.map(|v| v.raw())
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub async fn unboost(
+      &self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_unreblog_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub async fn bookmark(
+      &self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_bookmark_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub async fn unbookmark(
+      &self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_unbookmark_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub async fn pin(
+      &self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_pin_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub async fn unpin(
+      &self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_unpin_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub async fn mute(
+      &self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_mute_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   pub async fn unmute(
+      &self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_unmute_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   /// [StatusRepository::refresh_poll]の非同期版
+   pub async fn refresh_poll(
+      &self,
+      token: &Token,
+      poll_id: &PollId
+   ) -> anyhow::Result<Poll> {
+      use mastodon_webapi::api::polls;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_poll = polls::get_poll_async(
+         self.transport.as_ref(),
+         &instance_url,
+         Some(&token.access_token),
+         &poll_id.local.0
+      ).await?;
+
+      self.with_repository(|repository| repository.save_poll(token, api_poll))
+   }
+
+   /// [StatusRepository::vote]の非同期版
+   pub async fn vote(
+      &self,
+      token: &Token,
+      poll: &Poll,
+      choices: &[usize]
+   ) -> anyhow::Result<Poll> {
+      use mastodon_webapi::api::polls;
+
+      check_choices(poll, choices)?;
+      let choices: Vec<u64> = choices.iter().map(|&c| c as u64).collect();
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_poll = polls::post_votes_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &poll.id.local.0,
+         &choices
+      ).await?;
+
+      self.with_repository(|repository| repository.save_poll(token, api_poll))
+   }
+
+   /// [StatusRepository::post_status]の非同期版
+   pub async fn post_status(
+      &self,
+      token: &Token,
+      draft: &StatusDraft,
+      idempotency_key: Option<&str>
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let draft = self.with_repository(|repository|
+         repository.fill_post_defaults(token, draft)
+      )?;
+      let media_ids = media_ids(&draft);
+      let poll_options = poll_options(&draft);
+      let params = post_params(&draft, &media_ids, &poll_options);
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::post_status_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         idempotency_key,
+         &params
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   /// [StatusRepository::edit_status]の非同期版
+   pub async fn edit_status(
+      &self,
+      token: &Token,
+      status_id: &StatusId,
+      draft: &StatusDraft
+   ) -> anyhow::Result<Cache<Status>> {
+      use mastodon_webapi::api::statuses;
+
+      let media_ids = media_ids(draft);
+      let poll_options = poll_options(draft);
+      let params = edit_params(draft, &media_ids, &poll_options);
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::put_status_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0,
+         &params
+      ).await?;
+
+      self.save_status(token, api_status)
+   }
+
+   /// [StatusRepository::delete_status]の非同期版
+   pub async fn delete_status(
+      &self,
+      token: &Token,
+      status_id: &StatusId
+   ) -> anyhow::Result<StatusDraft> {
+      use mastodon_webapi::api::statuses;
+      use crate::conversion;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_status = statuses::delete_status_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &status_id.local.0
+      ).await?;
+
+      conversion::status::draft_from_api(token.instance.clone(), api_status)
+   }
+
+   /// [StatusRepository::get_context]の非同期版
+   pub async fn get_context(
+      &self,
+      token: &Token,
+      status: &Cache<Status>
+   ) -> anyhow::Result<StatusContext> {
+      use mastodon_webapi::api::statuses;
+
+      let status = status.get().boosted_status.clone()
+         .unwrap_or_else(|| status.clone());
+
+      let instance_url = token.instance.get().url.clone();
+      let status_local_id = status.get().id.local.0.clone();
+
+      let api_context = statuses::get_context_async(
+         self.transport.as_ref(),
+         &instance_url,
+         Some(&token.access_token),
+         &status_local_id
+      ).await?;
+
+      self.with_repository(|repository|
+         repository.save_context(token, &status, api_context)
+      )
+   }
+
+   fn save_status(
+      &self,
+      token: &Token,
+      api_status: ApiStatus
+   ) -> anyhow::Result<Cache<Status>> {
+      self.with_repository(|repository| repository.save_status(token, api_status))
+   }
+
+   /// レスポンスを受け取ったスレッドでそのままキャッシュに変換する
+   fn with_repository<T>(
+      &self,
+      f: impl FnOnce(&mut StatusRepository) -> anyhow::Result<T>
+   ) -> anyhow::Result<T> {
+      #[cfg(not(feature = "jvm"))]
+      {
+         f(&mut StatusRepository::new())
+      }
+
+      #[cfg(feature = "jvm")]
+      {
+         // ランタイムのスレッドはJVMにアタッチしたままにし、
+         // ローカル参照はフレームごとに解放する
+         let mut env = self.vm.attach_current_thread_as_daemon()?;
+         env.with_local_frame(16, |env| f(&mut StatusRepository::new(env)))
+      }
+   }
+}
+
+fn replied_status_id(status: &Cache<Status>) -> Option<StatusId> {
+   status.get().no_credential.get().replied_status_id.clone()
+}
+
+fn media_ids(draft: &StatusDraft) -> Vec<&str> {
+   draft.media_attachment_ids.iter()
+      .map(|id| id.0.as_str())
+      .collect()
+}
+
+fn poll_options(draft: &StatusDraft) -> Vec<&str> {
+   draft.poll.iter()
+      .flat_map(|poll| poll.options.iter().map(String::as_str))
+      .collect()
+}
+
+fn post_params<'a>(
+   draft: &'a StatusDraft,
+   media_ids: &'a [&'a str],
+   poll_options: &'a [&'a str]
+) -> StatusParams<'a> {
+   StatusParams {
+      status: draft.text.as_deref(),
+      media_ids,
+      poll: poll_params(draft, poll_options),
+      in_reply_to_id: draft.replied_status_id.as_ref().map(|id| id.local.0.as_str()),
+      sensitive: draft.is_sensitive,
+      spoiler_text: draft.spoiler_text.as_deref(),
+      visibility: draft.visibility.as_ref().map(|v| v.raw()),
+      language: draft.language.as_ref().and_then(|l| l.to_639_1()),
+   }
+}
+
+/// 公開範囲とリプライ先は編集できないため含まない
+fn edit_params<'a>(
+   draft: &'a StatusDraft,
+   media_ids: &'a [&'a str],
+   poll_options: &'a [&'a str]
+) -> StatusParams<'a> {
+   StatusParams {
+      in_reply_to_id: None,
+      visibility: None,
+      ..post_params(draft, media_ids, poll_options)
+   }
+}
+
+/// 締め切られた投票や複数選択できない投票に複数の選択肢を指定した場合など、
+/// 投票できない場合はErrを返す
+fn check_choices(poll: &Poll, choices: &[usize]) -> anyhow::Result<()> {
+   use anyhow::bail;
+   use chrono::Utc;
+
+   let no_credential = poll.no_credential.get();
+
+   if no_credential.is_expired == Some(true)
+      || no_credential.expire_time.is_some_and(|t| t <= Utc::now())
+   {
+      bail!("the poll has expired: {}", poll.id.local.0);
+   }
+
+   if poll.is_voted == Some(true) {
+      bail!("already voted: {}", poll.id.local.0);
+   }
+
+   if choices.is_empty() {
+      bail!("no choices");
+   }
+
+   if choices.len() > 1 && no_credential.allows_multiple_choices != Some(true) {
+      bail!("the poll does not allow multiple choices: {}", poll.id.local.0);
+   }
+
+   let option_count = no_credential.poll_options.len();
+   for (i, &choice) in choices.iter().enumerate() {
+      if choice >= option_count {
+         bail!("no such option: {choice}");
+      }
+      if choices[..i].contains(&choice) {
+         bail!("duplicated choice: {choice}");
+      }
+   }
+
+   Ok(())
+}
+
+fn poll_params<'a>(
+   draft: &StatusDraft,
+   poll_options: &'a [&'a str]
+) -> Option<PollParams<'a>> {
+   let poll = draft.poll.as_ref()?;
+
+   Some(PollParams {
+      options: poll_options,
+      expires_in: poll.duration.as_secs(),
+      multiple: Some(poll.allows_multiple_choices),
+      hide_totals: Some(poll.hides_totals),
+   })
+}
+
+fn account_id(status: &Cache<Status>) -> Option<AccountId> {
+   status.get().no_credential.get().account.as_ref().map(|a| a.get().id.clone())
+}
+
+/// 返信先ごとの返信。`descendants`の順序を保つ
+fn group_replies(descendants: &[Cache<Status>]) -> HashMap<StatusId, Vec<Cache<Status>>> {
+   let mut replies: HashMap<StatusId, Vec<Cache<Status>>> = HashMap::new();
+   for descendant in descendants {
+      if let Some(replied_status_id) = replied_status_id(descendant) {
+         replies.entry(replied_status_id).or_default().push(descendant.clone());
+      }
+   }
+   replies
+}
+
+fn build_reply_tree(
+   status: &Cache<Status>,
+   ancestors: &[Cache<Status>],
+   descendants: &[Cache<Status>]
+) -> ReplyTreeNode {
+   /// サーバーが循環した返信を返した場合に無限に再帰しないよう、
+   /// `visited`に含まれる投稿は辿らない
+   fn status_node(
+      status: &Cache<Status>,
+      replies: &HashMap<StatusId, Vec<Cache<Status>>>,
+      visited: &mut HashSet<StatusId>
+   ) -> ReplyTreeNode {
+      let children = reply_nodes(&status.get().id, replies, visited);
+
+      let missing_reply_count = missing_reply_count(status, children.len());
+
+      ReplyTreeNode::Status {
+         status: status.clone(),
+         replies: children,
+         missing_reply_count,
+      }
+   }
+
+   fn reply_nodes(
+      status_id: &StatusId,
+      replies: &HashMap<StatusId, Vec<Cache<Status>>>,
+      visited: &mut HashSet<StatusId>
+   ) -> Vec<ReplyTreeNode> {
+      let mut nodes = vec![];
+      for reply in replies.get(status_id).into_iter().flatten() {
+         if visited.insert(reply.get().id.clone()) {
+            nodes.push(status_node(reply, replies, visited));
+         }
+      }
+      nodes
+   }
+
+   fn missing_reply_count(status: &Cache<Status>, reply_count: usize) -> u64 {
+      let expected = status.get().no_credential.get().reply_count.unwrap_or(0);
+      (expected.max(0) as u64).saturating_sub(reply_count as u64)
+   }
+
+   let replies = group_replies(descendants);
+
+   let mut visited = HashSet::from([status.get().id.clone()]);
+   let mut node = status_node(status, &replies, &mut visited);
+
+   // 返信先が取得できなかった返信は対象の投稿の返信の末尾にまとめる
+   let known_ids: HashSet<StatusId> = descendants.iter()
+      .map(|s| s.get().id.clone())
+      .chain(std::iter::once(status.get().id.clone()))
+      .collect();
+
+   let mut missing_ids: Vec<StatusId> = vec![];
+   for descendant in descendants {
+      let Some(replied_status_id) = replied_status_id(descendant) else { continue };
+      if !known_ids.contains(&replied_status_id) && !missing_ids.contains(&replied_status_id) {
+         missing_ids.push(replied_status_id);
+      }
+   }
+
+   if let ReplyTreeNode::Status { replies: ref mut children, .. } = node {
+      for missing_id in missing_ids {
+         let orphans = reply_nodes(&missing_id, &replies, &mut visited);
+         if orphans.is_empty() { continue; }
+
+         children.push(ReplyTreeNode::Missing {
+            status_id: missing_id,
+            replies: orphans,
+         });
+      }
+   }
+
+   // 対象の投稿から返信先を辿ってツリーの根まで組み立てる
+   let mut expected_parent_id = replied_status_id(status);
+
+   for ancestor in ancestors.iter().rev() {
+      let ancestor_id = ancestor.get().id.clone();
+
+      if let Some(parent_id) = expected_parent_id {
+         if parent_id != ancestor_id {
+            node = ReplyTreeNode::Missing {
+               status_id: parent_id,
+               replies: vec![node],
+            };
+         }
+      }
+
+      node = ReplyTreeNode::Status {
+         status: ancestor.clone(),
+         replies: vec![node],
+         missing_reply_count: missing_reply_count(ancestor, 1),
+      };
+
+      expected_parent_id = replied_status_id(ancestor);
+   }
+
+   if let Some(parent_id) = expected_parent_id {
+      node = ReplyTreeNode::Missing {
+         status_id: parent_id,
+         replies: vec![node],
+      };
+   }
+
+   node
+}
+
+/// `status`に対して同じアカウントが続けて返信した投稿を辿る。
+/// 同じアカウントによる返信が複数ある場合は最も古いものを辿る
+fn find_self_reply_chain(
+   status: &Cache<Status>,
+   descendants: &[Cache<Status>]
+) -> Vec<Cache<Status>> {
+   let Some(author_id) = account_id(status) else { return vec![] };
+
+   let replies = group_replies(descendants);
+
+   let mut chain = vec![];
+   let mut current = status.clone();
+   let mut visited = HashSet::from([status.get().id.clone()]);
+
+   loop {
+      let next = replies.get(&current.get().id)
+         .and_then(|r| r.iter().find(|s| account_id(s).as_ref() == Some(&author_id)))
+         .cloned();
+
+      let Some(next) = next else { break };
+      if !visited.insert(next.get().id.clone()) { break; }
+      chain.push(next.clone());
+      current = next;
+   }
+
+   chain
+}
+
+#[cfg(feature = "jvm")]
+mod jvm {
+   use std::future::Future;
+   use jni::JNIEnv;
+   use jni::objects::JObject;
+   use mastodon_entity::jvm_types::{
+      JvmPoll, JvmStatus, JvmStatusContext, JvmStatusDraft, JvmStatusId, JvmToken,
+   };
+   use mastodon_entity::poll::Poll;
+   use mastodon_entity::status::{Status, StatusId};
+   use mastodon_entity::token::Token;
+   use panoptiqon::cache::Cache;
+   use panoptiqon::jvm_types::{JvmCache, JvmList, JvmLong, JvmNullable, JvmString};
+   use crate::cache;
+   use super::{AsyncStatusRepository, StatusRepository};
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_favorite<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::favorite)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_favorite<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::favorite)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_unfavorite<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::unfavorite)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_unfavorite<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::unfavorite)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_postBoost<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      raw_visibility: JvmNullable<'local, JvmString<'local>>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      boost(&mut env, token, status_id, raw_visibility)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_postBoost<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      raw_visibility: JvmNullable<'local, JvmString<'local>>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      boost(&mut env, token, status_id, raw_visibility)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn boost<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      raw_visibility: JvmNullable<'local, JvmString<'local>>
+   ) -> anyhow::Result<JvmCache<'local, JvmStatus<'local>>> {
+      use mastodon_entity::status::StatusVisibility;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut status_repository = StatusRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let status_id = StatusId::clone_from_jvm(env, &status_id);
+      let visibility = Option::<String>::clone_from_jvm(env, &raw_visibility)
+         .map(StatusVisibility::from_raw);
+
+      let status = status_repository.boost(&token, &status_id, visibility.as_ref())?;
+      Ok(status.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_unboost<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::unboost)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_unboost<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::unboost)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_bookmark<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::bookmark)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_bookmark<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::bookmark)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_unbookmark<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::unbookmark)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_unbookmark<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::unbookmark)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_pin<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::pin)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_pin<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::pin)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_unpin<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::unpin)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_unpin<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::unpin)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_mute<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::mute)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_mute<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::mute)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_unmute<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::unmute)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_unmute<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      call(&mut env, token, status_id, StatusRepository::unmute)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_refreshPoll<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>
+   ) -> JvmPoll<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      refresh_poll(&mut env, token, poll)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_refreshPoll<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>
+   ) -> JvmPoll<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      refresh_poll(&mut env, token, poll)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn refresh_poll<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>
+   ) -> anyhow::Result<JvmPoll<'local>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut status_repository = StatusRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let poll = Poll::clone_from_jvm(env, &poll);
+      let poll = status_repository.refresh_poll(&token, &poll.id)?;
+      Ok(poll.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_postVote<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>,
+      choices: JvmList<'local, JvmLong<'local>>
+   ) -> JvmPoll<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      vote(&mut env, token, poll, choices)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_postVote<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>,
+      choices: JvmList<'local, JvmLong<'local>>
+   ) -> JvmPoll<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      vote(&mut env, token, poll, choices)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn vote<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>,
+      choices: JvmList<'local, JvmLong<'local>>
+   ) -> anyhow::Result<JvmPoll<'local>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut status_repository = StatusRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let poll = Poll::clone_from_jvm(env, &poll);
+      let choices: Vec<usize> = Vec::<i64>::clone_from_jvm(env, &choices)
+         .into_iter()
+         .map(|choice| choice as usize)
+         .collect();
+
+      let poll = status_repository.vote(&token, &poll, &choices)?;
+      Ok(poll.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_postStatus<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      draft: JvmStatusDraft<'local>,
+      idempotency_key: JvmNullable<'local, JvmString<'local>>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      post_status(&mut env, token, draft, idempotency_key)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_postStatus<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      draft: JvmStatusDraft<'local>,
+      idempotency_key: JvmNullable<'local, JvmString<'local>>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      post_status(&mut env, token, draft, idempotency_key)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn post_status<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      draft: JvmStatusDraft<'local>,
+      idempotency_key: JvmNullable<'local, JvmString<'local>>
+   ) -> anyhow::Result<JvmCache<'local, JvmStatus<'local>>> {
+      use mastodon_entity::status_draft::StatusDraft;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut status_repository = StatusRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let draft = StatusDraft::clone_from_jvm(env, &draft);
+      let idempotency_key = Option::<String>::clone_from_jvm(env, &idempotency_key);
+
+      let status = status_repository.post_status(&token, &draft, idempotency_key.as_deref())?;
+      Ok(status.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_editStatus<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      draft: JvmStatusDraft<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      edit_status(&mut env, token, status_id, draft)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_editStatus<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      draft: JvmStatusDraft<'local>
+   ) -> JvmCache<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      edit_status(&mut env, token, status_id, draft)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn edit_status<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      draft: JvmStatusDraft<'local>
+   ) -> anyhow::Result<JvmCache<'local, JvmStatus<'local>>> {
+      use mastodon_entity::status_draft::StatusDraft;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut status_repository = StatusRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let status_id = StatusId::clone_from_jvm(env, &status_id);
+      let draft = StatusDraft::clone_from_jvm(env, &draft);
+
+      let status = status_repository.edit_status(&token, &status_id, &draft)?;
+      Ok(status.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_deleteStatus<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmStatusDraft<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      delete_status(&mut env, token, status_id)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_deleteStatus<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> JvmStatusDraft<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      delete_status(&mut env, token, status_id)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn delete_status<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>
+   ) -> anyhow::Result<JvmStatusDraft<'local>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut status_repository = StatusRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let status_id = StatusId::clone_from_jvm(env, &status_id);
+
+      let draft = status_repository.delete_status(&token, &status_id)?;
+      Ok(draft.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_getContext<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status: JvmCache<'local, JvmStatus<'local>>
+   ) -> JvmStatusContext<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_context(&mut env, token, status)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_getContext<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status: JvmCache<'local, JvmStatus<'local>>
+   ) -> JvmStatusContext<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_context(&mut env, token, status)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_context<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      status: JvmCache<'local, JvmStatus<'local>>
+   ) -> anyhow::Result<JvmStatusContext<'local>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut status_repository = StatusRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let status = Cache::<Status>::clone_from_jvm(env, &status);
+
+      let context = status_repository.get_context(&token, &status)?;
+      Ok(context.clone_into_jvm(env))
+   }
+
+   fn call<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      action: impl FnOnce(&mut StatusRepository<'local>, &Token, &StatusId) -> anyhow::Result<Cache<Status>>
+   ) -> anyhow::Result<JvmCache<'local, JvmStatus<'local>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+
+      let mut status_repository = StatusRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let status_id = StatusId::clone_from_jvm(env, &status_id);
+      let status = action(&mut status_repository, &token, &status_id)?;
+      Ok(status.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_favoriteAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.favorite(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_favoriteAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.favorite(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_unfavoriteAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.unfavorite(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_unfavoriteAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.unfavorite(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_postBoostAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      raw_visibility: JvmNullable<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      boost_async(&mut env, token, status_id, raw_visibility, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_postBoostAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      raw_visibility: JvmNullable<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      boost_async(&mut env, token, status_id, raw_visibility, callback);
+   }
+
+   fn boost_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      raw_visibility: JvmNullable<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      use mastodon_entity::status::StatusVisibility;
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let visibility = Option::<String>::clone_from_jvm(env, &raw_visibility)
+         .map(StatusVisibility::from_raw);
+
+      spawn_status_action(env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.boost(&token, &status_id, visibility.as_ref()).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_unboostAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.unboost(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_unboostAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.unboost(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_bookmarkAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.bookmark(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_bookmarkAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.bookmark(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_unbookmarkAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.unbookmark(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_unbookmarkAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.unbookmark(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_pinAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.pin(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_pinAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.pin(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_unpinAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.unpin(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_unpinAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.unpin(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_muteAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.mute(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_muteAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.mute(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_unmuteAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.unmute(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_unmuteAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_status_action(&mut env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.unmute(&token, &status_id).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_refreshPollAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>,
+      callback: JObject<'local>
+   ) {
+      refresh_poll_async(&mut env, token, poll, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_refreshPollAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>,
+      callback: JObject<'local>
+   ) {
+      refresh_poll_async(&mut env, token, poll, callback);
+   }
+
+   fn refresh_poll_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>,
+      callback: JObject<'local>
+   ) {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+      use panoptiqon::jvm_type::JvmType;
+
+      let poll = Poll::clone_from_jvm(env, &poll);
+
+      spawn(env, token, callback, |repository, token| async move {
+         repository.refresh_poll(&token, &poll.id).await
+      }, |env, poll| {
+         let poll: JvmPoll = poll.clone_into_jvm(env);
+         poll.into_j_object()
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_postVoteAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>,
+      choices: JvmList<'local, JvmLong<'local>>,
+      callback: JObject<'local>
+   ) {
+      vote_async(&mut env, token, poll, choices, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_postVoteAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>,
+      choices: JvmList<'local, JvmLong<'local>>,
+      callback: JObject<'local>
+   ) {
+      vote_async(&mut env, token, poll, choices, callback);
+   }
+
+   fn vote_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      poll: JvmPoll<'local>,
+      choices: JvmList<'local, JvmLong<'local>>,
+      callback: JObject<'local>
+   ) {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+      use panoptiqon::jvm_type::JvmType;
+
+      let poll = Poll::clone_from_jvm(env, &poll);
+      let choices: Vec<usize> = Vec::<i64>::clone_from_jvm(env, &choices)
+         .into_iter()
+         .map(|choice| choice as usize)
+         .collect();
+
+      spawn(env, token, callback, |repository, token| async move {
+         repository.vote(&token, &poll, &choices).await
+      }, |env, poll| {
+         let poll: JvmPoll = poll.clone_into_jvm(env);
+         poll.into_j_object()
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_postStatusAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      draft: JvmStatusDraft<'local>,
+      idempotency_key: JvmNullable<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      post_status_async(&mut env, token, draft, idempotency_key, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_postStatusAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      draft: JvmStatusDraft<'local>,
+      idempotency_key: JvmNullable<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      post_status_async(&mut env, token, draft, idempotency_key, callback);
+   }
+
+   fn post_status_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      draft: JvmStatusDraft<'local>,
+      idempotency_key: JvmNullable<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      use mastodon_entity::status_draft::StatusDraft;
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let draft = StatusDraft::clone_from_jvm(env, &draft);
+      let idempotency_key = Option::<String>::clone_from_jvm(env, &idempotency_key);
+
+      spawn(env, token, callback, |repository, token| async move {
+         repository.post_status(&token, &draft, idempotency_key.as_deref()).await
+      }, status_into_jvm);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_editStatusAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      draft: JvmStatusDraft<'local>,
+      callback: JObject<'local>
+   ) {
+      edit_status_async(&mut env, token, status_id, draft, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_editStatusAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      draft: JvmStatusDraft<'local>,
+      callback: JObject<'local>
+   ) {
+      edit_status_async(&mut env, token, status_id, draft, callback);
+   }
+
+   fn edit_status_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      draft: JvmStatusDraft<'local>,
+      callback: JObject<'local>
+   ) {
+      use mastodon_entity::status_draft::StatusDraft;
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let draft = StatusDraft::clone_from_jvm(env, &draft);
+
+      spawn_status_action(env, token, status_id, callback, |repository, token, status_id| async move {
+         repository.edit_status(&token, &status_id, &draft).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_deleteStatusAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      delete_status_async(&mut env, token, status_id, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_deleteStatusAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      delete_status_async(&mut env, token, status_id, callback);
+   }
+
+   fn delete_status_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>
+   ) {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+      use panoptiqon::jvm_type::JvmType;
+
+      let status_id = StatusId::clone_from_jvm(env, &status_id);
+
+      spawn(env, token, callback, |repository, token| async move {
+         repository.delete_status(&token, &status_id).await
+      }, |env, draft| {
+         let draft: JvmStatusDraft = draft.clone_into_jvm(env);
+         draft.into_j_object()
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidStatusRepository_getContextAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status: JvmCache<'local, JvmStatus<'local>>,
+      callback: JObject<'local>
+   ) {
+      get_context_async(&mut env, token, status, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopStatusRepository_getContextAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      status: JvmCache<'local, JvmStatus<'local>>,
+      callback: JObject<'local>
+   ) {
+      get_context_async(&mut env, token, status, callback);
+   }
+
+   fn get_context_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      status: JvmCache<'local, JvmStatus<'local>>,
+      callback: JObject<'local>
+   ) {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+      use panoptiqon::jvm_type::JvmType;
+
+      let status = Cache::<Status>::clone_from_jvm(env, &status);
+
+      spawn(env, token, callback, |repository, token| async move {
+         repository.get_context(&token, &status).await
+      }, |env, context| {
+         let context: JvmStatusContext = context.clone_into_jvm(env);
+         context.into_j_object()
+      });
+   }
+
+   fn spawn_status_action<'local, F>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      status_id: JvmStatusId<'local>,
+      callback: JObject<'local>,
+      action: impl FnOnce(AsyncStatusRepository, Token, StatusId) -> F
+   )
+      where F: Future<Output = anyhow::Result<Cache<Status>>> + Send + 'static
+   {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let status_id = StatusId::clone_from_jvm(env, &status_id);
+
+      spawn(env, token, callback, |repository, token| {
+         action(repository, token, status_id)
+      }, status_into_jvm);
+   }
+
+   /// `action`を共有のランタイムで実行し、結果を`into_jvm`で変換して`callback`に
+   /// 渡す。JNI関数はリクエストの完了を待たずに戻る
+   fn spawn<'local, T, F, C>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      callback: JObject<'local>,
+      action: impl FnOnce(AsyncStatusRepository, Token) -> F,
+      into_jvm: C
+   )
+      where T: Send + 'static,
+            F: Future<Output = anyhow::Result<T>> + Send + 'static,
+            C: for<'a> FnOnce(&mut JNIEnv<'a>, T) -> JObject<'a> + Send + 'static
+   {
+      use ext_reqwest::callback::WebApiCallback;
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+
+      let result = (|| {
+         let repository = AsyncStatusRepository::new(env)?;
+         let token = clone_token_from_jvm(env, &token)?;
+         let callback = WebApiCallback::new(env, &callback)?;
+
+         callback.spawn(action(repository, token), into_jvm);
+
+         anyhow::Ok(())
+      })();
+
+      if let Err(e) = result {
+         throw_web_api_exception(env, &e);
+      }
+   }
+
+   fn status_into_jvm<'a>(env: &mut JNIEnv<'a>, status: Cache<Status>) -> JObject<'a> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+      use panoptiqon::jvm_type::JvmType;
+
+      let status: JvmCache<JvmStatus> = status.clone_into_jvm(env);
+      status.into_j_object()
+   }
+
+   fn clone_token_from_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      token: &JvmToken<'local>
+   ) -> anyhow::Result<Token> {
+      let instance = token.instance(env);
+      let instance = cache::instance::clone_from_jvm(env, &instance)?;
+      Ok(Token::clone_from_jvm(env, token, instance))
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use crate::fixture;
+   use super::StatusRepository;
+
+   #[test]
+   fn favorite_updates_status_cache() {
+      use chrono::{TimeZone, Utc};
+      use mastodon_entity::account::{AccountId, AccountLocalId};
+      use mastodon_entity::instance::Instance;
+      use mastodon_entity::status::{StatusId, StatusLocalId};
+      use mastodon_entity::token::Token;
+      use crate::cache;
+
+      let mut repository = StatusRepository::new();
+      repository.transport = fixture::replay("status/favorite.json");
+
+      let instance = Instance {
+         url: "https://example.com/".parse().unwrap(),
+         version: "0.0.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let instance_cache = cache::instance::repo().write().unwrap().save(instance);
+
+      let token = Token {
+         instance: instance_cache.clone(),
+         account: None,
+         account_id: AccountId {
+            instance_url: instance_cache.get().url.clone(),
+            local: AccountLocalId("credential account id".to_string()),
+         },
+         access_token: "access token".to_string(),
+         token_type: "token type".to_string(),
+         scope: "scope".to_string(),
+         created_at: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let status_id = StatusId {
+         instance_url: instance_cache.get().url.clone(),
+         local: StatusLocalId("status id".to_string()),
+      };
+
+      let favorited = repository.favorite(&token, &status_id).unwrap();
+      assert_eq!(status_id, favorited.get().id);
+      assert_eq!(Some(true), favorited.get().is_favorited);
+
+      let unfavorited = repository.unfavorite(&token, &status_id).unwrap();
+      assert_eq!(Some(false), unfavorited.get().is_favorited);
+      assert_eq!(Some(false), favorited.get().is_favorited);
+   }
+
+   #[test]
+   fn post_status_uses_credential_account_defaults() {
+      use chrono::{TimeZone, Utc};
+      use isolang::Language;
+      use mastodon_entity::account::{
+         Account, AccountId, AccountLocalId, CredentialAccount,
+      };
+      use mastodon_entity::instance::Instance;
+      use mastodon_entity::status::StatusVisibility;
+      use mastodon_entity::status_draft::StatusDraft;
+      use mastodon_entity::token::Token;
+      use crate::cache;
+
+      let mut repository = StatusRepository::new();
+      repository.transport = fixture::replay("status/post_status.json");
+
+      let instance = Instance {
+         url: "https://example.com/".parse().unwrap(),
+         version: "0.0.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let instance_cache = cache::instance::repo().write().unwrap().save(instance);
+
+      let account_id = AccountId {
+         instance_url: instance_cache.get().url.clone(),
+         local: AccountLocalId("poster account id".to_string()),
+      };
+
+      let account = Account {
+         instance: instance_cache.clone(),
+         id: account_id.clone(),
+         username: None,
+         acct: None,
+         url: None,
+         display_name: None,
+         profile_note: None,
+         avatar_image_url: None,
+         avatar_static_image_url: None,
+         header_image_url: None,
+         header_static_image_url: None,
+         is_locked: None,
+         profile_fields: vec![],
+         emojis_in_profile: vec![],
+         is_bot: None,
+         is_group: None,
+         is_discoverable: None,
+         is_noindex: None,
+         moved_to: None,
+         is_suspended: None,
+         is_limited: None,
+         created_time: None,
+         last_status_post_time: None,
+         status_count: None,
+         follower_count: None,
+         followee_count: None,
+      };
+
+      let account_cache = cache::account::repo().write().unwrap().save(account);
+
+      let credential_account = CredentialAccount {
+         id: account_id.clone(),
+         account: account_cache,
+         raw_profile_note: None,
+         raw_profile_fields: vec![],
+         default_post_visibility: Some(StatusVisibility::Unlisted),
+         default_post_sensitivity: Some(true),
+         default_post_language: Some(Language::Jpn),
+         follow_request_count: None,
+         role: None,
+      };
+
+      cache::account::credential_account_repo().write().unwrap()
+         .save(credential_account);
+
+      let token = Token {
+         instance: instance_cache.clone(),
+         account: None,
+         account_id,
+         access_token: "access token".to_string(),
+         token_type: "token type".to_string(),
+         scope: "scope".to_string(),
+         created_at: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let draft = StatusDraft {
+         text: Some("content".to_string()),
+         ..Default::default()
+      };
+
+      let status = repository
+         .post_status(&token, &draft, Some("idempotency key"))
+         .unwrap();
+
+      assert_eq!(
+         Some(StatusVisibility::Unlisted),
+         status.get().no_credential.get().visibility
+      );
+
+      let draft = StatusDraft {
+         text: Some("content".to_string()),
+         visibility: Some(StatusVisibility::Direct),
+         ..Default::default()
+      };
+
+      let status = repository
+         .post_status(&token, &draft, Some("idempotency key"))
+         .unwrap();
+
+      assert_eq!(
+         Some(StatusVisibility::Direct),
+         status.get().no_credential.get().visibility
+      );
+   }
+
+   #[test]
+   fn delete_status_returns_draft() {
+      use std::time::Duration;
+      use chrono::{TimeZone, Utc};
+      use mastodon_entity::account::{AccountId, AccountLocalId};
+      use mastodon_entity::instance::Instance;
+      use mastodon_entity::media_attachment::MediaAttachmentId;
+      use mastodon_entity::status::{StatusId, StatusLocalId, StatusVisibility};
+      use mastodon_entity::status_draft::{PollDraft, StatusDraft};
+      use mastodon_entity::token::Token;
+      use crate::cache;
+
+      let mut repository = StatusRepository::new();
+      repository.transport = fixture::replay("status/delete_status.json");
+
+      let instance = Instance {
+         url: "https://example.com/".parse().unwrap(),
+         version: "0.0.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let instance_cache = cache::instance::repo().write().unwrap().save(instance);
+
+      let token = Token {
+         instance: instance_cache.clone(),
+         account: None,
+         account_id: AccountId {
+            instance_url: instance_cache.get().url.clone(),
+            local: AccountLocalId("credential account id".to_string()),
+         },
+         access_token: "access token".to_string(),
+         token_type: "token type".to_string(),
+         scope: "scope".to_string(),
+         created_at: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let status_id = StatusId {
+         instance_url: instance_cache.get().url.clone(),
+         local: StatusLocalId("deleted status id".to_string()),
+      };
+
+      let draft = repository.delete_status(&token, &status_id).unwrap();
+
+      assert_eq!(
+         StatusDraft {
+            text: Some("source text".to_string()),
+            spoiler_text: None,
+            visibility: Some(StatusVisibility::Private),
+            is_sensitive: None,
+            language: None,
+            replied_status_id: Some(StatusId {
+               instance_url: instance_cache.get().url.clone(),
+               local: StatusLocalId("replied status id".to_string()),
+            }),
+            media_attachment_ids: vec![
+               MediaAttachmentId("media id".to_string()),
+            ],
+            poll: Some(PollDraft {
+               options: vec!["option 1".to_string(), "option 2".to_string()],
+               duration: Duration::from_secs(60 * 60),
+               allows_multiple_choices: true,
+               hides_totals: false,
+            }),
+         },
+         draft
+      );
+   }
+
+   #[test]
+   fn get_context_builds_reply_tree() {
+      use chrono::{TimeZone, Utc};
+      use mastodon_entity::account::{AccountId, AccountLocalId};
+      use mastodon_entity::instance::Instance;
+      use mastodon_entity::status::{StatusId, StatusLocalId};
+      use mastodon_entity::status_context::ReplyTreeNode;
+      use mastodon_entity::token::Token;
+      use crate::cache;
+
+      fn status_id(local: &str) -> StatusId {
+         StatusId {
+            instance_url: "https://context.example.com/".parse().unwrap(),
+            local: StatusLocalId(local.to_string()),
+         }
+      }
+
+      fn status_node_id(node: &ReplyTreeNode) -> StatusId {
+         let ReplyTreeNode::Status { status, .. } = node else { panic!("{node:?}") };
+         let id = status.get().id.clone();
+         id
+      }
+
+      let mut repository = StatusRepository::new();
+      repository.transport = fixture::replay("status/context.json");
+
+      // 0 (取得できない)
+      // └ 1 bob
+      //   └ 2 alice (対象の投稿)
+      //     ├ 3 alice
+      //     │ └ 5 alice
+      //     ├ 4 bob
+      //     ├ (取得できない返信)
+      //     └ 6 (取得できない)
+      //       └ 7 bob
+
+      let instance = Instance {
+         url: "https://context.example.com/".parse().unwrap(),
+         version: "0.0.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let instance_cache = cache::instance::repo().write().unwrap().save(instance);
+
+      let token = Token {
+         instance: instance_cache.clone(),
+         account: None,
+         account_id: AccountId {
+            instance_url: instance_cache.get().url.clone(),
+            local: AccountLocalId("alice".to_string()),
+         },
+         access_token: "access token".to_string(),
+         token_type: "token type".to_string(),
+         scope: "scope".to_string(),
+         created_at: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let status = repository.favorite(&token, &status_id("2")).unwrap();
+      let context = repository.get_context(&token, &status).unwrap();
+
+      assert_eq!(vec![status_id("1")], context.ancestors.iter().map(|s| s.get().id.clone()).collect::<Vec<_>>());
+      assert_eq!(4, context.descendants.len());
+      assert_eq!(
+         vec![status_id("3"), status_id("5")],
+         context.self_reply_chain.iter().map(|s| s.get().id.clone()).collect::<Vec<_>>()
+      );
+
+      let ReplyTreeNode::Missing { status_id: root_id, replies: root_replies } = &context.reply_tree else {
+         panic!("{:?}", context.reply_tree);
+      };
+      assert_eq!(&status_id("0"), root_id);
+      assert_eq!(1, root_replies.len());
+      assert_eq!(status_id("1"), status_node_id(&root_replies[0]));
+
+      let focused = &root_replies[0].replies()[0];
+      assert_eq!(status_id("2"), status_node_id(focused));
+      let ReplyTreeNode::Status { status: focused_status, replies, missing_reply_count } = focused else {
+         panic!("{focused:?}");
+      };
+      assert_eq!(&status, focused_status);
+      assert_eq!(1, *missing_reply_count);
+      assert_eq!(3, replies.len());
+      assert_eq!(status_id("3"), status_node_id(&replies[0]));
+      assert_eq!(status_id("5"), status_node_id(&replies[0].replies()[0]));
+      assert_eq!(status_id("4"), status_node_id(&replies[1]));
+      let ReplyTreeNode::Missing { status_id: missing_id, replies: orphans } = &replies[2] else {
+         panic!("{:?}", replies[2]);
+      };
+      assert_eq!(&status_id("6"), missing_id);
+      assert_eq!(status_id("7"), status_node_id(&orphans[0]));
+
+      // ツリーの投稿はグローバルなキャッシュと共有される
+      let favorited = repository.favorite(&token, &status_id("4")).unwrap();
+      let ReplyTreeNode::Status { status: reply, .. } = &replies[1] else { unreachable!() };
+      assert_eq!(&favorited, reply);
+      assert_eq!(Some(true), reply.get().is_favorited);
+   }
+
+   #[test]
+   fn get_context_ignores_cyclic_and_broken_statuses() {
+      use mastodon_entity::status::{StatusId, StatusLocalId};
+      use mastodon_entity::status_context::ReplyTreeNode;
+
+      fn status_id(local: &str) -> StatusId {
+         StatusId {
+            instance_url: "https://context.example.com/".parse().unwrap(),
+            local: StatusLocalId(local.to_string()),
+         }
+      }
+
+      fn status_node_id(node: &ReplyTreeNode) -> StatusId {
+         let ReplyTreeNode::Status { status, .. } = node else { panic!("{node:?}") };
+         let id = status.get().id.clone();
+         id
+      }
+
+      let mut repository = StatusRepository::new();
+      repository.transport = fixture::replay("status/context_cyclic.json");
+
+      // descendantsに含まれる2は4への返信になっているため、2 → 3 → 4 → 2 と循環する。
+      // idのない投稿はancestorsとdescendantsにそれぞれ1件ずつ含まれる
+      //
+      // 4 (descendantsには含まれるが返信先としては取得できない)
+      // └ 2 alice (対象の投稿)
+      //   └ 3 alice
+      //     └ 4 alice
+
+      let token = fixture::token("https://context.example.com/", "0.0.0");
+
+      let status = repository.favorite(&token, &status_id("2")).unwrap();
+      let context = repository.get_context(&token, &status).unwrap();
+
+      assert!(context.ancestors.is_empty());
+      assert_eq!(
+         vec![status_id("3"), status_id("4"), status_id("2")],
+         context.descendants.iter().map(|s| s.get().id.clone()).collect::<Vec<_>>()
+      );
+      assert_eq!(
+         vec![status_id("3"), status_id("4")],
+         context.self_reply_chain.iter().map(|s| s.get().id.clone()).collect::<Vec<_>>()
+      );
+
+      let ReplyTreeNode::Missing { status_id: root_id, replies: root_replies } = &context.reply_tree else {
+         panic!("{:?}", context.reply_tree);
+      };
+      assert_eq!(&status_id("4"), root_id);
+      assert_eq!(1, root_replies.len());
+
+      let focused = &root_replies[0];
+      assert_eq!(status_id("2"), status_node_id(focused));
+      assert_eq!(1, focused.replies().len());
+
+      let reply = &focused.replies()[0];
+      assert_eq!(status_id("3"), status_node_id(reply));
+      assert_eq!(1, reply.replies().len());
+
+      let reply = &reply.replies()[0];
+      assert_eq!(status_id("4"), status_node_id(reply));
+      assert!(reply.replies().is_empty());
+   }
+
+   fn poll_token() -> mastodon_entity::token::Token {
+      fixture::token("https://poll.example.com/", "0.0.0")
+   }
+
+   #[test]
+   fn vote_updates_no_credential_poll_cache() {
+      use mastodon_entity::poll::{PollId, PollLocalId};
+
+      let mut repository = StatusRepository::new();
+      repository.transport = fixture::replay("status/vote.json");
+      let token = poll_token();
+
+      let poll_id = PollId {
+         instance_url: token.instance.get().url.clone(),
+         local: PollLocalId("vote".to_string()),
+      };
+
+      let poll = repository.refresh_poll(&token, &poll_id).unwrap();
+      assert_eq!(Some(false), poll.is_voted);
+      assert_eq!(Some(0), poll.no_credential.get().vote_count);
+
+      let voted = repository.vote(&token, &poll, &[1]).unwrap();
+      assert_eq!(Some(true), voted.is_voted);
+      assert_eq!(vec![1], voted.voted_options);
+
+      // 投票前に取得したPollのNoCredentialPollも更新される
+      assert_eq!(Some(1), poll.no_credential.get().vote_count);
+      assert_eq!(Some(1), poll.no_credential.get().poll_options[1].vote_count);
+   }
+
+   #[test]
+   fn vote_checks_poll_state() {
+      use mastodon_entity::poll::{PollId, PollLocalId};
+
+      let mut repository = StatusRepository::new();
+      repository.transport = fixture::replay("status/vote_checks.json");
+      let token = poll_token();
+
+      let mut refresh = |local_id: &str| {
+         let poll_id = PollId {
+            instance_url: token.instance.get().url.clone(),
+            local: PollLocalId(local_id.to_string()),
+         };
+         repository.refresh_poll(&token, &poll_id).unwrap()
+      };
+
+      let expired = refresh("expired");
+      let single = refresh("single");
+      let multiple = refresh("multiple");
+      let voted = refresh("voted");
+
+      assert!(repository.vote(&token, &expired, &[0]).is_err());
+      assert!(repository.vote(&token, &single, &[0, 2]).is_err());
+      assert!(repository.vote(&token, &single, &[3]).is_err());
+      assert!(repository.vote(&token, &multiple, &[]).is_err());
+      assert!(repository.vote(&token, &multiple, &[0, 0]).is_err());
+      assert!(repository.vote(&token, &voted, &[1]).is_err());
+
+      let poll = repository.vote(&token, &multiple, &[0, 2]).unwrap();
+      assert_eq!(vec![0, 2], poll.voted_options);
+   }
+
+   #[test]
+   fn favorite_async_updates_status_cache() {
+      use ext_reqwest::runtime::RUNTIME;
+      use mastodon_entity::status::{StatusId, StatusLocalId};
+      use super::AsyncStatusRepository;
+
+      let mut repository = AsyncStatusRepository::new();
+      repository.transport = fixture::replay("status/favorite.json");
+
+      let token = fixture::token("https://example.com/", "0.0.0");
+
+      let status_id = StatusId {
+         instance_url: token.instance.get().url.clone(),
+         local: StatusLocalId("status id".to_string()),
+      };
+
+      let favorited = RUNTIME.block_on(repository.favorite(&token, &status_id)).unwrap();
+      assert_eq!(status_id, favorited.get().id);
+      assert_eq!(Some(true), favorited.get().is_favorited);
+
+      let unfavorited = RUNTIME.block_on(repository.unfavorite(&token, &status_id)).unwrap();
+      assert_eq!(Some(false), unfavorited.get().is_favorited);
+      assert_eq!(Some(false), favorited.get().is_favorited);
+   }
+   #[test]
+   fn vote_async_updates_no_credential_poll_cache() {
+      use ext_reqwest::runtime::RUNTIME;
+      use mastodon_entity::poll::{PollId, PollLocalId};
+      use super::AsyncStatusRepository;
+
+      let mut repository = AsyncStatusRepository::new();
+      repository.transport = fixture::replay("status/vote.json");
+      let token = poll_token();
+
+      let poll_id = PollId {
+         instance_url: token.instance.get().url.clone(),
+         local: PollLocalId("vote".to_string()),
+      };
+
+      let poll = RUNTIME.block_on(repository.refresh_poll(&token, &poll_id)).unwrap();
+      assert_eq!(Some(false), poll.is_voted);
+
+      assert!(RUNTIME.block_on(repository.vote(&token, &poll, &[3])).is_err());
+
+      let voted = RUNTIME.block_on(repository.vote(&token, &poll, &[1])).unwrap();
+      assert_eq!(Some(true), voted.is_voted);
+      assert_eq!(vec![1], voted.voted_options);
+      assert_eq!(Some(1), poll.no_credential.get().vote_count);
+   }
+}
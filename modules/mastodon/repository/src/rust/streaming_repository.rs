@@ -0,0 +1,861 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+use ext_reqwest::error::{WebApiError, WebApiResult};
+use ext_reqwest::transport::{ReqwestTransport, Transport};
+use mastodon_entity::notification::Notification;
+use mastodon_entity::status::{Status, StatusId};
+use mastodon_entity::token::Token;
+use mastodon_webapi::api::streaming::{
+   SseCloseHandle, SseStreaming, Stream, StreamEvent, WebSocketCloseHandle,
+   WebSocketStreaming,
+};
+use mastodon_webapi::entity::notification::Notification as ApiNotification;
+use mastodon_webapi::entity::status::Status as ApiStatus;
+use panoptiqon::cache::Cache;
+
+#[cfg(not(feature = "jvm"))]
+use std::marker::PhantomData;
+
+#[cfg(feature = "jvm")]
+use jni::JNIEnv;
+
+pub enum StreamingEvent {
+   Update(Cache<Status>),
+   Delete(StatusId),
+   Notification(Notification),
+   StatusUpdate(Cache<Status>),
+   FiltersChanged,
+}
+
+/// キャンセルや切断を確認する間隔
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 購読を終了するためのハンドル。
+/// Cloneして別スレッドから[StreamingCanceller::cancel]を呼ぶ
+#[derive(Clone, Default)]
+pub struct StreamingCanceller(Arc<CancellerState>);
+
+#[derive(Default)]
+struct CancellerState {
+   is_cancelled: AtomicBool,
+   /// 接続中のWebSocket。キャンセル時にソケットを切断して読み込みのブロックを解除する
+   web_socket: Mutex<Option<WebSocketCloseHandle>>,
+}
+
+impl StreamingCanceller {
+   pub fn new() -> StreamingCanceller {
+      StreamingCanceller::default()
+   }
+
+   pub fn cancel(&self) {
+      self.0.is_cancelled.store(true, Ordering::Relaxed);
+
+      if let Ok(web_socket) = self.0.web_socket.lock() {
+         if let Some(ref close_handle) = *web_socket {
+            close_handle.close();
+         }
+      }
+   }
+
+   pub fn is_cancelled(&self) -> bool {
+      self.0.is_cancelled.load(Ordering::Relaxed)
+   }
+
+   fn set_web_socket(&self, close_handle: Option<WebSocketCloseHandle>) {
+      if let Ok(mut web_socket) = self.0.web_socket.lock() {
+         *web_socket = close_handle;
+      }
+   }
+}
+
+pub struct StreamingRepository<'jni> {
+   #[cfg(not(feature = "jvm"))]
+   env: PhantomData<&'jni ()>,
+   #[cfg(feature = "jvm")]
+   env: JNIEnv<'jni>,
+   transport: Arc<dyn Transport>,
+   initial_backoff: Duration,
+   max_backoff: Duration,
+}
+
+impl StreamingRepository<'_> {
+   #[cfg(not(feature = "jvm"))]
+   pub fn new() -> StreamingRepository<'static> {
+      StreamingRepository {
+         env: PhantomData,
+         transport: Arc::new(ReqwestTransport),
+         initial_backoff: Duration::from_secs(1),
+         max_backoff: Duration::from_secs(60),
+      }
+   }
+
+   #[cfg(feature = "jvm")]
+   pub fn new<'jni>(env: &JNIEnv<'jni>) -> StreamingRepository<'jni> {
+      StreamingRepository {
+         env: unsafe { env.unsafe_clone() },
+         transport: Arc::new(ReqwestTransport),
+         initial_backoff: Duration::from_secs(1),
+         max_backoff: Duration::from_secs(60),
+      }
+   }
+
+   /// `streams`を購読し、イベントを受信するたびに`on_event`を呼び出す。
+   /// `on_event`が`ControlFlow::Break`を返すか、`canceller`でキャンセル
+   /// されるまでブロックする。
+   ///
+   /// WebSocketで接続できない場合はServer-sent eventsで接続する。
+   /// 切断された場合は間隔を空けながら再接続し、切断中に投稿された投稿と
+   /// 通知を取得してから購読を再開する。
+   /// アクセストークンが無効な場合など再接続しても解決しないエラーはErrを返す
+   pub fn subscribe(
+      &mut self,
+      token: &Token,
+      streams: &[Stream],
+      canceller: &StreamingCanceller,
+      mut on_event: impl FnMut(&Stream, StreamingEvent) -> ControlFlow<()>
+   ) -> anyhow::Result<()> {
+      let mut last_status_ids: HashMap<Stream, String> = HashMap::new();
+      let mut last_notification_id: Option<String> = None;
+      let mut backoff = self.initial_backoff;
+      let mut is_reconnection = false;
+
+      while !canceller.is_cancelled() {
+         let connection = Connection::connect(self.transport.as_ref(), token, streams, canceller);
+         let mut connection = match connection {
+            Ok(connection) => connection,
+            Err(e) if !is_retryable(&e) => return Err(e),
+            Err(_) => {
+               sleep_unless_cancelled(backoff, canceller);
+               backoff = (backoff * 2).min(self.max_backoff);
+               continue;
+            }
+         };
+
+         if is_reconnection {
+            let flow = self.fetch_missed_events(
+               token, streams, &mut last_status_ids, &mut last_notification_id,
+               &mut on_event
+            )?;
+
+            if flow.is_break() {
+               return Ok(());
+            }
+         }
+         is_reconnection = true;
+
+         while !canceller.is_cancelled() {
+            let StreamEvent { stream, event } = match connection.read_event(canceller) {
+               Ok(Some(event)) => event,
+               // デコードできないイベントは読み飛ばし、接続は維持する
+               Err(WebApiError::Decode(_)) => continue,
+               Ok(None) | Err(_) => break,
+            };
+
+            backoff = self.initial_backoff;
+
+            let Some(stream) = stream.filter(|s| streams.contains(s)) else {
+               continue;
+            };
+
+            let Ok(event) = self.convert_event(token, event) else { continue; };
+            let Some(event) = event else { continue; };
+
+            match event {
+               StreamingEvent::Update(ref status) => {
+                  last_status_ids.insert(stream.clone(), status.get().id.local.0.clone());
+               }
+               StreamingEvent::Notification(ref notification) => {
+                  last_notification_id = Some(notification.id.local.0.clone());
+               }
+               _ => {}
+            }
+
+            if on_event(&stream, event).is_break() {
+               return Ok(());
+            }
+         }
+
+         sleep_unless_cancelled(backoff, canceller);
+         backoff = (backoff * 2).min(self.max_backoff);
+      }
+
+      Ok(())
+   }
+
+   fn convert_event(
+      &mut self,
+      token: &Token,
+      event: mastodon_webapi::api::streaming::Event
+   ) -> anyhow::Result<Option<StreamingEvent>> {
+      use mastodon_entity::status::StatusLocalId;
+      use mastodon_webapi::api::streaming::Event;
+
+      let event = match event {
+         Event::Update(status) => StreamingEvent::Update(
+            self.save_status(token, *status)?
+         ),
+         Event::Delete(id) => StreamingEvent::Delete(
+            StatusId {
+               instance_url: token.instance.get().url.clone(),
+               local: StatusLocalId(id),
+            }
+         ),
+         Event::Notification(notification) => StreamingEvent::Notification(
+            self.convert_notification(token, *notification)?
+         ),
+         Event::StatusUpdate(status) => StreamingEvent::StatusUpdate(
+            self.save_status(token, *status)?
+         ),
+         Event::FiltersChanged => StreamingEvent::FiltersChanged,
+         Event::Other(_) => return Ok(None),
+      };
+
+      Ok(Some(event))
+   }
+
+   /// 最後に受信したidより新しい投稿と通知を取得して古い順に`on_event`に渡す
+   fn fetch_missed_events(
+      &mut self,
+      token: &Token,
+      streams: &[Stream],
+      last_status_ids: &mut HashMap<Stream, String>,
+      last_notification_id: &mut Option<String>,
+      on_event: &mut impl FnMut(&Stream, StreamingEvent) -> ControlFlow<()>
+   ) -> anyhow::Result<ControlFlow<()>> {
+      for stream in streams {
+         while let Some(min_id) = last_status_ids.get(stream) {
+            let Ok(statuses) = fetch_statuses(self.transport.as_ref(), token, stream, min_id) else { break; };
+            let Some(newest_id) = statuses.first().and_then(|s| s.id.clone()) else {
+               break;
+            };
+
+            for status in statuses.into_iter().rev() {
+               let Ok(status) = self.save_status(token, status) else { continue; };
+               if on_event(stream, StreamingEvent::Update(status)).is_break() {
+                  return Ok(ControlFlow::Break(()));
+               }
+            }
+
+            last_status_ids.insert(stream.clone(), newest_id);
+         }
+      }
+
+      if streams.contains(&Stream::User) {
+         while let Some(min_id) = last_notification_id.as_deref() {
+            let Ok(notifications) = fetch_notifications(self.transport.as_ref(), token, min_id) else { break; };
+            let Some(newest_id) = notifications.first().and_then(|n| n.id.clone()) else {
+               break;
+            };
+
+            for notification in notifications.into_iter().rev() {
+               let Ok(notification) = self.convert_notification(token, notification) else {
+                  continue;
+               };
+
+               let event = StreamingEvent::Notification(notification);
+               if on_event(&Stream::User, event).is_break() {
+                  return Ok(ControlFlow::Break(()));
+               }
+            }
+
+            *last_notification_id = Some(newest_id);
+         }
+      }
+
+      Ok(ControlFlow::Continue(()))
+   }
+
+   fn save_status(
+      &mut self,
+      token: &Token,
+      api_status: ApiStatus
+   ) -> anyhow::Result<Cache<Status>> {
+      use crate::cache;
+      use crate::conversion;
+
+      let mut account_repo = cache::account::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut status_repo = cache::status::status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_status_repo = cache::status::no_credential_status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_poll_repo = cache::poll::no_credential_poll_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let status = conversion::status::from_api(
+         #[cfg(feature = "jvm")] &mut self.env,
+         token.instance.clone(),
+         api_status,
+         &mut account_repo,
+         &mut status_repo,
+         &mut no_credential_status_repo,
+         &mut no_credential_poll_repo
+      )?;
+
+      Ok(status_repo.save(status))
+   }
+
+   fn convert_notification(
+      &mut self,
+      token: &Token,
+      api_notification: ApiNotification
+   ) -> anyhow::Result<Notification> {
+      use crate::cache;
+      use crate::conversion;
+
+      let mut account_repo = cache::account::repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut status_repo = cache::status::status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_status_repo = cache::status::no_credential_status_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      let mut no_credential_poll_repo = cache::poll::no_credential_poll_repo()
+         .write(#[cfg(feature = "jvm")] &mut self.env)?;
+
+      conversion::notification::from_api(
+         #[cfg(feature = "jvm")] &mut self.env,
+         token.instance.clone(),
+         api_notification,
+         &mut account_repo,
+         &mut status_repo,
+         &mut no_credential_status_repo,
+         &mut no_credential_poll_repo
+      )
+   }
+}
+
+/// 4xx(429を除く)やURLの誤りなど、再接続しても解決しないエラーでないか
+fn is_retryable(error: &anyhow::Error) -> bool {
+   match error.downcast_ref::<WebApiError>() {
+      Some(WebApiError::Unauthorized { .. }) => false,
+      Some(WebApiError::HttpStatus { status, .. }) => !(400..500).contains(status),
+      Some(WebApiError::InvalidUrl(_)) => false,
+      _ => true,
+   }
+}
+
+/// `duration`が経過するかキャンセルされるまで待機する
+fn sleep_unless_cancelled(duration: Duration, canceller: &StreamingCanceller) {
+   use std::thread;
+
+   let deadline = Instant::now() + duration;
+
+   while !canceller.is_cancelled() {
+      let now = Instant::now();
+      if now >= deadline { break; }
+      thread::sleep((deadline - now).min(POLL_INTERVAL));
+   }
+}
+
+fn fetch_statuses(
+   transport: &dyn Transport,
+   token: &Token,
+   stream: &Stream,
+   min_id: &str
+) -> anyhow::Result<Vec<ApiStatus>> {
+   use mastodon_webapi::api::timelines::{self, PublicTimelineParams, TagTimelineParams};
+   use mastodon_webapi::pagination::PageParams;
+
+   let instance_url = &token.instance.get().url;
+   let access_token = &token.access_token;
+   let page_params = PageParams {
+      min_id: Some(min_id),
+      ..PageParams::default()
+   };
+
+   let page = match stream {
+      Stream::User => timelines::get_home(
+         transport, instance_url, access_token, &page_params
+      )?,
+      Stream::Public | Stream::PublicLocal => {
+         let params = PublicTimelineParams {
+            local: Some(*stream == Stream::PublicLocal),
+            ..PublicTimelineParams::default()
+         };
+
+         timelines::get_public(
+            transport, instance_url, Some(access_token), &params, &page_params
+         )?
+      }
+      Stream::Hashtag(hashtag) => timelines::get_tag(
+         transport, instance_url, Some(access_token), hashtag,
+         &TagTimelineParams::default(), &page_params
+      )?,
+      Stream::List(list_id) => timelines::get_list(
+         transport, instance_url, access_token, list_id, &page_params
+      )?,
+   };
+
+   Ok(page.items)
+}
+
+fn fetch_notifications(
+   transport: &dyn Transport,
+   token: &Token,
+   min_id: &str
+) -> anyhow::Result<Vec<ApiNotification>> {
+   use mastodon_webapi::api::notifications::{self, NotificationsV1Params};
+   use mastodon_webapi::pagination::PageParams;
+
+   let page = notifications::get_notifications_v1(
+      transport,
+      &token.instance.get().url,
+      &token.access_token,
+      &NotificationsV1Params::default(),
+      &PageParams {
+         min_id: Some(min_id),
+         ..PageParams::default()
+      }
+   )?;
+
+   Ok(page.items)
+}
+
+enum Connection {
+   /// キャンセル時に読み込みのブロックを解除できるよう、`canceller`にソケットを登録している
+   WebSocket {
+      web_socket: Box<WebSocketStreaming>,
+      canceller: StreamingCanceller,
+   },
+   /// SSEは1つの接続で1つのストリームしか購読できないため、
+   /// ストリームごとにスレッドを立ててイベントをまとめて受け取る。
+   /// スレッドはcloseで各接続を閉じると終了する
+   Sse {
+      receiver: Receiver<WebApiResult<Option<StreamEvent>>>,
+      close_handles: Vec<SseCloseHandle>,
+   },
+}
+
+impl Connection {
+   fn connect(
+      transport: &dyn Transport,
+      token: &Token,
+      streams: &[Stream],
+      canceller: &StreamingCanceller
+   ) -> anyhow::Result<Connection> {
+      use ext_reqwest::client_registry;
+
+      let instance_url = &token.instance.get().url;
+
+      // プロキシを経由したWebSocketには対応していないため、SSEで接続する
+      if client_registry::settings_for(instance_url).proxy.is_some() {
+         return Connection::connect_sse(transport, token, streams);
+      }
+
+      match WebSocketStreaming::connect(instance_url, &token.access_token) {
+         Ok(mut web_socket) => {
+            for stream in streams {
+               web_socket.subscribe(stream)?;
+            }
+            canceller.set_web_socket(Some(web_socket.close_handle()?));
+
+            Ok(Connection::WebSocket {
+               web_socket: Box::new(web_socket),
+               canceller: canceller.clone(),
+            })
+         }
+         Err(_) => Connection::connect_sse(transport, token, streams),
+      }
+   }
+
+   fn connect_sse(
+      transport: &dyn Transport,
+      token: &Token,
+      streams: &[Stream]
+   ) -> anyhow::Result<Connection> {
+      use std::sync::mpsc;
+      use std::thread;
+
+      let instance_url = &token.instance.get().url;
+
+      let connections = streams.iter()
+         .map(|stream|
+            SseStreaming::connect(transport, instance_url, &token.access_token, stream)
+         )
+         .collect::<WebApiResult<Vec<_>>>()?;
+
+      let (sender, receiver) = mpsc::channel();
+      let mut close_handles = Vec::with_capacity(connections.len());
+
+      for mut connection in connections {
+         close_handles.push(connection.close_handle());
+
+         let sender = sender.clone();
+         thread::spawn(move || loop {
+            let event = connection.read_event();
+            let is_disconnected = match event {
+               Ok(Some(_)) | Err(WebApiError::Decode(_)) => false,
+               Ok(None) | Err(_) => true,
+            };
+            if sender.send(event).is_err() || is_disconnected {
+               break;
+            }
+         });
+      }
+
+      Ok(Connection::Sse { receiver, close_handles })
+   }
+
+   /// キャンセルされた場合はNoneを返す
+   fn read_event(
+      &mut self,
+      canceller: &StreamingCanceller
+   ) -> WebApiResult<Option<StreamEvent>> {
+      use std::sync::mpsc::RecvTimeoutError;
+
+      match self {
+         Connection::WebSocket { web_socket, .. } => web_socket.read_event(),
+         Connection::Sse { receiver, .. } => loop {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+               Ok(event) => return event,
+               Err(RecvTimeoutError::Timeout) if !canceller.is_cancelled() => continue,
+               Err(_) => return Ok(None),
+            }
+         },
+      }
+   }
+}
+
+/// 再接続時や購読の終了時に古い接続を閉じる
+impl Drop for Connection {
+   fn drop(&mut self) {
+      match self {
+         Connection::WebSocket { web_socket, canceller } => {
+            canceller.set_web_socket(None);
+            let _ = web_socket.close();
+         }
+         Connection::Sse { close_handles, .. } => {
+            for close_handle in close_handles {
+               close_handle.close();
+            }
+         }
+      }
+   }
+}
+
+#[cfg(all(test, not(feature = "jvm")))]
+mod test {
+   use std::net::TcpListener;
+   use std::ops::ControlFlow;
+   use std::thread;
+   use std::time::Duration;
+   use mastodon_entity::status::StatusLocalId;
+   use mastodon_entity::token::Token;
+   use mastodon_webapi::api::streaming::Stream;
+   use tungstenite::Message;
+   use crate::fixture;
+   use super::{StreamingCanceller, StreamingEvent, StreamingRepository};
+
+   #[test]
+   fn reconnect_and_fetch_missed_statuses() {
+      use crate::cache;
+
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let port = listener.local_addr().unwrap().port();
+
+      let server = thread::spawn(move || {
+         let (stream, _) = listener.accept().unwrap();
+         let mut socket = tungstenite::accept(stream).unwrap();
+         let subscription = socket.read().unwrap().into_text().unwrap();
+         assert!(subscription.contains(r#""stream":"user""#));
+
+         socket.send(Message::Text(
+            r#"{"stream":["user"],"event":"update","payload":"{\"id\":\"1\",\"content\":\"content\"}"}"#
+               .to_string()
+         )).unwrap();
+         socket.close(None).unwrap();
+         while socket.read().is_ok() {}
+
+         let (stream, _) = listener.accept().unwrap();
+         let mut socket = tungstenite::accept(stream).unwrap();
+         socket.read().unwrap();
+
+         socket.send(Message::Text(
+            r#"{"stream":["user"],"event":"delete","payload":"1"}"#.to_string()
+         )).unwrap();
+         while socket.read().is_ok() {}
+      });
+
+      let token = token(port);
+
+      let transport = fixture::replay_with_instance_url(
+         "streaming/missed_statuses.json", &token.instance.get().url
+      );
+      let mut repository = StreamingRepository::new();
+      repository.transport = transport.clone();
+      repository.initial_backoff = Duration::from_millis(10);
+
+      let mut events = vec![];
+      repository.subscribe(&token, &[Stream::User], &StreamingCanceller::new(), |stream, event| {
+         assert_eq!(Stream::User, *stream);
+
+         let event = match event {
+            StreamingEvent::Update(status) => format!("update {}", status.get().id.local.0),
+            StreamingEvent::Delete(id) => format!("delete {}", id.local.0),
+            _ => "other".to_string(),
+         };
+         let is_delete = event.starts_with("delete");
+         events.push(event);
+
+         if is_delete { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+      }).unwrap();
+
+      assert_eq!(vec!["update 1", "update 2", "update 3", "delete 1"], events);
+      assert_eq!(2, transport.requests().len());
+
+      let status_repo = cache::status::status_repo().read().unwrap();
+      let status_id = mastodon_entity::status::StatusId {
+         instance_url: format!("http://127.0.0.1:{port}/").parse().unwrap(),
+         local: StatusLocalId("3".to_string()),
+      };
+      assert!(status_repo.load(status_id).is_ok());
+      drop(status_repo);
+
+      server.join().unwrap();
+   }
+
+   fn token(port: u16) -> Token {
+      fixture::token(&format!("http://127.0.0.1:{port}/"), "4.3.0")
+   }
+
+   #[test]
+   fn stop_on_unauthorized() {
+      use std::io::{BufRead, BufReader, Write};
+      use ext_reqwest::error::WebApiError;
+
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let port = listener.local_addr().unwrap().port();
+
+      // WebSocketとSSEの両方の接続を401で拒否する
+      let server = thread::spawn(move || {
+         for _ in 0..2 {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+               line.clear();
+            }
+
+            let body = r#"{"error":"The access token is invalid"}"#;
+            write!(
+               stream,
+               "HTTP/1.1 401 Unauthorized\r\n\
+                Content-Type: application/json\r\n\
+                Content-Length: {}\r\n\
+                Connection: close\r\n\
+                \r\n\
+                {body}",
+               body.len()
+            ).unwrap();
+         }
+      });
+
+      let token = token(port);
+
+      let mut repository = StreamingRepository::new();
+      repository.initial_backoff = Duration::from_secs(60);
+
+      let result = repository.subscribe(
+         &token, &[Stream::User], &StreamingCanceller::new(), |_, _| ControlFlow::Continue(())
+      );
+
+      let error = result.unwrap_err();
+      assert!(matches!(error.downcast_ref(), Some(WebApiError::Unauthorized { .. })));
+
+      server.join().unwrap();
+   }
+
+   #[test]
+   fn cancel_during_backoff() {
+      use std::time::Instant;
+
+      // 接続できないポート
+      let port = TcpListener::bind("127.0.0.1:0").unwrap()
+         .local_addr().unwrap().port();
+
+      let token = token(port);
+
+      let mut repository = StreamingRepository::new();
+      repository.initial_backoff = Duration::from_secs(60);
+
+      let canceller = StreamingCanceller::new();
+      let canceller_clone = canceller.clone();
+      thread::spawn(move || {
+         thread::sleep(Duration::from_millis(50));
+         canceller_clone.cancel();
+      });
+
+      let start = Instant::now();
+      repository.subscribe(&token, &[Stream::User], &canceller, |_, _| {
+         ControlFlow::Continue(())
+      }).unwrap();
+
+      assert!(start.elapsed() < Duration::from_secs(10));
+   }
+
+   #[test]
+   fn cancel_idle_web_socket() {
+      use std::time::Instant;
+
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let port = listener.local_addr().unwrap().port();
+
+      // 購読を受け付けた後は何も送らない
+      let server = thread::spawn(move || {
+         let (stream, _) = listener.accept().unwrap();
+         let mut socket = tungstenite::accept(stream).unwrap();
+         socket.read().unwrap();
+         while socket.read().is_ok() {}
+      });
+
+      let token = token(port);
+      let mut repository = StreamingRepository::new();
+
+      let canceller = StreamingCanceller::new();
+      let canceller_clone = canceller.clone();
+      thread::spawn(move || {
+         thread::sleep(Duration::from_millis(200));
+         canceller_clone.cancel();
+      });
+
+      let start = Instant::now();
+      repository.subscribe(&token, &[Stream::User], &canceller, |_, _| {
+         ControlFlow::Continue(())
+      }).unwrap();
+
+      assert!(start.elapsed() < Duration::from_secs(10));
+
+      server.join().unwrap();
+   }
+
+   #[test]
+   fn cancel_idle_sse() {
+      use std::io::{BufRead, BufReader, Write};
+      use std::sync::mpsc;
+      use std::time::Instant;
+
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let port = listener.local_addr().unwrap().port();
+
+      let (cancelled_sender, cancelled_receiver) = mpsc::channel::<()>();
+
+      // WebSocketを拒否し、SSEはヘッダーを返した後何も送らない
+      let server = thread::spawn(move || {
+         let read_request = |stream: &std::net::TcpStream| {
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+               line.clear();
+            }
+         };
+
+         let (mut stream, _) = listener.accept().unwrap();
+         read_request(&stream);
+         write!(
+            stream,
+            "HTTP/1.1 404 Not Found\r\n\
+             Content-Length: 0\r\n\
+             Connection: close\r\n\
+             \r\n"
+         ).unwrap();
+         drop(stream);
+
+         let (mut stream, _) = listener.accept().unwrap();
+         read_request(&stream);
+         write!(
+            stream,
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: text/event-stream\r\n\
+             \r\n"
+         ).unwrap();
+
+         cancelled_receiver.recv().unwrap();
+      });
+
+      let token = token(port);
+      let mut repository = StreamingRepository::new();
+
+      let canceller = StreamingCanceller::new();
+      let canceller_clone = canceller.clone();
+      thread::spawn(move || {
+         thread::sleep(Duration::from_millis(200));
+         canceller_clone.cancel();
+      });
+
+      let start = Instant::now();
+      repository.subscribe(&token, &[Stream::User], &canceller, |_, _| {
+         ControlFlow::Continue(())
+      }).unwrap();
+
+      assert!(start.elapsed() < Duration::from_secs(10));
+
+      cancelled_sender.send(()).unwrap();
+      server.join().unwrap();
+   }
+
+   #[test]
+   fn skip_undecodable_event() {
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let port = listener.local_addr().unwrap().port();
+
+      let server = thread::spawn(move || {
+         let (stream, _) = listener.accept().unwrap();
+         let mut socket = tungstenite::accept(stream).unwrap();
+         socket.read().unwrap();
+
+         socket.send(Message::Text(
+            r#"{"stream":["user"],"event":"update","payload":"{\"id\":"}"#.to_string()
+         )).unwrap();
+         socket.send(Message::Text(
+            r#"{"stream":["user"],"event":"delete","payload":"1"}"#.to_string()
+         )).unwrap();
+         while socket.read().is_ok() {}
+      });
+
+      let token = token(port);
+
+      let transport = fixture::replay("streaming/undecodable_event.json");
+      let mut repository = StreamingRepository::new();
+      repository.transport = transport.clone();
+
+      let mut events = vec![];
+      repository.subscribe(&token, &[Stream::User], &StreamingCanceller::new(), |_, event| {
+         let is_delete = matches!(event, StreamingEvent::Delete(_));
+         events.push(is_delete);
+         if is_delete { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+      }).unwrap();
+
+      // 再接続していれば切断中の投稿を取得しているはず
+      assert_eq!(vec![true], events);
+      assert!(transport.requests().is_empty());
+
+      server.join().unwrap();
+   }
+}
@@ -14,34 +14,64 @@
  * limitations under the License.
  */
 
+use std::sync::Arc;
+use ext_reqwest::transport::{ReqwestTransport, Transport};
+use mastodon_entity::page::{Page, PageCursor};
+use mastodon_entity::rate_limit::RateLimitBudget;
 use mastodon_entity::status::Status;
 use mastodon_entity::token::Token;
+use mastodon_webapi::entity::status::Status as ApiStatus;
+use mastodon_webapi::api::timelines::{PublicTimelineParams, TagTimelineParams};
+use mastodon_webapi::pagination::{Page as ApiPage, PageParams};
 
 #[cfg(not(feature = "jvm"))]
 use std::marker::PhantomData;
 
 #[cfg(feature = "jvm")]
-use jni::JNIEnv;
+use jni::{JavaVM, JNIEnv};
+
+/// ページングして読み込むタイムライン
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Timeline {
+   Home,
+   Public {
+      is_local: bool,
+      is_remote: bool,
+      is_only_media: bool,
+   },
+   Hashtag {
+      hashtag: String,
+      any: Vec<String>,
+      all: Vec<String>,
+      none: Vec<String>,
+   },
+   List {
+      list_id: String,
+   },
+}
 
 pub struct TimelineRepository<'jni> {
    #[cfg(not(feature = "jvm"))]
    env: PhantomData<&'jni ()>,
    #[cfg(feature = "jvm")]
-   env: JNIEnv<'jni>
+   env: JNIEnv<'jni>,
+   transport: Arc<dyn Transport>,
 }
 
 impl TimelineRepository<'_> {
    #[cfg(not(feature = "jvm"))]
    pub fn new() -> TimelineRepository<'static> {
       TimelineRepository {
-         env: PhantomData
+         env: PhantomData,
+         transport: Arc::new(ReqwestTransport),
       }
    }
 
    #[cfg(feature = "jvm")]
    pub fn new<'jni>(env: &JNIEnv<'jni>) -> TimelineRepository<'jni> {
       TimelineRepository {
-         env: unsafe { env.unsafe_clone() }
+         env: unsafe { env.unsafe_clone() },
+         transport: Arc::new(ReqwestTransport),
       }
    }
 
@@ -49,17 +79,215 @@ impl TimelineRepository<'_> {
       &mut self,
       token: &Token
    ) -> anyhow::Result<Vec<Status>> {
-      use ext_reqwest::CLIENT;
+      let page = self.fetch_timeline(token, &Timeline::Home, &PageParams::default())?;
+      Ok(page.items)
+   }
+
+   /// `cursor`には前回取得したページの`next`を渡す
+   pub fn load_older_home_timeline(
+      &mut self,
+      token: &Token,
+      cursor: &PageCursor,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Status>> {
+      self.load_older_timeline(token, &Timeline::Home, cursor, limit)
+   }
+
+   /// `cursor`には前回取得したページの`prev`を渡す
+   pub fn load_newer_home_timeline(
+      &mut self,
+      token: &Token,
+      cursor: &PageCursor,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Status>> {
+      self.load_newer_timeline(token, &Timeline::Home, cursor, limit)
+   }
+
+   /// `cursor`には前回取得したページの`next`を渡す。
+   /// 最初のページを取得する場合は`PageCursor::default()`を渡す
+   pub fn load_older_timeline(
+      &mut self,
+      token: &Token,
+      timeline: &Timeline,
+      cursor: &PageCursor,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Status>> {
+      let page = PageParams {
+         max_id: cursor.max_id.as_deref(),
+         since_id: cursor.since_id.as_deref(),
+         min_id: None,
+         limit,
+      };
+
+      self.fetch_timeline(token, timeline, &page)
+   }
+
+   /// `cursor`には前回取得したページの`prev`を渡す
+   pub fn load_newer_timeline(
+      &mut self,
+      token: &Token,
+      timeline: &Timeline,
+      cursor: &PageCursor,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Status>> {
+      let page = PageParams {
+         max_id: None,
+         since_id: cursor.since_id.as_deref(),
+         min_id: cursor.min_id.as_deref(),
+         limit,
+      };
+
+      self.fetch_timeline(token, timeline, &page)
+   }
+
+   pub fn get_public_timeline(
+      &mut self,
+      token: &Token,
+      is_local: bool,
+      is_remote: bool,
+      is_only_media: bool
+   ) -> anyhow::Result<Vec<Status>> {
       use mastodon_webapi::api::timelines;
-      use crate::cache;
-      use crate::conversion;
 
-      let api_timeline = timelines::get_home(
-         &CLIENT,
+      let params = PublicTimelineParams {
+         local: Some(is_local),
+         remote: Some(is_remote),
+         only_media: Some(is_only_media),
+      };
+
+      let api_page = timelines::get_public(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         Some(&token.access_token),
+         &params,
+         &PageParams::default()
+      )?;
+
+      let page = self.convert_page(token, api_page)?;
+      Ok(page.items)
+   }
+
+   pub fn get_hashtag_timeline(
+      &mut self,
+      token: &Token,
+      hashtag: &str,
+      any: &[&str],
+      all: &[&str],
+      none: &[&str]
+   ) -> anyhow::Result<Vec<Status>> {
+      use mastodon_webapi::api::timelines;
+
+      let params = TagTimelineParams {
+         any,
+         all,
+         none,
+         ..TagTimelineParams::default()
+      };
+
+      let api_page = timelines::get_tag(
+         self.transport.as_ref(),
+         &token.instance.get().url,
+         Some(&token.access_token),
+         hashtag,
+         &params,
+         &PageParams::default()
+      )?;
+
+      let page = self.convert_page(token, api_page)?;
+      Ok(page.items)
+   }
+
+   pub fn get_list_timeline(
+      &mut self,
+      token: &Token,
+      list_id: &str
+   ) -> anyhow::Result<Vec<Status>> {
+      use mastodon_webapi::api::timelines;
+
+      let api_page = timelines::get_list(
+         self.transport.as_ref(),
          &token.instance.get().url,
-         &token.access_token
+         &token.access_token,
+         list_id,
+         &PageParams::default()
       )?;
 
+      let page = self.convert_page(token, api_page)?;
+      Ok(page.items)
+   }
+
+   /// このアカウントのレート制限の残り。
+   /// 多数のカラムを更新する際、残りが少なければ更新頻度を下げるために使う。
+   /// まだ一度もリクエストしていない場合などはNone
+   pub fn get_rate_limit_budget(&self, token: &Token) -> Option<RateLimitBudget> {
+      use ext_reqwest::rate_limit;
+      use crate::conversion;
+
+      rate_limit::budget(&token.instance.get().url, Some(&token.access_token))
+         .map(conversion::rate_limit::from_api)
+   }
+
+   fn fetch_timeline(
+      &mut self,
+      token: &Token,
+      timeline: &Timeline,
+      page: &PageParams<'_>
+   ) -> anyhow::Result<Page<Status>> {
+      use mastodon_webapi::api::timelines;
+
+      let transport = self.transport.as_ref();
+      let instance_url = &token.instance.get().url;
+      let access_token = &token.access_token;
+
+      let api_page = match timeline {
+         Timeline::Home => timelines::get_home(
+            transport, instance_url, access_token, page
+         )?,
+         Timeline::Public { is_local, is_remote, is_only_media } => {
+            let params = PublicTimelineParams {
+               local: Some(*is_local),
+               remote: Some(*is_remote),
+               only_media: Some(*is_only_media),
+            };
+
+            timelines::get_public(
+               transport, instance_url, Some(access_token), &params, page
+            )?
+         }
+         Timeline::Hashtag { hashtag, any, all, none } => {
+            let any = any.iter().map(String::as_str).collect::<Vec<_>>();
+            let all = all.iter().map(String::as_str).collect::<Vec<_>>();
+            let none = none.iter().map(String::as_str).collect::<Vec<_>>();
+
+            let params = TagTimelineParams {
+               any: &any,
+               all: &all,
+               none: &none,
+               ..TagTimelineParams::default()
+            };
+
+            timelines::get_tag(
+               transport, instance_url, Some(access_token), hashtag, &params, page
+            )?
+         }
+         Timeline::List { list_id } => timelines::get_list(
+            transport, instance_url, access_token, list_id, page
+         )?,
+      };
+
+      self.convert_page(token, api_page)
+   }
+
+   fn convert_page(
+      &mut self,
+      token: &Token,
+      api_page: ApiPage<ApiStatus>
+   ) -> anyhow::Result<Page<Status>> {
+      use crate::cache;
+      use crate::conversion;
+
+      let ApiPage { items, next, prev } = api_page;
+
       let mut account_repo = cache::account::repo()
          .write(#[cfg(feature = "jvm")] &mut self.env)?;
 
@@ -72,7 +300,7 @@ impl TimelineRepository<'_> {
       let mut no_credential_poll_repo = cache::poll::no_credential_poll_repo()
          .write(#[cfg(feature = "jvm")] &mut self.env)?;
 
-      let timeline = api_timeline.into_iter()
+      let statuses = items.into_iter()
          .flat_map(|api_status|
             conversion::status::from_api(
                #[cfg(feature = "jvm")] &mut self.env,
@@ -86,57 +314,1330 @@ impl TimelineRepository<'_> {
          )
          .collect();
 
-      Ok(timeline)
+      let page = Page {
+         items: statuses,
+         next: next.map(conversion::page::from_api),
+         prev: prev.map(conversion::page::from_api),
+      };
+
+      Ok(page)
+   }
+}
+
+/// [TimelineRepository]の非同期版。
+/// レスポンスを待つ間スレッドを占有しないため、
+/// 多数のカラムを同時に読み込む場合にはこちらを使う
+pub struct AsyncTimelineRepository {
+   #[cfg(feature = "jvm")]
+   vm: JavaVM,
+   transport: Arc<dyn Transport>,
+}
+
+#[cfg(not(feature = "jvm"))]
+impl Default for AsyncTimelineRepository {
+   fn default() -> AsyncTimelineRepository {
+      AsyncTimelineRepository::new()
+   }
+}
+
+impl AsyncTimelineRepository {
+   #[cfg(not(feature = "jvm"))]
+   pub fn new() -> AsyncTimelineRepository {
+      AsyncTimelineRepository {
+         transport: Arc::new(ReqwestTransport),
+      }
+   }
+
+   #[cfg(feature = "jvm")]
+   pub fn new(env: &JNIEnv) -> jni::errors::Result<AsyncTimelineRepository> {
+      Ok(AsyncTimelineRepository {
+         vm: env.get_java_vm()?,
+         transport: Arc::new(ReqwestTransport),
+      })
+   }
+
+   pub async fn get_home_timeline(
+      &self,
+      token: &Token
+   ) -> anyhow::Result<Vec<Status>> {
+      use mastodon_webapi::api::timelines;
+
+      // CacheのガードをFutureが保持しないよう先に取り出しておく
+      let instance_url = token.instance.get().url.clone();
+
+      let api_page = timelines::get_home_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         &PageParams::default()
+      ).await?;
+
+      let page = self.convert_page(token, api_page)?;
+      Ok(page.items)
+   }
+
+   pub async fn get_public_timeline(
+      &self,
+      token: &Token,
+      is_local: bool,
+      is_remote: bool,
+      is_only_media: bool
+   ) -> anyhow::Result<Vec<Status>> {
+      use mastodon_webapi::api::timelines;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let params = PublicTimelineParams {
+         local: Some(is_local),
+         remote: Some(is_remote),
+         only_media: Some(is_only_media),
+      };
+
+      let api_page = timelines::get_public_async(
+         self.transport.as_ref(),
+         &instance_url,
+         Some(&token.access_token),
+         &params,
+         &PageParams::default()
+      ).await?;
+
+      let page = self.convert_page(token, api_page)?;
+      Ok(page.items)
+   }
+
+   pub async fn get_hashtag_timeline(
+      &self,
+      token: &Token,
+      hashtag: &str,
+      any: &[&str],
+      all: &[&str],
+      none: &[&str]
+   ) -> anyhow::Result<Vec<Status>> {
+      use mastodon_webapi::api::timelines;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let params = TagTimelineParams {
+         any,
+         all,
+         none,
+         ..TagTimelineParams::default()
+      };
+
+      let api_page = timelines::get_tag_async(
+         self.transport.as_ref(),
+         &instance_url,
+         Some(&token.access_token),
+         hashtag,
+         &params,
+         &PageParams::default()
+      ).await?;
+
+      let page = self.convert_page(token, api_page)?;
+      Ok(page.items)
+   }
+
+   pub async fn get_list_timeline(
+      &self,
+      token: &Token,
+      list_id: &str
+   ) -> anyhow::Result<Vec<Status>> {
+      use mastodon_webapi::api::timelines;
+
+      let instance_url = token.instance.get().url.clone();
+
+      let api_page = timelines::get_list_async(
+         self.transport.as_ref(),
+         &instance_url,
+         &token.access_token,
+         list_id,
+         &PageParams::default()
+      ).await?;
+
+      let page = self.convert_page(token, api_page)?;
+      Ok(page.items)
+   }
+
+   /// [TimelineRepository::load_older_timeline]の非同期版
+   pub async fn load_older_timeline(
+      &self,
+      token: &Token,
+      timeline: &Timeline,
+      cursor: &PageCursor,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Status>> {
+      let page = PageParams {
+         max_id: cursor.max_id.as_deref(),
+         since_id: cursor.since_id.as_deref(),
+         min_id: None,
+         limit,
+      };
+
+      self.fetch_timeline(token, timeline, &page).await
+   }
+
+   /// [TimelineRepository::load_newer_timeline]の非同期版
+   pub async fn load_newer_timeline(
+      &self,
+      token: &Token,
+      timeline: &Timeline,
+      cursor: &PageCursor,
+      limit: Option<u32>
+   ) -> anyhow::Result<Page<Status>> {
+      let page = PageParams {
+         max_id: None,
+         since_id: cursor.since_id.as_deref(),
+         min_id: cursor.min_id.as_deref(),
+         limit,
+      };
+
+      self.fetch_timeline(token, timeline, &page).await
+   }
+
+   async fn fetch_timeline(
+      &self,
+      token: &Token,
+      timeline: &Timeline,
+      page: &PageParams<'_>
+   ) -> anyhow::Result<Page<Status>> {
+      use mastodon_webapi::api::timelines;
+
+      let transport = self.transport.as_ref();
+      let instance_url = token.instance.get().url.clone();
+      let access_token = &token.access_token;
+
+      let api_page = match timeline {
+         Timeline::Home => timelines::get_home_async(
+            transport, &instance_url, access_token, page
+         ).await?,
+         Timeline::Public { is_local, is_remote, is_only_media } => {
+            let params = PublicTimelineParams {
+               local: Some(*is_local),
+               remote: Some(*is_remote),
+               only_media: Some(*is_only_media),
+            };
+
+            timelines::get_public_async(
+               transport, &instance_url, Some(access_token), &params, page
+            ).await?
+         }
+         Timeline::Hashtag { hashtag, any, all, none } => {
+            let any = any.iter().map(String::as_str).collect::<Vec<_>>();
+            let all = all.iter().map(String::as_str).collect::<Vec<_>>();
+            let none = none.iter().map(String::as_str).collect::<Vec<_>>();
+
+            let params = TagTimelineParams {
+               any: &any,
+               all: &all,
+               none: &none,
+               ..TagTimelineParams::default()
+            };
+
+            timelines::get_tag_async(
+               transport, &instance_url, Some(access_token), hashtag, &params, page
+            ).await?
+         }
+         Timeline::List { list_id } => timelines::get_list_async(
+            transport, &instance_url, access_token, list_id, page
+         ).await?,
+      };
+
+      self.convert_page(token, api_page)
+   }
+
+   /// レスポンスを受け取ったスレッドでそのままキャッシュに変換する。
+   /// 複数のタイムラインの変換は別々のワーカースレッドで並行に行われる
+   fn convert_page(
+      &self,
+      token: &Token,
+      api_page: ApiPage<ApiStatus>
+   ) -> anyhow::Result<Page<Status>> {
+      #[cfg(not(feature = "jvm"))]
+      {
+         TimelineRepository::new().convert_page(token, api_page)
+      }
+
+      #[cfg(feature = "jvm")]
+      {
+         // ランタイムのスレッドはJVMにアタッチしたままにし、
+         // ローカル参照はフレームごとに解放する
+         let mut env = self.vm.attach_current_thread_as_daemon()?;
+         env.with_local_frame(64, |env| {
+            TimelineRepository::new(env).convert_page(token, api_page)
+         })
+      }
+   }
+}
+
+#[cfg(feature = "jvm")]
+mod jvm {
+   use std::future::Future;
+   use jni::JNIEnv;
+   use jni::objects::JObject;
+   use jni::sys::jboolean;
+   use mastodon_entity::jvm_types::{
+      JvmPage, JvmPageCursor, JvmRateLimitBudget, JvmStatus, JvmToken,
+   };
+   use mastodon_entity::page::Page;
+   use mastodon_entity::status::Status;
+   use mastodon_entity::token::Token;
+   use panoptiqon::jvm_types::{JvmList, JvmNullable, JvmString};
+   use crate::cache;
+   use super::{AsyncTimelineRepository, Timeline};
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_getHomeTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>
+   ) -> JvmList<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_home_time_line(&mut env, token)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_getHomeTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>
+   ) -> JvmList<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_home_time_line(&mut env, token)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_home_time_line<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>
+   ) -> anyhow::Result<JvmList<'local, JvmStatus<'local>>> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+      use super::TimelineRepository;
+
+      let mut status_repository = TimelineRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let timeline = status_repository.get_home_timeline(&token)?;
+      Ok(timeline.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_getPublicTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean
+   ) -> JvmList<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_public_timeline(&mut env, token, is_local, is_remote, is_only_media)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_getPublicTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean
+   ) -> JvmList<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_public_timeline(&mut env, token, is_local, is_remote, is_only_media)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_public_timeline<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean
+   ) -> anyhow::Result<JvmList<'local, JvmStatus<'local>>> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+      use super::TimelineRepository;
+
+      let mut status_repository = TimelineRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let timeline = status_repository.get_public_timeline(
+         &token, is_local != 0, is_remote != 0, is_only_media != 0
+      )?;
+      Ok(timeline.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_getHashtagTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>
+   ) -> JvmList<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_hashtag_timeline(&mut env, token, hashtag, any, all, none)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_getHashtagTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>
+   ) -> JvmList<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_hashtag_timeline(&mut env, token, hashtag, any, all, none)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_hashtag_timeline<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>
+   ) -> anyhow::Result<JvmList<'local, JvmStatus<'local>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+      use super::TimelineRepository;
+
+      let mut status_repository = TimelineRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let hashtag = String::clone_from_jvm(env, &hashtag);
+      let any = Vec::<String>::clone_from_jvm(env, &any);
+      let all = Vec::<String>::clone_from_jvm(env, &all);
+      let none = Vec::<String>::clone_from_jvm(env, &none);
+
+      let timeline = status_repository.get_hashtag_timeline(
+         &token,
+         &hashtag,
+         &any.iter().map(String::as_str).collect::<Vec<_>>(),
+         &all.iter().map(String::as_str).collect::<Vec<_>>(),
+         &none.iter().map(String::as_str).collect::<Vec<_>>()
+      )?;
+      Ok(timeline.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_getListTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>
+   ) -> JvmList<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_list_timeline(&mut env, token, list_id)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_getListTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>
+   ) -> JvmList<'local, JvmStatus<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_list_timeline(&mut env, token, list_id)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_list_timeline<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>
+   ) -> anyhow::Result<JvmList<'local, JvmStatus<'local>>> {
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+      use super::TimelineRepository;
+
+      let mut status_repository = TimelineRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let list_id = String::clone_from_jvm(env, &list_id);
+      let timeline = status_repository.get_list_timeline(&token, &list_id)?;
+      Ok(timeline.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_getRateLimitBudget<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>
+   ) -> JvmNullable<'local, JvmRateLimitBudget<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_rate_limit_budget(&mut env, token)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_getRateLimitBudget<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>
+   ) -> JvmNullable<'local, JvmRateLimitBudget<'local>> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      get_rate_limit_budget(&mut env, token)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn get_rate_limit_budget<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>
+   ) -> anyhow::Result<JvmNullable<'local, JvmRateLimitBudget<'local>>> {
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+      use super::TimelineRepository;
+
+      let timeline_repository = TimelineRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let budget = timeline_repository.get_rate_limit_budget(&token);
+      Ok(budget.clone_into_jvm(env))
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadOlderHomeTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = Timeline::Home;
+      load_older_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadOlderHomeTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = Timeline::Home;
+      load_older_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadNewerHomeTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = Timeline::Home;
+      load_newer_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadNewerHomeTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = Timeline::Home;
+      load_newer_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadOlderPublicTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = public_timeline(is_local, is_remote, is_only_media);
+      load_older_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadOlderPublicTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = public_timeline(is_local, is_remote, is_only_media);
+      load_older_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadNewerPublicTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = public_timeline(is_local, is_remote, is_only_media);
+      load_newer_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadNewerPublicTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = public_timeline(is_local, is_remote, is_only_media);
+      load_newer_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadOlderHashtagTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = hashtag_timeline(&mut env, hashtag, any, all, none);
+      load_older_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadOlderHashtagTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = hashtag_timeline(&mut env, hashtag, any, all, none);
+      load_older_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadNewerHashtagTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = hashtag_timeline(&mut env, hashtag, any, all, none);
+      load_newer_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadNewerHashtagTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = hashtag_timeline(&mut env, hashtag, any, all, none);
+      load_newer_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadOlderListTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = list_timeline(&mut env, list_id);
+      load_older_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadOlderListTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = list_timeline(&mut env, list_id);
+      load_older_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadNewerListTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = list_timeline(&mut env, list_id);
+      load_newer_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadNewerListTimeline<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      cursor: JvmPageCursor<'local>
+   ) -> JvmPage<'local> {
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
+
+      let timeline = list_timeline(&mut env, list_id);
+      load_newer_timeline(&mut env, token, timeline, cursor)
+         .unwrap_or_throw_web_api_exception(&mut env)
+   }
+
+   fn load_older_timeline<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      timeline: Timeline,
+      cursor: JvmPageCursor<'local>
+   ) -> anyhow::Result<JvmPage<'local>> {
+      use mastodon_entity::page::PageCursor;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+      use super::TimelineRepository;
+
+      let mut timeline_repository = TimelineRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let cursor = PageCursor::clone_from_jvm(env, &cursor);
+      let page = timeline_repository.load_older_timeline(&token, &timeline, &cursor, None)?;
+      Ok(page.clone_into_jvm(env))
+   }
+
+   fn load_newer_timeline<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      timeline: Timeline,
+      cursor: JvmPageCursor<'local>
+   ) -> anyhow::Result<JvmPage<'local>> {
+      use mastodon_entity::page::PageCursor;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+      use super::TimelineRepository;
+
+      let mut timeline_repository = TimelineRepository::new(env);
+
+      let token = clone_token_from_jvm(env, &token)?;
+      let cursor = PageCursor::clone_from_jvm(env, &cursor);
+      let page = timeline_repository.load_newer_timeline(&token, &timeline, &cursor, None)?;
+      Ok(page.clone_into_jvm(env))
+   }
+
+   fn public_timeline(
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean
+   ) -> Timeline {
+      Timeline::Public {
+         is_local: is_local != 0,
+         is_remote: is_remote != 0,
+         is_only_media: is_only_media != 0,
+      }
+   }
+
+   fn hashtag_timeline<'local>(
+      env: &mut JNIEnv<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>
+   ) -> Timeline {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      Timeline::Hashtag {
+         hashtag: String::clone_from_jvm(env, &hashtag),
+         any: Vec::<String>::clone_from_jvm(env, &any),
+         all: Vec::<String>::clone_from_jvm(env, &all),
+         none: Vec::<String>::clone_from_jvm(env, &none),
+      }
+   }
+
+   fn list_timeline<'local>(
+      env: &mut JNIEnv<'local>,
+      list_id: JvmString<'local>
+   ) -> Timeline {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      Timeline::List {
+         list_id: String::clone_from_jvm(env, &list_id),
+      }
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_getHomeTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      callback: JObject<'local>
+   ) {
+      get_home_timeline_async(&mut env, token, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_getHomeTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      callback: JObject<'local>
+   ) {
+      get_home_timeline_async(&mut env, token, callback);
+   }
+
+   fn get_home_timeline_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      callback: JObject<'local>
+   ) {
+      spawn_timeline(env, token, callback, |repository, token| async move {
+         repository.get_home_timeline(&token).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_getPublicTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      callback: JObject<'local>
+   ) {
+      get_public_timeline_async(&mut env, token, is_local, is_remote, is_only_media, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_getPublicTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      callback: JObject<'local>
+   ) {
+      get_public_timeline_async(&mut env, token, is_local, is_remote, is_only_media, callback);
+   }
+
+   fn get_public_timeline_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      callback: JObject<'local>
+   ) {
+      spawn_timeline(env, token, callback, move |repository, token| async move {
+         repository.get_public_timeline(
+            &token, is_local != 0, is_remote != 0, is_only_media != 0
+         ).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_getHashtagTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      get_hashtag_timeline_async(&mut env, token, hashtag, any, all, none, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_getHashtagTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      get_hashtag_timeline_async(&mut env, token, hashtag, any, all, none, callback);
+   }
+
+   fn get_hashtag_timeline_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      callback: JObject<'local>
+   ) {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let hashtag = String::clone_from_jvm(env, &hashtag);
+      let any = Vec::<String>::clone_from_jvm(env, &any);
+      let all = Vec::<String>::clone_from_jvm(env, &all);
+      let none = Vec::<String>::clone_from_jvm(env, &none);
+
+      spawn_timeline(env, token, callback, |repository, token| async move {
+         repository.get_hashtag_timeline(
+            &token,
+            &hashtag,
+            &any.iter().map(String::as_str).collect::<Vec<_>>(),
+            &all.iter().map(String::as_str).collect::<Vec<_>>(),
+            &none.iter().map(String::as_str).collect::<Vec<_>>()
+         ).await
+      });
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_getListTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      callback: JObject<'local>
+   ) {
+      get_list_timeline_async(&mut env, token, list_id, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_getListTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      callback: JObject<'local>
+   ) {
+      get_list_timeline_async(&mut env, token, list_id, callback);
+   }
+
+   fn get_list_timeline_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      callback: JObject<'local>
+   ) {
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let list_id = String::clone_from_jvm(env, &list_id);
+
+      spawn_timeline(env, token, callback, |repository, token| async move {
+         repository.get_list_timeline(&token, &list_id).await
+      });
+   }
+
+   /// `load`を共有のランタイムで実行し、結果を`callback`に渡す。
+   /// JNI関数はリクエストの完了を待たずに戻る
+   fn spawn_timeline<'local, F>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      callback: JObject<'local>,
+      load: impl FnOnce(AsyncTimelineRepository, Token) -> F
+   )
+      where F: Future<Output = anyhow::Result<Vec<Status>>> + Send + 'static
+   {
+      use ext_reqwest::callback::WebApiCallback;
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+      use panoptiqon::jvm_type::JvmType;
+
+      let result = (|| {
+         let repository = AsyncTimelineRepository::new(env)?;
+         let token = clone_token_from_jvm(env, &token)?;
+         let callback = WebApiCallback::new(env, &callback)?;
+
+         callback.spawn(load(repository, token), |env, timeline| {
+            let timeline: JvmList<JvmStatus> = timeline.clone_into_jvm(env);
+            timeline.into_j_object()
+         });
+
+         anyhow::Ok(())
+      })();
+
+      if let Err(e) = result {
+         throw_web_api_exception(env, &e);
+      }
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadOlderHomeTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = Timeline::Home;
+      load_older_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadOlderHomeTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = Timeline::Home;
+      load_older_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadNewerHomeTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = Timeline::Home;
+      load_newer_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadNewerHomeTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = Timeline::Home;
+      load_newer_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadOlderPublicTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = public_timeline(is_local, is_remote, is_only_media);
+      load_older_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadOlderPublicTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = public_timeline(is_local, is_remote, is_only_media);
+      load_older_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadNewerPublicTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = public_timeline(is_local, is_remote, is_only_media);
+      load_newer_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadNewerPublicTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      is_local: jboolean,
+      is_remote: jboolean,
+      is_only_media: jboolean,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = public_timeline(is_local, is_remote, is_only_media);
+      load_newer_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadOlderHashtagTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = hashtag_timeline(&mut env, hashtag, any, all, none);
+      load_older_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadOlderHashtagTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = hashtag_timeline(&mut env, hashtag, any, all, none);
+      load_older_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadNewerHashtagTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = hashtag_timeline(&mut env, hashtag, any, all, none);
+      load_newer_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadNewerHashtagTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      hashtag: JvmString<'local>,
+      any: JvmList<'local, JvmString<'local>>,
+      all: JvmList<'local, JvmString<'local>>,
+      none: JvmList<'local, JvmString<'local>>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = hashtag_timeline(&mut env, hashtag, any, all, none);
+      load_newer_timeline_async(&mut env, token, timeline, cursor, callback);
    }
-}
 
-#[cfg(feature = "jvm")]
-mod jvm {
-   use jni::JNIEnv;
-   use jni::objects::JObject;
-   use mastodon_entity::jvm_types::{JvmStatus, JvmToken};
-   use panoptiqon::jvm_types::JvmList;
-   use crate::cache;
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadOlderListTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = list_timeline(&mut env, list_id);
+      load_older_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
 
    #[no_mangle]
-   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_getHomeTimeline<'local>(
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadOlderListTimelineAsync<'local>(
       mut env: JNIEnv<'local>,
       _obj: JObject<'local>,
-      token: JvmToken<'local>
-   ) -> JvmList<'local, JvmStatus<'local>> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = list_timeline(&mut env, list_id);
+      load_older_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
 
-      get_home_time_line(&mut env, token)
-         .unwrap_or_throw_io_exception(&mut env)
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_AndroidTimelineRepository_loadNewerListTimelineAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = list_timeline(&mut env, list_id);
+      load_newer_timeline_async(&mut env, token, timeline, cursor, callback);
    }
 
    #[no_mangle]
-   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_getHomeTimeline<'local>(
+   extern "C" fn Java_com_wcaokaze_probosqis_mastodon_repository_DesktopTimelineRepository_loadNewerListTimelineAsync<'local>(
       mut env: JNIEnv<'local>,
       _obj: JObject<'local>,
-      token: JvmToken<'local>
-   ) -> JvmList<'local, JvmStatus<'local>> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      token: JvmToken<'local>,
+      list_id: JvmString<'local>,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      let timeline = list_timeline(&mut env, list_id);
+      load_newer_timeline_async(&mut env, token, timeline, cursor, callback);
+   }
 
-      get_home_time_line(&mut env, token)
-         .unwrap_or_throw_io_exception(&mut env)
+   fn load_older_timeline_async<'local>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      timeline: Timeline,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      use mastodon_entity::page::PageCursor;
+      use panoptiqon::convert_jvm::CloneFromJvm;
+
+      let cursor = PageCursor::clone_from_jvm(env, &cursor);
+
+      spawn_page(env, token, callback, |repository, token| async move {
+         repository.load_older_timeline(&token, &timeline, &cursor, None).await
+      });
    }
 
-   fn get_home_time_line<'local>(
+   fn load_newer_timeline_async<'local>(
       env: &mut JNIEnv<'local>,
-      token: JvmToken<'local>
-   ) -> anyhow::Result<JvmList<'local, JvmStatus<'local>>> {
-      use mastodon_entity::token::Token;
-      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
-      use super::TimelineRepository;
+      token: JvmToken<'local>,
+      timeline: Timeline,
+      cursor: JvmPageCursor<'local>,
+      callback: JObject<'local>
+   ) {
+      use mastodon_entity::page::PageCursor;
+      use panoptiqon::convert_jvm::CloneFromJvm;
 
-      let mut status_repository = TimelineRepository::new(env);
+      let cursor = PageCursor::clone_from_jvm(env, &cursor);
+
+      spawn_page(env, token, callback, |repository, token| async move {
+         repository.load_newer_timeline(&token, &timeline, &cursor, None).await
+      });
+   }
+
+   /// [spawn_timeline]のページングする版
+   fn spawn_page<'local, F>(
+      env: &mut JNIEnv<'local>,
+      token: JvmToken<'local>,
+      callback: JObject<'local>,
+      load: impl FnOnce(AsyncTimelineRepository, Token) -> F
+   )
+      where F: Future<Output = anyhow::Result<Page<Status>>> + Send + 'static
+   {
+      use ext_reqwest::callback::WebApiCallback;
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+      use panoptiqon::convert_jvm::CloneIntoJvm;
+      use panoptiqon::jvm_type::JvmType;
+
+      let result = (|| {
+         let repository = AsyncTimelineRepository::new(env)?;
+         let token = clone_token_from_jvm(env, &token)?;
+         let callback = WebApiCallback::new(env, &callback)?;
+
+         callback.spawn(load(repository, token), |env, page| {
+            let page: JvmPage = page.clone_into_jvm(env);
+            page.into_j_object()
+         });
 
+         anyhow::Ok(())
+      })();
+
+      if let Err(e) = result {
+         throw_web_api_exception(env, &e);
+      }
+   }
+
+   fn clone_token_from_jvm<'local>(
+      env: &mut JNIEnv<'local>,
+      token: &JvmToken<'local>
+   ) -> anyhow::Result<Token> {
       let instance = token.instance(env);
       let instance = cache::instance::clone_from_jvm(env, &instance)?;
-      let token = Token::clone_from_jvm(env, &token, instance);
-      let timeline = status_repository.get_home_timeline(&token)?;
-      Ok(timeline.clone_into_jvm(env))
+      Ok(Token::clone_from_jvm(env, token, instance))
    }
 }
 
@@ -144,6 +1645,7 @@ mod jvm {
 mod test {
    use std::time::Duration;
    use isolang::Language;
+   use crate::fixture;
    use super::TimelineRepository;
 
    #[test]
@@ -172,556 +1674,10 @@ mod test {
          StatusMention, StatusVisibility,
       };
       use mastodon_entity::token::Token;
-      use mastodon_webapi::api::timelines;
-      use mastodon_webapi::entity::account::{
-         Account as ApiAccount,
-         AccountField as ApiAccountField,
-      };
-      use mastodon_webapi::entity::application::{
-         Application as ApiApplication,
-         ApplicationClientSecretExpiresAt as ApiApplicationClientSecretExpiresAt,
-      };
-      use mastodon_webapi::entity::custom_emoji::CustomEmoji as ApiCustomEmoji;
-      use mastodon_webapi::entity::filter::{
-         Filter as ApiFilter,
-         FilterKeyword as ApiFilterKeyword,
-         FilterResult as ApiFilterResult,
-         FilterStatus as ApiFilterStatus,
-      };
-      use mastodon_webapi::entity::media_attachment::{
-         MediaAttachment as ApiMediaAttachment,
-         MediaAttachmentFocus as ApiMediaAttachmentFocus,
-         MediaAttachmentMetadata as ApiMediaAttachmentMetadata,
-         MediaAttachmentSize as ApiMediaAttachmentSize,
-      };
-      use mastodon_webapi::entity::poll::{
-         Poll as ApiPoll,
-         PollOption as ApiPollOption,
-      };
-      use mastodon_webapi::entity::preview_card::{
-         PreviewCard as ApiPreviewCard,
-         PreviewCardAuthor as ApiPreviewCardAuthor,
-      };
-      use mastodon_webapi::entity::status::{
-         Status as ApiStatus,
-         StatusMention as ApiStatusMention,
-         StatusTag as ApiStatusTag,
-      };
       use crate::cache;
 
       let mut repository = TimelineRepository::new();
-
-      timelines::inject_get_verify_credentials(|_, _, _|
-         Ok(vec![
-            ApiStatus {
-               id: Some("status id 1".to_string()),
-               uri: Some("uri1".to_string()),
-               created_at: Some("2000-01-01T00:00:00Z".to_string()),
-               account: Some(ApiAccount {
-                  id: Some("account id 1".to_string()),
-                  username: Some("username1".to_string()),
-                  acct: Some("acct1".to_string()),
-                  url: Some("https://example.com/account1".to_string()),
-                  display_name: Some("display name 1".to_string()),
-                  note: Some("note1".to_string()),
-                  avatar: Some("https://example.com/avatar1".to_string()),
-                  avatar_static: Some("https://example.com/static/avatar1".to_string()),
-                  header: Some("https://example.com/header1".to_string()),
-                  header_static: Some("https://example.com/static/header1".to_string()),
-                  locked: Some(false),
-                  fields: Some(vec![
-                     ApiAccountField {
-                        name: Some("account field name 1".to_string()),
-                        value: Some("account field value 1".to_string()),
-                        verified_at: Some("2000-01-02T00:00:00Z".to_string()),
-                     },
-                     ApiAccountField {
-                        name: Some("account field name 2".to_string()),
-                        value: Some("account field value 2".to_string()),
-                        verified_at: Some("2000-01-03T00:00:00Z".to_string()),
-                     },
-                  ]),
-                  emojis: Some(vec![
-                     ApiCustomEmoji {
-                        shortcode: Some("shortcode1".to_string()),
-                        url: Some("https://example.com/emoji1".to_string()),
-                        static_url: Some("https://example.com/static/emoji1".to_string()),
-                        visible_in_picker: Some(false),
-                        category: Some("category".to_string()),
-                     },
-                     ApiCustomEmoji {
-                        shortcode: Some("shortcode2".to_string()),
-                        url: Some("https://example.com/emoji2".to_string()),
-                        static_url: Some("https://example.com/static/emoji2".to_string()),
-                        visible_in_picker: Some(true),
-                        category: Some("category".to_string()),
-                     },
-                  ]),
-                  bot: Some(true),
-                  group: Some(false),
-                  discoverable: Some(true),
-                  noindex: Some(false),
-                  moved: Some(Box::new(ApiAccount {
-                     id: Some("moved account id".to_string()),
-                     username: None,
-                     acct: None,
-                     url: None,
-                     display_name: None,
-                     note: None,
-                     avatar: None,
-                     avatar_static: None,
-                     header: None,
-                     header_static: None,
-                     locked: None,
-                     fields: None,
-                     emojis: None,
-                     bot: None,
-                     group: None,
-                     discoverable: None,
-                     noindex: None,
-                     moved: None,
-                     suspended: None,
-                     limited: None,
-                     created_at: None,
-                     last_status_at: None,
-                     statuses_count: None,
-                     followers_count: None,
-                     following_count: None,
-                     source: None,
-                     role: None,
-                     mute_expires_at: None,
-                  })),
-                  suspended: Some(false),
-                  limited: Some(true),
-                  created_at: Some("2000-01-04T00:00:00Z".to_string()),
-                  last_status_at: Some("2000-01-05T00:00:00Z".to_string()),
-                  statuses_count: Some(123),
-                  followers_count: Some(45),
-                  following_count: Some(67),
-                  source: None,
-                  role: None,
-                  mute_expires_at: None,
-               }),
-               content: Some("content".to_string()),
-               visibility: Some("public".to_string()),
-               sensitive: Some(true),
-               spoiler_text: Some("spoiler text".to_string()),
-               media_attachments: Some(vec![
-                  ApiMediaAttachment {
-                     id: Some("media attachment id 1".to_string()),
-                     r#type: Some("image".to_string()),
-                     url: Some("https://example.com/media_attachment1".to_string()),
-                     preview_url: Some("https://example.com/preview/media_attachment1".to_string()),
-                     remote_url: Some("https://example.com/remote/media_attachment1".to_string()),
-                     meta: Some(ApiMediaAttachmentMetadata {
-                        original: Some(ApiMediaAttachmentSize {
-                           width: Some(123),
-                           height: Some(456),
-                           size: None,
-                           aspect: None,
-                           frame_rate: None,
-                           duration: None,
-                           bitrate: None,
-                        }),
-                        small: Some(ApiMediaAttachmentSize {
-                           width: Some(78),
-                           height: Some(90),
-                           size: None,
-                           aspect: None,
-                           frame_rate: None,
-                           duration: None,
-                           bitrate: None,
-                        }),
-                        focus: Some(ApiMediaAttachmentFocus {
-                           x: Some(0.1),
-                           y: Some(0.2),
-                        }),
-                        length: None,
-                        duration: None,
-                        fps: None,
-                        size: None,
-                        width: None,
-                        height: None,
-                        aspect: None,
-                        audio_encode: None,
-                        audio_bitrate: None,
-                        audio_channels: None,
-                     }),
-                     description: Some("description".to_string()),
-                     blurhash: Some("blurhash".to_string()),
-                     text_url: None,
-                  },
-                  ApiMediaAttachment {
-                     id: Some("media attachment id 2".to_string()),
-                     r#type: Some("video".to_string()),
-                     url: Some("https://example.com/media_attachment2".to_string()),
-                     preview_url: Some("https://example.com/preview/media_attachment2".to_string()),
-                     remote_url: Some("https://example.com/remote/media_attachment2".to_string()),
-                     meta: Some(ApiMediaAttachmentMetadata {
-                        original: Some(ApiMediaAttachmentSize {
-                           width: Some(123),
-                           height: Some(456),
-                           size: None,
-                           aspect: None,
-                           frame_rate: Some("frame rate".to_string()),
-                           duration: Some(12.3),
-                           bitrate: Some(78),
-                        }),
-                        small: Some(ApiMediaAttachmentSize {
-                           width: Some(901),
-                           height: Some(234),
-                           size: None,
-                           aspect: None,
-                           frame_rate: None,
-                           duration: None,
-                           bitrate: None,
-                        }),
-                        focus: None,
-                        length: Some("length".to_string()),
-                        duration: Some(45.6),
-                        fps: Some(56),
-                        size: None,
-                        width: None,
-                        height: None,
-                        aspect: None,
-                        audio_encode: Some("audio encode".to_string()),
-                        audio_bitrate: Some("audio bitrate".to_string()),
-                        audio_channels: Some("audio channels".to_string()),
-                     }),
-                     description: Some("description".to_string()),
-                     blurhash: Some("blurhash".to_string()),
-                     text_url: None,
-                  },
-               ]),
-               application: Some(ApiApplication {
-                  name: "application name".to_string(),
-                  website: Some("https://example.com/application".to_string()),
-                  scopes: Some(vec!["read".to_string(), "write".to_string()]),
-                  redirect_uris: Some(vec!["https://example.com/application/redirect".to_string()]),
-                  redirect_uri: Some("https://example.com/application/redirect".to_string()),
-                  vapid_key: None,
-                  client_id: Some("client id".to_string()),
-                  client_secret: Some("client secret".to_string()),
-                  client_secret_expires_at: Some(
-                     ApiApplicationClientSecretExpiresAt
-                        ::ExpiresAt("2000-01-09T00:00:00Z".to_string()),
-                  ),
-               }),
-               mentions: Some(vec![
-                  ApiStatusMention {
-                     id: Some("mentioned account id 1".to_string()),
-                     username: Some("mentioned username 1".to_string()),
-                     url: Some("https://example.com/mentioned1".to_string()),
-                     acct: Some("mentioned acct 1".to_string()),
-                  },
-                  ApiStatusMention {
-                     id: Some("mentioned account id 2".to_string()),
-                     username: Some("mentioned username 2".to_string()),
-                     url: Some("https://example.com/mentioned2".to_string()),
-                     acct: Some("mentioned acct 2".to_string()),
-                  },
-               ]),
-               tags: Some(vec![
-                  ApiStatusTag {
-                     name: Some("hash tag 1".to_string()),
-                     url: Some("https://example.com/hashtag1".to_string()),
-                  },
-                  ApiStatusTag {
-                     name: Some("hash tag 2".to_string()),
-                     url: Some("https://example.com/hashtag2".to_string()),
-                  },
-               ]),
-               emojis: Some(vec![
-                  ApiCustomEmoji {
-                     shortcode: Some("shortcode3".to_string()),
-                     url: Some("https://example.com/emoji3".to_string()),
-                     static_url: Some("https://example.com/static/emoji3".to_string()),
-                     visible_in_picker: Some(true),
-                     category: Some("category".to_string()),
-                  },
-                  ApiCustomEmoji {
-                     shortcode: Some("shortcode4".to_string()),
-                     url: Some("https://example.com/emoji4".to_string()),
-                     static_url: Some("https://example.com/static/emoji4".to_string()),
-                     visible_in_picker: Some(true),
-                     category: Some("category".to_string()),
-                  },
-               ]),
-               reblogs_count: Some(123),
-               favourites_count: Some(456),
-               replies_count: Some(78),
-               url: Some("https://example.com/status".to_string()),
-               in_reply_to_id: Some("replied status id".to_string()),
-               in_reply_to_account_id: Some("replied account id".to_string()),
-               reblog: Some(Box::new(
-                  ApiStatus {
-                     id: Some("boosted status id".to_string()),
-                     uri: None,
-                     created_at: None,
-                     account: None,
-                     content: None,
-                     visibility: None,
-                     sensitive: None,
-                     spoiler_text: None,
-                     media_attachments: None,
-                     application: None,
-                     mentions: None,
-                     tags: None,
-                     emojis: None,
-                     reblogs_count: None,
-                     favourites_count: None,
-                     replies_count: None,
-                     url: None,
-                     in_reply_to_id: None,
-                     in_reply_to_account_id: None,
-                     reblog: None,
-                     poll: None,
-                     card: None,
-                     language: None,
-                     text: None,
-                     edited_at: None,
-                     favourited: None,
-                     reblogged: None,
-                     muted: None,
-                     bookmarked: None,
-                     pinned: None,
-                     filtered: None,
-                  }
-               )),
-               poll: Some(ApiPoll {
-                  id: Some("poll id".to_string()),
-                  expires_at: Some("2000-01-10T00:00:00Z".to_string()),
-                  expired: Some(false),
-                  multiple: Some(true),
-                  votes_count: Some(12),
-                  voters_count: Some(3),
-                  options: Some(vec![
-                     ApiPollOption {
-                        title: Some("poll option 1".to_string()),
-                        votes_count: Some(4),
-                     },
-                     ApiPollOption {
-                        title: Some("poll option 2".to_string()),
-                        votes_count: Some(5),
-                     },
-                  ]),
-                  emojis: Some(vec![
-                     ApiCustomEmoji {
-                        shortcode: Some("shortcode5".to_string()),
-                        url: Some("https://example.com/emoji5".to_string()),
-                        static_url: Some("https://example.com/static/emoji5".to_string()),
-                        visible_in_picker: Some(true),
-                        category: Some("category".to_string()),
-                     },
-                     ApiCustomEmoji {
-                        shortcode: Some("shortcode6".to_string()),
-                        url: Some("https://example.com/emoji6".to_string()),
-                        static_url: Some("https://example.com/static/emoji6".to_string()),
-                        visible_in_picker: Some(true),
-                        category: Some("category".to_string()),
-                     },
-                  ]),
-                  voted: Some(true),
-                  own_voted: Some(vec![6, 7]),
-               }),
-               card: Some(ApiPreviewCard {
-                  url: Some("https://example.com/preview/card".to_string()),
-                  title: Some("preview card title".to_string()),
-                  description: Some("preview card description".to_string()),
-                  r#type: Some("link".to_string()),
-                  authors: Some(vec![
-                     ApiPreviewCardAuthor {
-                        name: Some("preview card author name 1".to_string()),
-                        url: Some("https://example.com/preview/author1".to_string()),
-                        account: Some(ApiAccount {
-                           id: Some("preview card author id 1".to_string()),
-                           username: None,
-                           acct: None,
-                           url: None,
-                           display_name: None,
-                           note: None,
-                           avatar: None,
-                           avatar_static: None,
-                           header: None,
-                           header_static: None,
-                           locked: None,
-                           fields: None,
-                           emojis: None,
-                           bot: None,
-                           group: None,
-                           discoverable: None,
-                           noindex: None,
-                           moved: None,
-                           suspended: None,
-                           limited: None,
-                           created_at: None,
-                           last_status_at: None,
-                           statuses_count: None,
-                           followers_count: None,
-                           following_count: None,
-                           source: None,
-                           role: None,
-                           mute_expires_at: None,
-                        }),
-                     },
-                     ApiPreviewCardAuthor {
-                        name: Some("preview card author name 2".to_string()),
-                        url: Some("https://example.com/preview/author2".to_string()),
-                        account: Some(ApiAccount {
-                           id: Some("preview card author id 2".to_string()),
-                           username: None,
-                           acct: None,
-                           url: None,
-                           display_name: None,
-                           note: None,
-                           avatar: None,
-                           avatar_static: None,
-                           header: None,
-                           header_static: None,
-                           locked: None,
-                           fields: None,
-                           emojis: None,
-                           bot: None,
-                           group: None,
-                           discoverable: None,
-                           noindex: None,
-                           moved: None,
-                           suspended: None,
-                           limited: None,
-                           created_at: None,
-                           last_status_at: None,
-                           statuses_count: None,
-                           followers_count: None,
-                           following_count: None,
-                           source: None,
-                           role: None,
-                           mute_expires_at: None,
-                        }),
-                     },
-                  ]),
-                  author_name: None,
-                  author_url: None,
-                  provider_name: Some("preview card provider name".to_string()),
-                  provider_url: Some("https://example.com/preview/provider".to_string()),
-                  html: Some("preview card html".to_string()),
-                  width: Some(1234),
-                  height: Some(567),
-                  image: Some("https://example.com/preview/image".to_string()),
-                  embed_url: Some("https://example.com/preview/embed".to_string()),
-                  blurhash: Some("blurhash".to_string()),
-               }),
-               language: Some("ja".to_string()),
-               text: Some("text".to_string()),
-               edited_at: Some("2000-01-11T00:00:00Z".to_string()),
-               favourited: Some(false),
-               reblogged: Some(true),
-               muted: Some(false),
-               bookmarked: Some(true),
-               pinned: Some(false),
-               filtered: Some(vec![
-                  ApiFilterResult {
-                     filter: Some(ApiFilter {
-                        id: Some("filter id 1".to_string()),
-                        title: Some("filter title 1".to_string()),
-                        context: Some(vec!["home".to_string()]),
-                        expires_at: Some("2000-01-12T00:00:00Z".to_string()),
-                        filter_action: Some("hide".to_string()),
-                        keywords: Some(vec![
-                           ApiFilterKeyword {
-                              id: Some("filter keyword id 1".to_string()),
-                              keyword: Some("filter keyword 1".to_string()),
-                              whole_word: Some(true),
-                           },
-                           ApiFilterKeyword {
-                              id: Some("filter keyword id 2".to_string()),
-                              keyword: Some("filter keyword 2".to_string()),
-                              whole_word: Some(true),
-                           },
-                        ]),
-                        statuses: Some(vec![
-                           ApiFilterStatus {
-                              id: Some("filter status id 1".to_string()),
-                              status_id: Some("filtered status id 1".to_string()),
-                           },
-                           ApiFilterStatus {
-                              id: Some("filter status id 2".to_string()),
-                              status_id: Some("filtered status id 2".to_string()),
-                           },
-                        ]),
-                     }),
-                     keyword_matches: Some(vec!["matched keyword 1".to_string()]),
-                     status_matches: Some(vec!["matched status id 1".to_string()]),
-                  },
-                  ApiFilterResult {
-                     filter: Some(ApiFilter {
-                        id: Some("filter id 2".to_string()),
-                        title: Some("filter title 2".to_string()),
-                        context: Some(vec!["public".to_string()]),
-                        expires_at: Some("2000-01-13T00:00:00Z".to_string()),
-                        filter_action: Some("warn".to_string()),
-                        keywords: Some(vec![
-                           ApiFilterKeyword {
-                              id: Some("filter keyword id 3".to_string()),
-                              keyword: Some("filter keyword 3".to_string()),
-                              whole_word: Some(true),
-                           },
-                           ApiFilterKeyword {
-                              id: Some("filter keyword id 4".to_string()),
-                              keyword: Some("filter keyword 4".to_string()),
-                              whole_word: Some(true),
-                           },
-                        ]),
-                        statuses: Some(vec![
-                           ApiFilterStatus {
-                              id: Some("filter status id 3".to_string()),
-                              status_id: Some("filtered status id 3".to_string()),
-                           },
-                           ApiFilterStatus {
-                              id: Some("filter status id 4".to_string()),
-                              status_id: Some("filtered status id 4".to_string()),
-                           },
-                        ]),
-                     }),
-                     keyword_matches: Some(vec!["matched keyword 2".to_string()]),
-                     status_matches: Some(vec!["matched status id 2".to_string()]),
-                  },
-               ]),
-            },
-            ApiStatus {
-               id: Some("status id 2".to_string()),
-               uri: None,
-               created_at: None,
-               account: None,
-               content: None,
-               visibility: None,
-               sensitive: None,
-               spoiler_text: None,
-               media_attachments: None,
-               application: None,
-               mentions: None,
-               tags: None,
-               emojis: None,
-               reblogs_count: None,
-               favourites_count: None,
-               replies_count: None,
-               url: None,
-               in_reply_to_id: None,
-               in_reply_to_account_id: None,
-               reblog: None,
-               poll: None,
-               card: None,
-               language: None,
-               text: None,
-               edited_at: None,
-               favourited: None,
-               reblogged: None,
-               muted: None,
-               bookmarked: None,
-               pinned: None,
-               filtered: None,
-            },
-         ])
-      );
+      repository.transport = fixture::replay("timeline/home.json");
 
       let instance = Instance {
          url: "https://example.com/".parse().unwrap(),
@@ -883,7 +1839,7 @@ mod test {
                            Some(account)
                         },
                         content: Some("content".to_string()),
-                        visibility: Some(StatusVisibility("public".to_string())),
+                        visibility: Some(StatusVisibility::Public),
                         is_sensitive: Some(true),
                         spoiler_text: Some("spoiler text".to_string()),
                         media_attachments: vec![
@@ -1292,9 +2248,9 @@ mod test {
                      filter: Some(Filter {
                         id: FilterId("filter id 1".to_string()),
                         title: Some("filter title 1".to_string()),
-                        context: vec![FilterContext("home".to_string())],
+                        context: vec![FilterContext::Home],
                         expire_time: Some(Utc.with_ymd_and_hms(2000, 1, 12, 0, 0, 0).unwrap()),
-                        filter_action: Some(FilterAction("hide".to_string())),
+                        filter_action: Some(FilterAction::Hide),
                         keywords: vec![
                            FilterKeyword {
                               id: FilterKeywordId("filter keyword id 1".to_string()),
@@ -1336,9 +2292,9 @@ mod test {
                      filter: Some(Filter {
                         id: FilterId("filter id 2".to_string()),
                         title: Some("filter title 2".to_string()),
-                        context: vec![FilterContext("public".to_string())],
+                        context: vec![FilterContext::Public],
                         expire_time: Some(Utc.with_ymd_and_hms(2000, 1, 13, 0, 0, 0).unwrap()),
-                        filter_action: Some(FilterAction("warn".to_string())),
+                        filter_action: Some(FilterAction::Warn),
                         keywords: vec![
                            FilterKeyword {
                               id: FilterKeywordId("filter keyword id 3".to_string()),
@@ -1437,4 +2393,235 @@ mod test {
          statuses
       );
    }
+
+   #[test]
+   fn load_older_home_timeline() {
+      use chrono::{TimeZone, Utc};
+      use mastodon_entity::account::{AccountId, AccountLocalId};
+      use mastodon_entity::instance::Instance;
+      use mastodon_entity::page::{Page, PageCursor};
+      use mastodon_entity::token::Token;
+      use crate::cache;
+
+      let mut repository = TimelineRepository::new();
+      repository.transport = fixture::replay("timeline/older_home.json");
+
+      let instance = Instance {
+         url: "https://example.com/".parse().unwrap(),
+         version: "0.0.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let instance_cache = cache::instance::repo().write().unwrap().save(instance);
+
+      let token = Token {
+         instance: instance_cache.clone(),
+         account: None,
+         account_id: AccountId {
+            instance_url: instance_cache.get().url.clone(),
+            local: AccountLocalId("credential account id".to_string()),
+         },
+         access_token: "access token".to_string(),
+         token_type: "token type".to_string(),
+         scope: "scope".to_string(),
+         created_at: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let cursor = PageCursor {
+         max_id: Some("109".to_string()),
+         since_id: None,
+         min_id: None,
+      };
+
+      let page = repository
+         .load_older_home_timeline(&token, &cursor, Some(40))
+         .unwrap();
+
+      assert_eq!(
+         Page {
+            items: vec![],
+            next: Some(PageCursor {
+               max_id: Some("89".to_string()),
+               since_id: None,
+               min_id: None,
+            }),
+            prev: Some(PageCursor {
+               max_id: None,
+               since_id: None,
+               min_id: Some("108".to_string()),
+            }),
+         },
+         page
+      );
+   }
+
+   #[test]
+   fn load_newer_list_timeline() {
+      use chrono::{TimeZone, Utc};
+      use mastodon_entity::account::{AccountId, AccountLocalId};
+      use mastodon_entity::instance::Instance;
+      use mastodon_entity::page::{Page, PageCursor};
+      use mastodon_entity::token::Token;
+      use crate::cache;
+      use super::Timeline;
+
+      let mut repository = TimelineRepository::new();
+      repository.transport = fixture::replay("timeline/newer_list.json");
+
+      let instance = Instance {
+         url: "https://example.com/".parse().unwrap(),
+         version: "0.0.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let instance_cache = cache::instance::repo().write().unwrap().save(instance);
+
+      let token = Token {
+         instance: instance_cache.clone(),
+         account: None,
+         account_id: AccountId {
+            instance_url: instance_cache.get().url.clone(),
+            local: AccountLocalId("credential account id".to_string()),
+         },
+         access_token: "access token".to_string(),
+         token_type: "token type".to_string(),
+         scope: "scope".to_string(),
+         created_at: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let timeline = Timeline::List { list_id: "1".to_string() };
+
+      let cursor = PageCursor {
+         max_id: None,
+         since_id: None,
+         min_id: Some("108".to_string()),
+      };
+
+      let page = repository
+         .load_newer_timeline(&token, &timeline, &cursor, None)
+         .unwrap();
+
+      assert_eq!(
+         Page {
+            items: vec![],
+            next: Some(PageCursor {
+               max_id: Some("109".to_string()),
+               since_id: None,
+               min_id: None,
+            }),
+            prev: Some(PageCursor {
+               max_id: None,
+               since_id: None,
+               min_id: Some("110".to_string()),
+            }),
+         },
+         page
+      );
+   }
+
+   #[test]
+   fn get_hashtag_timeline() {
+      use chrono::{TimeZone, Utc};
+      use mastodon_entity::account::{AccountId, AccountLocalId};
+      use mastodon_entity::instance::Instance;
+      use mastodon_entity::token::Token;
+      use crate::cache;
+
+      let mut repository = TimelineRepository::new();
+      repository.transport = fixture::replay("timeline/hashtag.json");
+
+      let instance = Instance {
+         url: "https://example.com/".parse().unwrap(),
+         version: "0.0.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let instance_cache = cache::instance::repo().write().unwrap().save(instance);
+
+      let token = Token {
+         instance: instance_cache.clone(),
+         account: None,
+         account_id: AccountId {
+            instance_url: instance_cache.get().url.clone(),
+            local: AccountLocalId("credential account id".to_string()),
+         },
+         access_token: "access token".to_string(),
+         token_type: "token type".to_string(),
+         scope: "scope".to_string(),
+         created_at: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let statuses = repository
+         .get_hashtag_timeline(&token, "hashtag", &["any1", "any2"], &["all"], &[])
+         .unwrap();
+
+      assert!(statuses.is_empty());
+   }
+
+   #[test]
+   fn get_list_timeline_async() {
+      use chrono::{TimeZone, Utc};
+      use ext_reqwest::runtime::RUNTIME;
+      use mastodon_entity::account::{AccountId, AccountLocalId};
+      use mastodon_entity::instance::Instance;
+      use mastodon_entity::token::Token;
+      use crate::cache;
+      use super::AsyncTimelineRepository;
+
+      let mut repository = AsyncTimelineRepository::new();
+      repository.transport = fixture::replay("timeline/list.json");
+
+      let instance = Instance {
+         url: "https://example.com/".parse().unwrap(),
+         version: "0.0.0".to_string(),
+         version_checked_time: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let instance_cache = cache::instance::repo().write().unwrap().save(instance);
+
+      let token = Token {
+         instance: instance_cache.clone(),
+         account: None,
+         account_id: AccountId {
+            instance_url: instance_cache.get().url.clone(),
+            local: AccountLocalId("credential account id".to_string()),
+         },
+         access_token: "access token".to_string(),
+         token_type: "token type".to_string(),
+         scope: "scope".to_string(),
+         created_at: Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+      };
+
+      let statuses = RUNTIME
+         .block_on(repository.get_list_timeline(&token, "list id"))
+         .unwrap();
+
+      assert!(statuses.is_empty());
+   }
+
+   #[test]
+   fn load_older_home_timeline_async() {
+      use ext_reqwest::runtime::RUNTIME;
+      use mastodon_entity::page::PageCursor;
+      use super::{AsyncTimelineRepository, Timeline};
+
+      let mut repository = AsyncTimelineRepository::new();
+      repository.transport = fixture::replay("timeline/older_home.json");
+
+      let token = fixture::token("https://example.com/", "0.0.0");
+
+      let cursor = PageCursor {
+         max_id: Some("109".to_string()),
+         since_id: None,
+         min_id: None,
+      };
+
+      let page = RUNTIME
+         .block_on(repository.load_older_timeline(&token, &Timeline::Home, &cursor, Some(40)))
+         .unwrap();
+
+      assert!(page.items.is_empty());
+      assert_eq!(Some("89".to_string()), page.next.and_then(|c| c.max_id));
+      assert_eq!(Some("108".to_string()), page.prev.and_then(|c| c.min_id));
+   }
 }
@@ -76,7 +76,7 @@ class CallbackProcessorTest {
       }
 
       val accountRepository: AccountRepository = mockk {
-         every { getAccountIcon(any()) } returns Cache(mockk())
+         every { getAccountIcon(any(), any()) } returns Cache(mockk())
       }
 
       val credentialRepository: CredentialRepository = mockk {
@@ -118,7 +118,7 @@ class CallbackProcessorTest {
          verify { appRepository.loadAppCache(Url("https://example.com/")) }
          verify { appRepository.getToken(any(), "abcdefghijk") }
          verify { appRepository.getCredentialAccount(any()) }
-         verify { accountRepository.getAccountIcon(any()) }
+         verify { accountRepository.getAccountIcon(any(), any()) }
          verify { credentialRepository.saveCredential(any()) }
       }
    }
@@ -84,7 +84,7 @@ abstract class AbstractCallbackWaiterPageState : PPageState<CallbackWaiterPage>(
                // TODO: Token.accountが非nullになったあとRepositoryを叩く必要はなくなる
                val credentialAccount = appRepository.getCredentialAccount(token).value
                val credentialAccountIcon
-                  = accountRepository.getAccountIcon(credentialAccount.account.value)
+                  = accountRepository.getAccountIcon(token, credentialAccount.account.value)
 
                credentialRepository.saveCredential(token)
 
@@ -45,9 +45,7 @@ import com.wcaokaze.probosqis.panoptiqon.compose.asState
 import kotlinx.collections.immutable.ImmutableList
 import kotlinx.collections.immutable.persistentListOf
 import kotlinx.collections.immutable.toImmutableList
-import kotlinx.coroutines.Dispatchers
 import kotlinx.coroutines.launch
-import kotlinx.coroutines.withContext
 import kotlinx.serialization.SerialName
 import kotlinx.serialization.Serializable
 import org.koin.core.component.inject
@@ -66,9 +64,8 @@ class HomeTimelinePageState : PPageState<HomeTimelinePage>() {
    init {
       pageStateScope.launch {
          try {
-            statuses = withContext(Dispatchers.IO) {
-               timelineRepository.getHomeTimeline(page.token).toImmutableList()
-            }
+            statuses = timelineRepository.getHomeTimelineAsync(page.token)
+               .toImmutableList()
          } catch (e: Exception) {
             throw e
          }
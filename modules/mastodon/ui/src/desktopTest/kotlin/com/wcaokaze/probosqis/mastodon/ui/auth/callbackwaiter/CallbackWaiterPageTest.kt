@@ -287,8 +287,8 @@ class CallbackWaiterPageTest {
       }
 
       val accountRepository: AccountRepository = mockk {
-         every { getAccountIcon(any()) } answers {
-            val imageUrl = firstArg<Account>().avatarImageUrl ?: throw IOException()
+         every { getAccountIcon(any(), any()) } answers {
+            val imageUrl = secondArg<Account>().avatarImageUrl ?: throw IOException()
 
             Cache(
                Image(imageUrl, ImageBitmap(100, 100))
@@ -354,8 +354,8 @@ class CallbackWaiterPageTest {
       }
 
       val accountRepository: AccountRepository = mockk {
-         every { getAccountIcon(any()) } answers {
-            val imageUrl = firstArg<Account>().avatarImageUrl ?: throw IOException()
+         every { getAccountIcon(any(), any()) } answers {
+            val imageUrl = secondArg<Account>().avatarImageUrl ?: throw IOException()
 
             Cache(
                Image(imageUrl, ImageBitmap(100, 100))
@@ -419,8 +419,8 @@ class CallbackWaiterPageTest {
       }
 
       val accountRepository: AccountRepository = mockk {
-         every { getAccountIcon(any()) } answers {
-            val imageUrl = firstArg<Account>().avatarImageUrl ?: throw IOException()
+         every { getAccountIcon(any(), any()) } answers {
+            val imageUrl = secondArg<Account>().avatarImageUrl ?: throw IOException()
 
             Cache(
                Image(imageUrl, ImageBitmap(100, 100))
@@ -484,8 +484,8 @@ class CallbackWaiterPageTest {
       }
 
       val accountRepository: AccountRepository = mockk {
-         every { getAccountIcon(any()) } answers {
-            val imageUrl = firstArg<Account>().avatarImageUrl ?: throw IOException()
+         every { getAccountIcon(any(), any()) } answers {
+            val imageUrl = secondArg<Account>().avatarImageUrl ?: throw IOException()
 
             Cache(
                Image(imageUrl, ImageBitmap(100, 100))
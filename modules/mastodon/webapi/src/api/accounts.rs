@@ -14,50 +14,495 @@
  * limitations under the License.
  */
 
-use reqwest::blocking::Client;
+use ext_reqwest::error::{AsyncResponseExt, ResponseExt, WebApiResult};
+use ext_reqwest::rate_limit::{AsyncRateLimitedSend, RateLimitedSend};
+use ext_reqwest::transport::Transport;
 use url::Url;
 use crate::entity::account::Account;
+use crate::entity::relationship::Relationship;
+use crate::entity::status::Status;
+use crate::pagination::{Page, PageParams};
 
-#[cfg(feature = "mock")]
-use std::cell::RefCell;
+pub fn get_verify_credentials(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str
+) -> WebApiResult<Account> {
+   let url = instance_base_url.join("api/v1/accounts/verify_credentials")?;
+
+   let account = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
 
-thread_local! {
-   #[cfg(feature = "mock")]
-   static GET_VERIFY_CREDENTIALS: RefCell<Box<dyn Fn(&Client, &Url, &str) -> anyhow::Result<Account>>>
-      = RefCell::new(Box::new(|_, _, _| panic!()));
+   Ok(account)
 }
 
-pub fn get_verify_credentials(
-   client: &Client,
+/// since mastodon 0.0.0
+pub fn get_account(
+   transport: &dyn Transport,
    instance_base_url: &Url,
-   access_token: &str
-) -> anyhow::Result<Account> {
-   #[cfg(not(feature = "mock"))]
+   access_token: Option<&str>,
+   id: &str
+) -> WebApiResult<Account> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join("api/v1/accounts/")?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .pop_if_empty()
+      .push(id);
+
+   let mut request = transport.get(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let account = request
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(account)
+}
+
+/// [get_account]の非同期版
+pub async fn get_account_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   id: &str
+) -> WebApiResult<Account> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join("api/v1/accounts/")?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .pop_if_empty()
+      .push(id);
+
+   let mut request = transport.get_async(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let account = request
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(account)
+}
+
+/// since mastodon 3.4.0
+///
+/// `acct`はローカルのアカウントの場合`username`、
+/// リモートのアカウントの場合`username@domain`
+pub fn get_lookup(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   acct: &str
+) -> WebApiResult<Account> {
+   let mut url = instance_base_url.join("api/v1/accounts/lookup")?;
+   url.query_pairs_mut().append_pair("acct", acct);
+
+   let mut request = transport.get(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let account = request
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(account)
+}
+
+/// [get_statuses]のクエリ
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct AccountStatusesParams<'a> {
+   pub only_media: Option<bool>,
+   pub exclude_replies: Option<bool>,
+   pub exclude_reblogs: Option<bool>,
+   pub pinned: Option<bool>,
+   pub tagged: Option<&'a str>,
+}
+
+/// since mastodon 0.0.0
+///
+/// * `only_media` - since mastodon 1.4.2
+/// * `exclude_replies` - since mastodon 1.4.2
+/// * `pinned` - since mastodon 1.6.0
+/// * `exclude_reblogs` - since mastodon 2.7.0
+/// * `tagged` - since mastodon 2.8.0
+/// * `min_id` - since mastodon 2.6.0
+pub fn get_statuses(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   id: &str,
+   params: &AccountStatusesParams<'_>,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Status>> {
+   use crate::pagination;
+
+   let mut url = account_action_url(instance_base_url, id, "statuses")?;
+
    {
-      let url = instance_base_url.join("api/v1/accounts/verify_credentials")?;
+      let mut query_pairs = url.query_pairs_mut();
+      if let Some(only_media) = params.only_media {
+         query_pairs.append_pair("only_media", &only_media.to_string());
+      }
+      if let Some(exclude_replies) = params.exclude_replies {
+         query_pairs.append_pair("exclude_replies", &exclude_replies.to_string());
+      }
+      if let Some(exclude_reblogs) = params.exclude_reblogs {
+         query_pairs.append_pair("exclude_reblogs", &exclude_reblogs.to_string());
+      }
+      if let Some(pinned) = params.pinned {
+         query_pairs.append_pair("pinned", &pinned.to_string());
+      }
+      if let Some(tagged) = params.tagged {
+         query_pairs.append_pair("tagged", tagged);
+      }
+   }
 
-      let account = client
-         .get(url)
-         .bearer_auth(access_token)
-         .send()?
-         .json()?;
+   pagination::append_page_query(&mut url, page);
 
-      Ok(account)
+   let mut request = transport.get(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
    }
 
-   #[cfg(feature = "mock")]
+   let response = request.send_rate_limited()?;
+
+   pagination::read_page(response)
+}
+
+/// since mastodon 0.0.0
+///
+/// ページングにはLinkヘッダーのカーソルを使用する。
+/// `max_id`などはアカウントのidではないことに注意
+pub fn get_followers(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   id: &str,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Account>> {
+   use crate::pagination;
+
+   let mut url = account_action_url(instance_base_url, id, "followers")?;
+   pagination::append_page_query(&mut url, page);
+
+   let mut request = transport.get(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let response = request.send_rate_limited()?;
+
+   pagination::read_page(response)
+}
+
+/// since mastodon 0.0.0
+///
+/// ページングにはLinkヘッダーのカーソルを使用する。
+/// `max_id`などはアカウントのidではないことに注意
+pub fn get_following(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   id: &str,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Account>> {
+   use crate::pagination;
+
+   let mut url = account_action_url(instance_base_url, id, "following")?;
+   pagination::append_page_query(&mut url, page);
+
+   let mut request = transport.get(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let response = request.send_rate_limited()?;
+
+   pagination::read_page(response)
+}
+
+/// since mastodon 0.0.0
+///
+/// * `with_suspended` - since mastodon 4.3.0
+pub fn get_relationships(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   ids: &[&str],
+   with_suspended: Option<bool>
+) -> WebApiResult<Vec<Relationship>> {
+   let mut url = instance_base_url.join("api/v1/accounts/relationships")?;
+
    {
-      GET_VERIFY_CREDENTIALS.with(|f| {
-         let f = f.borrow();
-         f(client, instance_base_url, access_token)
-      })
+      let mut query = url.query_pairs_mut();
+      for id in ids {
+         query.append_pair("id[]", id);
+      }
+      if let Some(with_suspended) = with_suspended {
+         query.append_pair("with_suspended", &with_suspended.to_string());
+      }
+   }
+
+   let relationships = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(relationships)
+}
+
+/// since mastodon 0.0.0
+///
+/// * `reblogs` - since mastodon 2.1.0
+/// * `notify` - since mastodon 3.3.0
+/// * `languages` - since mastodon 4.0.0
+pub fn post_follow(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   reblogs: Option<bool>,
+   notify: Option<bool>,
+   languages: &[&str]
+) -> WebApiResult<Relationship> {
+   let url = account_action_url(instance_base_url, id, "follow")?;
+
+   let mut form = Vec::new();
+   if let Some(reblogs) = reblogs {
+      form.push(("reblogs", reblogs.to_string()));
+   }
+   if let Some(notify) = notify {
+      form.push(("notify", notify.to_string()));
+   }
+   for language in languages {
+      form.push(("languages[]", language.to_string()));
+   }
+
+   let relationship = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(relationship)
+}
+
+/// [post_follow]の非同期版
+pub async fn post_follow_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   reblogs: Option<bool>,
+   notify: Option<bool>,
+   languages: &[&str]
+) -> WebApiResult<Relationship> {
+   let url = account_action_url(instance_base_url, id, "follow")?;
+
+   let mut form = Vec::new();
+   if let Some(reblogs) = reblogs {
+      form.push(("reblogs", reblogs.to_string()));
+   }
+   if let Some(notify) = notify {
+      form.push(("notify", notify.to_string()));
+   }
+   for language in languages {
+      form.push(("languages[]", language.to_string()));
+   }
+
+   let relationship = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(relationship)
+}
+
+/// since mastodon 0.0.0
+pub fn post_unfollow(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Relationship> {
+   let url = account_action_url(instance_base_url, id, "unfollow")?;
+
+   let relationship = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(relationship)
+}
+
+/// [post_unfollow]の非同期版
+pub async fn post_unfollow_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Relationship> {
+   let url = account_action_url(instance_base_url, id, "unfollow")?;
+
+   let relationship = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(relationship)
+}
+
+/// since mastodon 0.0.0
+pub fn post_block(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Relationship> {
+   let url = account_action_url(instance_base_url, id, "block")?;
+
+   let relationship = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(relationship)
+}
+
+/// since mastodon 0.0.0
+pub fn post_unblock(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Relationship> {
+   let url = account_action_url(instance_base_url, id, "unblock")?;
+
+   let relationship = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(relationship)
+}
+
+/// since mastodon 0.0.0
+///
+/// * `notifications` - since mastodon 2.1.0
+/// * `duration` - since mastodon 3.3.0。秒数で指定する。0の場合無期限
+pub fn post_mute(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   notifications: Option<bool>,
+   duration: Option<u64>
+) -> WebApiResult<Relationship> {
+   let url = account_action_url(instance_base_url, id, "mute")?;
+
+   let mut form = Vec::new();
+   if let Some(notifications) = notifications {
+      form.push(("notifications", notifications.to_string()));
+   }
+   if let Some(duration) = duration {
+      form.push(("duration", duration.to_string()));
    }
+
+   let relationship = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(relationship)
+}
+
+/// since mastodon 0.0.0
+pub fn post_unmute(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Relationship> {
+   let url = account_action_url(instance_base_url, id, "unmute")?;
+
+   let relationship = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(relationship)
+}
+
+/// since mastodon 3.2.0
+///
+/// `comment`がNoneの場合メモを削除する
+pub fn post_note(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   comment: Option<&str>
+) -> WebApiResult<Relationship> {
+   let url = account_action_url(instance_base_url, id, "note")?;
+
+   let relationship = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .form(&[("comment", comment.unwrap_or(""))])
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(relationship)
 }
 
-#[allow(dead_code)]
-#[cfg(feature = "mock")]
-pub fn inject_get_verify_credentials(
-   get_verify_credentials: impl Fn(&Client, &Url, &str) -> anyhow::Result<Account> + 'static
-) {
-   GET_VERIFY_CREDENTIALS.set(Box::new(get_verify_credentials));
+fn account_action_url(
+   instance_base_url: &Url,
+   id: &str,
+   action: &str
+) -> WebApiResult<Url> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join("api/v1/accounts/")?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .pop_if_empty()
+      .push(id)
+      .push(action);
+
+   Ok(url)
 }
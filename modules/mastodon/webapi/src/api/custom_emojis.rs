@@ -0,0 +1,37 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ext_reqwest::error::{ResponseExt, WebApiResult};
+use ext_reqwest::rate_limit::RateLimitedSend;
+use ext_reqwest::transport::Transport;
+use url::Url;
+use crate::entity::custom_emoji::CustomEmoji;
+
+/// since mastodon 2.0.0
+pub fn get_custom_emojis(
+   transport: &dyn Transport,
+   instance_base_url: &Url
+) -> WebApiResult<Vec<CustomEmoji>> {
+   let url = instance_base_url.join("api/v1/custom_emojis")?;
+
+   let custom_emojis = transport
+      .get(url)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(custom_emojis)
+}
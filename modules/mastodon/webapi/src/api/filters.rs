@@ -0,0 +1,376 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ext_reqwest::error::{ResponseExt, WebApiResult};
+use ext_reqwest::rate_limit::RateLimitedSend;
+use ext_reqwest::transport::Transport;
+use url::Url;
+use crate::entity::filter::{Filter, FilterKeyword, FilterStatus};
+
+/// since mastodon 4.0.0
+pub fn get_filters(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str
+) -> WebApiResult<Vec<Filter>> {
+   let url = filters_url(instance_base_url, &[])?;
+
+   let filters = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(filters)
+}
+
+/// since mastodon 4.0.0
+pub fn get_filter(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Filter> {
+   let url = filters_url(instance_base_url, &[id])?;
+
+   let filter = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(filter)
+}
+
+/// [post_filter]で送信する内容
+///
+/// `keywords`はキーワードと`whole_word`の組
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct PostFilterParams<'a> {
+   pub title: &'a str,
+   pub context: &'a [&'a str],
+   pub filter_action: Option<&'a str>,
+   pub expires_in: Option<u64>,
+   pub keywords: &'a [(&'a str, bool)],
+}
+
+/// since mastodon 4.0.0
+pub fn post_filter(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   params: &PostFilterParams<'_>
+) -> WebApiResult<Filter> {
+   let url = filters_url(instance_base_url, &[])?;
+
+   let mut form = vec![("title", params.title.to_string())];
+   for context in params.context {
+      form.push(("context[]", context.to_string()));
+   }
+   if let Some(filter_action) = params.filter_action {
+      form.push(("filter_action", filter_action.to_string()));
+   }
+   if let Some(expires_in) = params.expires_in {
+      form.push(("expires_in", expires_in.to_string()));
+   }
+   for (keyword, whole_word) in params.keywords {
+      form.push(("keywords_attributes[][keyword]", keyword.to_string()));
+      form.push(("keywords_attributes[][whole_word]", whole_word.to_string()));
+   }
+
+   let filter = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(filter)
+}
+
+/// [put_filter]で送信する内容
+///
+/// `context`が空の場合は変更しない。
+/// `expires_in`に`Some(None)`を指定すると無期限になる
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct PutFilterParams<'a> {
+   pub title: Option<&'a str>,
+   pub context: &'a [&'a str],
+   pub filter_action: Option<&'a str>,
+   pub expires_in: Option<Option<u64>>,
+}
+
+/// since mastodon 4.0.0
+pub fn put_filter(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   params: &PutFilterParams<'_>
+) -> WebApiResult<Filter> {
+   let url = filters_url(instance_base_url, &[id])?;
+
+   let mut form = vec![];
+   if let Some(title) = params.title {
+      form.push(("title", title.to_string()));
+   }
+   for context in params.context {
+      form.push(("context[]", context.to_string()));
+   }
+   if let Some(filter_action) = params.filter_action {
+      form.push(("filter_action", filter_action.to_string()));
+   }
+   if let Some(expires_in) = params.expires_in {
+      form.push(("expires_in", expires_in.map(|e| e.to_string()).unwrap_or_default()));
+   }
+
+   let filter = transport
+      .put(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(filter)
+}
+
+/// since mastodon 4.0.0
+pub fn delete_filter(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<()> {
+   let url = filters_url(instance_base_url, &[id])?;
+
+   transport
+      .delete(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?;
+
+   Ok(())
+}
+
+/// since mastodon 4.0.0
+pub fn get_filter_keywords(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   filter_id: &str
+) -> WebApiResult<Vec<FilterKeyword>> {
+   let url = filters_url(instance_base_url, &[filter_id, "keywords"])?;
+
+   let keywords = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(keywords)
+}
+
+/// since mastodon 4.0.0
+pub fn post_filter_keyword(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   filter_id: &str,
+   keyword: &str,
+   whole_word: Option<bool>
+) -> WebApiResult<FilterKeyword> {
+   let url = filters_url(instance_base_url, &[filter_id, "keywords"])?;
+
+   let mut form = vec![("keyword", keyword.to_string())];
+   if let Some(whole_word) = whole_word {
+      form.push(("whole_word", whole_word.to_string()));
+   }
+
+   let filter_keyword = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(filter_keyword)
+}
+
+/// since mastodon 4.0.0
+pub fn get_filter_keyword(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<FilterKeyword> {
+   let url = filters_url(instance_base_url, &["keywords", id])?;
+
+   let keyword = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(keyword)
+}
+
+/// since mastodon 4.0.0
+pub fn put_filter_keyword(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   keyword: Option<&str>,
+   whole_word: Option<bool>
+) -> WebApiResult<FilterKeyword> {
+   let url = filters_url(instance_base_url, &["keywords", id])?;
+
+   let mut form = vec![];
+   if let Some(keyword) = keyword {
+      form.push(("keyword", keyword.to_string()));
+   }
+   if let Some(whole_word) = whole_word {
+      form.push(("whole_word", whole_word.to_string()));
+   }
+
+   let filter_keyword = transport
+      .put(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(filter_keyword)
+}
+
+/// since mastodon 4.0.0
+pub fn delete_filter_keyword(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<()> {
+   let url = filters_url(instance_base_url, &["keywords", id])?;
+
+   transport
+      .delete(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?;
+
+   Ok(())
+}
+
+/// since mastodon 4.0.0
+pub fn get_filter_statuses(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   filter_id: &str
+) -> WebApiResult<Vec<FilterStatus>> {
+   let url = filters_url(instance_base_url, &[filter_id, "statuses"])?;
+
+   let statuses = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(statuses)
+}
+
+/// since mastodon 4.0.0
+pub fn post_filter_status(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   filter_id: &str,
+   status_id: &str
+) -> WebApiResult<FilterStatus> {
+   let url = filters_url(instance_base_url, &[filter_id, "statuses"])?;
+
+   let form = [("status_id", status_id.to_string())];
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// since mastodon 4.0.0
+pub fn get_filter_status(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<FilterStatus> {
+   let url = filters_url(instance_base_url, &["statuses", id])?;
+
+   let status = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// since mastodon 4.0.0
+pub fn delete_filter_status(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<()> {
+   let url = filters_url(instance_base_url, &["statuses", id])?;
+
+   transport
+      .delete(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?;
+
+   Ok(())
+}
+
+fn filters_url(
+   instance_base_url: &Url,
+   segments: &[&str]
+) -> WebApiResult<Url> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join("api/v2/filters")?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .extend(segments);
+
+   Ok(url)
+}
@@ -0,0 +1,150 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::Read;
+use std::time::Duration;
+use ext_reqwest::error::{ResponseExt, WebApiResult};
+use ext_reqwest::rate_limit::RateLimitedSend;
+use ext_reqwest::transport::Transport;
+use url::Url;
+use crate::entity::media_attachment::MediaAttachment;
+
+/// アップロードのタイムアウト。
+/// blockingのクライアントのタイムアウトはリクエスト全体に掛かるため、
+/// 大きいファイルを送信できるよう通常のリクエストより長くする
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// [post_media_v2]で送信するファイル
+pub struct MediaFile<'a> {
+   pub file_name: &'a str,
+   pub mime_type: Option<&'a str>,
+   pub reader: Box<dyn Read + Send>,
+   pub size: u64,
+}
+
+/// since mastodon 3.1.3
+///
+/// 大きいファイルの場合サーバーは202を返し、処理が終わるまで`url`がnullになる。
+/// その場合は[get_media]で処理の完了を確認する必要がある
+///
+/// * `focus` - "x,y"の形式。x, yはそれぞれ-1.0から1.0
+pub fn post_media_v2(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   file: MediaFile<'_>,
+   description: Option<&str>,
+   focus: Option<&str>
+) -> WebApiResult<MediaAttachment> {
+   use reqwest::blocking::multipart::{Form, Part};
+
+   let url = instance_base_url.join("api/v2/media")?;
+
+   let mut part = Part::reader_with_length(file.reader, file.size)
+      .file_name(file.file_name.to_string());
+   if let Some(mime_type) = file.mime_type {
+      part = part.mime_str(mime_type)?;
+   }
+
+   let mut form = Form::new().part("file", part);
+   if let Some(description) = description {
+      form = form.text("description", description.to_string());
+   }
+   if let Some(focus) = focus {
+      form = form.text("focus", focus.to_string());
+   }
+
+   let media_attachment = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .timeout(UPLOAD_TIMEOUT)
+      .multipart(form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(media_attachment)
+}
+
+/// since mastodon 3.1.3
+///
+/// 処理中の場合サーバーは206を返し、`url`がnullになる
+pub fn get_media(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<MediaAttachment> {
+   let url = media_url(instance_base_url, id)?;
+
+   let media_attachment = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(media_attachment)
+}
+
+/// since mastodon 0.0.0
+///
+/// * `focus` - since mastodon 2.3.0
+pub fn put_media(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   description: Option<&str>,
+   focus: Option<&str>
+) -> WebApiResult<MediaAttachment> {
+   use std::collections::HashMap;
+
+   let url = media_url(instance_base_url, id)?;
+
+   let mut form = HashMap::new();
+   if let Some(description) = description {
+      form.insert("description", description);
+   }
+   if let Some(focus) = focus {
+      form.insert("focus", focus);
+   }
+
+   let media_attachment = transport
+      .put(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(media_attachment)
+}
+
+fn media_url(
+   instance_base_url: &Url,
+   id: &str
+) -> WebApiResult<Url> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join("api/v1/media/")?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .pop_if_empty()
+      .push(id);
+
+   Ok(url)
+}
@@ -16,5 +16,14 @@
 
 pub mod accounts;
 pub mod apps;
+pub mod custom_emojis;
+pub mod filters;
+pub mod media;
+pub mod mutes;
+pub mod notifications;
 pub mod oauth;
+pub mod polls;
+pub mod search;
+pub mod statuses;
+pub mod streaming;
 pub mod timelines;
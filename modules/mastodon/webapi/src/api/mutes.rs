@@ -0,0 +1,50 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ext_reqwest::error::WebApiResult;
+use ext_reqwest::rate_limit::RateLimitedSend;
+use ext_reqwest::transport::Transport;
+use url::Url;
+use crate::entity::account::Account;
+use crate::pagination::{Page, PageParams};
+
+/// since mastodon 0.0.0
+///
+/// ミュートした新しい順に返る。
+/// 期限付きのミュートはmastodon 3.3.0以降`mute_expires_at`を含む
+pub fn get_mutes(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   max_id: Option<&str>,
+   since_id: Option<&str>,
+   limit: Option<u32>
+) -> WebApiResult<Page<Account>> {
+   use crate::pagination;
+
+   let mut url = instance_base_url.join("api/v1/mutes")?;
+   pagination::append_page_query(
+      &mut url,
+      &PageParams { max_id, since_id, min_id: None, limit }
+   );
+
+   let response = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?;
+
+   pagination::read_page(response)
+}
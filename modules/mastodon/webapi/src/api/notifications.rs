@@ -0,0 +1,241 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ext_reqwest::error::{ResponseExt, WebApiResult};
+use ext_reqwest::rate_limit::{AsyncRateLimitedSend, RateLimitedSend};
+use ext_reqwest::transport::Transport;
+use url::Url;
+use crate::entity::notification::{GroupedNotificationsResults, Notification};
+use crate::pagination::{Page, PageParams, Paged};
+
+/// [get_notifications_v1]のクエリ
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct NotificationsV1Params<'a> {
+   pub types: &'a [&'a str],
+   pub exclude_types: &'a [&'a str],
+   pub account_id: Option<&'a str>,
+}
+
+/// since mastodon 0.0.0
+///
+/// * `exclude_types` - since mastodon 2.6.0
+/// * `account_id` - since mastodon 2.9.0
+/// * `types` - since mastodon 3.5.0
+/// * `min_id` - since mastodon 2.6.0
+pub fn get_notifications_v1(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   params: &NotificationsV1Params<'_>,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Notification>> {
+   use crate::pagination;
+
+   let mut url = notifications_v1_url(instance_base_url, params)?;
+   pagination::append_page_query(&mut url, page);
+
+   let response = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?;
+
+   pagination::read_page(response)
+}
+
+/// [get_notifications_v1]の非同期版
+pub async fn get_notifications_v1_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   params: &NotificationsV1Params<'_>,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Notification>> {
+   use crate::pagination;
+
+   let mut url = notifications_v1_url(instance_base_url, params)?;
+   pagination::append_page_query(&mut url, page);
+
+   let response = transport
+      .get_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?;
+
+   pagination::read_page_async(response).await
+}
+
+fn notifications_v1_url(
+   instance_base_url: &Url,
+   params: &NotificationsV1Params<'_>
+) -> WebApiResult<Url> {
+   let mut url = instance_base_url.join("api/v1/notifications")?;
+
+   {
+      let mut query_pairs = url.query_pairs_mut();
+      for t in params.types {
+         query_pairs.append_pair("types[]", t);
+      }
+      for t in params.exclude_types {
+         query_pairs.append_pair("exclude_types[]", t);
+      }
+      if let Some(account_id) = params.account_id {
+         query_pairs.append_pair("account_id", account_id);
+      }
+   }
+
+   Ok(url)
+}
+
+/// [get_notifications_v2]のクエリ
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct NotificationsV2Params<'a> {
+   pub types: &'a [&'a str],
+   pub exclude_types: &'a [&'a str],
+   pub grouped_types: &'a [&'a str],
+}
+
+/// since mastodon 4.3.0
+pub fn get_notifications_v2(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   params: &NotificationsV2Params<'_>,
+   page: &PageParams<'_>
+) -> WebApiResult<Paged<GroupedNotificationsResults>> {
+   use crate::pagination;
+
+   let mut url = notifications_v2_url(instance_base_url, params)?;
+   pagination::append_page_query(&mut url, page);
+
+   let response = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?;
+
+   pagination::read_paged(response)
+}
+
+/// [get_notifications_v2]の非同期版
+pub async fn get_notifications_v2_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   params: &NotificationsV2Params<'_>,
+   page: &PageParams<'_>
+) -> WebApiResult<Paged<GroupedNotificationsResults>> {
+   use crate::pagination;
+
+   let mut url = notifications_v2_url(instance_base_url, params)?;
+   pagination::append_page_query(&mut url, page);
+
+   let response = transport
+      .get_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?;
+
+   pagination::read_paged_async(response).await
+}
+
+fn notifications_v2_url(
+   instance_base_url: &Url,
+   params: &NotificationsV2Params<'_>
+) -> WebApiResult<Url> {
+   let mut url = instance_base_url.join("api/v2/notifications")?;
+
+   {
+      let mut query_pairs = url.query_pairs_mut();
+      for t in params.types {
+         query_pairs.append_pair("types[]", t);
+      }
+      for t in params.exclude_types {
+         query_pairs.append_pair("exclude_types[]", t);
+      }
+      for t in params.grouped_types {
+         query_pairs.append_pair("grouped_types[]", t);
+      }
+   }
+
+   Ok(url)
+}
+
+/// since mastodon 1.3.0
+pub fn post_dismiss_v1(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<()> {
+   let url = notification_action_url(instance_base_url, "api/v1/notifications/", id)?;
+
+   transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?;
+
+   Ok(())
+}
+
+/// since mastodon 4.3.0
+pub fn post_dismiss_v2(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   group_key: &str
+) -> WebApiResult<()> {
+   let url = notification_action_url(instance_base_url, "api/v2/notifications/", group_key)?;
+
+   transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?;
+
+   Ok(())
+}
+
+/// since mastodon 0.0.0
+pub fn post_clear(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str
+) -> WebApiResult<()> {
+   let url = instance_base_url.join("api/v1/notifications/clear")?;
+
+   transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?;
+
+   Ok(())
+}
+
+fn notification_action_url(
+   instance_base_url: &Url,
+   path: &str,
+   id: &str
+) -> WebApiResult<Url> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join(path)?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .pop_if_empty()
+      .push(id)
+      .push("dismiss");
+
+   Ok(url)
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ext_reqwest::error::{AsyncResponseExt, ResponseExt, WebApiResult};
+use ext_reqwest::rate_limit::{AsyncRateLimitedSend, RateLimitedSend};
+use ext_reqwest::transport::Transport;
+use url::Url;
+use crate::entity::poll::Poll;
+
+/// since mastodon 2.8.0
+///
+/// 公開された投稿の投票であれば`access_token`なしでも取得できる
+pub fn get_poll(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   id: &str
+) -> WebApiResult<Poll> {
+   let url = poll_url(instance_base_url, id)?;
+
+   let mut request = transport.get(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let poll = request
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(poll)
+}
+
+/// [get_poll]の非同期版
+pub async fn get_poll_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   id: &str
+) -> WebApiResult<Poll> {
+   let url = poll_url(instance_base_url, id)?;
+
+   let mut request = transport.get_async(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let poll = request
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(poll)
+}
+
+/// since mastodon 2.8.0
+///
+/// `choices`は選択肢のインデックス
+pub fn post_votes(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   choices: &[u64]
+) -> WebApiResult<Poll> {
+   let url = votes_url(instance_base_url, id)?;
+   let form = votes_form(choices);
+
+   let poll = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(poll)
+}
+
+/// [post_votes]の非同期版
+pub async fn post_votes_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   choices: &[u64]
+) -> WebApiResult<Poll> {
+   let url = votes_url(instance_base_url, id)?;
+   let form = votes_form(choices);
+
+   let poll = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(poll)
+}
+
+fn poll_url(
+   instance_base_url: &Url,
+   id: &str
+) -> WebApiResult<Url> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join("api/v1/polls/")?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .pop_if_empty()
+      .push(id);
+
+   Ok(url)
+}
+
+fn votes_url(
+   instance_base_url: &Url,
+   id: &str
+) -> WebApiResult<Url> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = poll_url(instance_base_url, id)?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .push("votes");
+
+   Ok(url)
+}
+
+fn votes_form(choices: &[u64]) -> Vec<(&'static str, String)> {
+   choices.iter()
+      .map(|choice| ("choices[]", choice.to_string()))
+      .collect()
+}
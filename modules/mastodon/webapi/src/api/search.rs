@@ -0,0 +1,100 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ext_reqwest::error::{ResponseExt, WebApiResult};
+use ext_reqwest::rate_limit::RateLimitedSend;
+use ext_reqwest::transport::Transport;
+use url::Url;
+use crate::entity::search::Search;
+
+/// [get_search_v2]のクエリ
+///
+/// * `type` - `accounts`, `hashtags`, `statuses`のいずれか
+/// * `resolve` - URLやacctで指定されたリモートのアカウントや投稿を取得して
+///   インスタンスに登録する。アクセストークンが必要
+/// * `following`, `account_id`, `max_id`, `min_id`, `offset` -
+///   since mastodon 2.8.0
+/// * `exclude_unreviewed` - since mastodon 3.0.0
+///
+/// `max_id`、`min_id`は投稿にのみ有効。
+/// アカウントやハッシュタグのページングには`offset`を使う
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct SearchParams<'a> {
+   pub r#type: Option<&'a str>,
+   pub resolve: Option<bool>,
+   pub following: Option<bool>,
+   pub account_id: Option<&'a str>,
+   pub exclude_unreviewed: Option<bool>,
+   pub max_id: Option<&'a str>,
+   pub min_id: Option<&'a str>,
+   pub limit: Option<u32>,
+   pub offset: Option<u32>,
+}
+
+/// since mastodon 2.4.1
+pub fn get_search_v2(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   q: &str,
+   params: &SearchParams<'_>
+) -> WebApiResult<Search> {
+   let mut url = instance_base_url.join("api/v2/search")?;
+
+   {
+      let mut query_pairs = url.query_pairs_mut();
+      query_pairs.append_pair("q", q);
+      if let Some(r#type) = params.r#type {
+         query_pairs.append_pair("type", r#type);
+      }
+      if let Some(resolve) = params.resolve {
+         query_pairs.append_pair("resolve", &resolve.to_string());
+      }
+      if let Some(following) = params.following {
+         query_pairs.append_pair("following", &following.to_string());
+      }
+      if let Some(account_id) = params.account_id {
+         query_pairs.append_pair("account_id", account_id);
+      }
+      if let Some(exclude_unreviewed) = params.exclude_unreviewed {
+         query_pairs.append_pair("exclude_unreviewed", &exclude_unreviewed.to_string());
+      }
+      if let Some(max_id) = params.max_id {
+         query_pairs.append_pair("max_id", max_id);
+      }
+      if let Some(min_id) = params.min_id {
+         query_pairs.append_pair("min_id", min_id);
+      }
+      if let Some(limit) = params.limit {
+         query_pairs.append_pair("limit", &limit.to_string());
+      }
+      if let Some(offset) = params.offset {
+         query_pairs.append_pair("offset", &offset.to_string());
+      }
+   }
+
+   let mut request = transport.get(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let search = request
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(search)
+}
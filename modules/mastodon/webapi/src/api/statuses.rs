@@ -0,0 +1,720 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ext_reqwest::error::{AsyncResponseExt, ResponseExt, WebApiResult};
+use ext_reqwest::rate_limit::{AsyncRateLimitedSend, RateLimitedSend};
+use ext_reqwest::transport::Transport;
+use url::Url;
+use crate::entity::context::Context;
+use crate::entity::status::Status;
+
+/// [post_status]と[put_status]で送信する内容
+///
+/// `in_reply_to_id`と`visibility`は編集できないため[put_status]では送信されない
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct StatusParams<'a> {
+   pub status: Option<&'a str>,
+   pub media_ids: &'a [&'a str],
+   pub poll: Option<PollParams<'a>>,
+   pub in_reply_to_id: Option<&'a str>,
+   pub sensitive: Option<bool>,
+   pub spoiler_text: Option<&'a str>,
+   pub visibility: Option<&'a str>,
+   pub language: Option<&'a str>,
+}
+
+/// 投稿する投票。since mastodon 2.8.0
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct PollParams<'a> {
+   pub options: &'a [&'a str],
+   pub expires_in: u64,
+   pub multiple: Option<bool>,
+   pub hide_totals: Option<bool>,
+}
+
+/// since mastodon 0.0.0
+///
+/// * `idempotency_key` - since mastodon 2.5.0
+/// * `params.language` - since mastodon 2.4.2
+///
+/// `status`と`media_ids`と`poll`のうちいずれかが必要
+pub fn post_status(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   idempotency_key: Option<&str>,
+   params: &StatusParams<'_>
+) -> WebApiResult<Status> {
+   let url = instance_base_url.join("api/v1/statuses")?;
+   let form = post_status_form(params);
+
+   let mut request = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .form(&form);
+
+   if let Some(idempotency_key) = idempotency_key {
+      request = request.header("Idempotency-Key", idempotency_key);
+   }
+
+   let status = request
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_status]の非同期版
+pub async fn post_status_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   idempotency_key: Option<&str>,
+   params: &StatusParams<'_>
+) -> WebApiResult<Status> {
+   let url = instance_base_url.join("api/v1/statuses")?;
+   let form = post_status_form(params);
+
+   let mut request = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .form(&form);
+
+   if let Some(idempotency_key) = idempotency_key {
+      request = request.header("Idempotency-Key", idempotency_key);
+   }
+
+   let status = request
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 3.5.0
+///
+/// 指定しなかった項目は編集前の値が維持されず、削除されることに注意
+pub fn put_status(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   params: &StatusParams<'_>
+) -> WebApiResult<Status> {
+   let url = status_url(instance_base_url, id)?;
+
+   let mut form = Vec::new();
+   append_status_form(&mut form, params);
+
+   let status = transport
+      .put(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [put_status]の非同期版
+pub async fn put_status_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   params: &StatusParams<'_>
+) -> WebApiResult<Status> {
+   let url = status_url(instance_base_url, id)?;
+
+   let mut form = Vec::new();
+   append_status_form(&mut form, params);
+
+   let status = transport
+      .put_async(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 0.0.0
+///
+/// 削除したStatusが返される。`text`に投稿時のソースが含まれる(since mastodon 2.9.0)
+pub fn delete_status(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_url(instance_base_url, id)?;
+
+   let status = transport
+      .delete(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [delete_status]の非同期版
+pub async fn delete_status_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_url(instance_base_url, id)?;
+
+   let status = transport
+      .delete_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 0.0.0
+pub fn post_favourite(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "favourite")?;
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_favourite]の非同期版
+pub async fn post_favourite_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "favourite")?;
+
+   let status = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 0.0.0
+pub fn post_unfavourite(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "unfavourite")?;
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_unfavourite]の非同期版
+pub async fn post_unfavourite_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "unfavourite")?;
+
+   let status = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 0.0.0
+///
+/// * `visibility` - since mastodon 2.8.0
+pub fn post_reblog(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   visibility: Option<&str>
+) -> WebApiResult<Status> {
+   use std::collections::HashMap;
+
+   let url = status_action_url(instance_base_url, id, "reblog")?;
+
+   let mut form = HashMap::new();
+   if let Some(visibility) = visibility {
+      form.insert("visibility", visibility);
+   }
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_reblog]の非同期版
+pub async fn post_reblog_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str,
+   visibility: Option<&str>
+) -> WebApiResult<Status> {
+   use std::collections::HashMap;
+
+   let url = status_action_url(instance_base_url, id, "reblog")?;
+
+   let mut form = HashMap::new();
+   if let Some(visibility) = visibility {
+      form.insert("visibility", visibility);
+   }
+
+   let status = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .form(&form)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 0.0.0
+pub fn post_unreblog(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "unreblog")?;
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_unreblog]の非同期版
+pub async fn post_unreblog_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "unreblog")?;
+
+   let status = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 3.1.0
+pub fn post_bookmark(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "bookmark")?;
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_bookmark]の非同期版
+pub async fn post_bookmark_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "bookmark")?;
+
+   let status = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 3.1.0
+pub fn post_unbookmark(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "unbookmark")?;
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_unbookmark]の非同期版
+pub async fn post_unbookmark_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "unbookmark")?;
+
+   let status = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 1.6.0
+pub fn post_pin(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "pin")?;
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_pin]の非同期版
+pub async fn post_pin_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "pin")?;
+
+   let status = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 1.6.0
+pub fn post_unpin(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "unpin")?;
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_unpin]の非同期版
+pub async fn post_unpin_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "unpin")?;
+
+   let status = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 1.4.2
+pub fn post_mute(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "mute")?;
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_mute]の非同期版
+pub async fn post_mute_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "mute")?;
+
+   let status = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 1.4.2
+pub fn post_unmute(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "unmute")?;
+
+   let status = transport
+      .post(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(status)
+}
+
+/// [post_unmute]の非同期版
+pub async fn post_unmute_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   id: &str
+) -> WebApiResult<Status> {
+   let url = status_action_url(instance_base_url, id, "unmute")?;
+
+   let status = transport
+      .post_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(status)
+}
+
+/// since mastodon 0.0.0
+///
+/// 公開された投稿であれば`access_token`なしでも取得できる
+pub fn get_context(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   id: &str
+) -> WebApiResult<Context> {
+   let url = status_action_url(instance_base_url, id, "context")?;
+
+   let mut request = transport.get(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let context = request
+      .send_rate_limited()?
+      .check_status()?
+      .json()?;
+
+   Ok(context)
+}
+
+/// [get_context]の非同期版
+pub async fn get_context_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   id: &str
+) -> WebApiResult<Context> {
+   let url = status_action_url(instance_base_url, id, "context")?;
+
+   let mut request = transport.get_async(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let context = request
+      .send_rate_limited_async().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(context)
+}
+
+fn status_url(
+   instance_base_url: &Url,
+   id: &str
+) -> WebApiResult<Url> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join("api/v1/statuses/")?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .pop_if_empty()
+      .push(id);
+
+   Ok(url)
+}
+
+fn post_status_form(params: &StatusParams<'_>) -> Vec<(&'static str, String)> {
+   let mut form = Vec::new();
+   append_status_form(&mut form, params);
+   if let Some(in_reply_to_id) = params.in_reply_to_id {
+      form.push(("in_reply_to_id", in_reply_to_id.to_string()));
+   }
+   if let Some(visibility) = params.visibility {
+      form.push(("visibility", visibility.to_string()));
+   }
+   form
+}
+
+fn append_status_form(
+   form: &mut Vec<(&str, String)>,
+   params: &StatusParams<'_>
+) {
+   if let Some(status) = params.status {
+      form.push(("status", status.to_string()));
+   }
+   for media_id in params.media_ids {
+      form.push(("media_ids[]", media_id.to_string()));
+   }
+   if let Some(poll) = params.poll {
+      for option in poll.options {
+         form.push(("poll[options][]", option.to_string()));
+      }
+      form.push(("poll[expires_in]", poll.expires_in.to_string()));
+      if let Some(multiple) = poll.multiple {
+         form.push(("poll[multiple]", multiple.to_string()));
+      }
+      if let Some(hide_totals) = poll.hide_totals {
+         form.push(("poll[hide_totals]", hide_totals.to_string()));
+      }
+   }
+   if let Some(sensitive) = params.sensitive {
+      form.push(("sensitive", sensitive.to_string()));
+   }
+   if let Some(spoiler_text) = params.spoiler_text {
+      form.push(("spoiler_text", spoiler_text.to_string()));
+   }
+   if let Some(language) = params.language {
+      form.push(("language", language.to_string()));
+   }
+}
+
+fn status_action_url(
+   instance_base_url: &Url,
+   id: &str,
+   action: &str
+) -> WebApiResult<Url> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join("api/v1/statuses/")?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .pop_if_empty()
+      .push(id)
+      .push(action);
+
+   Ok(url)
+}
@@ -0,0 +1,566 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::{self, BufReader, Read};
+use std::net::{Shutdown, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use ext_reqwest::error::{ResponseExt, WebApiError, WebApiResult};
+use ext_reqwest::transport::Transport;
+use reqwest::blocking::Response;
+use serde::Deserialize;
+use tungstenite::WebSocket;
+use tungstenite::stream::MaybeTlsStream;
+use url::Url;
+use crate::entity::notification::Notification;
+use crate::entity::status::Status;
+
+/// サーバーはハートビートを送ってくるため、これ以上何も受信しなかった場合は
+/// 接続が切れたとみなす
+const READ_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// SSEはレスポンスボディを読み続けるため、リクエスト全体のタイムアウトは
+/// 実質無制限にする
+const SSE_TIMEOUT: Duration = Duration::from_secs(60 * 60 * 24 * 365);
+
+/// 購読するストリーム
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub enum Stream {
+   /// since mastodon 1.0.0
+   User,
+   /// since mastodon 1.0.0
+   Public,
+   /// since mastodon 1.1.0
+   PublicLocal,
+   /// since mastodon 1.0.0
+   Hashtag(String),
+   /// since mastodon 2.1.0
+   List(String),
+}
+
+impl Stream {
+   pub fn name(&self) -> &'static str {
+      match self {
+         Stream::User        => "user",
+         Stream::Public      => "public",
+         Stream::PublicLocal => "public:local",
+         Stream::Hashtag(_)  => "hashtag",
+         Stream::List(_)     => "list",
+      }
+   }
+
+   /// WebSocketで受信したイベントの`stream`からStreamを復元する
+   pub fn from_names(names: &[String]) -> Option<Stream> {
+      let stream = match names {
+         [name] | [name, _] if name == "user" || name == "user:notification" => Stream::User,
+         [name] if name == "public" => Stream::Public,
+         [name] if name == "public:local" => Stream::PublicLocal,
+         [name, tag] if name == "hashtag" => Stream::Hashtag(tag.clone()),
+         [name, list] if name == "list" => Stream::List(list.clone()),
+         _ => return None,
+      };
+
+      Some(stream)
+   }
+
+   fn subscribe_message(&self) -> String {
+      let mut message = serde_json::json!({
+         "type": "subscribe",
+         "stream": self.name(),
+      });
+
+      match self {
+         Stream::Hashtag(tag) => { message["tag"]  = tag.as_str().into(); }
+         Stream::List(list)   => { message["list"] = list.as_str().into(); }
+         _ => {}
+      }
+
+      message.to_string()
+   }
+
+   fn sse_url(&self, streaming_base_url: &Url) -> WebApiResult<Url> {
+      let path = match self {
+         Stream::User        => "api/v1/streaming/user",
+         Stream::Public      => "api/v1/streaming/public",
+         Stream::PublicLocal => "api/v1/streaming/public/local",
+         Stream::Hashtag(_)  => "api/v1/streaming/hashtag",
+         Stream::List(_)     => "api/v1/streaming/list",
+      };
+
+      let mut url = streaming_base_url.join(path)?;
+
+      match self {
+         Stream::Hashtag(tag) => { url.query_pairs_mut().append_pair("tag", tag); }
+         Stream::List(list)   => { url.query_pairs_mut().append_pair("list", list); }
+         _ => {}
+      }
+
+      Ok(url)
+   }
+}
+
+pub enum Event {
+   /// since mastodon 1.0.0
+   Update(Box<Status>),
+   /// since mastodon 1.0.0
+   Delete(String),
+   /// since mastodon 1.0.0
+   Notification(Box<Notification>),
+   /// since mastodon 3.5.0
+   StatusUpdate(Box<Status>),
+   /// since mastodon 2.4.3
+   FiltersChanged,
+   /// 未対応のイベント。イベント名が入る
+   Other(String),
+}
+
+pub struct StreamEvent {
+   /// イベントを受信したストリーム。
+   /// WebSocketで購読していないストリームのイベントを受信した場合None
+   pub stream: Option<Stream>,
+   pub event: Event,
+}
+
+/// tungstenite::Errorを[WebApiError]に変換する
+fn from_tungstenite(e: tungstenite::Error) -> WebApiError {
+   match e {
+      tungstenite::Error::Io(e) => e.into(),
+      tungstenite::Error::Url(_) => WebApiError::InvalidUrl(e.to_string()),
+      tungstenite::Error::Http(response) => {
+         let error = response.body().as_deref()
+            .and_then(|body| std::str::from_utf8(body).ok())
+            .and_then(|body| serde_json::from_str::<serde_json::Value>(body).ok())
+            .and_then(|body| body["error"].as_str().map(str::to_string));
+
+         WebApiError::from_status(response.status().as_u16(), error, None)
+      }
+      e => WebApiError::Network(Box::new(e)),
+   }
+}
+
+fn decode_event(event: &str, payload: Option<&str>) -> WebApiResult<Event> {
+   let payload = || payload.ok_or_else(|| WebApiError::Decode("No payload".into()));
+
+   let event = match event {
+      "update" => Event::Update(
+         serde_json::from_str(payload()?)?
+      ),
+      "delete" => Event::Delete(payload()?.to_string()),
+      "notification" => Event::Notification(
+         serde_json::from_str(payload()?)?
+      ),
+      "status.update" => Event::StatusUpdate(
+         serde_json::from_str(payload()?)?
+      ),
+      "filters_changed" => Event::FiltersChanged,
+      _ => Event::Other(event.to_string()),
+   };
+
+   Ok(event)
+}
+
+/// 解決したアドレスに順に接続を試みる
+fn connect_tcp(url: &Url, connect_timeout: Duration) -> WebApiResult<TcpStream> {
+   let addrs = url.socket_addrs(|| None)?;
+
+   let mut last_error = None;
+   for addr in addrs {
+      match TcpStream::connect_timeout(&addr, connect_timeout) {
+         Ok(stream) => return Ok(stream),
+         Err(e) => last_error = Some(e),
+      }
+   }
+
+   match last_error {
+      Some(e) => Err(e.into()),
+      None => Err(WebApiError::InvalidUrl(url.to_string())),
+   }
+}
+
+/// since mastodon 3.3.0
+///
+/// 1つの接続で複数のストリームを購読できるWebSocket接続
+pub struct WebSocketStreaming {
+   socket: WebSocket<MaybeTlsStream<TcpStream>>,
+   /// [WebSocketCloseHandle]でソケットを切断するための複製
+   tcp_stream: TcpStream,
+}
+
+/// 別スレッドで読み込み中の[WebSocketStreaming]を閉じるためのハンドル
+pub struct WebSocketCloseHandle(TcpStream);
+
+impl WebSocketCloseHandle {
+   /// ソケットを切断する。
+   /// 読み込み中のスレッドは直ちに[WebSocketStreaming::read_event]から戻る
+   pub fn close(&self) {
+      let _ = self.0.shutdown(Shutdown::Both);
+   }
+}
+
+impl WebSocketStreaming {
+   /// [client_registry][ext_reqwest::client_registry]の接続タイムアウトと
+   /// User-Agentを使用して接続する。
+   ///
+   /// プロキシを経由したWebSocketには対応していないため、プロキシが
+   /// 設定されている場合はエラーになる。その場合は[SseStreaming]を使うこと
+   pub fn connect(
+      streaming_base_url: &Url,
+      access_token: &str
+   ) -> WebApiResult<WebSocketStreaming> {
+      use ext_reqwest::client_registry;
+      use tungstenite::client::IntoClientRequest;
+      use tungstenite::handshake::HandshakeError;
+      use tungstenite::http::HeaderValue;
+
+      let settings = client_registry::settings_for(streaming_base_url);
+      if settings.proxy.is_some() {
+         return Err(WebApiError::Network("WebSocket over a proxy is not supported".into()));
+      }
+
+      let mut url = streaming_base_url.join("api/v1/streaming")?;
+      let scheme = match url.scheme() {
+         "https" | "wss" => "wss",
+         "http"  | "ws"  => "ws",
+         scheme => return Err(WebApiError::InvalidUrl(format!("unsupported scheme: {scheme}"))),
+      };
+      url.set_scheme(scheme)
+         .map_err(|_| WebApiError::InvalidUrl(url.to_string()))?;
+
+      let mut request = url.as_str().into_client_request()
+         .map_err(from_tungstenite)?;
+
+      // ヘッダに使用できない文字を含むアクセストークンは無効なトークンとして扱う
+      let authorization = HeaderValue::from_str(&format!("Bearer {access_token}"))
+         .map_err(|_| WebApiError::Unauthorized { error: None })?;
+      request.headers_mut().insert("Authorization", authorization);
+
+      if let Ok(user_agent) = HeaderValue::from_str(&settings.user_agent()) {
+         request.headers_mut().insert("User-Agent", user_agent);
+      }
+
+      let stream = connect_tcp(&url, settings.connect_timeout)?;
+      stream.set_read_timeout(Some(READ_TIMEOUT))?;
+      let tcp_stream = stream.try_clone()?;
+
+      let (socket, _) = tungstenite::client_tls(request, stream)
+         .map_err(|e| match e {
+            HandshakeError::Failure(e) => from_tungstenite(e),
+            // ブロッキングのソケットでは発生しない
+            HandshakeError::Interrupted(_) => WebApiError::Timeout,
+         })?;
+
+      Ok(WebSocketStreaming { socket, tcp_stream })
+   }
+
+   pub fn close_handle(&self) -> WebApiResult<WebSocketCloseHandle> {
+      Ok(WebSocketCloseHandle(self.tcp_stream.try_clone()?))
+   }
+
+   pub fn subscribe(&mut self, stream: &Stream) -> WebApiResult<()> {
+      use tungstenite::Message;
+
+      self.socket.send(Message::Text(stream.subscribe_message()))
+         .map_err(from_tungstenite)?;
+      Ok(())
+   }
+
+   /// 次のイベントを受信するまでブロックする。
+   /// サーバーから切断された場合None
+   pub fn read_event(&mut self) -> WebApiResult<Option<StreamEvent>> {
+      use tungstenite::Message;
+
+      #[derive(Deserialize)]
+      struct Payload {
+         stream: Option<Vec<String>>,
+         event: Option<String>,
+         payload: Option<String>,
+      }
+
+      loop {
+         let text = match self.socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) => return Ok(None),
+            Ok(_) => continue,
+            Err(tungstenite::Error::ConnectionClosed) => return Ok(None),
+            Err(e) => return Err(from_tungstenite(e)),
+         };
+
+         let Payload { stream, event, payload } = serde_json::from_str(&text)?;
+
+         // subscribeに失敗した場合などはeventを含まないメッセージが届く
+         let Some(event) = event else { continue; };
+
+         let event = StreamEvent {
+            stream: stream.and_then(|names| Stream::from_names(&names)),
+            event: decode_event(&event, payload.as_deref())?,
+         };
+
+         return Ok(Some(event));
+      }
+   }
+
+   pub fn close(&mut self) -> WebApiResult<()> {
+      self.socket.close(None)
+         .map_err(from_tungstenite)?;
+      Ok(())
+   }
+}
+
+/// since mastodon 1.0.0
+///
+/// Server-sent eventsによる接続。1つの接続で1つのストリームのみ購読できる
+pub struct SseStreaming {
+   stream: Stream,
+   reader: BufReader<ClosableResponse>,
+}
+
+/// 別スレッドで読み込み中の[SseStreaming]を閉じるためのハンドル
+#[derive(Clone)]
+pub struct SseCloseHandle(Arc<AtomicBool>);
+
+impl SseCloseHandle {
+   /// 以降の読み込みでは切断されたものとして扱う。
+   /// 読み込み中のスレッドはハートビートかイベントを受信した時点で
+   /// [SseStreaming::read_event]からNoneを返す
+   pub fn close(&self) {
+      self.0.store(true, Ordering::Relaxed);
+   }
+}
+
+struct ClosableResponse {
+   response: Response,
+   is_closed: Arc<AtomicBool>,
+}
+
+impl Read for ClosableResponse {
+   fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+      if self.is_closed.load(Ordering::Relaxed) {
+         return Ok(0);
+      }
+
+      self.response.read(buf)
+   }
+}
+
+impl SseStreaming {
+   pub fn connect(
+      transport: &dyn Transport,
+      streaming_base_url: &Url,
+      access_token: &str,
+      stream: &Stream
+   ) -> WebApiResult<SseStreaming> {
+      let url = stream.sse_url(streaming_base_url)?;
+
+      let response = transport
+         .get(url)
+         .bearer_auth(access_token)
+         .header("Accept", "text/event-stream")
+         .timeout(SSE_TIMEOUT)
+         .send()?
+         .check_status()?;
+
+      let reader = ClosableResponse {
+         response,
+         is_closed: Arc::new(AtomicBool::new(false)),
+      };
+
+      Ok(SseStreaming {
+         stream: stream.clone(),
+         reader: BufReader::new(reader),
+      })
+   }
+
+   pub fn close_handle(&self) -> SseCloseHandle {
+      SseCloseHandle(self.reader.get_ref().is_closed.clone())
+   }
+
+   /// 次のイベントを受信するまでブロックする。
+   /// サーバーから切断された場合None
+   pub fn read_event(&mut self) -> WebApiResult<Option<StreamEvent>> {
+      use std::io::BufRead;
+
+      let mut event: Option<String> = None;
+      let mut data: Option<String> = None;
+
+      loop {
+         let mut line = String::new();
+         if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+         }
+
+         let line = line.trim_end_matches(['\r', '\n']);
+
+         if line.is_empty() {
+            let Some(event) = event.take() else {
+               data = None;
+               continue;
+            };
+
+            let event = StreamEvent {
+               stream: Some(self.stream.clone()),
+               event: decode_event(&event, data.as_deref())?,
+            };
+
+            return Ok(Some(event));
+         }
+
+         // ':'から始まる行はコメント(ハートビート)
+         if line.starts_with(':') {
+            continue;
+         }
+
+         let (field, value) = line.split_once(':').unwrap_or((line, ""));
+         let value = value.strip_prefix(' ').unwrap_or(value);
+
+         match field {
+            "event" => event = Some(value.to_string()),
+            "data" => match data {
+               Some(ref mut data) => {
+                  data.push('\n');
+                  data.push_str(value);
+               }
+               None => data = Some(value.to_string()),
+            },
+            _ => {}
+         }
+      }
+   }
+}
+
+#[cfg(test)]
+mod test {
+   use super::{Event, SseStreaming, Stream};
+
+   #[test]
+   fn stream_from_names() {
+      let names = |names: &[&str]| -> Vec<String> {
+         names.iter().map(|n| n.to_string()).collect()
+      };
+
+      assert_eq!(Some(Stream::User), Stream::from_names(&names(&["user"])));
+      assert_eq!(Some(Stream::User), Stream::from_names(&names(&["user:notification"])));
+      assert_eq!(Some(Stream::PublicLocal), Stream::from_names(&names(&["public:local"])));
+      assert_eq!(
+         Some(Stream::Hashtag("tag".to_string())),
+         Stream::from_names(&names(&["hashtag", "tag"]))
+      );
+      assert_eq!(
+         Some(Stream::List("123".to_string())),
+         Stream::from_names(&names(&["list", "123"]))
+      );
+      assert_eq!(None, Stream::from_names(&names(&["direct"])));
+   }
+
+   #[test]
+   fn read_sse() {
+      use std::io::{BufRead, BufReader, Write};
+      use std::net::TcpListener;
+      use std::thread;
+      use ext_reqwest::transport::ReqwestTransport;
+
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let port = listener.local_addr().unwrap().port();
+
+      let server = thread::spawn(move || {
+         let (mut stream, _) = listener.accept().unwrap();
+
+         let mut request_line = String::new();
+         BufReader::new(&stream).read_line(&mut request_line).unwrap();
+         assert!(request_line.starts_with("GET /api/v1/streaming/hashtag?tag=tag "));
+
+         write!(
+            stream,
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: text/event-stream\r\n\
+             Connection: close\r\n\
+             \r\n\
+             :thump\n\
+             \n\
+             event: delete\n\
+             data: 123\n\
+             \n\
+             event: filters_changed\n\
+             data: undefined\n\
+             \n"
+         ).unwrap();
+      });
+
+      let url = format!("http://127.0.0.1:{port}/").parse().unwrap();
+      let mut sse = SseStreaming::connect(
+         &ReqwestTransport, &url, "access token", &Stream::Hashtag("tag".to_string())
+      ).unwrap();
+
+      let event = sse.read_event().unwrap().unwrap();
+      assert_eq!(Some(Stream::Hashtag("tag".to_string())), event.stream);
+      assert!(matches!(event.event, Event::Delete(id) if id == "123"));
+
+      let event = sse.read_event().unwrap().unwrap();
+      assert!(matches!(event.event, Event::FiltersChanged));
+
+      assert!(sse.read_event().unwrap().is_none());
+
+      server.join().unwrap();
+   }
+
+   #[test]
+   fn close_sse() {
+      use std::io::{BufRead, BufReader, Write};
+      use std::net::TcpListener;
+      use std::sync::mpsc;
+      use std::thread;
+      use std::time::Duration;
+      use ext_reqwest::transport::ReqwestTransport;
+
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let port = listener.local_addr().unwrap().port();
+
+      let (closed_sender, closed_receiver) = mpsc::channel::<()>();
+
+      let server = thread::spawn(move || {
+         let (mut stream, _) = listener.accept().unwrap();
+         BufReader::new(&stream).read_line(&mut String::new()).unwrap();
+
+         write!(
+            stream,
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: text/event-stream\r\n\
+             \r\n"
+         ).unwrap();
+
+         // 閉じられるまでハートビートだけを送り続ける
+         while closed_receiver.recv_timeout(Duration::from_millis(10)).is_err() {
+            if writeln!(stream, ":thump").is_err() { break; }
+         }
+      });
+
+      let url = format!("http://127.0.0.1:{port}/").parse().unwrap();
+      let mut sse = SseStreaming::connect(
+         &ReqwestTransport, &url, "access token", &Stream::User
+      ).unwrap();
+
+      let close_handle = sse.close_handle();
+      let reader = thread::spawn(move || sse.read_event().unwrap().is_none());
+
+      thread::sleep(Duration::from_millis(50));
+      close_handle.close();
+
+      assert!(reader.join().unwrap());
+
+      closed_sender.send(()).unwrap();
+      server.join().unwrap();
+   }
+}
@@ -14,50 +14,291 @@
  * limitations under the License.
  */
 
-use reqwest::blocking::Client;
+use ext_reqwest::error::WebApiResult;
+use ext_reqwest::rate_limit::{AsyncRateLimitedSend, RateLimitedSend};
+use ext_reqwest::transport::Transport;
 use url::Url;
 use crate::entity::status::Status;
+use crate::pagination::{Page, PageParams};
 
-#[cfg(feature = "mock")]
-use std::cell::RefCell;
+/// since mastodon 0.0.0
+///
+/// * `min_id` - since mastodon 2.6.0
+pub fn get_home(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Status>> {
+   use crate::pagination;
+
+   let mut url = instance_base_url.join("api/v1/timelines/home")?;
+   pagination::append_page_query(&mut url, page);
 
-thread_local! {
-   #[cfg(feature = "mock")]
-   static GET_HOME: RefCell<Box<dyn Fn(&Client, &Url, &str) -> anyhow::Result<Vec<Status>>>>
-      = RefCell::new(Box::new(|_, _, _| panic!()));
+   let response = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?;
+
+   pagination::read_page(response)
 }
 
-pub fn get_home(
-   client: &Client,
+/// [get_home]の非同期版
+pub async fn get_home_async(
+   transport: &dyn Transport,
    instance_base_url: &Url,
-   access_token: &str
-) -> anyhow::Result<Vec<Status>> {
-   #[cfg(not(feature = "mock"))]
+   access_token: &str,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Status>> {
+   use crate::pagination;
+
+   let mut url = instance_base_url.join("api/v1/timelines/home")?;
+   pagination::append_page_query(&mut url, page);
+
+   let response = transport
+      .get_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?;
+
+   pagination::read_page_async(response).await
+}
+
+/// [get_public]のクエリ
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct PublicTimelineParams {
+   pub local: Option<bool>,
+   pub remote: Option<bool>,
+   pub only_media: Option<bool>,
+}
+
+/// since mastodon 0.0.0
+///
+/// * `local` - since mastodon 0.1.0
+/// * `only_media` - since mastodon 2.3.0
+/// * `min_id` - since mastodon 2.6.0
+/// * `remote` - since mastodon 3.1.4
+pub fn get_public(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   params: &PublicTimelineParams,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Status>> {
+   use crate::pagination;
+
+   let mut url = public_url(instance_base_url, params)?;
+   pagination::append_page_query(&mut url, page);
+
+   let mut request = transport.get(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let response = request.send_rate_limited()?;
+
+   pagination::read_page(response)
+}
+
+/// [get_public]の非同期版
+pub async fn get_public_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   params: &PublicTimelineParams,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Status>> {
+   use crate::pagination;
+
+   let mut url = public_url(instance_base_url, params)?;
+   pagination::append_page_query(&mut url, page);
+
+   let mut request = transport.get_async(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let response = request.send_rate_limited_async().await?;
+
+   pagination::read_page_async(response).await
+}
+
+fn public_url(
+   instance_base_url: &Url,
+   params: &PublicTimelineParams
+) -> WebApiResult<Url> {
+   let mut url = instance_base_url.join("api/v1/timelines/public")?;
+
    {
-      let url = instance_base_url.join("api/v1/timelines/home")?;
+      let mut query_pairs = url.query_pairs_mut();
+      if let Some(local) = params.local {
+         query_pairs.append_pair("local", &local.to_string());
+      }
+      if let Some(remote) = params.remote {
+         query_pairs.append_pair("remote", &remote.to_string());
+      }
+      if let Some(only_media) = params.only_media {
+         query_pairs.append_pair("only_media", &only_media.to_string());
+      }
+   }
+
+   Ok(url)
+}
 
-      let statuses = client
-         .get(url)
-         .bearer_auth(access_token)
-         .send()?
-         .json()?;
+/// [get_tag]のクエリ
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct TagTimelineParams<'a> {
+   pub any: &'a [&'a str],
+   pub all: &'a [&'a str],
+   pub none: &'a [&'a str],
+   pub local: Option<bool>,
+   pub remote: Option<bool>,
+   pub only_media: Option<bool>,
+}
+
+/// since mastodon 0.0.0
+///
+/// * `local` - since mastodon 0.1.0
+/// * `any`, `all`, `none` - since mastodon 2.7.0
+/// * `only_media` - since mastodon 2.3.0
+/// * `min_id` - since mastodon 2.6.0
+/// * `remote` - since mastodon 3.3.0
+pub fn get_tag(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   hashtag: &str,
+   params: &TagTimelineParams<'_>,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Status>> {
+   use crate::pagination;
 
-      Ok(statuses)
+   let mut url = tag_url(instance_base_url, hashtag, params)?;
+   pagination::append_page_query(&mut url, page);
+
+   let mut request = transport.get(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
    }
 
-   #[cfg(feature = "mock")]
+   let response = request.send_rate_limited()?;
+
+   pagination::read_page(response)
+}
+
+/// [get_tag]の非同期版
+pub async fn get_tag_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: Option<&str>,
+   hashtag: &str,
+   params: &TagTimelineParams<'_>,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Status>> {
+   use crate::pagination;
+
+   let mut url = tag_url(instance_base_url, hashtag, params)?;
+   pagination::append_page_query(&mut url, page);
+
+   let mut request = transport.get_async(url);
+   if let Some(access_token) = access_token {
+      request = request.bearer_auth(access_token);
+   }
+
+   let response = request.send_rate_limited_async().await?;
+
+   pagination::read_page_async(response).await
+}
+
+fn tag_url(
+   instance_base_url: &Url,
+   hashtag: &str,
+   params: &TagTimelineParams<'_>
+) -> WebApiResult<Url> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join("api/v1/timelines/tag/")?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .pop_if_empty()
+      .push(hashtag);
+
    {
-      GET_HOME.with(|f| {
-         let f = f.borrow();
-         f(client, instance_base_url, access_token)
-      })
+      let mut query_pairs = url.query_pairs_mut();
+      for t in params.any {
+         query_pairs.append_pair("any[]", t);
+      }
+      for t in params.all {
+         query_pairs.append_pair("all[]", t);
+      }
+      for t in params.none {
+         query_pairs.append_pair("none[]", t);
+      }
+      if let Some(local) = params.local {
+         query_pairs.append_pair("local", &local.to_string());
+      }
+      if let Some(remote) = params.remote {
+         query_pairs.append_pair("remote", &remote.to_string());
+      }
+      if let Some(only_media) = params.only_media {
+         query_pairs.append_pair("only_media", &only_media.to_string());
+      }
    }
+
+   Ok(url)
+}
+
+/// since mastodon 2.1.0
+///
+/// * `min_id` - since mastodon 2.6.0
+pub fn get_list(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   list_id: &str,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Status>> {
+   use crate::pagination;
+
+   let mut url = list_url(instance_base_url, list_id)?;
+   pagination::append_page_query(&mut url, page);
+
+   let response = transport
+      .get(url)
+      .bearer_auth(access_token)
+      .send_rate_limited()?;
+
+   pagination::read_page(response)
 }
 
-#[allow(dead_code)]
-#[cfg(feature = "mock")]
-pub fn inject_get_verify_credentials(
-   get_verify_credentials: impl Fn(&Client, &Url, &str) -> anyhow::Result<Vec<Status>> + 'static
-) {
-   GET_HOME.set(Box::new(get_verify_credentials));
+/// [get_list]の非同期版
+pub async fn get_list_async(
+   transport: &dyn Transport,
+   instance_base_url: &Url,
+   access_token: &str,
+   list_id: &str,
+   page: &PageParams<'_>
+) -> WebApiResult<Page<Status>> {
+   use crate::pagination;
+
+   let mut url = list_url(instance_base_url, list_id)?;
+   pagination::append_page_query(&mut url, page);
+
+   let response = transport
+      .get_async(url)
+      .bearer_auth(access_token)
+      .send_rate_limited_async().await?;
+
+   pagination::read_page_async(response).await
+}
+
+fn list_url(instance_base_url: &Url, list_id: &str) -> WebApiResult<Url> {
+   use ext_reqwest::error::WebApiError;
+
+   let mut url = instance_base_url.join("api/v1/timelines/list/")?;
+   url.path_segments_mut()
+      .map_err(|_| WebApiError::InvalidUrl(instance_base_url.to_string()))?
+      .pop_if_empty()
+      .push(list_id);
+
+   Ok(url)
 }
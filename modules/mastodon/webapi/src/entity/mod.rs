@@ -16,11 +16,15 @@
 
 pub mod account;
 pub mod application;
+pub mod context;
 pub mod custom_emoji;
 pub mod filter;
 pub mod media_attachment;
+pub mod notification;
 pub mod poll;
 pub mod preview_card;
+pub mod relationship;
 pub mod role;
+pub mod search;
 pub mod status;
 pub mod token;
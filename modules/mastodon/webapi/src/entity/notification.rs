@@ -0,0 +1,77 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::Deserialize;
+use crate::entity::account::Account;
+use crate::entity::status::Status;
+
+/// since mastodon 0.9.9
+#[derive(Deserialize)]
+pub struct Notification {
+   /// since mastodon 0.9.9
+   pub id: Option<String>,
+   /// since mastodon 0.9.9
+   /// mastodon 2.8.0 "poll"追加
+   /// mastodon 3.1.0 "follow_request"追加
+   /// mastodon 3.3.0 "status"追加
+   /// mastodon 3.5.0 "update", "admin.sign_up"追加
+   /// mastodon 4.0.0 "admin.report"追加
+   #[serde(rename = "type")]
+   pub r#type: Option<String>,
+   /// since mastodon 4.3.0
+   pub group_key: Option<String>,
+   /// since mastodon 0.9.9
+   pub created_at: Option<String>,
+   /// since mastodon 0.9.9
+   pub account: Option<Account>,
+   /// since mastodon 0.9.9
+   pub status: Option<Status>,
+}
+
+/// since mastodon 4.3.0
+#[derive(Deserialize)]
+pub struct GroupedNotificationsResults {
+   /// since mastodon 4.3.0
+   pub accounts: Option<Vec<Account>>,
+   /// since mastodon 4.3.0
+   pub statuses: Option<Vec<Status>>,
+   /// since mastodon 4.3.0
+   pub notification_groups: Option<Vec<NotificationGroup>>,
+}
+
+/// since mastodon 4.3.0
+#[derive(Deserialize)]
+pub struct NotificationGroup {
+   /// since mastodon 4.3.0
+   pub group_key: Option<String>,
+   /// since mastodon 4.3.0
+   pub notifications_count: Option<i64>,
+   /// since mastodon 4.3.0
+   #[serde(rename = "type")]
+   pub r#type: Option<String>,
+   /// since mastodon 4.3.0
+   pub most_recent_notification_id: Option<String>,
+   /// since mastodon 4.3.0
+   pub page_min_id: Option<String>,
+   /// since mastodon 4.3.0
+   pub page_max_id: Option<String>,
+   /// since mastodon 4.3.0
+   pub latest_page_notification_at: Option<String>,
+   /// since mastodon 4.3.0
+   pub sample_account_ids: Option<Vec<String>>,
+   /// since mastodon 4.3.0
+   pub status_id: Option<String>,
+}
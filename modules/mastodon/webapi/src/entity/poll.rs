@@ -39,7 +39,7 @@ pub struct Poll {
    /// since mastodon 2.8.0
    pub voted: Option<bool>,
    /// since mastodon 2.8.0
-   pub own_voted: Option<Vec<i64>>,
+   pub own_votes: Option<Vec<i64>>,
 }
 
 /// since mastodon 2.8.0
@@ -0,0 +1,46 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::Deserialize;
+
+/// since mastodon 0.6.0
+#[derive(Deserialize)]
+pub struct Relationship {
+   pub id: Option<String>,
+   pub following: Option<bool>,
+   /// since mastodon 2.1.0
+   pub showing_reblogs: Option<bool>,
+   /// since mastodon 3.3.0
+   pub notifying: Option<bool>,
+   /// since mastodon 4.0.0
+   pub languages: Option<Vec<String>>,
+   pub followed_by: Option<bool>,
+   pub blocking: Option<bool>,
+   /// since mastodon 2.8.0
+   pub blocked_by: Option<bool>,
+   pub muting: Option<bool>,
+   /// since mastodon 2.1.0
+   pub muting_notifications: Option<bool>,
+   pub requested: Option<bool>,
+   /// since mastodon 4.1.0
+   pub requested_by: Option<bool>,
+   /// since mastodon 1.4.0
+   pub domain_blocking: Option<bool>,
+   /// since mastodon 2.5.0
+   pub endorsed: Option<bool>,
+   /// since mastodon 3.2.0
+   pub note: Option<String>,
+}
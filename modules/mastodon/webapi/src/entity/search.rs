@@ -0,0 +1,49 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::Deserialize;
+use crate::entity::account::Account;
+use crate::entity::status::Status;
+
+/// since mastodon 2.4.1
+#[derive(Deserialize)]
+pub struct Search {
+   pub accounts: Option<Vec<Account>>,
+   pub statuses: Option<Vec<Status>>,
+   pub hashtags: Option<Vec<Tag>>,
+}
+
+/// since mastodon 0.9.0
+#[derive(Deserialize)]
+pub struct Tag {
+   /// since mastodon 0.9.0
+   pub name: Option<String>,
+   /// since mastodon 0.9.0
+   pub url: Option<String>,
+   /// since mastodon 2.4.1
+   pub history: Option<Vec<TagHistory>>,
+   /// since mastodon 4.0.0
+   pub following: Option<bool>,
+}
+
+/// since mastodon 2.4.1
+#[derive(Deserialize)]
+pub struct TagHistory {
+   /// UNIXタイムスタンプ(秒)の文字列
+   pub day: Option<String>,
+   pub uses: Option<String>,
+   pub accounts: Option<String>,
+}
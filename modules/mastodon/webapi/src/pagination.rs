@@ -0,0 +1,222 @@
+/*
+ * Copyright 2025 wcaokaze
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ext_reqwest::error::{AsyncResponseExt, ResponseExt, WebApiResult};
+use reqwest::blocking::Response;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+use url::Url;
+
+/// Linkヘッダから取得した1ページ分のレスポンス
+pub struct Page<T> {
+   pub items: Vec<T>,
+   /// より古い要素を取得するためのカーソル。Linkヘッダの`rel="next"`
+   pub next: Option<PageCursor>,
+   /// より新しい要素を取得するためのカーソル。Linkヘッダの`rel="prev"`
+   pub prev: Option<PageCursor>,
+}
+
+/// レスポンスボディが配列でないAPIの1ページ分のレスポンス
+pub struct Paged<T> {
+   pub body: T,
+   /// より古い要素を取得するためのカーソル。Linkヘッダの`rel="next"`
+   pub next: Option<PageCursor>,
+   /// より新しい要素を取得するためのカーソル。Linkヘッダの`rel="prev"`
+   pub prev: Option<PageCursor>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct PageCursor {
+   pub max_id: Option<String>,
+   pub since_id: Option<String>,
+   pub min_id: Option<String>,
+}
+
+/// ページングするAPIに渡すクエリ。
+/// すべてNoneの場合は最新のページを取得する
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct PageParams<'a> {
+   pub max_id: Option<&'a str>,
+   pub since_id: Option<&'a str>,
+   pub min_id: Option<&'a str>,
+   pub limit: Option<u32>,
+}
+
+pub(crate) fn append_page_query(url: &mut Url, page: &PageParams<'_>) {
+   let mut query_pairs = url.query_pairs_mut();
+   if let Some(max_id) = page.max_id {
+      query_pairs.append_pair("max_id", max_id);
+   }
+   if let Some(since_id) = page.since_id {
+      query_pairs.append_pair("since_id", since_id);
+   }
+   if let Some(min_id) = page.min_id {
+      query_pairs.append_pair("min_id", min_id);
+   }
+   if let Some(limit) = page.limit {
+      query_pairs.append_pair("limit", &limit.to_string());
+   }
+}
+
+pub(crate) fn read_page<T: DeserializeOwned>(
+   response: Response
+) -> WebApiResult<Page<T>> {
+   let Paged { body: items, next, prev } = read_paged(response)?;
+   Ok(Page { items, next, prev })
+}
+
+pub(crate) fn read_paged<T: DeserializeOwned>(
+   response: Response
+) -> WebApiResult<Paged<T>> {
+   let response = response.check_status()?;
+   let (next, prev) = read_link_headers(response.headers());
+   let body = response.json()?;
+
+   Ok(Paged { body, next, prev })
+}
+
+pub(crate) async fn read_page_async<T: DeserializeOwned>(
+   response: reqwest::Response
+) -> WebApiResult<Page<T>> {
+   let Paged { body: items, next, prev } = read_paged_async(response).await?;
+   Ok(Page { items, next, prev })
+}
+
+pub(crate) async fn read_paged_async<T: DeserializeOwned>(
+   response: reqwest::Response
+) -> WebApiResult<Paged<T>> {
+   let response = response.check_status_async().await?;
+   let (next, prev) = read_link_headers(response.headers());
+   let body = response.json().await?;
+
+   Ok(Paged { body, next, prev })
+}
+
+fn read_link_headers(headers: &HeaderMap) -> (Option<PageCursor>, Option<PageCursor>) {
+   use reqwest::header::LINK;
+
+   headers
+      .get_all(LINK)
+      .iter()
+      .filter_map(|value| value.to_str().ok())
+      .map(parse_link_header)
+      .fold((None, None), |(next, prev), (n, p)| (next.or(n), prev.or(p)))
+}
+
+/// RFC 8288のLinkヘッダの値をパースし、`rel="next"`と`rel="prev"`の
+/// URLに含まれるクエリをカーソルとして返す
+pub fn parse_link_header(value: &str) -> (Option<PageCursor>, Option<PageCursor>) {
+   let mut next = None;
+   let mut prev = None;
+
+   let mut rest = value;
+   while let Some(start) = rest.find('<') {
+      let Some(len) = rest[start..].find('>') else { break; };
+      let target = &rest[start + 1..start + len];
+      rest = &rest[start + len + 1..];
+
+      let params_len = rest.find('<').unwrap_or(rest.len());
+      let params = &rest[..params_len];
+      rest = &rest[params_len..];
+
+      let rels = params.split([';', ','])
+         .filter_map(|param| param.split_once('='))
+         .filter(|(name, _)| name.trim().eq_ignore_ascii_case("rel"))
+         .flat_map(|(_, value)| value.trim().trim_matches('"').split_whitespace());
+
+      for rel in rels {
+         if rel.eq_ignore_ascii_case("next") {
+            next = next.or_else(|| parse_cursor(target));
+         } else if rel.eq_ignore_ascii_case("prev") {
+            prev = prev.or_else(|| parse_cursor(target));
+         }
+      }
+   }
+
+   (next, prev)
+}
+
+fn parse_cursor(target: &str) -> Option<PageCursor> {
+   let url = Url::parse(target).ok()?;
+
+   let mut cursor = PageCursor::default();
+   for (key, value) in url.query_pairs() {
+      match key.as_ref() {
+         "max_id"   => cursor.max_id   = Some(value.into_owned()),
+         "since_id" => cursor.since_id = Some(value.into_owned()),
+         "min_id"   => cursor.min_id   = Some(value.into_owned()),
+         _ => {}
+      }
+   }
+
+   Some(cursor)
+}
+
+#[cfg(test)]
+mod test {
+   use super::{parse_link_header, PageCursor};
+
+   #[test]
+   fn parse_next_and_prev() {
+      let (next, prev) = parse_link_header(
+         "<https://example.com/api/v1/timelines/home?max_id=109>; rel=\"next\", \
+          <https://example.com/api/v1/timelines/home?min_id=120>; rel=\"prev\""
+      );
+
+      assert_eq!(
+         Some(PageCursor {
+            max_id: Some("109".to_string()),
+            since_id: None,
+            min_id: None,
+         }),
+         next
+      );
+
+      assert_eq!(
+         Some(PageCursor {
+            max_id: None,
+            since_id: None,
+            min_id: Some("120".to_string()),
+         }),
+         prev
+      );
+   }
+
+   #[test]
+   fn parse_only_next() {
+      let (next, prev) = parse_link_header(
+         "<https://example.com/api/v1/timelines/home?limit=40&max_id=109>; rel=\"next\""
+      );
+
+      assert_eq!(
+         Some(PageCursor {
+            max_id: Some("109".to_string()),
+            since_id: None,
+            min_id: None,
+         }),
+         next
+      );
+
+      assert_eq!(None, prev);
+   }
+
+   #[test]
+   fn parse_invalid() {
+      assert_eq!((None, None), parse_link_header(""));
+      assert_eq!((None, None), parse_link_header("<not a url>; rel=\"next\""));
+      assert_eq!((None, None), parse_link_header("<https://example.com/?max_id=1"));
+   }
+}
@@ -17,11 +17,21 @@
 package com.wcaokaze.probosqis.nodeinfo.repository
 
 import com.wcaokaze.probosqis.ext.kotlin.Url
+import com.wcaokaze.probosqis.ext.kotlin.webapi.WebApiCallback
+import com.wcaokaze.probosqis.ext.kotlin.webapi.awaitWebApi
 import com.wcaokaze.probosqis.nodeinfo.entity.FediverseSoftware
 
 class AndroidNodeInfoRepository : NodeInfoRepository {
    override fun getServerSoftware(serverUrl: Url): FediverseSoftware
       = getServerSoftware(serverUrl.raw)
 
+   override suspend fun getServerSoftwareAsync(serverUrl: Url): FediverseSoftware
+      = awaitWebApi { getServerSoftwareAsync(serverUrl.raw, it) }
+
    private external fun getServerSoftware(serverUrl: String): FediverseSoftware
+
+   private external fun getServerSoftwareAsync(
+      serverUrl: String,
+      callback: WebApiCallback<FediverseSoftware>
+   )
 }
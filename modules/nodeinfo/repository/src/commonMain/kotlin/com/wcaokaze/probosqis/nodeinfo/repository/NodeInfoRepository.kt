@@ -21,4 +21,9 @@ import com.wcaokaze.probosqis.nodeinfo.entity.FediverseSoftware
 
 interface NodeInfoRepository {
    fun getServerSoftware(serverUrl: Url): FediverseSoftware
+
+   /**
+    * [getServerSoftware]の非同期版。レスポンスを待つ間スレッドを占有しない
+    */
+   suspend fun getServerSoftwareAsync(serverUrl: Url): FediverseSoftware
 }
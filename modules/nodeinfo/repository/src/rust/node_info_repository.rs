@@ -18,10 +18,13 @@
 mod jvm {
    use jni::JNIEnv;
    use jni::objects::JObject;
+   use nodeinfo_entity::fediverse_software::FediverseSoftware;
    use nodeinfo_entity::jvm_types::JvmFediverseSoftware;
    use nodeinfo_webapi::api::node_info;
+   use nodeinfo_webapi::entity::node_info::{NodeInfo, Software};
    use nodeinfo_webapi::entity::resource_descriptor::ResourceDescriptor;
    use panoptiqon::jvm_types::JvmString;
+   use url::Url;
 
    const REL_MAPPING: [(&str, &str); 4] = [
       ("http://nodeinfo.diaspora.software/ns/schema/1.0", "1.0"),
@@ -36,10 +39,10 @@ mod jvm {
       _obj: JObject<'local>,
       server_url: JvmString<'local>
    ) -> JvmFediverseSoftware<'local> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
 
       get_node_info(&mut env, server_url)
-         .unwrap_or_throw_io_exception(&mut env)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    #[no_mangle]
@@ -48,41 +51,110 @@ mod jvm {
       _obj: JObject<'local>,
       server_url: JvmString<'local>
    ) -> JvmFediverseSoftware<'local> {
-      use ext_panoptiqon::unwrap_or_throw::UnwrapOrThrow;
+      use ext_reqwest::unwrap_or_throw::UnwrapOrThrowWebApiException;
 
       get_node_info(&mut env, server_url)
-         .unwrap_or_throw_io_exception(&mut env)
+         .unwrap_or_throw_web_api_exception(&mut env)
    }
 
    fn get_node_info<'local>(
       env: &mut JNIEnv<'local>,
       server_url: JvmString<'local>
    ) -> anyhow::Result<JvmFediverseSoftware<'local>> {
-      use chrono::Utc;
-      use ext_reqwest::CLIENT;
-      use anyhow::anyhow;
-      use mastodon_entity::instance::Instance;
-      use nodeinfo_entity::fediverse_software::FediverseSoftware;
-      use nodeinfo_webapi::entity::node_info::{NodeInfo, Software};
+      use ext_reqwest::error::WebApiError;
+      use ext_reqwest::transport::ReqwestTransport;
       use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
-      use url::Url;
 
       let server_url = String::clone_from_jvm(env, &server_url);
       let server_url: Url = server_url.parse()?;
 
       let resource_descriptor
-         = node_info::get_node_info_resource_descriptor(&CLIENT, &server_url)?;
+         = node_info::get_node_info_resource_descriptor(&ReqwestTransport, &server_url)?;
 
       let (version, node_info_url) = get_node_info_url(&resource_descriptor)
-         .ok_or(anyhow!("cannot detect NodeInfo URL"))?;
+         .ok_or_else(|| WebApiError::UnsupportedServer("cannot detect NodeInfo URL".to_string()))?;
+
+      let node_info = node_info::get_node_info(&ReqwestTransport, node_info_url, version)?;
+
+      let fediverse_software = to_fediverse_software(server_url, node_info);
+      let jvm_instance = fediverse_software.clone_into_jvm(env);
+      Ok(jvm_instance)
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_nodeinfo_repository_DesktopNodeInfoRepository_getServerSoftwareAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      server_url: JvmString<'local>,
+      callback: JObject<'local>
+   ) {
+      get_node_info_async(&mut env, server_url, callback);
+   }
+
+   #[no_mangle]
+   extern "C" fn Java_com_wcaokaze_probosqis_nodeinfo_repository_AndroidNodeInfoRepository_getServerSoftwareAsync<'local>(
+      mut env: JNIEnv<'local>,
+      _obj: JObject<'local>,
+      server_url: JvmString<'local>,
+      callback: JObject<'local>
+   ) {
+      get_node_info_async(&mut env, server_url, callback);
+   }
+
+   fn get_node_info_async<'local>(
+      env: &mut JNIEnv<'local>,
+      server_url: JvmString<'local>,
+      callback: JObject<'local>
+   ) {
+      use ext_reqwest::callback::WebApiCallback;
+      use ext_reqwest::unwrap_or_throw::throw_web_api_exception;
+      use panoptiqon::convert_jvm::{CloneFromJvm, CloneIntoJvm};
+      use panoptiqon::jvm_type::JvmType;
+
+      let result = (|| {
+         let server_url = String::clone_from_jvm(env, &server_url);
+         let server_url: Url = server_url.parse()?;
+         let callback = WebApiCallback::new(env, &callback)?;
+
+         callback.spawn(fetch_fediverse_software(server_url), |env, fediverse_software| {
+            let fediverse_software: JvmFediverseSoftware = fediverse_software.clone_into_jvm(env);
+            fediverse_software.into_j_object()
+         });
+
+         anyhow::Ok(())
+      })();
+
+      if let Err(e) = result {
+         throw_web_api_exception(env, &e);
+      }
+   }
+
+   async fn fetch_fediverse_software(server_url: Url) -> anyhow::Result<FediverseSoftware> {
+      use ext_reqwest::error::WebApiError;
+      use ext_reqwest::transport::ReqwestTransport;
+
+      let resource_descriptor
+         = node_info::get_node_info_resource_descriptor_async(&ReqwestTransport, &server_url).await?;
+
+      let (version, node_info_url) = get_node_info_url(&resource_descriptor)
+         .ok_or_else(|| WebApiError::UnsupportedServer("cannot detect NodeInfo URL".to_string()))?;
+
+      let node_info = node_info::get_node_info_async(&ReqwestTransport, node_info_url, version).await?;
+
+      Ok(to_fediverse_software(server_url, node_info))
+   }
+
+   fn to_fediverse_software(server_url: Url, node_info: NodeInfo) -> FediverseSoftware {
+      use chrono::Utc;
+      use mastodon_entity::instance::Instance;
 
       let NodeInfo {
          software: Software { mut name, version }
-      } = node_info::get_node_info(&CLIENT, node_info_url, version)?;
+      } = node_info;
 
       name.make_ascii_lowercase();
 
-      let fediverse_software = if name == "mastodon" {
+      if name == "mastodon" {
          FediverseSoftware::Mastodon {
             instance: Instance {
                url: server_url,
@@ -95,10 +167,7 @@ mod jvm {
             name,
             version,
          }
-      };
-
-      let jvm_instance = fediverse_software.clone_into_jvm(env);
-      Ok(jvm_instance)
+      }
    }
 
    fn get_node_info_url(
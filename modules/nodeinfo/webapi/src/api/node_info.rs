@@ -13,40 +13,83 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use anyhow::{anyhow, Result};
-use reqwest::blocking::Client;
+use ext_reqwest::error::{AsyncResponseExt, ResponseExt, WebApiError, WebApiResult};
+use ext_reqwest::transport::Transport;
 use url::Url;
 
 use crate::entity::node_info::NodeInfo;
 use crate::entity::resource_descriptor::ResourceDescriptor;
 
 pub fn get_node_info_resource_descriptor(
-   client: &Client,
+   transport: &dyn Transport,
    server_base_url: &Url
-) -> Result<ResourceDescriptor> {
+) -> WebApiResult<ResourceDescriptor> {
    let url = server_base_url.join(".well-known/nodeinfo")?;
 
-   let resource_descriptor = client
+   let resource_descriptor = transport
       .get(url)
       .send()?
+      .check_status()?
       .json()?;
 
    Ok(resource_descriptor)
 }
 
+/// [get_node_info_resource_descriptor]の非同期版
+pub async fn get_node_info_resource_descriptor_async(
+   transport: &dyn Transport,
+   server_base_url: &Url
+) -> WebApiResult<ResourceDescriptor> {
+   let url = server_base_url.join(".well-known/nodeinfo")?;
+
+   let resource_descriptor = transport
+      .get_async(url)
+      .send().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(resource_descriptor)
+}
+
 pub fn get_node_info(
-   client: &Client,
+   transport: &dyn Transport,
    node_info_url: &str,
    version: &str
-) -> Result<NodeInfo> {
-   if ["1.0", "1.1", "2.0", "2.1"].iter().all(|&v| version != v) {
-      return Err(anyhow!("Unexpected NodeInfo ver: {}", version));
-   }
+) -> WebApiResult<NodeInfo> {
+   check_version(version)?;
 
-   let node_info = client
-      .get(node_info_url)
+   let node_info = transport
+      .get(Url::parse(node_info_url)?)
       .send()?
+      .check_status()?
       .json()?;
 
    Ok(node_info)
 }
+
+/// [get_node_info]の非同期版
+pub async fn get_node_info_async(
+   transport: &dyn Transport,
+   node_info_url: &str,
+   version: &str
+) -> WebApiResult<NodeInfo> {
+   check_version(version)?;
+
+   let node_info = transport
+      .get_async(Url::parse(node_info_url)?)
+      .send().await?
+      .check_status_async().await?
+      .json().await?;
+
+   Ok(node_info)
+}
+
+fn check_version(version: &str) -> WebApiResult<()> {
+   if ["1.0", "1.1", "2.0", "2.1"].iter().all(|&v| version != v) {
+      return Err(WebApiError::UnsupportedServer(
+         format!("Unexpected NodeInfo ver: {}", version)
+      ));
+   }
+
+   Ok(())
+}